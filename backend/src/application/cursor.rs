@@ -0,0 +1,138 @@
+// ページングカーソル
+// 取引一覧の読み出し位置を、クライアントからは中身を扱えない署名付き文字列にする
+
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::repositories::TransactionCursor;
+use crate::domain::value_objects::UserId;
+use anyhow::anyhow;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL;
+use base64::Engine;
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+
+/// カーソルに署名する内容
+///
+/// ユーザーIDも含めて署名し、他のユーザーが発行されたカーソルを使えないようにする
+#[derive(Serialize, Deserialize)]
+struct CursorPayload {
+    user_id: String,
+    #[serde(flatten)]
+    position: TransactionCursor,
+}
+
+/// カーソルの符号化・検証
+///
+/// 形式は `base64url(JSON).base64url(HMAC-SHA256)`
+#[derive(Clone)]
+pub struct CursorCodec {
+    key: hmac::Key,
+}
+
+impl CursorCodec {
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, secret),
+        }
+    }
+
+    /// ランダムな鍵で生成（発行したプロセス内でのみ有効なカーソルになる）
+    pub fn random() -> anyhow::Result<Self> {
+        let mut secret = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut secret)
+            .map_err(|_| anyhow!("failed to generate cursor secret"))?;
+        Ok(Self::new(&secret))
+    }
+
+    pub fn encode(&self, user_id: &UserId, position: &TransactionCursor) -> DomainResult<String> {
+        let payload = serde_json::to_vec(&CursorPayload {
+            user_id: user_id.value().to_string(),
+            position: position.clone(),
+        })
+        .map_err(anyhow::Error::from)?;
+        let tag = hmac::sign(&self.key, &payload);
+        Ok(format!(
+            "{}.{}",
+            BASE64_URL.encode(&payload),
+            BASE64_URL.encode(tag.as_ref())
+        ))
+    }
+
+    /// 署名とユーザーを検証して読み出し位置を取り出す
+    pub fn decode(&self, user_id: &UserId, cursor: &str) -> DomainResult<TransactionCursor> {
+        let invalid = || DomainError::Validation("Invalid cursor".to_string());
+
+        let (payload, tag) = cursor.split_once('.').ok_or_else(invalid)?;
+        let payload = BASE64_URL.decode(payload).map_err(|_| invalid())?;
+        let tag = BASE64_URL.decode(tag).map_err(|_| invalid())?;
+        hmac::verify(&self.key, &payload, &tag).map_err(|_| invalid())?;
+
+        let payload: CursorPayload = serde_json::from_slice(&payload).map_err(|_| invalid())?;
+        if payload.user_id != user_id.value() {
+            return Err(invalid());
+        }
+        Ok(payload.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::TransactionDate;
+    use chrono::Utc;
+
+    fn position() -> TransactionCursor {
+        TransactionCursor {
+            transaction_date: TransactionDate::new(Utc::now().date_naive()),
+            transaction_id: "tx-1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let codec = CursorCodec::new(b"secret");
+        let user_id = UserId::new("user123".to_string());
+        let position = position();
+
+        let cursor = codec.encode(&user_id, &position).unwrap();
+        assert_eq!(codec.decode(&user_id, &cursor).unwrap(), position);
+    }
+
+    #[test]
+    fn test_tampered_cursor_is_rejected() {
+        let codec = CursorCodec::new(b"secret");
+        let user_id = UserId::new("user123".to_string());
+        let cursor = codec.encode(&user_id, &position()).unwrap();
+
+        // 読み出し位置を書き換えたもの
+        let (_, tag) = cursor.split_once('.').unwrap();
+        let forged = format!(
+            "{}.{}",
+            BASE64_URL.encode(br#"{"user_id":"user123","transaction_date":"2000-01-01T00:00:00Z","transaction_id":"tx-0"}"#),
+            tag
+        );
+        assert!(matches!(
+            codec.decode(&user_id, &forged),
+            Err(DomainError::Validation(_))
+        ));
+
+        // 別の鍵で署名されたもの・形式が不正なもの
+        assert!(CursorCodec::new(b"other")
+            .decode(&user_id, &cursor)
+            .is_err());
+        assert!(codec.decode(&user_id, "not-a-cursor").is_err());
+    }
+
+    #[test]
+    fn test_cursor_of_other_user_is_rejected() {
+        let codec = CursorCodec::new(b"secret");
+        let cursor = codec
+            .encode(&UserId::new("user123".to_string()), &position())
+            .unwrap();
+
+        assert!(codec
+            .decode(&UserId::new("user456".to_string()), &cursor)
+            .is_err());
+    }
+}
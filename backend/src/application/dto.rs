@@ -0,0 +1,1857 @@
+// データ転送オブジェクト
+// api-schema/openapi.yml のリクエスト・レスポンススキーマに対応する型
+
+use crate::domain::repositories::TransactionQuery;
+use crate::domain::services::{
+    BudgetStatus, CardBillingSummary, CategoryExpense, CategoryShare, CategorySpending,
+    DebitProjection, MonthlyReport, PeriodTotals, ScheduledDebit, SettlementPlan, Transfer,
+    TrendPoint, TrendReport,
+};
+use crate::domain::*;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// ユーザープロフィール
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserProfileResponse {
+    pub user_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub currency: String,
+    pub timezone: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<UserProfile> for UserProfileResponse {
+    fn from(user: UserProfile) -> Self {
+        Self {
+            user_id: user.user_id.0,
+            display_name: user.display_name,
+            currency: user.currency,
+            timezone: user.timezone,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+/// ユーザープロフィール作成・更新リクエスト（`UpdateUserProfileRequest`）
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateUserProfileRequest {
+    pub display_name: Option<String>,
+    #[validate(length(min = 1))]
+    pub currency: Option<String>,
+    #[validate(length(min = 1))]
+    pub timezone: Option<String>,
+}
+
+impl UpdateUserProfileRequest {
+    /// 認証済みユーザーの新規プロフィールを作成
+    pub fn into_entity(self, user_id: UserId) -> UserProfile {
+        let mut user = UserProfile::new(user_id);
+        self.apply(&mut user);
+        user
+    }
+
+    /// 指定された項目のみをプロフィールに反映
+    pub fn apply(self, user: &mut UserProfile) {
+        user.update(self.display_name, self.currency, self.timezone);
+    }
+}
+
+/// 精算情報
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementInfoDto {
+    pub settlement_id: String,
+    pub creditor_user_id: String,
+    pub debtor_user_id: String,
+    pub status: SettlementStatus,
+}
+
+impl From<SettlementInfo> for SettlementInfoDto {
+    fn from(info: SettlementInfo) -> Self {
+        Self {
+            settlement_id: info.settlement_id,
+            creditor_user_id: info.creditor_user_id.0,
+            debtor_user_id: info.debtor_user_id.0,
+            status: info.status,
+        }
+    }
+}
+
+impl From<SettlementInfoDto> for SettlementInfo {
+    fn from(dto: SettlementInfoDto) -> Self {
+        Self {
+            settlement_id: dto.settlement_id,
+            creditor_user_id: UserId::new(dto.creditor_user_id),
+            debtor_user_id: UserId::new(dto.debtor_user_id),
+            status: dto.status,
+        }
+    }
+}
+
+/// 口座間移動の情報
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferInfoDto {
+    pub transfer_id: String,
+    pub from_account_id: String,
+    pub to_account_id: String,
+}
+
+impl From<TransferInfo> for TransferInfoDto {
+    fn from(info: TransferInfo) -> Self {
+        Self {
+            transfer_id: info.transfer_id,
+            from_account_id: info.from_account_id,
+            to_account_id: info.to_account_id,
+        }
+    }
+}
+
+/// 取引
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionResponse {
+    pub transaction_id: String,
+    pub user_id: String,
+    #[serde(rename = "type")]
+    pub transaction_type: TransactionType,
+    pub direction: TransactionDirection,
+    pub amount: Amount,
+    pub description: String,
+    pub category: TransactionCategory,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<String>,
+    pub tags: Vec<String>,
+    pub transaction_date: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement_info: Option<SettlementInfoDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer: Option<TransferInfoDto>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Transaction> for TransactionResponse {
+    fn from(transaction: Transaction) -> Self {
+        Self {
+            transaction_id: transaction.transaction_id.0,
+            user_id: transaction.user_id.0,
+            transaction_type: transaction.transaction_type,
+            direction: transaction.direction,
+            amount: transaction.amount,
+            description: transaction.description,
+            category: transaction.category,
+            category_id: transaction.category_id,
+            tags: transaction.tags,
+            transaction_date: transaction.transaction_date.date(),
+            settlement_info: transaction.settlement_info.map(Into::into),
+            account_id: transaction.account_id,
+            transfer: transaction.transfer.map(Into::into),
+            created_at: transaction.created_at,
+            updated_at: transaction.updated_at,
+        }
+    }
+}
+
+/// 取引作成リクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTransactionRequest {
+    #[serde(rename = "type")]
+    pub transaction_type: TransactionType,
+    /// 省略時は支出（返金は負の金額の支出として記録する）
+    #[serde(default)]
+    pub direction: TransactionDirection,
+    pub amount: Amount,
+    #[validate(length(min = 1))]
+    pub description: String,
+    /// `categoryId` を指定した場合は省略でき、指定してもそのカテゴリの分類で上書きされる
+    pub category: Option<TransactionCategory>,
+    pub category_id: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub transaction_date: Option<NaiveDate>,
+    pub settlement_info: Option<SettlementInfoDto>,
+    pub account_id: Option<String>,
+}
+
+impl CreateTransactionRequest {
+    /// 認証済みユーザーの取引を作成
+    ///
+    /// `categoryId` のカテゴリは呼び出し側で確認し、`Transaction::assign_category` で反映する。
+    /// `transactionDate` の省略時は `today`（ユーザーのタイムゾーンでの今日）の取引にする
+    pub fn into_entity(self, user_id: UserId, today: NaiveDate) -> DomainResult<Transaction> {
+        let category = match (self.category, &self.category_id) {
+            (Some(category), _) => category,
+            (None, Some(_)) => TransactionCategory::Other,
+            (None, None) => {
+                return Err(DomainError::Validation(
+                    "Either category or categoryId is required".to_string(),
+                ))
+            }
+        };
+        let mut transaction = Transaction::new(
+            user_id,
+            self.transaction_type,
+            self.amount,
+            self.description,
+            category,
+        );
+        transaction.category_id = self.category_id;
+        transaction.direction = self.direction;
+        for tag in self.tags {
+            transaction.add_tag(tag);
+        }
+        transaction.transaction_date = TransactionDate::new(self.transaction_date.unwrap_or(today));
+        transaction.settlement_info = self.settlement_info.map(Into::into);
+        transaction.account_id = self.account_id;
+        Ok(transaction)
+    }
+}
+
+/// 取引更新リクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTransactionRequest {
+    pub direction: Option<TransactionDirection>,
+    pub amount: Option<Amount>,
+    #[validate(length(min = 1))]
+    pub description: Option<String>,
+    pub category: Option<TransactionCategory>,
+    pub category_id: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub transaction_date: Option<NaiveDate>,
+    pub account_id: Option<String>,
+}
+
+impl UpdateTransactionRequest {
+    /// 指定された項目のみを取引に反映
+    ///
+    /// 分類だけを変更するとユーザー定義カテゴリは外れる
+    pub fn apply(self, transaction: &mut Transaction) {
+        if let Some(direction) = self.direction {
+            transaction.direction = direction;
+        }
+        if let Some(amount) = self.amount {
+            transaction.amount = amount;
+        }
+        if let Some(tags) = self.tags {
+            transaction.tags.clear();
+            for tag in tags {
+                transaction.add_tag(tag);
+            }
+        }
+        if let Some(date) = self.transaction_date {
+            transaction.transaction_date = TransactionDate::new(date);
+        }
+        if let Some(account_id) = self.account_id {
+            transaction.account_id = Some(account_id);
+        }
+        transaction.update(self.description, self.category);
+        if let Some(category_id) = self.category_id {
+            transaction.category_id = Some(category_id);
+        }
+    }
+}
+
+fn default_page_size() -> usize {
+    50
+}
+
+/// 取引一覧の検索条件（`GET /transactions` のクエリパラメーター）
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTransactionsParams {
+    #[serde(default = "default_page_size")]
+    #[validate(range(min = 1, max = 100))]
+    pub limit: usize,
+    pub cursor: Option<String>,
+    #[serde(rename = "type")]
+    pub transaction_type: Option<TransactionType>,
+    pub direction: Option<TransactionDirection>,
+    pub category: Option<TransactionCategory>,
+    /// この日以降（含む）
+    pub start_date: Option<NaiveDate>,
+    /// この日以前（含む）
+    pub end_date: Option<NaiveDate>,
+}
+
+impl ListTransactionsParams {
+    /// リポジトリの検索条件に変換（カーソルは別途検証して設定する）
+    pub fn to_query(&self) -> TransactionQuery {
+        TransactionQuery {
+            transaction_type: self.transaction_type.clone(),
+            direction: self.direction,
+            category: self.category.clone(),
+            from: self.start_date.map(TransactionDate::new),
+            until: self
+                .end_date
+                .and_then(|date| date.succ_opt())
+                .map(TransactionDate::new),
+            limit: self.limit,
+            after: None,
+        }
+    }
+}
+
+/// 取引一覧
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionListResponse {
+    pub transactions: Vec<TransactionResponse>,
+    pub has_more: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl TransactionListResponse {
+    pub fn new(transactions: Vec<Transaction>, next_cursor: Option<String>) -> Self {
+        Self {
+            transactions: transactions.into_iter().map(Into::into).collect(),
+            has_more: next_cursor.is_some(),
+            next_cursor,
+        }
+    }
+}
+
+/// 予算
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetResponse {
+    pub budget_id: String,
+    pub user_id: String,
+    pub target: BudgetTarget,
+    /// 対象が1つのカテゴリの場合のみ（`target` 導入前のクライアント向け）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<TransactionCategory>,
+    pub amount: Amount,
+    pub period: BudgetPeriod,
+    pub alert_threshold: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub month_start_day: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub week_start: Option<DayOfWeek>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<NaiveDate>,
+    pub rollover: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Budget> for BudgetResponse {
+    fn from(budget: Budget) -> Self {
+        Self {
+            budget_id: budget.budget_id,
+            user_id: budget.user_id.0,
+            category: budget.target.single_category().cloned(),
+            target: budget.target,
+            amount: budget.amount,
+            period: budget.period,
+            alert_threshold: budget.alert_threshold,
+            month_start_day: budget.month_start_day,
+            week_start: budget.week_start,
+            start_date: budget.start_date,
+            end_date: budget.end_date,
+            rollover: budget.rollover,
+            created_at: budget.created_at,
+            updated_at: budget.updated_at,
+        }
+    }
+}
+
+/// アラート閾値の既定値（OpenAPI の `alertThreshold` の default）
+pub const DEFAULT_ALERT_THRESHOLD: f64 = 0.8;
+
+/// 予算の対象（`target`、または1つのカテゴリを指定する `category` のいずれか一方）
+fn budget_target(
+    category: Option<TransactionCategory>,
+    target: Option<BudgetTarget>,
+) -> DomainResult<Option<BudgetTarget>> {
+    match (category, target) {
+        (Some(_), Some(_)) => Err(DomainError::Validation(
+            "Specify either category or target, not both".to_string(),
+        )),
+        (Some(category), None) => Ok(Some(BudgetTarget::category(category))),
+        (None, target) => Ok(target),
+    }
+}
+
+/// 予算作成リクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBudgetRequest {
+    pub category: Option<TransactionCategory>,
+    pub target: Option<BudgetTarget>,
+    pub amount: Amount,
+    pub period: BudgetPeriod,
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub alert_threshold: Option<f64>,
+    #[validate(range(min = 1, max = 31))]
+    pub month_start_day: Option<u32>,
+    pub week_start: Option<DayOfWeek>,
+    /// 期間指定（`CUSTOM`）の初日と最終日
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub rollover: bool,
+}
+
+impl CreateBudgetRequest {
+    /// 認証済みユーザーの予算を作成
+    pub fn into_entity(self, user_id: UserId) -> DomainResult<Budget> {
+        let target = budget_target(self.category, self.target)?.ok_or_else(|| {
+            DomainError::Validation("Either category or target is required".to_string())
+        })?;
+        let mut budget = Budget::new(
+            user_id,
+            target,
+            self.amount,
+            self.period,
+            self.alert_threshold.unwrap_or(DEFAULT_ALERT_THRESHOLD),
+        );
+        budget.month_start_day = self.month_start_day;
+        budget.week_start = self.week_start;
+        budget.start_date = self.start_date;
+        budget.end_date = self.end_date;
+        budget.rollover = self.rollover;
+        Ok(budget)
+    }
+}
+
+/// 予算更新リクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBudgetRequest {
+    pub category: Option<TransactionCategory>,
+    pub target: Option<BudgetTarget>,
+    pub amount: Option<Amount>,
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub alert_threshold: Option<f64>,
+    #[validate(range(min = 1, max = 31))]
+    pub month_start_day: Option<u32>,
+    pub week_start: Option<DayOfWeek>,
+    pub rollover: Option<bool>,
+}
+
+impl UpdateBudgetRequest {
+    /// 指定された項目のみを予算に反映
+    pub fn apply(self, budget: &mut Budget) -> DomainResult<()> {
+        budget.update(
+            budget_target(self.category, self.target)?,
+            self.amount,
+            self.alert_threshold,
+            self.month_start_day,
+            self.week_start,
+            self.rollover,
+        )
+    }
+}
+
+/// 予算の消化状況
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatusResponse {
+    pub budget_id: String,
+    pub target: BudgetTarget,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<TransactionCategory>,
+    pub period: BudgetPeriod,
+    /// 期間の初日（ユーザーのタイムゾーンでの日付）
+    pub period_start: NaiveDate,
+    /// 期間の最終日
+    pub period_end: NaiveDate,
+    pub budget_amount: Amount,
+    pub spent_amount: Amount,
+    /// 前の期間からの繰越額（超過を繰り越した場合は負）
+    pub carried_over_amount: Amount,
+    /// 残額（超過している場合は負）
+    pub remaining_amount: Amount,
+    /// 使用率（1.0 で予算額ちょうど）
+    pub usage: f64,
+    pub should_alert: bool,
+}
+
+impl From<BudgetStatus> for BudgetStatusResponse {
+    fn from(status: BudgetStatus) -> Self {
+        Self {
+            budget_id: status.budget.budget_id,
+            category: status.budget.target.single_category().cloned(),
+            target: status.budget.target,
+            period: status.budget.period,
+            period_start: status.window.start,
+            period_end: status.window.last_day(),
+            budget_amount: status.budget.amount,
+            spent_amount: status.spent,
+            carried_over_amount: status.carried_over,
+            remaining_amount: status.remaining,
+            usage: status.usage,
+            should_alert: status.should_alert,
+        }
+    }
+}
+
+/// 発生した予算アラート
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetAlertResponse {
+    pub alert_id: String,
+    pub budget_id: String,
+    /// アラートが発生した期間の初日
+    pub period_start: NaiveDate,
+    /// 期間の最終日
+    pub period_end: NaiveDate,
+    /// 超えた閾値（予算額に対する割合）
+    pub threshold: f64,
+    /// 発生時点の使用率
+    pub usage: f64,
+    pub spent_amount: Amount,
+    pub budget_amount: Amount,
+    pub raised_at: DateTime<Utc>,
+    /// 通知先への送信が済んだ日時（未送信の場合は省略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<BudgetAlertRaised> for BudgetAlertResponse {
+    fn from(alert: BudgetAlertRaised) -> Self {
+        Self {
+            alert_id: alert.alert_id,
+            budget_id: alert.budget_id,
+            period_start: alert.period_start,
+            period_end: alert.period_end,
+            threshold: alert.threshold,
+            usage: alert.usage,
+            spent_amount: alert.spent_amount,
+            budget_amount: alert.budget_amount,
+            raised_at: alert.raised_at,
+            delivered_at: alert.delivered_at,
+        }
+    }
+}
+
+/// 値が `null` の項目と省略された項目を区別する（`null` は `Some(None)` になる）
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// ユーザー定義カテゴリ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryResponse {
+    pub category_id: String,
+    pub user_id: String,
+    pub name: String,
+    pub kind: TransactionCategory,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    pub is_default: bool,
+    pub is_archived: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Category> for CategoryResponse {
+    fn from(category: Category) -> Self {
+        Self {
+            category_id: category.category_id,
+            user_id: category.user_id.0,
+            name: category.name,
+            kind: category.kind,
+            icon: category.icon,
+            color: category.color,
+            parent_id: category.parent_id,
+            is_default: category.is_default,
+            is_archived: category.is_archived,
+            created_at: category.created_at,
+            updated_at: category.updated_at,
+        }
+    }
+}
+
+/// カテゴリ作成リクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCategoryRequest {
+    #[validate(length(min = 1))]
+    pub name: String,
+    /// 子カテゴリは親の分類を引き継ぐため省略できる
+    pub kind: Option<TransactionCategory>,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    pub parent_id: Option<String>,
+}
+
+impl CreateCategoryRequest {
+    /// 認証済みユーザーのカテゴリを作成
+    pub fn into_entity(self, user_id: UserId) -> DomainResult<Category> {
+        let kind = match (self.kind, &self.parent_id) {
+            (Some(kind), _) => kind,
+            (None, Some(_)) => TransactionCategory::Other,
+            (None, None) => {
+                return Err(DomainError::Validation(
+                    "kind is required for top-level categories".to_string(),
+                ))
+            }
+        };
+        let mut category = Category::new(user_id, self.name, kind);
+        category.icon = self.icon;
+        category.color = self.color;
+        category.parent_id = self.parent_id;
+        Ok(category)
+    }
+}
+
+/// カテゴリ更新リクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCategoryRequest {
+    #[validate(length(min = 1))]
+    pub name: Option<String>,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    /// `null` を指定すると親から外してトップレベルのカテゴリにする
+    #[serde(default, deserialize_with = "nullable")]
+    pub parent_id: Option<Option<String>>,
+    pub is_archived: Option<bool>,
+}
+
+impl UpdateCategoryRequest {
+    /// 指定された項目のみをカテゴリに反映
+    pub fn apply(self, category: &mut Category) {
+        category.update(
+            self.name,
+            self.icon,
+            self.color,
+            self.parent_id,
+            self.is_archived,
+        );
+    }
+}
+
+/// カテゴリ別支出の集計期間（`GET /reports/category-spending` のクエリパラメーター）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorySpendingParams {
+    pub start_date: NaiveDate,
+    /// 集計期間の最終日（この日を含む）
+    pub end_date: NaiveDate,
+}
+
+impl CategorySpendingParams {
+    pub fn window(&self) -> DomainResult<PeriodWindow> {
+        if self.end_date < self.start_date {
+            return Err(DomainError::Validation(
+                "endDate must not be before startDate".to_string(),
+            ));
+        }
+        Ok(PeriodWindow::custom(self.start_date, self.end_date))
+    }
+}
+
+/// カテゴリごとの支出額
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorySpendingResponse {
+    pub category_id: String,
+    pub name: String,
+    pub kind: TransactionCategory,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    /// このカテゴリに直接記録された支出額
+    pub own_amount: Amount,
+    /// 子カテゴリの支出額を含めた合計
+    pub total_amount: Amount,
+}
+
+impl From<CategorySpending> for CategorySpendingResponse {
+    fn from(spending: CategorySpending) -> Self {
+        Self {
+            category_id: spending.category.category_id,
+            name: spending.category.name,
+            kind: spending.category.kind,
+            parent_id: spending.category.parent_id,
+            own_amount: spending.own,
+            total_amount: spending.total,
+        }
+    }
+}
+
+/// カテゴリ別支出レポート
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorySpendingReportResponse {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub categories: Vec<CategorySpendingResponse>,
+}
+
+impl CategorySpendingReportResponse {
+    pub fn new(params: &CategorySpendingParams, spending: Vec<CategorySpending>) -> Self {
+        Self {
+            start_date: params.start_date,
+            end_date: params.end_date,
+            categories: spending.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// 月次レポートの対象月（`GET /reports/monthly` のクエリパラメーター）
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyReportParams {
+    pub year: i32,
+    #[validate(range(min = 1, max = 12))]
+    pub month: u32,
+}
+
+impl MonthlyReportParams {
+    /// 対象月の初日
+    pub fn month_start(&self) -> DomainResult<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1).ok_or_else(|| {
+            DomainError::Validation(format!("Invalid month: {}-{}", self.year, self.month))
+        })
+    }
+}
+
+/// 割合（1.0 で100%）をパーセントに変換
+fn percentage(ratio: f64) -> f64 {
+    ratio * 100.0
+}
+
+/// 分類ごとの月の支出
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryExpenseResponse {
+    pub category: TransactionCategory,
+    pub amount: Amount,
+    /// 月の支出全体に占める割合（%）
+    pub percentage: f64,
+    pub previous_amount: Amount,
+    /// 前月からの増減
+    pub change: Amount,
+}
+
+impl CategoryExpenseResponse {
+    fn new(expense: CategoryExpense) -> DomainResult<Self> {
+        Ok(Self {
+            change: expense.amount.subtract(&expense.previous_amount)?,
+            category: expense.category,
+            amount: expense.amount,
+            percentage: percentage(expense.share),
+            previous_amount: expense.previous_amount,
+        })
+    }
+}
+
+/// 予算と実績
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetComparisonResponse {
+    pub budget_id: String,
+    pub target: BudgetTarget,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<TransactionCategory>,
+    pub period: BudgetPeriod,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub budget_amount: Amount,
+    pub actual_amount: Amount,
+    /// 残額（繰越額を含む。超過している場合は負）
+    pub remaining_amount: Amount,
+    /// 予算の使用率（%）
+    pub percentage: f64,
+}
+
+impl From<BudgetStatus> for BudgetComparisonResponse {
+    fn from(status: BudgetStatus) -> Self {
+        Self {
+            budget_id: status.budget.budget_id,
+            category: status.budget.target.single_category().cloned(),
+            target: status.budget.target,
+            period: status.budget.period,
+            period_start: status.window.start,
+            period_end: status.window.last_day(),
+            budget_amount: status.budget.amount,
+            actual_amount: status.spent,
+            remaining_amount: status.remaining,
+            percentage: percentage(status.usage),
+        }
+    }
+}
+
+/// 期間の収支
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodTotalsResponse {
+    pub total_income: Amount,
+    pub total_expense: Amount,
+    pub net_amount: Amount,
+}
+
+impl PeriodTotalsResponse {
+    fn new(totals: &PeriodTotals) -> DomainResult<Self> {
+        Ok(Self {
+            total_income: totals.income.clone(),
+            total_expense: totals.expense.clone(),
+            net_amount: totals.net()?,
+        })
+    }
+}
+
+/// 月次レポート
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyReportResponse {
+    pub year: i32,
+    pub month: u32,
+    pub total_income: Amount,
+    pub total_expense: Amount,
+    pub net_amount: Amount,
+    pub category_breakdown: Vec<CategoryExpenseResponse>,
+    pub budget_comparison: Vec<BudgetComparisonResponse>,
+    /// 月末時点で返済されていない立て替えの合計
+    pub outstanding_flow_amount: Amount,
+    /// 前月の収支
+    pub previous_month: PeriodTotalsResponse,
+    /// 前月からの増減
+    pub month_over_month: PeriodTotalsResponse,
+}
+
+impl MonthlyReportResponse {
+    pub fn new(report: MonthlyReport) -> DomainResult<Self> {
+        let current = PeriodTotalsResponse::new(&report.totals)?;
+        let previous = PeriodTotalsResponse::new(&report.previous)?;
+        let month_over_month = PeriodTotalsResponse {
+            total_income: current.total_income.subtract(&previous.total_income)?,
+            total_expense: current.total_expense.subtract(&previous.total_expense)?,
+            net_amount: current.net_amount.subtract(&previous.net_amount)?,
+        };
+        Ok(Self {
+            year: report.month.start.year(),
+            month: report.month.start.month(),
+            total_income: current.total_income,
+            total_expense: current.total_expense,
+            net_amount: current.net_amount,
+            category_breakdown: report
+                .categories
+                .into_iter()
+                .map(CategoryExpenseResponse::new)
+                .collect::<DomainResult<_>>()?,
+            budget_comparison: report.budgets.into_iter().map(Into::into).collect(),
+            outstanding_flow_amount: report.outstanding_flow,
+            previous_month: previous,
+            month_over_month,
+        })
+    }
+}
+
+/// 推移レポートの集計単位と範囲（`GET /reports/trends` のクエリパラメーター）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendReportParams {
+    pub granularity: ReportGranularity,
+    pub start_date: Option<NaiveDate>,
+    /// 集計範囲の最終日（この日を含む）
+    pub end_date: Option<NaiveDate>,
+}
+
+impl TrendReportParams {
+    /// 集計範囲（省略した場合は `None`。開始日と最終日は両方指定する）
+    pub fn window(&self) -> DomainResult<Option<PeriodWindow>> {
+        match (self.start_date, self.end_date) {
+            (None, None) => Ok(None),
+            (Some(start_date), Some(end_date)) => CategorySpendingParams {
+                start_date,
+                end_date,
+            }
+            .window()
+            .map(Some),
+            _ => Err(DomainError::Validation(
+                "startDate and endDate must be specified together".to_string(),
+            )),
+        }
+    }
+}
+
+/// 分類ごとの支出と割合
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryShareResponse {
+    pub category: TransactionCategory,
+    pub amount: Amount,
+    /// 区間の支出全体に占める割合（%）
+    pub percentage: f64,
+}
+
+impl From<CategoryShare> for CategoryShareResponse {
+    fn from(share: CategoryShare) -> Self {
+        Self {
+            category: share.category,
+            amount: share.amount,
+            percentage: percentage(share.share),
+        }
+    }
+}
+
+/// 推移レポートの1区間
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendPointResponse {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub total_income: Amount,
+    pub total_expense: Amount,
+    pub net_amount: Amount,
+    pub category_breakdown: Vec<CategoryShareResponse>,
+}
+
+impl TrendPointResponse {
+    fn new(point: TrendPoint) -> DomainResult<Self> {
+        let totals = PeriodTotalsResponse::new(&point.totals)?;
+        Ok(Self {
+            period_start: point.window.start,
+            period_end: point.window.last_day(),
+            total_income: totals.total_income,
+            total_expense: totals.total_expense,
+            net_amount: totals.net_amount,
+            category_breakdown: point.categories.into_iter().map(Into::into).collect(),
+        })
+    }
+}
+
+/// 推移レポート
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendReportResponse {
+    pub granularity: ReportGranularity,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// 集計範囲全体の収支
+    pub total_income: Amount,
+    pub total_expense: Amount,
+    pub net_amount: Amount,
+    pub points: Vec<TrendPointResponse>,
+}
+
+impl TrendReportResponse {
+    pub fn new(report: TrendReport) -> DomainResult<Self> {
+        let totals = PeriodTotalsResponse::new(&report.totals)?;
+        Ok(Self {
+            granularity: report.granularity,
+            start_date: report.window.start,
+            end_date: report.window.last_day(),
+            total_income: totals.total_income,
+            total_expense: totals.total_expense,
+            net_amount: totals.net_amount,
+            points: report
+                .points
+                .into_iter()
+                .map(TrendPointResponse::new)
+                .collect::<DomainResult<_>>()?,
+        })
+    }
+}
+
+fn default_payment_month_offset() -> u32 {
+    1
+}
+
+/// クレジットカードの締め日・引き落とし日
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingCycleDto {
+    pub closing_day: u32,
+    pub payment_day: u32,
+    /// 省略時は翌月払い
+    #[serde(default = "default_payment_month_offset")]
+    pub payment_month_offset: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_account_id: Option<String>,
+}
+
+impl From<BillingCycle> for BillingCycleDto {
+    fn from(cycle: BillingCycle) -> Self {
+        Self {
+            closing_day: cycle.closing_day,
+            payment_day: cycle.payment_day,
+            payment_month_offset: cycle.payment_month_offset,
+            payment_account_id: cycle.payment_account_id,
+        }
+    }
+}
+
+impl From<BillingCycleDto> for BillingCycle {
+    fn from(dto: BillingCycleDto) -> Self {
+        Self {
+            closing_day: dto.closing_day,
+            payment_day: dto.payment_day,
+            payment_month_offset: dto.payment_month_offset,
+            payment_account_id: dto.payment_account_id,
+        }
+    }
+}
+
+/// 口座
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountResponse {
+    pub account_id: String,
+    pub user_id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub account_type: AccountType,
+    pub opening_balance: Amount,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credit_limit: Option<Amount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billing_cycle: Option<BillingCycleDto>,
+    pub is_active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Account> for AccountResponse {
+    fn from(account: Account) -> Self {
+        Self {
+            account_id: account.account_id,
+            user_id: account.user_id.0,
+            name: account.name,
+            account_type: account.account_type,
+            opening_balance: account.opening_balance,
+            credit_limit: account.credit_limit,
+            billing_cycle: account.billing_cycle.map(Into::into),
+            is_active: account.is_active,
+            description: account.description,
+            created_at: account.created_at,
+            updated_at: account.updated_at,
+        }
+    }
+}
+
+/// 口座作成リクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAccountRequest {
+    #[validate(length(min = 1))]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub account_type: AccountType,
+    /// 省略時は 0 円
+    pub opening_balance: Option<Amount>,
+    pub credit_limit: Option<Amount>,
+    pub billing_cycle: Option<BillingCycleDto>,
+    pub description: Option<String>,
+}
+
+impl CreateAccountRequest {
+    /// 認証済みユーザーの口座を作成
+    pub fn into_entity(self, user_id: UserId) -> Account {
+        let mut account = Account::new(
+            user_id,
+            self.name,
+            self.account_type,
+            self.opening_balance.unwrap_or_else(|| Amount::jpy(0)),
+        );
+        account.credit_limit = self.credit_limit;
+        account.billing_cycle = self.billing_cycle.map(Into::into);
+        account.description = self.description;
+        account
+    }
+}
+
+/// 口座更新リクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAccountRequest {
+    #[validate(length(min = 1))]
+    pub name: Option<String>,
+    pub credit_limit: Option<Amount>,
+    pub billing_cycle: Option<BillingCycleDto>,
+    pub is_active: Option<bool>,
+    pub description: Option<String>,
+}
+
+impl UpdateAccountRequest {
+    /// 指定された項目のみを口座に反映
+    pub fn apply(self, account: &mut Account) {
+        if let Some(cycle) = self.billing_cycle {
+            account.billing_cycle = Some(cycle.into());
+        }
+        account.update(
+            self.name,
+            self.description,
+            self.credit_limit,
+            self.is_active,
+        );
+    }
+}
+
+/// 口座残高の取得条件（`GET /accounts/{accountId}/balance` のクエリパラメーター）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalanceParams {
+    /// この日（ユーザーのタイムゾーンでの暦日）の終わり時点の残高（省略時はすべての取引を反映）
+    pub as_of: Option<NaiveDate>,
+}
+
+/// 口座残高
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalanceResponse {
+    pub account_id: String,
+    pub current_balance: Amount,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_balance: Option<Amount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<NaiveDate>,
+}
+
+impl AccountBalanceResponse {
+    pub fn new(balance: AccountBalance, as_of: Option<NaiveDate>) -> Self {
+        Self {
+            account_id: balance.account_id,
+            current_balance: balance.current_balance,
+            available_balance: balance.available_balance,
+            as_of,
+        }
+    }
+}
+
+/// 明細・引き落とし予定の基準日（`asOf` クエリパラメーター）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardStatementParams {
+    /// この日の時点で締め・引き落としを判定する（省略時はユーザーのタイムゾーンでの今日）
+    pub as_of: Option<NaiveDate>,
+}
+
+/// クレジットカードの利用明細
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardStatementResponse {
+    pub period_start: NaiveDate,
+    pub closing_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub total_amount: Amount,
+    /// 締め済み（請求額が確定している）かどうか
+    pub is_closed: bool,
+    pub transaction_ids: Vec<String>,
+}
+
+impl CardStatementResponse {
+    pub fn new(statement: CardStatement, today: NaiveDate) -> Self {
+        Self {
+            is_closed: statement.is_closed(today),
+            period_start: statement.period_start,
+            closing_date: statement.closing_date,
+            due_date: statement.due_date,
+            total_amount: statement.total,
+            transaction_ids: statement
+                .transaction_ids
+                .into_iter()
+                .map(|id| id.0)
+                .collect(),
+        }
+    }
+}
+
+/// クレジットカードの明細一覧
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardStatementListResponse {
+    pub account_id: String,
+    pub as_of: NaiveDate,
+    pub statements: Vec<CardStatementResponse>,
+    /// まだ締められていない利用額
+    pub unbilled_amount: Amount,
+}
+
+impl CardStatementListResponse {
+    pub fn new(summary: CardBillingSummary, today: NaiveDate) -> Self {
+        Self {
+            account_id: summary.account_id,
+            as_of: today,
+            statements: summary
+                .statements
+                .into_iter()
+                .map(|statement| CardStatementResponse::new(statement, today))
+                .collect(),
+            unbilled_amount: summary.unbilled,
+        }
+    }
+}
+
+/// カード請求の引き落とし予定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledDebitResponse {
+    pub card_account_id: String,
+    pub closing_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub amount: Amount,
+    pub is_confirmed: bool,
+}
+
+impl From<ScheduledDebit> for ScheduledDebitResponse {
+    fn from(debit: ScheduledDebit) -> Self {
+        Self {
+            card_account_id: debit.card_account_id,
+            closing_date: debit.closing_date,
+            due_date: debit.due_date,
+            amount: debit.amount,
+            is_confirmed: debit.is_confirmed,
+        }
+    }
+}
+
+/// 口座の引き落とし予定一覧
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingDebitsResponse {
+    pub account_id: String,
+    pub as_of: NaiveDate,
+    pub debits: Vec<ScheduledDebitResponse>,
+    pub total_amount: Amount,
+}
+
+impl UpcomingDebitsResponse {
+    pub fn new(projection: DebitProjection, today: NaiveDate) -> Self {
+        Self {
+            account_id: projection.account_id,
+            as_of: today,
+            debits: projection.debits.into_iter().map(Into::into).collect(),
+            total_amount: projection.total,
+        }
+    }
+}
+
+/// 口座間移動
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferResponse {
+    pub transfer_id: String,
+    pub from_account_id: String,
+    pub to_account_id: String,
+    pub amount: Amount,
+    pub description: String,
+    pub transaction_date: NaiveDate,
+    /// 出金側・入金側の取引
+    pub transactions: Vec<TransactionResponse>,
+}
+
+impl TransferResponse {
+    pub fn new(transfer: AccountTransfer, transactions: Vec<Transaction>) -> Self {
+        Self {
+            transfer_id: transfer.transfer_id,
+            from_account_id: transfer.from_account_id,
+            to_account_id: transfer.to_account_id,
+            amount: transfer.amount,
+            description: transfer.description,
+            transaction_date: transfer.transaction_date.date(),
+            transactions: transactions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// 口座間移動の作成リクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTransferRequest {
+    #[validate(length(min = 1))]
+    pub from_account_id: String,
+    #[validate(length(min = 1))]
+    pub to_account_id: String,
+    pub amount: Amount,
+    #[validate(length(min = 1))]
+    pub description: String,
+    pub transaction_date: Option<NaiveDate>,
+}
+
+impl CreateTransferRequest {
+    /// 認証済みユーザーの口座間移動を作成
+    ///
+    /// `transactionDate` の省略時は `today`（ユーザーのタイムゾーンでの今日）の移動にする
+    pub fn into_entity(self, user_id: UserId, today: NaiveDate) -> AccountTransfer {
+        let mut transfer = AccountTransfer::new(
+            user_id,
+            self.from_account_id,
+            self.to_account_id,
+            self.amount,
+            self.description,
+        );
+        transfer.transaction_date = TransactionDate::new(self.transaction_date.unwrap_or(today));
+        transfer
+    }
+}
+
+/// 精算の返済記録
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepaymentDto {
+    pub amount: Amount,
+    pub repaid_at: DateTime<Utc>,
+}
+
+impl From<Repayment> for RepaymentDto {
+    fn from(repayment: Repayment) -> Self {
+        Self {
+            amount: repayment.amount,
+            repaid_at: repayment.repaid_at,
+        }
+    }
+}
+
+/// 精算
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementResponse {
+    pub settlement_id: String,
+    pub creditor_user_id: String,
+    pub debtor_user_id: String,
+    pub amount: Amount,
+    pub repaid_amount: Amount,
+    pub remaining_amount: Amount,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    pub repayments: Vec<RepaymentDto>,
+    pub status: SettlementStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Settlement> for SettlementResponse {
+    fn from(settlement: Settlement) -> Self {
+        Self {
+            repaid_amount: settlement.repaid_amount(),
+            remaining_amount: settlement.remaining_amount(),
+            settlement_id: settlement.settlement_id,
+            creditor_user_id: settlement.creditor_user_id.0,
+            debtor_user_id: settlement.debtor_user_id.0,
+            amount: settlement.amount,
+            description: settlement.description,
+            transaction_id: settlement.transaction_id.map(|id| id.0),
+            repayments: settlement.repayments.into_iter().map(Into::into).collect(),
+            status: settlement.status,
+            completed_at: settlement.completed_at,
+            cancelled_at: settlement.cancelled_at,
+            created_at: settlement.created_at,
+            updated_at: settlement.updated_at,
+        }
+    }
+}
+
+/// 精算作成リクエスト
+///
+/// `transactionId` を指定すると、その立て替え取引の精算として作成する（`amount` は省略可）
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSettlementRequest {
+    #[validate(length(min = 1))]
+    pub debtor_user_id: String,
+    pub amount: Option<Amount>,
+    pub description: Option<String>,
+    #[validate(length(min = 1))]
+    pub transaction_id: Option<String>,
+}
+
+impl CreateSettlementRequest {
+    /// 取引に紐づかない精算を作成（認証済みユーザーが債権者）
+    pub fn into_entity(self, creditor_user_id: UserId) -> DomainResult<Settlement> {
+        let amount = self.amount.ok_or_else(|| {
+            DomainError::Validation("amount is required without transactionId".to_string())
+        })?;
+        Settlement::new(
+            creditor_user_id,
+            UserId::new(self.debtor_user_id),
+            amount,
+            self.description,
+        )
+    }
+}
+
+/// 返済記録リクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct RepaySettlementRequest {
+    pub amount: Amount,
+}
+
+/// 割り勘方法の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SplitMethod {
+    Equal,
+    Shares,
+    Percentages,
+    Exact,
+}
+
+/// 割り勘の対象メンバー（`method` に応じた項目だけを指定する）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitMemberDto {
+    pub user_id: String,
+    /// 口数（`SHARES`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shares: Option<u64>,
+    /// 割合（`PERCENTAGES`。1/100 % 単位で、合計は 10000）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub basis_points: Option<u32>,
+    /// 負担額（`EXACT`。合計は取引額と一致すること）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<Amount>,
+}
+
+impl SplitMemberDto {
+    fn of(user_id: UserId) -> Self {
+        Self {
+            user_id: user_id.0,
+            shares: None,
+            basis_points: None,
+            amount: None,
+        }
+    }
+
+    /// `method` に必要な項目を取り出す
+    fn required<T>(&self, value: Option<T>, field: &str) -> DomainResult<T> {
+        value.ok_or_else(|| {
+            DomainError::Validation(format!("{} is required for {}", field, self.user_id))
+        })
+    }
+}
+
+/// 割り勘方法
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitRuleDto {
+    pub method: SplitMethod,
+    pub members: Vec<SplitMemberDto>,
+}
+
+impl SplitRuleDto {
+    pub fn into_rule(self) -> DomainResult<SplitRule> {
+        let members = self.members.into_iter();
+        Ok(match self.method {
+            SplitMethod::Equal => {
+                SplitRule::Equal(members.map(|m| UserId::new(m.user_id)).collect())
+            }
+            SplitMethod::Shares => SplitRule::Shares(
+                members
+                    .map(|m| {
+                        let shares = m.required(m.shares, "shares")?;
+                        Ok((UserId::new(m.user_id), shares))
+                    })
+                    .collect::<DomainResult<_>>()?,
+            ),
+            SplitMethod::Percentages => SplitRule::Percentages(
+                members
+                    .map(|m| {
+                        let points = m.required(m.basis_points, "basisPoints")?;
+                        Ok((UserId::new(m.user_id), points))
+                    })
+                    .collect::<DomainResult<_>>()?,
+            ),
+            SplitMethod::Exact => SplitRule::Exact(
+                members
+                    .map(|m| {
+                        let amount = m.required(m.amount.clone(), "amount")?;
+                        Ok((UserId::new(m.user_id), amount))
+                    })
+                    .collect::<DomainResult<_>>()?,
+            ),
+        })
+    }
+}
+
+impl From<SplitRule> for SplitRuleDto {
+    fn from(rule: SplitRule) -> Self {
+        let (method, members) = match rule {
+            SplitRule::Equal(members) => (
+                SplitMethod::Equal,
+                members.into_iter().map(SplitMemberDto::of).collect(),
+            ),
+            SplitRule::Shares(shares) => (
+                SplitMethod::Shares,
+                shares
+                    .into_iter()
+                    .map(|(user_id, shares)| SplitMemberDto {
+                        shares: Some(shares),
+                        ..SplitMemberDto::of(user_id)
+                    })
+                    .collect(),
+            ),
+            SplitRule::Percentages(percentages) => (
+                SplitMethod::Percentages,
+                percentages
+                    .into_iter()
+                    .map(|(user_id, points)| SplitMemberDto {
+                        basis_points: Some(points),
+                        ..SplitMemberDto::of(user_id)
+                    })
+                    .collect(),
+            ),
+            SplitRule::Exact(amounts) => (
+                SplitMethod::Exact,
+                amounts
+                    .into_iter()
+                    .map(|(user_id, amount)| SplitMemberDto {
+                        amount: Some(amount),
+                        ..SplitMemberDto::of(user_id)
+                    })
+                    .collect(),
+            ),
+        };
+        Self { method, members }
+    }
+}
+
+/// グループ取引の負担者と負担額
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionParticipantDto {
+    pub user_id: String,
+    pub share_amount: Amount,
+}
+
+impl From<TransactionParticipant> for TransactionParticipantDto {
+    fn from(participant: TransactionParticipant) -> Self {
+        Self {
+            user_id: participant.user_id.0,
+            share_amount: participant.share_amount,
+        }
+    }
+}
+
+/// グループ取引
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupTransactionResponse {
+    pub transaction_id: String,
+    pub group_id: String,
+    pub paid_by: String,
+    pub amount: Amount,
+    pub description: String,
+    pub category: TransactionCategory,
+    pub split_rule: SplitRuleDto,
+    pub participants: Vec<TransactionParticipantDto>,
+    pub transaction_date: NaiveDate,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<GroupTransaction> for GroupTransactionResponse {
+    fn from(transaction: GroupTransaction) -> Self {
+        Self {
+            transaction_id: transaction.transaction_id.0,
+            group_id: transaction.group_id,
+            paid_by: transaction.paid_by.0,
+            amount: transaction.amount,
+            description: transaction.description,
+            category: transaction.category,
+            split_rule: transaction.split_rule.into(),
+            participants: transaction
+                .participants
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            transaction_date: transaction.transaction_date.date(),
+            created_at: transaction.created_at,
+            updated_at: transaction.updated_at,
+        }
+    }
+}
+
+/// グループ取引作成リクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateGroupTransactionRequest {
+    /// 省略時は記録したメンバーが支払った取引にする
+    #[validate(length(min = 1))]
+    pub paid_by: Option<String>,
+    pub amount: Amount,
+    #[validate(length(min = 1))]
+    pub description: String,
+    pub category: Option<TransactionCategory>,
+    pub split_rule: SplitRuleDto,
+    pub transaction_date: Option<NaiveDate>,
+}
+
+impl CreateGroupTransactionRequest {
+    /// 認証済みユーザーがグループに記録する取引を作成
+    ///
+    /// 負担額は割り勘方法から計算する。`transactionDate` の省略時は `today`
+    /// （記録したユーザーのタイムゾーンでの今日）の取引にする
+    pub fn into_entity(
+        self,
+        group: &Group,
+        user_id: UserId,
+        today: NaiveDate,
+    ) -> DomainResult<GroupTransaction> {
+        let mut transaction = GroupTransaction::new(
+            group,
+            self.paid_by.map(UserId::new).unwrap_or(user_id),
+            self.amount,
+            self.description,
+            self.category.unwrap_or(TransactionCategory::Other),
+            self.split_rule.into_rule()?,
+        )?;
+        transaction.transaction_date = TransactionDate::new(self.transaction_date.unwrap_or(today));
+        Ok(transaction)
+    }
+}
+
+/// グループの精算プラン
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptimalSettlementPlanResponse {
+    pub group_id: String,
+    pub settlements: Vec<OptimalSettlementResponse>,
+    pub total_transfers: usize,
+}
+
+impl From<SettlementPlan> for OptimalSettlementPlanResponse {
+    fn from(plan: SettlementPlan) -> Self {
+        Self {
+            group_id: plan.group_id,
+            total_transfers: plan.transfers.len(),
+            settlements: plan.transfers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// 精算のための送金（`fromUserId` が `toUserId` に支払う）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptimalSettlementResponse {
+    pub from_user_id: String,
+    pub to_user_id: String,
+    pub amount: Amount,
+}
+
+impl From<Transfer> for OptimalSettlementResponse {
+    fn from(transfer: Transfer) -> Self {
+        Self {
+            from_user_id: transfer.from.0,
+            to_user_id: transfer.to.0,
+            amount: transfer.amount,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_create_transaction_request_uses_api_field_names() {
+        let request: CreateTransactionRequest = serde_json::from_value(json!({
+            "type": "FLOW",
+            "amount": { "value": 1200, "currency": "JPY" },
+            "description": "立て替え",
+            "category": "FOOD",
+            "tags": ["飲み会"],
+            "transactionDate": "2025-03-01"
+        }))
+        .unwrap();
+        assert!(request.validate().is_ok());
+
+        let today = NaiveDate::from_ymd_opt(2025, 4, 1).unwrap();
+        let transaction = request
+            .into_entity(UserId::new("user123".to_string()), today)
+            .unwrap();
+        assert_eq!(transaction.transaction_type, TransactionType::Flow);
+        assert_eq!(transaction.direction, TransactionDirection::Expense);
+        assert_eq!(transaction.tags, vec!["飲み会".to_string()]);
+        assert_eq!(
+            transaction.transaction_date.date(),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()
+        );
+
+        let response = serde_json::to_value(TransactionResponse::from(transaction)).unwrap();
+        assert_eq!(response["type"], "FLOW");
+        assert_eq!(response["direction"], "EXPENSE");
+        assert_eq!(response["transactionDate"], "2025-03-01");
+        assert_eq!(response["amount"]["value"], 1200);
+
+        // 日付の省略時は（UTC ではなく）ユーザーのタイムゾーンでの今日
+        let request: CreateTransactionRequest = serde_json::from_value(json!({
+            "type": "REAL",
+            "amount": { "value": 800, "currency": "JPY" },
+            "description": "朝食",
+            "category": "FOOD"
+        }))
+        .unwrap();
+        let transaction = request
+            .into_entity(UserId::new("user123".to_string()), today)
+            .unwrap();
+        assert_eq!(transaction.transaction_date.date(), today);
+    }
+
+    #[test]
+    fn test_budget_request_validation() {
+        let request: CreateBudgetRequest = serde_json::from_value(json!({
+            "category": "FOOD",
+            "amount": { "value": 30000, "currency": "JPY" },
+            "period": "MONTHLY",
+            "alertThreshold": 1.5
+        }))
+        .unwrap();
+        assert!(request.validate().is_err());
+
+        let request: CreateBudgetRequest = serde_json::from_value(json!({
+            "category": "FOOD",
+            "amount": { "value": 30000, "currency": "JPY" },
+            "period": "MONTHLY"
+        }))
+        .unwrap();
+        let budget = request
+            .into_entity(UserId::new("user123".to_string()))
+            .unwrap();
+        assert_eq!(budget.alert_threshold, DEFAULT_ALERT_THRESHOLD);
+        assert_eq!(
+            budget.target,
+            BudgetTarget::category(TransactionCategory::Food)
+        );
+    }
+
+    #[test]
+    fn test_budget_request_target() {
+        let create = |target: Value| -> DomainResult<Budget> {
+            let mut body = json!({
+                "amount": { "value": 30000, "currency": "JPY" },
+                "period": "MONTHLY"
+            });
+            body.as_object_mut()
+                .unwrap()
+                .extend(target.as_object().unwrap().clone());
+            serde_json::from_value::<CreateBudgetRequest>(body)
+                .unwrap()
+                .into_entity(UserId::new("user123".to_string()))
+        };
+
+        let budget = create(json!({
+            "target": { "type": "CATEGORIES", "categories": ["FOOD", "ENTERTAINMENT"] }
+        }))
+        .unwrap();
+        assert_eq!(
+            budget.target,
+            BudgetTarget::Categories {
+                categories: vec![
+                    TransactionCategory::Food,
+                    TransactionCategory::Entertainment
+                ]
+            }
+        );
+        let response = BudgetResponse::from(budget);
+        assert_eq!(response.category, None);
+
+        let budget = create(json!({ "target": { "type": "TAGS", "tags": ["旅行"] } })).unwrap();
+        assert_eq!(
+            budget.target,
+            BudgetTarget::Tags {
+                tags: vec!["旅行".to_string()]
+            }
+        );
+        let budget = create(json!({ "target": { "type": "ALL" } })).unwrap();
+        assert_eq!(budget.target, BudgetTarget::All);
+
+        assert!(create(json!({})).is_err());
+        assert!(create(json!({ "category": "FOOD", "target": { "type": "ALL" } })).is_err());
+    }
+
+    #[test]
+    fn test_list_transactions_params() {
+        let params: ListTransactionsParams = serde_json::from_value(json!({
+            "type": "REAL",
+            "direction": "INCOME",
+            "category": "FOOD",
+            "startDate": "2025-03-01",
+            "endDate": "2025-03-31"
+        }))
+        .unwrap();
+        assert!(params.validate().is_ok());
+
+        let query = params.to_query();
+        assert_eq!(query.limit, 50);
+        assert_eq!(query.transaction_type, Some(TransactionType::Real));
+        assert_eq!(query.direction, Some(TransactionDirection::Income));
+        assert_eq!(
+            query.from.unwrap().date(),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()
+        );
+        // 終了日は当日を含む
+        assert_eq!(
+            query.until.unwrap().date(),
+            NaiveDate::from_ymd_opt(2025, 4, 1).unwrap()
+        );
+
+        let params: ListTransactionsParams =
+            serde_json::from_value(json!({ "limit": 101 })).unwrap();
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn test_split_rule_dto() {
+        let dto: SplitRuleDto = serde_json::from_value(json!({
+            "method": "PERCENTAGES",
+            "members": [
+                { "userId": "alice", "basisPoints": 6000 },
+                { "userId": "bob", "basisPoints": 4000 }
+            ]
+        }))
+        .unwrap();
+        let rule = dto.clone().into_rule().unwrap();
+        assert_eq!(
+            rule,
+            SplitRule::Percentages(vec![
+                (UserId::new("alice".to_string()), 6000),
+                (UserId::new("bob".to_string()), 4000),
+            ])
+        );
+        assert_eq!(SplitRuleDto::from(rule), dto);
+        // 方法に関係しない項目は出力しない
+        assert_eq!(
+            serde_json::to_value(SplitRuleDto::from(SplitRule::Equal(vec![UserId::new(
+                "alice".to_string()
+            )])))
+            .unwrap(),
+            json!({ "method": "EQUAL", "members": [{ "userId": "alice" }] })
+        );
+
+        // 方法に必要な項目がない
+        let dto: SplitRuleDto = serde_json::from_value(json!({
+            "method": "SHARES",
+            "members": [{ "userId": "alice", "basisPoints": 10000 }]
+        }))
+        .unwrap();
+        assert!(matches!(dto.into_rule(), Err(DomainError::Validation(_))));
+    }
+}
@@ -1,6 +1,10 @@
 // アプリケーション層
 // ユースケース・アプリケーションサービス
 
+pub mod cursor;
+pub mod dto;
+pub mod state;
 pub mod use_cases;
 
+pub use state::*;
 pub use use_cases::*;
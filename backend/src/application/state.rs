@@ -0,0 +1,172 @@
+// アプリケーション状態
+// HTTPハンドラーから共有されるユースケース群
+
+use crate::application::cursor::CursorCodec;
+use crate::application::use_cases::*;
+use crate::auth::JwtVerifier;
+use crate::config::AggregateUpdates;
+use crate::domain::notifiers::BudgetAlertNotifier;
+use crate::domain::repositories::*;
+use crate::domain::services::*;
+use std::sync::Arc;
+
+pub type DynUserRepository = Arc<dyn UserRepository>;
+pub type DynTransactionRepository = Arc<dyn TransactionRepository>;
+pub type DynBudgetRepository = Arc<dyn BudgetRepository>;
+pub type DynGroupRepository = Arc<dyn GroupRepository>;
+pub type DynGroupTransactionRepository = Arc<dyn GroupTransactionRepository>;
+pub type DynSettlementRepository = Arc<dyn SettlementRepository>;
+pub type DynAccountRepository = Arc<dyn AccountRepository>;
+pub type DynCategoryRepository = Arc<dyn CategoryRepository>;
+pub type DynAggregateRepository = Arc<dyn AggregateRepository>;
+pub type DynBudgetAlertRepository = Arc<dyn BudgetAlertRepository>;
+pub type DynBudgetAlertNotifier = Arc<dyn BudgetAlertNotifier>;
+
+/// アプリケーション状態の構築に使うリポジトリ一式
+#[derive(Clone)]
+pub struct Repositories {
+    pub users: DynUserRepository,
+    pub transactions: DynTransactionRepository,
+    pub budgets: DynBudgetRepository,
+    pub groups: DynGroupRepository,
+    pub group_transactions: DynGroupTransactionRepository,
+    pub settlements: DynSettlementRepository,
+    pub accounts: DynAccountRepository,
+    pub categories: DynCategoryRepository,
+    pub aggregates: DynAggregateRepository,
+    pub budget_alerts: DynBudgetAlertRepository,
+}
+
+/// ルーターに渡すアプリケーション状態
+///
+/// リポジトリはトレイトオブジェクトで受け取り、ストレージ実装をハンドラーから隠蔽する
+#[derive(Clone)]
+pub struct AppState {
+    pub verifier: Arc<JwtVerifier>,
+    pub user_use_case: Arc<UserUseCase<DynUserRepository>>,
+    pub transaction_use_case:
+        Arc<TransactionUseCase<DynTransactionRepository, DynAggregateRepository>>,
+    pub budget_use_case: Arc<
+        BudgetUseCase<
+            DynBudgetRepository,
+            DynTransactionRepository,
+            DynUserRepository,
+            DynCategoryRepository,
+        >,
+    >,
+    pub budget_alert_use_case: Arc<
+        BudgetAlertUseCase<
+            DynBudgetRepository,
+            DynTransactionRepository,
+            DynUserRepository,
+            DynCategoryRepository,
+            DynBudgetAlertRepository,
+            DynBudgetAlertNotifier,
+        >,
+    >,
+    /// 支出の変更時に API で予算アラートを判定するか（ストリーム構成ではコンシューマーが判定する）
+    pub evaluate_budget_alerts: bool,
+    pub group_transaction_use_case: Arc<
+        GroupTransactionUseCase<
+            DynGroupRepository,
+            DynGroupTransactionRepository,
+            DynTransactionRepository,
+            DynAggregateRepository,
+        >,
+    >,
+    pub settlement_use_case:
+        Arc<SettlementUseCase<DynSettlementRepository, DynTransactionRepository>>,
+    pub account_use_case: Arc<AccountUseCase<DynAccountRepository, DynTransactionRepository>>,
+    pub category_use_case:
+        Arc<CategoryUseCase<DynCategoryRepository, DynTransactionRepository, DynUserRepository>>,
+    pub report_use_case: Arc<
+        ReportUseCase<
+            DynTransactionRepository,
+            DynBudgetRepository,
+            DynSettlementRepository,
+            DynUserRepository,
+            DynCategoryRepository,
+            DynAggregateRepository,
+        >,
+    >,
+}
+
+impl AppState {
+    pub fn new(
+        verifier: Arc<JwtVerifier>,
+        cursor_codec: CursorCodec,
+        repositories: Repositories,
+        aggregate_updates: AggregateUpdates,
+        notifier: DynBudgetAlertNotifier,
+    ) -> Self {
+        let Repositories {
+            users,
+            transactions,
+            budgets,
+            groups,
+            group_transactions,
+            settlements,
+            accounts,
+            categories,
+            aggregates,
+            budget_alerts,
+        } = repositories;
+        let transaction_service = || {
+            let service = TransactionService::new(transactions.clone(), aggregates.clone());
+            match aggregate_updates {
+                AggregateUpdates::Inline => service,
+                AggregateUpdates::Stream => service.without_aggregate_updates(),
+            }
+        };
+        Self {
+            verifier,
+            user_use_case: Arc::new(UserUseCase::new(UserService::new(users.clone()))),
+            transaction_use_case: Arc::new(TransactionUseCase::new(
+                transaction_service(),
+                cursor_codec,
+            )),
+            budget_use_case: Arc::new(BudgetUseCase::new(BudgetService::new(
+                budgets.clone(),
+                transactions.clone(),
+                users.clone(),
+                categories.clone(),
+            ))),
+            budget_alert_use_case: Arc::new(BudgetAlertUseCase::new(BudgetAlertService::new(
+                BudgetService::new(
+                    budgets.clone(),
+                    transactions.clone(),
+                    users.clone(),
+                    categories.clone(),
+                ),
+                budget_alerts,
+                notifier,
+            ))),
+            evaluate_budget_alerts: aggregate_updates == AggregateUpdates::Inline,
+            group_transaction_use_case: Arc::new(GroupTransactionUseCase::new(
+                GroupTransactionService::new(groups, group_transactions),
+                transaction_service(),
+            )),
+            settlement_use_case: Arc::new(SettlementUseCase::new(SettlementService::new(
+                settlements.clone(),
+                transactions.clone(),
+            ))),
+            account_use_case: Arc::new(AccountUseCase::new(AccountService::new(
+                accounts,
+                transactions.clone(),
+            ))),
+            category_use_case: Arc::new(CategoryUseCase::new(CategoryService::new(
+                categories.clone(),
+                transactions.clone(),
+                users.clone(),
+            ))),
+            report_use_case: Arc::new(ReportUseCase::new(ReportService::new(
+                transactions,
+                budgets,
+                settlements,
+                users,
+                categories,
+                aggregates,
+            ))),
+        }
+    }
+}
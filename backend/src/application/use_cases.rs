@@ -1,9 +1,13 @@
 // ユースケース実装
 
+use crate::application::cursor::CursorCodec;
 use crate::domain::entities::*;
 use crate::domain::services::*;
 use crate::domain::repositories::*;
-use anyhow::Result;
+use crate::domain::errors::DomainResult;
+use crate::domain::notifiers::BudgetAlertNotifier;
+use crate::domain::value_objects::{Amount, UserId};
+use chrono::{DateTime, NaiveDate, Utc};
 
 /// ユーザー管理ユースケース
 pub struct UserUseCase<R: UserRepository> {
@@ -15,65 +19,559 @@ impl<R: UserRepository> UserUseCase<R> {
         Self { user_service }
     }
 
-    pub async fn get_user_profile(&self, user_id: &str) -> Result<Option<UserProfile>> {
+    pub async fn get_user_profile(&self, user_id: &str) -> DomainResult<UserProfile> {
         self.user_service.get_user(user_id).await
     }
 
-    pub async fn create_user(&self, user: UserProfile) -> Result<()> {
+    pub async fn create_user(&self, user: UserProfile) -> DomainResult<()> {
         self.user_service.create_user(user).await
     }
+
+    pub async fn update_user_profile(&self, user: UserProfile) -> DomainResult<()> {
+        self.user_service.update_user(user).await
+    }
+
+    pub async fn local_date(
+        &self,
+        user_id: &UserId,
+        now: DateTime<Utc>,
+    ) -> DomainResult<NaiveDate> {
+        self.user_service.local_date(user_id, now).await
+    }
 }
 
 /// 取引管理ユースケース
-pub struct TransactionUseCase<R: TransactionRepository> {
-    transaction_service: TransactionService<R>,
+pub struct TransactionUseCase<R: TransactionRepository, A: AggregateRepository> {
+    transaction_service: TransactionService<R, A>,
+    cursor_codec: CursorCodec,
 }
 
-impl<R: TransactionRepository> TransactionUseCase<R> {
-    pub fn new(transaction_service: TransactionService<R>) -> Self {
-        Self { transaction_service }
+impl<R: TransactionRepository, A: AggregateRepository> TransactionUseCase<R, A> {
+    pub fn new(transaction_service: TransactionService<R, A>, cursor_codec: CursorCodec) -> Self {
+        Self {
+            transaction_service,
+            cursor_codec,
+        }
     }
 
-    pub async fn get_transactions(&self, user_id: &str) -> Result<Vec<Transaction>> {
+    pub async fn get_transaction(
+        &self,
+        user_id: &UserId,
+        transaction_id: &str,
+    ) -> DomainResult<Transaction> {
+        self.transaction_service
+            .get_transaction(user_id, transaction_id)
+            .await
+    }
+
+    pub async fn get_transactions(&self, user_id: &str) -> DomainResult<Vec<Transaction>> {
         self.transaction_service.get_transactions(user_id).await
     }
 
-    pub async fn create_transaction(&self, transaction: Transaction) -> Result<()> {
+    /// 取引一覧を1ページ取得し、続きがあれば次ページのカーソルを返す
+    ///
+    /// `cursor` は前ページで返したもの。署名と発行先のユーザーを検証してから使う
+    pub async fn list_transactions(
+        &self,
+        user_id: &UserId,
+        mut query: TransactionQuery,
+        cursor: Option<&str>,
+    ) -> DomainResult<(Vec<Transaction>, Option<String>)> {
+        query.after = cursor
+            .map(|cursor| self.cursor_codec.decode(user_id, cursor))
+            .transpose()?;
+        let page = self
+            .transaction_service
+            .list_transactions(user_id, &query)
+            .await?;
+        let next_cursor = page
+            .next
+            .map(|next| self.cursor_codec.encode(user_id, &next))
+            .transpose()?;
+        Ok((page.transactions, next_cursor))
+    }
+
+    pub async fn create_transaction(&self, transaction: Transaction) -> DomainResult<()> {
         self.transaction_service.create_transaction(transaction).await
     }
 
-    pub async fn update_transaction(&self, transaction: Transaction) -> Result<()> {
+    pub async fn update_transaction(&self, transaction: Transaction) -> DomainResult<()> {
         self.transaction_service.update_transaction(transaction).await
     }
 
-    pub async fn delete_transaction(&self, transaction_id: &str) -> Result<()> {
-        self.transaction_service.delete_transaction(transaction_id).await
+    pub async fn delete_transaction(
+        &self,
+        user_id: &UserId,
+        transaction_id: &str,
+    ) -> DomainResult<()> {
+        self.transaction_service
+            .delete_transaction(user_id, transaction_id)
+            .await
+    }
+
+    pub async fn create_transfer(
+        &self,
+        transfer: &AccountTransfer,
+    ) -> DomainResult<Vec<Transaction>> {
+        self.transaction_service.create_transfer(transfer).await
+    }
+
+    pub async fn get_transfer(
+        &self,
+        user_id: &UserId,
+        transfer_id: &str,
+    ) -> DomainResult<(AccountTransfer, Vec<Transaction>)> {
+        self.transaction_service
+            .get_transfer(user_id, transfer_id)
+            .await
+    }
+
+    pub async fn delete_transfer(&self, user_id: &UserId, transfer_id: &str) -> DomainResult<()> {
+        self.transaction_service
+            .delete_transfer(user_id, transfer_id)
+            .await
     }
 }
 
 /// 予算管理ユースケース
-pub struct BudgetUseCase<R: BudgetRepository> {
-    budget_service: BudgetService<R>,
+pub struct BudgetUseCase<
+    B: BudgetRepository,
+    T: TransactionRepository,
+    U: UserRepository,
+    C: CategoryRepository,
+> {
+    budget_service: BudgetService<B, T, U, C>,
 }
 
-impl<R: BudgetRepository> BudgetUseCase<R> {
-    pub fn new(budget_service: BudgetService<R>) -> Self {
+impl<B: BudgetRepository, T: TransactionRepository, U: UserRepository, C: CategoryRepository>
+    BudgetUseCase<B, T, U, C>
+{
+    pub fn new(budget_service: BudgetService<B, T, U, C>) -> Self {
         Self { budget_service }
     }
 
-    pub async fn get_budgets(&self, user_id: &str) -> Result<Vec<Budget>> {
+    pub async fn get_budget(&self, user_id: &UserId, budget_id: &str) -> DomainResult<Budget> {
+        self.budget_service.get_budget(user_id, budget_id).await
+    }
+
+    pub async fn get_budgets(&self, user_id: &str) -> DomainResult<Vec<Budget>> {
         self.budget_service.get_budgets(user_id).await
     }
 
-    pub async fn create_budget(&self, budget: Budget) -> Result<()> {
+    pub async fn create_budget(&self, budget: Budget) -> DomainResult<()> {
         self.budget_service.create_budget(budget).await
     }
 
-    pub async fn update_budget(&self, budget: Budget) -> Result<()> {
+    pub async fn update_budget(&self, budget: Budget) -> DomainResult<()> {
         self.budget_service.update_budget(budget).await
     }
 
-    pub async fn delete_budget(&self, budget_id: &str) -> Result<()> {
-        self.budget_service.delete_budget(budget_id).await
+    pub async fn delete_budget(&self, user_id: &UserId, budget_id: &str) -> DomainResult<()> {
+        self.budget_service.delete_budget(user_id, budget_id).await
+    }
+
+    pub async fn get_budget_status(
+        &self,
+        user_id: &UserId,
+        budget_id: &str,
+        now: DateTime<Utc>,
+    ) -> DomainResult<BudgetStatus> {
+        self.budget_service
+            .get_status(user_id, budget_id, now)
+            .await
+    }
+}
+
+/// 予算アラートユースケース
+pub struct BudgetAlertUseCase<
+    B: BudgetRepository,
+    T: TransactionRepository,
+    U: UserRepository,
+    C: CategoryRepository,
+    A: BudgetAlertRepository,
+    N: BudgetAlertNotifier,
+> {
+    budget_alert_service: BudgetAlertService<B, T, U, C, A, N>,
+}
+
+impl<
+        B: BudgetRepository,
+        T: TransactionRepository,
+        U: UserRepository,
+        C: CategoryRepository,
+        A: BudgetAlertRepository,
+        N: BudgetAlertNotifier,
+    > BudgetAlertUseCase<B, T, U, C, A, N>
+{
+    pub fn new(budget_alert_service: BudgetAlertService<B, T, U, C, A, N>) -> Self {
+        Self {
+            budget_alert_service,
+        }
+    }
+
+    pub async fn evaluate(
+        &self,
+        user_id: &UserId,
+        budget: Option<Budget>,
+        now: DateTime<Utc>,
+    ) -> DomainResult<Vec<BudgetAlertRaised>> {
+        self.budget_alert_service
+            .evaluate(user_id, budget, now)
+            .await
+    }
+
+    pub async fn deliver_pending(&self, user_id: &UserId, now: DateTime<Utc>) -> DomainResult<()> {
+        self.budget_alert_service
+            .deliver_pending(user_id, now)
+            .await
+    }
+
+    pub async fn get_alerts(&self, user_id: &UserId) -> DomainResult<Vec<BudgetAlertRaised>> {
+        self.budget_alert_service.get_history(user_id).await
+    }
+}
+
+/// カテゴリ管理ユースケース
+pub struct CategoryUseCase<C: CategoryRepository, T: TransactionRepository, U: UserRepository> {
+    category_service: CategoryService<C, T, U>,
+}
+
+impl<C: CategoryRepository, T: TransactionRepository, U: UserRepository> CategoryUseCase<C, T, U> {
+    pub fn new(category_service: CategoryService<C, T, U>) -> Self {
+        Self { category_service }
+    }
+
+    pub async fn get_category(
+        &self,
+        user_id: &UserId,
+        category_id: &str,
+    ) -> DomainResult<Category> {
+        self.category_service
+            .get_category(user_id, category_id)
+            .await
+    }
+
+    pub async fn get_categories(&self, user_id: &UserId) -> DomainResult<Vec<Category>> {
+        self.category_service.get_categories(user_id).await
+    }
+
+    pub async fn create_category(&self, category: Category) -> DomainResult<Category> {
+        self.category_service.create_category(category).await
+    }
+
+    pub async fn update_category(&self, category: Category) -> DomainResult<()> {
+        self.category_service.update_category(category).await
+    }
+
+    pub async fn ensure_usable(
+        &self,
+        user_id: &UserId,
+        category_id: &str,
+    ) -> DomainResult<Category> {
+        self.category_service
+            .ensure_usable(user_id, category_id)
+            .await
+    }
+
+    pub async fn get_spending(
+        &self,
+        user_id: &UserId,
+        window: &PeriodWindow,
+    ) -> DomainResult<Vec<CategorySpending>> {
+        self.category_service.get_spending(user_id, window).await
+    }
+}
+
+/// グループ取引ユースケース
+///
+/// 支払者の立て替え分の取引は `transaction_service` を通して月次集計に反映する
+pub struct GroupTransactionUseCase<
+    G: GroupRepository,
+    T: GroupTransactionRepository,
+    R: TransactionRepository,
+    A: AggregateRepository,
+> {
+    group_transaction_service: GroupTransactionService<G, T>,
+    transaction_service: TransactionService<R, A>,
+}
+
+impl<
+        G: GroupRepository,
+        T: GroupTransactionRepository,
+        R: TransactionRepository,
+        A: AggregateRepository,
+    > GroupTransactionUseCase<G, T, R, A>
+{
+    pub fn new(
+        group_transaction_service: GroupTransactionService<G, T>,
+        transaction_service: TransactionService<R, A>,
+    ) -> Self {
+        Self {
+            group_transaction_service,
+            transaction_service,
+        }
+    }
+
+    pub async fn get_group(&self, user_id: &UserId, group_id: &str) -> DomainResult<Group> {
+        self.group_transaction_service
+            .get_group(user_id, group_id)
+            .await
+    }
+
+    pub async fn get_transactions(
+        &self,
+        user_id: &UserId,
+        group_id: &str,
+    ) -> DomainResult<Vec<GroupTransaction>> {
+        self.group_transaction_service
+            .get_transactions(user_id, group_id)
+            .await
+    }
+
+    pub async fn create_transaction(
+        &self,
+        user_id: &UserId,
+        transaction: GroupTransaction,
+    ) -> DomainResult<Option<Transaction>> {
+        let payer_transaction = self
+            .group_transaction_service
+            .create_transaction(user_id, transaction)
+            .await?;
+        if let Some(payer_transaction) = &payer_transaction {
+            self.transaction_service
+                .record_saved_transactions(std::slice::from_ref(payer_transaction))
+                .await?;
+        }
+        Ok(payer_transaction)
+    }
+
+    pub async fn calculate_settlements(
+        &self,
+        user_id: &UserId,
+        group_id: &str,
+    ) -> DomainResult<SettlementPlan> {
+        self.group_transaction_service
+            .calculate_settlements(user_id, group_id)
+            .await
+    }
+}
+
+/// レポートユースケース
+pub struct ReportUseCase<
+    T: TransactionRepository,
+    B: BudgetRepository,
+    S: SettlementRepository,
+    U: UserRepository,
+    C: CategoryRepository,
+    A: AggregateRepository,
+> {
+    report_service: ReportService<T, B, S, U, C, A>,
+}
+
+impl<
+        T: TransactionRepository,
+        B: BudgetRepository,
+        S: SettlementRepository,
+        U: UserRepository,
+        C: CategoryRepository,
+        A: AggregateRepository,
+    > ReportUseCase<T, B, S, U, C, A>
+{
+    pub fn new(report_service: ReportService<T, B, S, U, C, A>) -> Self {
+        Self { report_service }
+    }
+
+    pub async fn get_monthly_report(
+        &self,
+        user_id: &UserId,
+        month_start: NaiveDate,
+        now: DateTime<Utc>,
+    ) -> DomainResult<MonthlyReport> {
+        self.report_service
+            .get_monthly_report(user_id, month_start, now)
+            .await
+    }
+
+    pub async fn get_trend_report(
+        &self,
+        user_id: &UserId,
+        granularity: ReportGranularity,
+        window: Option<PeriodWindow>,
+        now: DateTime<Utc>,
+    ) -> DomainResult<TrendReport> {
+        self.report_service
+            .get_trend_report(user_id, granularity, window, now)
+            .await
+    }
+}
+
+/// 月次集計の保守ユースケース
+pub struct AggregateUseCase<A: AggregateRepository, T: TransactionRepository> {
+    aggregate_service: AggregateService<A, T>,
+}
+
+impl<A: AggregateRepository, T: TransactionRepository> AggregateUseCase<A, T> {
+    pub fn new(aggregate_service: AggregateService<A, T>) -> Self {
+        Self { aggregate_service }
+    }
+
+    pub async fn apply_change(
+        &self,
+        before: Option<&Transaction>,
+        after: Option<&Transaction>,
+    ) -> DomainResult<()> {
+        self.aggregate_service.apply_change(before, after).await
+    }
+
+    pub async fn rebuild(&self, user_id: &UserId) -> DomainResult<Vec<MonthlyAggregate>> {
+        self.aggregate_service.rebuild(user_id).await
+    }
+
+    pub async fn check(&self, user_id: &UserId) -> DomainResult<Vec<AggregateMismatch>> {
+        self.aggregate_service.check(user_id).await
+    }
+}
+
+/// 口座管理ユースケース
+pub struct AccountUseCase<A: AccountRepository, T: TransactionRepository> {
+    account_service: AccountService<A, T>,
+}
+
+impl<A: AccountRepository, T: TransactionRepository> AccountUseCase<A, T> {
+    pub fn new(account_service: AccountService<A, T>) -> Self {
+        Self { account_service }
+    }
+
+    pub async fn get_account(&self, user_id: &UserId, account_id: &str) -> DomainResult<Account> {
+        self.account_service.get_account(user_id, account_id).await
+    }
+
+    pub async fn get_accounts(&self, user_id: &str) -> DomainResult<Vec<Account>> {
+        self.account_service.get_accounts(user_id).await
+    }
+
+    pub async fn create_account(&self, account: Account) -> DomainResult<()> {
+        self.account_service.create_account(account).await
+    }
+
+    pub async fn update_account(&self, account: Account) -> DomainResult<()> {
+        self.account_service.update_account(account).await
+    }
+
+    pub async fn delete_account(&self, user_id: &UserId, account_id: &str) -> DomainResult<()> {
+        self.account_service
+            .delete_account(user_id, account_id)
+            .await
+    }
+
+    pub async fn get_balance(
+        &self,
+        user_id: &UserId,
+        account_id: &str,
+        as_of: Option<NaiveDate>,
+    ) -> DomainResult<AccountBalance> {
+        self.account_service
+            .get_balance(user_id, account_id, as_of)
+            .await
+    }
+
+    pub async fn get_statements(
+        &self,
+        user_id: &UserId,
+        account_id: &str,
+        today: NaiveDate,
+    ) -> DomainResult<CardBillingSummary> {
+        self.account_service
+            .get_statements(user_id, account_id, today)
+            .await
+    }
+
+    pub async fn get_upcoming_debits(
+        &self,
+        user_id: &UserId,
+        account_id: &str,
+        today: NaiveDate,
+    ) -> DomainResult<DebitProjection> {
+        self.account_service
+            .get_upcoming_debits(user_id, account_id, today)
+            .await
+    }
+
+    pub async fn ensure_usable(
+        &self,
+        user_id: &UserId,
+        account_id: &str,
+        amount: &Amount,
+    ) -> DomainResult<()> {
+        self.account_service
+            .ensure_usable(user_id, account_id, amount)
+            .await
+    }
+}
+
+/// 精算管理ユースケース
+pub struct SettlementUseCase<S: SettlementRepository, T: TransactionRepository> {
+    settlement_service: SettlementService<S, T>,
+}
+
+impl<S: SettlementRepository, T: TransactionRepository> SettlementUseCase<S, T> {
+    pub fn new(settlement_service: SettlementService<S, T>) -> Self {
+        Self { settlement_service }
+    }
+
+    pub async fn get_settlement(
+        &self,
+        user_id: &UserId,
+        settlement_id: &str,
+    ) -> DomainResult<Settlement> {
+        self.settlement_service
+            .get_settlement(user_id, settlement_id)
+            .await
+    }
+
+    pub async fn get_settlements(&self, user_id: &str) -> DomainResult<Vec<Settlement>> {
+        self.settlement_service.get_settlements(user_id).await
+    }
+
+    pub async fn create_settlement(&self, settlement: Settlement) -> DomainResult<()> {
+        self.settlement_service.create_settlement(settlement).await
+    }
+
+    pub async fn create_from_transaction(
+        &self,
+        user_id: &UserId,
+        transaction_id: &str,
+        debtor_user_id: UserId,
+        amount: Option<Amount>,
+        description: Option<String>,
+    ) -> DomainResult<Settlement> {
+        self.settlement_service
+            .create_from_transaction(user_id, transaction_id, debtor_user_id, amount, description)
+            .await
+    }
+
+    pub async fn repay(
+        &self,
+        user_id: &UserId,
+        settlement_id: &str,
+        amount: Amount,
+    ) -> DomainResult<Settlement> {
+        self.settlement_service
+            .repay(user_id, settlement_id, amount)
+            .await
+    }
+
+    pub async fn complete(
+        &self,
+        user_id: &UserId,
+        settlement_id: &str,
+    ) -> DomainResult<Settlement> {
+        self.settlement_service
+            .complete(user_id, settlement_id)
+            .await
+    }
+
+    pub async fn cancel(&self, user_id: &UserId, settlement_id: &str) -> DomainResult<Settlement> {
+        self.settlement_service.cancel(user_id, settlement_id).await
     }
 }
@@ -0,0 +1,260 @@
+// 認証
+// Firebase / Cognito が発行する Bearer JWT（RS256）を JWKS で検証し、
+// 認証済みユーザーをリクエストに付与する
+
+use crate::application::AppState;
+use crate::config::{AuthConfig, JwksSource};
+use crate::domain::value_objects::UserId;
+use crate::handlers::ApiError;
+use anyhow::{bail, Context, Result};
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use jsonwebtoken::jwk::{AlgorithmParameters, JwkSet};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// 認証エラー
+#[derive(Debug, Error)]
+pub enum AuthError {
+    #[error("Missing bearer token")]
+    MissingToken,
+
+    #[error("Token is signed with an unknown key")]
+    UnknownKey,
+
+    #[error("Invalid token: {0}")]
+    InvalidToken(#[from] jsonwebtoken::errors::Error),
+}
+
+/// 検証対象のクレーム（`exp` / `iss` / `aud` は `Validation` 側で検証する）
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: String,
+}
+
+/// JWKS の公開鍵で JWT を検証する
+///
+/// 鍵は生成時に読み込んだものを使い続ける。Lambda ではコールドスタートごとに再取得される
+pub struct JwtVerifier {
+    keys: HashMap<String, DecodingKey>,
+    validation: Validation,
+}
+
+impl JwtVerifier {
+    pub fn new(jwks: &JwkSet, issuer: &str, audience: &str) -> Result<Self> {
+        let mut keys = HashMap::new();
+        for jwk in &jwks.keys {
+            // kid の無い鍵や RSA 以外の鍵はトークンと対応付けられないため無視する
+            let Some(kid) = &jwk.common.key_id else {
+                continue;
+            };
+            if !matches!(jwk.algorithm, AlgorithmParameters::RSA(_)) {
+                continue;
+            }
+            keys.insert(kid.clone(), DecodingKey::from_jwk(jwk)?);
+        }
+        if keys.is_empty() {
+            bail!("JWKS contains no RSA signing keys");
+        }
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_issuer(&[issuer]);
+        validation.set_audience(&[audience]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        Ok(Self { keys, validation })
+    }
+
+    /// ローカルファイルの JWKS から生成
+    pub fn from_jwks_file(path: &str, issuer: &str, audience: &str) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read JWKS file {}", path))?;
+        let jwks: JwkSet = serde_json::from_str(&json).context("invalid JWKS")?;
+        Self::new(&jwks, issuer, audience)
+    }
+
+    /// 公開されている JWKS を取得して生成
+    pub async fn from_jwks_url(url: &str, issuer: &str, audience: &str) -> Result<Self> {
+        let jwks: JwkSet = reqwest::get(url)
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .with_context(|| format!("failed to fetch JWKS from {}", url))?;
+        Self::new(&jwks, issuer, audience)
+    }
+
+    /// 設定に従って JWKS を読み込む
+    pub async fn load(config: &AuthConfig) -> Result<Self> {
+        match &config.jwks {
+            JwksSource::File(path) => Self::from_jwks_file(path, &config.issuer, &config.audience),
+            JwksSource::Url(url) => {
+                Self::from_jwks_url(url, &config.issuer, &config.audience).await
+            }
+        }
+    }
+
+    /// トークンを検証し、`sub` クレームをユーザーIDとして返す
+    pub fn verify(&self, token: &str) -> Result<UserId, AuthError> {
+        let header = decode_header(token)?;
+        let key = header
+            .kid
+            .as_deref()
+            .and_then(|kid| self.keys.get(kid))
+            .ok_or(AuthError::UnknownKey)?;
+        let data = decode::<Claims>(token, key, &self.validation)?;
+        Ok(UserId::new(data.claims.sub))
+    }
+}
+
+/// 認証済みユーザー
+///
+/// `authenticate` ミドルウェアを通ったリクエストからのみ取り出せる
+#[derive(Debug, Clone)]
+pub struct AuthUser(pub UserId);
+
+/// `Authorization: Bearer <JWT>` を検証し、リクエストに `AuthUser` を付与するミドルウェア
+pub async fn authenticate(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(AuthError::MissingToken)?;
+    let user_id = state.verifier.verify(token.trim())?;
+
+    request.extensions_mut().insert(AuthUser(user_id));
+    Ok(next.run(request).await)
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for AuthUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthUser>()
+            .cloned()
+            .ok_or(ApiError::Unauthorized(AuthError::MissingToken))
+    }
+}
+
+/// テスト用の鍵（tests/fixtures/auth）で署名したトークンと検証器
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::*;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde_json::{json, Value};
+
+    pub const ISSUER: &str = "https://securetoken.google.com/axi-budget";
+    pub const AUDIENCE: &str = "axi-budget";
+    pub const KEY_ID: &str = "test-key-1";
+    pub const TRUSTED_KEY: &str = include_str!("../tests/fixtures/auth/private_key.pem");
+    pub const UNTRUSTED_KEY: &str =
+        include_str!("../tests/fixtures/auth/untrusted_private_key.pem");
+
+    pub fn verifier() -> JwtVerifier {
+        let jwks: JwkSet =
+            serde_json::from_str(include_str!("../tests/fixtures/auth/jwks.json")).unwrap();
+        JwtVerifier::new(&jwks, ISSUER, AUDIENCE).unwrap()
+    }
+
+    pub fn claims(sub: &str, exp_offset: i64) -> Value {
+        json!({
+            "sub": sub,
+            "iss": ISSUER,
+            "aud": AUDIENCE,
+            "exp": chrono::Utc::now().timestamp() + exp_offset,
+        })
+    }
+
+    pub fn sign(claims: &Value, private_key: &str, kid: &str) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(kid.to_string());
+        encode(
+            &header,
+            claims,
+            &EncodingKey::from_rsa_pem(private_key.as_bytes()).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// 1時間有効なトークン
+    pub fn token(sub: &str) -> String {
+        sign(&claims(sub, 3600), TRUSTED_KEY, KEY_ID)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::*;
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_token() {
+        assert_eq!(
+            verifier().verify(&token("user123")).unwrap().value(),
+            "user123"
+        );
+    }
+
+    #[test]
+    fn test_expired_token() {
+        // 既定のリーウェイ（60秒）を超えて期限切れ
+        let token = sign(&claims("user123", -3600), TRUSTED_KEY, KEY_ID);
+        assert!(matches!(
+            verifier().verify(&token),
+            Err(AuthError::InvalidToken(_))
+        ));
+    }
+
+    #[test]
+    fn test_wrong_issuer_and_audience() {
+        let mut wrong_issuer = claims("user123", 3600);
+        wrong_issuer["iss"] = json!("https://example.com");
+        assert!(verifier()
+            .verify(&sign(&wrong_issuer, TRUSTED_KEY, KEY_ID))
+            .is_err());
+
+        let mut wrong_audience = claims("user123", 3600);
+        wrong_audience["aud"] = json!("other-project");
+        assert!(verifier()
+            .verify(&sign(&wrong_audience, TRUSTED_KEY, KEY_ID))
+            .is_err());
+    }
+
+    #[test]
+    fn test_untrusted_signature() {
+        // kid を偽っても署名は検証される
+        let forged = sign(&claims("user123", 3600), UNTRUSTED_KEY, KEY_ID);
+        assert!(matches!(
+            verifier().verify(&forged),
+            Err(AuthError::InvalidToken(_))
+        ));
+
+        let unknown_kid = sign(&claims("user123", 3600), TRUSTED_KEY, "rotated-key");
+        assert!(matches!(
+            verifier().verify(&unknown_kid),
+            Err(AuthError::UnknownKey)
+        ));
+    }
+
+    #[test]
+    fn test_jwks_without_rsa_keys_is_rejected() {
+        let jwks: JwkSet = serde_json::from_value(json!({ "keys": [] })).unwrap();
+        assert!(JwtVerifier::new(&jwks, ISSUER, AUDIENCE).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+// 保守コマンド
+// サーバーを起動せずに、設定されたストレージに対して保守作業を行うサブコマンド
+
+use crate::application::AggregateUseCase;
+use crate::domain::entities::MonthlyAggregate;
+use crate::domain::repositories::{AggregateRepository, TransactionRepository};
+use crate::domain::value_objects::UserId;
+use anyhow::{bail, Result};
+use std::io::Write;
+
+pub const USAGE: &str = "usage: axi-budget-backend aggregates <rebuild|check> <USER_ID>...";
+
+/// コマンドライン引数で指定された保守コマンド
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// 取引から月次集計を作り直す
+    RebuildAggregates(Vec<UserId>),
+    /// 保存済みの月次集計を取引から求めた集計と突き合わせる
+    CheckAggregates(Vec<UserId>),
+}
+
+impl Command {
+    /// プログラム名を除いた引数から解釈する
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let [group, action, user_ids @ ..] = args else {
+            bail!(USAGE);
+        };
+        if group != "aggregates" || user_ids.is_empty() {
+            bail!(USAGE);
+        }
+        let user_ids = user_ids.iter().cloned().map(UserId::new).collect();
+        match action.as_str() {
+            "rebuild" => Ok(Self::RebuildAggregates(user_ids)),
+            "check" => Ok(Self::CheckAggregates(user_ids)),
+            _ => bail!(USAGE),
+        }
+    }
+
+    /// コマンドを実行し、結果を `out` に書き出す
+    ///
+    /// 突き合わせで食い違いが見つかった場合は `false` を返す
+    pub async fn run<A: AggregateRepository, T: TransactionRepository>(
+        &self,
+        use_case: &AggregateUseCase<A, T>,
+        out: &mut impl Write,
+    ) -> Result<bool> {
+        match self {
+            Self::RebuildAggregates(user_ids) => {
+                for user_id in user_ids {
+                    let aggregates = use_case.rebuild(user_id).await?;
+                    writeln!(
+                        out,
+                        "{}: rebuilt {} monthly aggregates",
+                        user_id.value(),
+                        aggregates.len()
+                    )?;
+                }
+                Ok(true)
+            }
+            Self::CheckAggregates(user_ids) => {
+                let mut consistent = true;
+                for user_id in user_ids {
+                    let mismatches = use_case.check(user_id).await?;
+                    if mismatches.is_empty() {
+                        writeln!(out, "{}: consistent", user_id.value())?;
+                    }
+                    for mismatch in &mismatches {
+                        writeln!(
+                            out,
+                            "{}: {} differs (stored: {}, expected: {})",
+                            user_id.value(),
+                            mismatch.month.format("%Y-%m"),
+                            describe(mismatch.stored.as_ref()),
+                            describe(mismatch.expected.as_ref())
+                        )?;
+                    }
+                    consistent &= mismatches.is_empty();
+                }
+                Ok(consistent)
+            }
+        }
+    }
+}
+
+/// 集計の概要（取引の件数と行数）
+fn describe(aggregate: Option<&MonthlyAggregate>) -> String {
+    match aggregate {
+        Some(aggregate) => format!(
+            "{} transactions in {} entries",
+            aggregate.entries.iter().map(|e| e.count).sum::<i64>(),
+            aggregate.entries.len()
+        ),
+        None => "none".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::*;
+    use crate::domain::services::AggregateService;
+    use crate::domain::value_objects::{Amount, TransactionDate};
+    use crate::infrastructure::memory::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Command::parse(&args(&["aggregates", "rebuild", "alice", "bob"])).unwrap(),
+            Command::RebuildAggregates(vec![
+                UserId::new("alice".to_string()),
+                UserId::new("bob".to_string()),
+            ])
+        );
+        assert_eq!(
+            Command::parse(&args(&["aggregates", "check", "alice"])).unwrap(),
+            Command::CheckAggregates(vec![UserId::new("alice".to_string())])
+        );
+        for invalid in [
+            &["aggregates", "check"][..],
+            &["aggregates", "drop", "alice"],
+            &["budgets", "check", "alice"],
+            &["aggregates"],
+        ] {
+            assert!(Command::parse(&args(invalid)).is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_then_rebuild() {
+        let transactions = InMemoryTransactionRepository::new();
+        let mut lunch = Transaction::new(
+            UserId::new("alice".to_string()),
+            TransactionType::Real,
+            Amount::jpy(1000),
+            "ランチ".to_string(),
+            TransactionCategory::Food,
+        );
+        lunch.transaction_date = TransactionDate::new("2025-04-10".parse().unwrap());
+        transactions.save(lunch).await.unwrap();
+        let use_case = AggregateUseCase::new(AggregateService::new(
+            InMemoryAggregateRepository::new(),
+            transactions,
+        ));
+        let run = |command: Command| {
+            let use_case = &use_case;
+            async move {
+                let mut out = Vec::new();
+                let consistent = command.run(use_case, &mut out).await.unwrap();
+                (consistent, String::from_utf8(out).unwrap())
+            }
+        };
+        let check = Command::parse(&args(&["aggregates", "check", "alice"])).unwrap();
+
+        assert_eq!(
+            run(check.clone()).await,
+            (
+                false,
+                "alice: 2025-04 differs (stored: none, expected: 1 transactions in 1 entries)\n"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            run(Command::parse(&args(&["aggregates", "rebuild", "alice"])).unwrap()).await,
+            (true, "alice: rebuilt 1 monthly aggregates\n".to_string())
+        );
+        assert_eq!(run(check).await, (true, "alice: consistent\n".to_string()));
+    }
+}
@@ -0,0 +1,342 @@
+// 実行時設定
+// 環境変数からストレージ等の構成を読み込む
+
+use anyhow::{anyhow, bail, Result};
+use std::str::FromStr;
+
+/// テーブル名が指定されなかった場合の既定値
+pub const DEFAULT_TABLE_NAME: &str = "axi-budget-dev";
+
+/// SQLite ファイルのパスが指定されなかった場合の既定値
+pub const DEFAULT_SQLITE_PATH: &str = "axi-budget.db";
+
+/// リポジトリの保存先
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageBackend {
+    /// Amazon DynamoDB（シングルテーブル）
+    DynamoDb,
+    /// プロセス内メモリ（再起動で消える）
+    InMemory,
+    /// 組み込み SQLite ファイル（セルフホスト用）
+    Sqlite,
+}
+
+impl FromStr for StorageBackend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "dynamodb" => Ok(StorageBackend::DynamoDb),
+            "memory" | "in-memory" => Ok(StorageBackend::InMemory),
+            "sqlite" => Ok(StorageBackend::Sqlite),
+            other => bail!("unknown storage backend: {}", other),
+        }
+    }
+}
+
+/// 月次集計の更新方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateUpdates {
+    /// 取引の書き込みと同じリクエストの中で更新する
+    Inline,
+    /// DynamoDB Streams のコンシューマーが更新する（DynamoDB のみ）
+    Stream,
+}
+
+impl FromStr for AggregateUpdates {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "inline" => Ok(AggregateUpdates::Inline),
+            "stream" => Ok(AggregateUpdates::Stream),
+            other => bail!("unknown aggregate update mode: {}", other),
+        }
+    }
+}
+
+/// JWKS（JWT 署名検証用の公開鍵セット）の取得元
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwksSource {
+    /// ローカルファイル（テストやオフライン環境向け）
+    File(String),
+    /// HTTPS で公開されている JWKS（起動時に取得）
+    Url(String),
+}
+
+/// Bearer JWT の検証設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthConfig {
+    /// `iss` クレームの期待値
+    pub issuer: String,
+    /// `aud` クレームの期待値（Cognito のアプリクライアント ID / Firebase のプロジェクト ID）
+    pub audience: String,
+    pub jwks: JwksSource,
+}
+
+/// ストレージの設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+    pub table_name: String,
+    pub sqlite_path: String,
+    pub aggregate_updates: AggregateUpdates,
+}
+
+/// 予算アラートの通知設定
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlertConfig {
+    /// アラートを POST する Webhook の URL（未指定時はログへの出力のみ）
+    pub webhook_url: Option<String>,
+    /// Webhook の本文に付ける HMAC-SHA256 署名の鍵
+    pub webhook_secret: Option<String>,
+}
+
+/// アプリケーション設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub storage: StorageConfig,
+    pub auth: AuthConfig,
+    pub alerts: AlertConfig,
+    /// ページングカーソルの署名鍵（未指定時は起動ごとにランダムな鍵を使う）
+    pub cursor_secret: Option<String>,
+}
+
+impl Config {
+    /// 環境変数から設定を読み込む
+    ///
+    /// - `STORAGE_BACKEND`: `dynamodb`、`memory` または `sqlite`。未指定時は Lambda 上なら
+    ///   `dynamodb`、ローカル実行なら `memory`
+    /// - `DYNAMODB_TABLE_NAME`: DynamoDB のテーブル名
+    /// - `SQLITE_PATH`: SQLite ファイルのパス
+    /// - `AGGREGATE_UPDATES`: 月次集計の更新方法。`inline`（既定）または `stream`
+    ///   （`dynamodb` のみ。テーブルのストリームを同じバイナリで処理する）
+    /// - `AUTH_ISSUER` / `AUTH_AUDIENCE`: JWT の発行者と対象（必須）
+    /// - `AUTH_JWKS_PATH` または `AUTH_JWKS_URL`: JWKS の取得元（必須、両方あればファイル優先）
+    /// - `CURSOR_SECRET`: ページングカーソルの署名鍵。Lambda 上では必須
+    ///   （インスタンス間で同じ鍵を使わないと、別インスタンスが発行したカーソルを検証できない）
+    /// - `ALERT_WEBHOOK_URL` / `ALERT_WEBHOOK_SECRET`: 予算アラートの送信先と署名鍵（任意）
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let cursor_secret = lookup("CURSOR_SECRET").filter(|secret| !secret.is_empty());
+        if cursor_secret.is_none() && lookup("AWS_LAMBDA_FUNCTION_NAME").is_some() {
+            bail!("CURSOR_SECRET must be set when running on Lambda");
+        }
+
+        Ok(Self {
+            storage: StorageConfig::from_lookup(&lookup)?,
+            auth: AuthConfig::from_lookup(&lookup)?,
+            alerts: AlertConfig::from_lookup(&lookup)?,
+            cursor_secret,
+        })
+    }
+}
+
+impl StorageConfig {
+    /// 環境変数からストレージの設定だけを読み込む（認証を使わない保守コマンド向け）
+    ///
+    /// 読み込む変数は `Config::from_env` と同じ
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(&|key: &str| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: &impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let backend = match lookup("STORAGE_BACKEND") {
+            Some(value) => value.parse()?,
+            None if lookup("AWS_LAMBDA_FUNCTION_NAME").is_some() => StorageBackend::DynamoDb,
+            None => StorageBackend::InMemory,
+        };
+        let aggregate_updates = match lookup("AGGREGATE_UPDATES") {
+            Some(value) => value.parse()?,
+            None => AggregateUpdates::Inline,
+        };
+        if aggregate_updates == AggregateUpdates::Stream && backend != StorageBackend::DynamoDb {
+            bail!("AGGREGATE_UPDATES=stream requires the dynamodb storage backend");
+        }
+        Ok(Self {
+            backend,
+            table_name: lookup("DYNAMODB_TABLE_NAME")
+                .unwrap_or_else(|| DEFAULT_TABLE_NAME.to_string()),
+            sqlite_path: lookup("SQLITE_PATH").unwrap_or_else(|| DEFAULT_SQLITE_PATH.to_string()),
+            aggregate_updates,
+        })
+    }
+}
+
+impl AuthConfig {
+    fn from_lookup(lookup: &impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let required = |key: &str| match lookup(key) {
+            Some(value) if !value.is_empty() => Ok(value),
+            _ => Err(anyhow!("{} must be set", key)),
+        };
+        let jwks = match (lookup("AUTH_JWKS_PATH"), lookup("AUTH_JWKS_URL")) {
+            (Some(path), _) => JwksSource::File(path),
+            (None, Some(url)) => JwksSource::Url(url),
+            (None, None) => bail!("either AUTH_JWKS_PATH or AUTH_JWKS_URL must be set"),
+        };
+
+        Ok(Self {
+            issuer: required("AUTH_ISSUER")?,
+            audience: required("AUTH_AUDIENCE")?,
+            jwks,
+        })
+    }
+}
+
+impl AlertConfig {
+    fn from_lookup(lookup: &impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let non_empty = |key: &str| lookup(key).filter(|value| !value.is_empty());
+        let webhook_url = non_empty("ALERT_WEBHOOK_URL");
+        let webhook_secret = non_empty("ALERT_WEBHOOK_SECRET");
+        if webhook_secret.is_some() && webhook_url.is_none() {
+            bail!("ALERT_WEBHOOK_SECRET requires ALERT_WEBHOOK_URL");
+        }
+        Ok(Self {
+            webhook_url,
+            webhook_secret,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const AUTH_VARS: [(&str, &str); 3] = [
+        ("AUTH_ISSUER", "https://securetoken.google.com/axi-budget"),
+        ("AUTH_AUDIENCE", "axi-budget"),
+        ("AUTH_JWKS_PATH", "jwks.json"),
+    ];
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    fn config(vars: &[(&str, &str)]) -> Result<Config> {
+        let vars = [&AUTH_VARS[..], vars].concat();
+        Config::from_lookup(lookup(&vars))
+    }
+
+    #[test]
+    fn test_default_backend_depends_on_environment() {
+        assert_eq!(
+            config(&[]).unwrap().storage.backend,
+            StorageBackend::InMemory
+        );
+        assert_eq!(
+            config(&[
+                ("AWS_LAMBDA_FUNCTION_NAME", "api"),
+                ("CURSOR_SECRET", "secret")
+            ])
+            .unwrap()
+            .storage
+            .backend,
+            StorageBackend::DynamoDb
+        );
+    }
+
+    #[test]
+    fn test_cursor_secret_is_required_on_lambda() {
+        assert_eq!(config(&[]).unwrap().cursor_secret, None);
+        assert!(config(&[("AWS_LAMBDA_FUNCTION_NAME", "api")]).is_err());
+        assert_eq!(
+            config(&[("CURSOR_SECRET", "secret")])
+                .unwrap()
+                .cursor_secret
+                .as_deref(),
+            Some("secret")
+        );
+    }
+
+    #[test]
+    fn test_explicit_backend() {
+        let loaded = config(&[
+            ("STORAGE_BACKEND", "DynamoDB"),
+            ("DYNAMODB_TABLE_NAME", "axi-budget-prod"),
+        ])
+        .unwrap();
+        assert_eq!(loaded.storage.backend, StorageBackend::DynamoDb);
+        assert_eq!(loaded.storage.table_name, "axi-budget-prod");
+
+        let loaded = config(&[
+            ("STORAGE_BACKEND", "sqlite"),
+            ("SQLITE_PATH", "/data/axi.db"),
+        ])
+        .unwrap();
+        assert_eq!(loaded.storage.backend, StorageBackend::Sqlite);
+        assert_eq!(loaded.storage.sqlite_path, "/data/axi.db");
+
+        assert!(config(&[("STORAGE_BACKEND", "postgres")]).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_updates() {
+        assert_eq!(
+            config(&[]).unwrap().storage.aggregate_updates,
+            AggregateUpdates::Inline
+        );
+        assert_eq!(
+            config(&[
+                ("STORAGE_BACKEND", "dynamodb"),
+                ("AGGREGATE_UPDATES", "stream")
+            ])
+            .unwrap()
+            .storage
+            .aggregate_updates,
+            AggregateUpdates::Stream
+        );
+        // ストリームがあるのは DynamoDB だけ
+        assert!(config(&[("AGGREGATE_UPDATES", "stream")]).is_err());
+        assert!(config(&[("AGGREGATE_UPDATES", "batch")]).is_err());
+    }
+
+    #[test]
+    fn test_alert_webhook() {
+        assert_eq!(config(&[]).unwrap().alerts, AlertConfig::default());
+        let alerts = config(&[
+            ("ALERT_WEBHOOK_URL", "https://hooks.example.com/budget"),
+            ("ALERT_WEBHOOK_SECRET", "webhook-secret"),
+        ])
+        .unwrap()
+        .alerts;
+        assert_eq!(
+            alerts.webhook_url.as_deref(),
+            Some("https://hooks.example.com/budget")
+        );
+        assert_eq!(alerts.webhook_secret.as_deref(), Some("webhook-secret"));
+        // 送信先のない鍵は設定の誤り
+        assert!(config(&[("ALERT_WEBHOOK_SECRET", "webhook-secret")]).is_err());
+    }
+
+    #[test]
+    fn test_auth_settings_are_required() {
+        let auth = config(&[]).unwrap().auth;
+        assert_eq!(auth.audience, "axi-budget");
+        assert_eq!(auth.jwks, JwksSource::File("jwks.json".to_string()));
+
+        let url_only = AuthConfig::from_lookup(&lookup(&[
+            (
+                "AUTH_ISSUER",
+                "https://cognito-idp.ap-northeast-1.amazonaws.com/pool",
+            ),
+            ("AUTH_AUDIENCE", "client"),
+            (
+                "AUTH_JWKS_URL",
+                "https://cognito-idp.ap-northeast-1.amazonaws.com/pool/.well-known/jwks.json",
+            ),
+        ]))
+        .unwrap();
+        assert!(matches!(url_only.jwks, JwksSource::Url(_)));
+
+        assert!(Config::from_lookup(lookup(&[])).is_err());
+        assert!(AuthConfig::from_lookup(&lookup(&AUTH_VARS[..2])).is_err());
+    }
+}
@@ -1,18 +1,40 @@
+use crate::domain::errors::{DomainError, DomainResult};
 use crate::domain::value_objects::*;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// 取引の種別
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TransactionType {
     /// 実支出（家計に影響のある支出）
     Real,
     /// 立て替え（家計に影響のない支出）
     Flow,
+    /// 口座間移動（ATM引き出し・チャージ・カードの引き落としなど。家計に含めない）
+    Transfer,
 }
 
-/// 取引のカテゴリ
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// お金の向き
+///
+/// 返金は収入ではなく、金額が負の支出として記録する
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionDirection {
+    /// 支出
+    #[default]
+    Expense,
+    /// 収入
+    Income,
+}
+
+/// 取引のカテゴリ（組み込みの分類）
+///
+/// ユーザー定義カテゴリ（`Category`）はいずれかの分類に属する
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TransactionCategory {
     Food,
     Transportation,
@@ -24,6 +46,19 @@ pub enum TransactionCategory {
     Other,
 }
 
+impl TransactionCategory {
+    pub const ALL: [TransactionCategory; 8] = [
+        TransactionCategory::Food,
+        TransactionCategory::Transportation,
+        TransactionCategory::Utilities,
+        TransactionCategory::Entertainment,
+        TransactionCategory::Healthcare,
+        TransactionCategory::Shopping,
+        TransactionCategory::Education,
+        TransactionCategory::Other,
+    ];
+}
+
 /// 精算情報
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SettlementInfo {
@@ -33,11 +68,28 @@ pub struct SettlementInfo {
     pub status: SettlementStatus,
 }
 
+/// 口座間移動の情報
+///
+/// 移動元の出金と移動先の入金の両方の取引に同じ内容を持つ
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferInfo {
+    pub transfer_id: String,
+    pub from_account_id: String,
+    pub to_account_id: String,
+}
+
 /// 精算のステータス
+///
+/// `Pending` → `PartiallyRepaid` → `Completed` と進み、返済前であれば `Cancelled` にできる。
+/// `Completed` と `Cancelled` からは遷移しない
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SettlementStatus {
     Pending,
+    /// 一部返済済み
+    PartiallyRepaid,
     Completed,
+    Cancelled,
 }
 
 /// 取引エンティティ
@@ -46,12 +98,24 @@ pub struct Transaction {
     pub transaction_id: TransactionId,
     pub user_id: UserId,
     pub transaction_type: TransactionType,
+    /// 向きが導入される前に保存された取引は支出として扱う
+    #[serde(default)]
+    pub direction: TransactionDirection,
     pub amount: Amount,
     pub description: String,
     pub category: TransactionCategory,
+    /// ユーザー定義カテゴリ（未指定の場合は `category` の既定のカテゴリとして扱う）
+    #[serde(default)]
+    pub category_id: Option<String>,
     pub tags: Vec<String>,
-    pub transaction_date: DateTime<Utc>,
+    pub transaction_date: TransactionDate,
     pub settlement_info: Option<SettlementInfo>,
+    /// お金が出入りした口座
+    #[serde(default)]
+    pub account_id: Option<String>,
+    /// 口座間移動の片側であれば、その移動
+    #[serde(default)]
+    pub transfer: Option<TransferInfo>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -69,33 +133,111 @@ impl Transaction {
             transaction_id: TransactionId::generate(),
             user_id,
             transaction_type,
+            direction: TransactionDirection::Expense,
             amount,
             description,
             category,
+            category_id: None,
             tags: Vec::new(),
-            transaction_date: now,
+            transaction_date: TransactionDate::new(now.date_naive()),
             settlement_info: None,
+            account_id: None,
+            transfer: None,
             created_at: now,
             updated_at: now,
         }
     }
 
-    /// 取引が家計に影響するかどうかを判定
+    /// 取引が予算（支出）に影響するかどうかを判定
     pub fn affects_budget(&self) -> bool {
-        matches!(self.transaction_type, TransactionType::Real)
+        self.transaction_type == TransactionType::Real
+            && self.direction == TransactionDirection::Expense
+    }
+
+    /// 口座間移動の片側かどうか（予算やレポートの集計には含めない）
+    pub fn is_transfer(&self) -> bool {
+        self.transaction_type == TransactionType::Transfer
+    }
+
+    /// 返金（負の支出）かどうか
+    pub fn is_refund(&self) -> bool {
+        self.direction == TransactionDirection::Expense && self.amount.is_negative()
+    }
+
+    /// 収支への影響額（収入は正、支出は負。返金は正になる）
+    pub fn signed_amount(&self) -> Amount {
+        match self.direction {
+            TransactionDirection::Income => self.amount.clone(),
+            TransactionDirection::Expense => {
+                Amount::new(-self.amount.value, self.amount.currency.clone())
+            }
+        }
+    }
+
+    /// 金額と向きの不変条件を検証
+    ///
+    /// 支出は負の金額（返金）を認めるが、収入の取り消しを負の収入としては記録できない
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.amount.is_zero() {
+            return Err(DomainError::Validation(
+                "Transaction amount must not be zero".to_string(),
+            ));
+        }
+        if self.direction == TransactionDirection::Income && self.amount.is_negative() {
+            return Err(DomainError::Validation(
+                "Income must be positive; record refunds as negative expenses".to_string(),
+            ));
+        }
+        match (&self.transfer, self.is_transfer()) {
+            (Some(transfer), true) => {
+                let side = match self.direction {
+                    TransactionDirection::Expense => &transfer.from_account_id,
+                    TransactionDirection::Income => &transfer.to_account_id,
+                };
+                if self.amount.is_negative() || self.account_id.as_ref() != Some(side) {
+                    return Err(DomainError::Validation(
+                        "Transfer transaction does not match its transfer".to_string(),
+                    ));
+                }
+            }
+            (None, true) => {
+                return Err(DomainError::Validation(
+                    "Transfers must be created between two accounts".to_string(),
+                ));
+            }
+            (Some(_), false) => {
+                return Err(DomainError::Validation(
+                    "Only transfer transactions can belong to a transfer".to_string(),
+                ));
+            }
+            (None, false) => {}
+        }
+        Ok(())
     }
 
     /// 取引を更新
+    ///
+    /// 分類を変更した場合、別の分類に属するユーザー定義カテゴリは外す
     pub fn update(&mut self, description: Option<String>, category: Option<TransactionCategory>) {
         if let Some(desc) = description {
             self.description = desc;
         }
         if let Some(cat) = category {
+            if cat != self.category {
+                self.category_id = None;
+            }
             self.category = cat;
         }
         self.updated_at = Utc::now();
     }
 
+    /// ユーザー定義カテゴリを設定（分類はそのカテゴリの分類になる）
+    pub fn assign_category(&mut self, category: &Category) {
+        self.category = category.kind.clone();
+        self.category_id = Some(category.category_id.clone());
+        self.updated_at = Utc::now();
+    }
+
     /// タグを追加
     pub fn add_tag(&mut self, tag: String) {
         if !self.tags.contains(&tag) {
@@ -111,6 +253,190 @@ impl Transaction {
     }
 }
 
+/// 精算の返済記録
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Repayment {
+    pub amount: Amount,
+    pub repaid_at: DateTime<Utc>,
+}
+
+/// 精算エンティティ
+///
+/// 債務者（debtor）が債権者（creditor）に返すべき金額と、その返済状況を表す
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Settlement {
+    pub settlement_id: String,
+    pub creditor_user_id: UserId,
+    pub debtor_user_id: UserId,
+    pub amount: Amount,
+    pub description: Option<String>,
+    /// 元になった立て替え（`Flow`）取引
+    pub transaction_id: Option<TransactionId>,
+    pub repayments: Vec<Repayment>,
+    pub status: SettlementStatus,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Settlement {
+    pub fn new(
+        creditor_user_id: UserId,
+        debtor_user_id: UserId,
+        amount: Amount,
+        description: Option<String>,
+    ) -> DomainResult<Self> {
+        if creditor_user_id == debtor_user_id {
+            return Err(DomainError::Validation(
+                "Creditor and debtor must be different users".to_string(),
+            ));
+        }
+        if !amount.is_positive() {
+            return Err(DomainError::Validation(
+                "Settlement amount must be positive".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            settlement_id: uuid::Uuid::new_v4().to_string(),
+            creditor_user_id,
+            debtor_user_id,
+            amount,
+            description,
+            transaction_id: None,
+            repayments: Vec::new(),
+            status: SettlementStatus::Pending,
+            completed_at: None,
+            cancelled_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// 立て替え取引から精算を作成（債権者は取引の記録者）
+    ///
+    /// 金額を省略した場合は取引の全額を精算する
+    pub fn from_transaction(
+        transaction: &Transaction,
+        debtor_user_id: UserId,
+        amount: Option<Amount>,
+        description: Option<String>,
+    ) -> DomainResult<Self> {
+        if transaction.transaction_type != TransactionType::Flow {
+            return Err(DomainError::Validation(
+                "Only FLOW transactions can be settled".to_string(),
+            ));
+        }
+        let amount = amount.unwrap_or_else(|| transaction.amount.clone());
+        transaction.amount.ensure_same_currency(&amount)?;
+        if amount.value > transaction.amount.value {
+            return Err(DomainError::Validation(
+                "Settlement amount exceeds the transaction amount".to_string(),
+            ));
+        }
+
+        let mut settlement = Self::new(
+            transaction.user_id.clone(),
+            debtor_user_id,
+            amount,
+            description.or_else(|| Some(transaction.description.clone())),
+        )?;
+        settlement.transaction_id = Some(transaction.transaction_id.clone());
+        Ok(settlement)
+    }
+
+    /// 債権者または債務者かどうか
+    pub fn involves(&self, user_id: &UserId) -> bool {
+        self.creditor_user_id == *user_id || self.debtor_user_id == *user_id
+    }
+
+    /// 返済を受け付ける状態かどうか
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.status,
+            SettlementStatus::Pending | SettlementStatus::PartiallyRepaid
+        )
+    }
+
+    /// 返済済みの金額
+    pub fn repaid_amount(&self) -> Amount {
+        let value = self.repayments.iter().map(|r| r.amount.value).sum();
+        Amount::new(value, self.amount.currency.clone())
+    }
+
+    /// 未返済の金額
+    pub fn remaining_amount(&self) -> Amount {
+        Amount::new(
+            self.amount.value - self.repaid_amount().value,
+            self.amount.currency.clone(),
+        )
+    }
+
+    fn ensure_open(&self) -> DomainResult<()> {
+        if !self.is_open() {
+            return Err(DomainError::Conflict(format!(
+                "Settlement is already {:?}",
+                self.status
+            )));
+        }
+        Ok(())
+    }
+
+    /// 一部または全額の返済を記録（未返済額がなくなれば完了する）
+    pub fn repay(&mut self, amount: Amount) -> DomainResult<()> {
+        self.ensure_open()?;
+        self.amount.ensure_same_currency(&amount)?;
+        if !amount.is_positive() {
+            return Err(DomainError::Validation(
+                "Repayment amount must be positive".to_string(),
+            ));
+        }
+        if amount.value > self.remaining_amount().value {
+            return Err(DomainError::Validation(
+                "Repayment exceeds the remaining amount".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        self.repayments.push(Repayment {
+            amount,
+            repaid_at: now,
+        });
+        if self.remaining_amount().is_zero() {
+            self.status = SettlementStatus::Completed;
+            self.completed_at = Some(now);
+        } else {
+            self.status = SettlementStatus::PartiallyRepaid;
+        }
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// 未返済額をまとめて返済されたものとして完了する
+    pub fn complete(&mut self) -> DomainResult<()> {
+        self.ensure_open()?;
+        let remaining = self.remaining_amount();
+        self.repay(remaining)
+    }
+
+    /// 精算を取り消す（返済を記録済みのものは取り消せない）
+    pub fn cancel(&mut self) -> DomainResult<()> {
+        self.ensure_open()?;
+        if !self.repayments.is_empty() {
+            return Err(DomainError::Conflict(
+                "Settlement with repayments cannot be cancelled".to_string(),
+            ));
+        }
+        let now = Utc::now();
+        self.status = SettlementStatus::Cancelled;
+        self.cancelled_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+}
+
 /// ユーザープロフィールエンティティ
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserProfile {
@@ -135,6 +461,23 @@ impl UserProfile {
         }
     }
 
+    /// プロフィールのタイムゾーン（IANA のタイムゾーン名）
+    pub fn time_zone(&self) -> DomainResult<Tz> {
+        self.timezone
+            .parse()
+            .map_err(|_| DomainError::Validation(format!("Unknown timezone: {}", self.timezone)))
+    }
+
+    /// ユーザーのタイムゾーンでの `now` の日付
+    pub fn local_date(&self, now: DateTime<Utc>) -> DomainResult<NaiveDate> {
+        Ok(now.with_timezone(&self.time_zone()?).date_naive())
+    }
+
+    pub fn validate(&self) -> DomainResult<()> {
+        self.time_zone()?;
+        Ok(())
+    }
+
     pub fn update(&mut self, display_name: Option<String>, currency: Option<String>, timezone: Option<String>) {
         if let Some(name) = display_name {
             self.display_name = Some(name);
@@ -154,24 +497,269 @@ impl UserProfile {
 pub struct Budget {
     pub budget_id: String,
     pub user_id: UserId,
-    pub category: TransactionCategory,
+    /// 対象が1つのカテゴリだった頃の `category` も読み込む
+    #[serde(alias = "category", deserialize_with = "deserialize_budget_target")]
+    pub target: BudgetTarget,
     pub amount: Amount,
     pub period: BudgetPeriod,
     pub alert_threshold: f64, // 0.0 - 1.0
+    /// 月の開始日（給料日など。未指定の場合は1日。月・四半期・年の予算のみ）
+    #[serde(default)]
+    pub month_start_day: Option<u32>,
+    /// 週の開始曜日（未指定の場合は月曜日。週の予算のみ）
+    #[serde(default)]
+    pub week_start: Option<DayOfWeek>,
+    /// 期間の初日と最終日（期間指定の予算のみ）
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub end_date: Option<NaiveDate>,
+    /// 使い残し（超過）を次の期間に繰り越すか
+    #[serde(default)]
+    pub rollover: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// 予算の対象となる取引
+///
+/// 1つの取引が複数の予算の対象になってもよい（例: 食費の予算と家計全体の予算）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "SCREAMING_SNAKE_CASE",
+    rename_all_fields = "camelCase"
+)]
+pub enum BudgetTarget {
+    /// いずれかのカテゴリの取引
+    Categories {
+        categories: Vec<TransactionCategory>,
+    },
+    /// いずれかのユーザー定義カテゴリ（その子カテゴリを含む）の取引
+    UserCategories { category_ids: Vec<String> },
+    /// いずれかのタグが付いた取引
+    Tags { tags: Vec<String> },
+    /// すべての取引（家計全体）
+    All,
+}
+
+impl BudgetTarget {
+    /// 1つのカテゴリを対象にする
+    pub fn category(category: TransactionCategory) -> Self {
+        BudgetTarget::Categories {
+            categories: vec![category],
+        }
+    }
+
+    /// 対象が1つのカテゴリだけであれば、そのカテゴリ
+    pub fn single_category(&self) -> Option<&TransactionCategory> {
+        match self {
+            BudgetTarget::Categories { categories } if categories.len() == 1 => categories.first(),
+            _ => None,
+        }
+    }
+
+    /// 取引がこの対象に含まれるか（ユーザー定義カテゴリは `categories` で親子関係をたどる）
+    pub fn matches(&self, transaction: &Transaction, categories: &CategoryTree) -> bool {
+        match self {
+            BudgetTarget::Categories { categories } => categories.contains(&transaction.category),
+            BudgetTarget::UserCategories { category_ids } => {
+                categories.category_of(transaction).is_some_and(|category| {
+                    category_ids
+                        .iter()
+                        .any(|id| categories.is_within(&category.category_id, id))
+                })
+            }
+            BudgetTarget::Tags { tags } => tags.iter().any(|tag| transaction.tags.contains(tag)),
+            BudgetTarget::All => true,
+        }
+    }
+
+    /// 対象が空でないことを検証
+    pub fn validate(&self) -> DomainResult<()> {
+        match self {
+            BudgetTarget::Categories { categories } if categories.is_empty() => Err(
+                DomainError::Validation("Budget must target at least one category".to_string()),
+            ),
+            BudgetTarget::UserCategories { category_ids } if category_ids.is_empty() => Err(
+                DomainError::Validation("Budget must target at least one category".to_string()),
+            ),
+            BudgetTarget::Tags { tags } if tags.is_empty() => Err(DomainError::Validation(
+                "Budget must target at least one tag".to_string(),
+            )),
+            BudgetTarget::Tags { tags } if tags.iter().any(|tag| tag.trim().is_empty()) => Err(
+                DomainError::Validation("Budget tags must not be blank".to_string()),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 予算の対象を読み込む（カテゴリ名だけの古い形式にも対応する）
+fn deserialize_budget_target<'de, D>(deserializer: D) -> Result<BudgetTarget, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StoredTarget {
+        Category(TransactionCategory),
+        Target(BudgetTarget),
+    }
+
+    Ok(match StoredTarget::deserialize(deserializer)? {
+        StoredTarget::Category(category) => BudgetTarget::category(category),
+        StoredTarget::Target(target) => target,
+    })
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum BudgetPeriod {
+    Weekly,
+    Monthly,
+    Quarterly,
+    Yearly,
+    /// 旅行・イベントなど、開始日と終了日を指定する1回限りの期間
+    Custom,
+}
+
+/// 曜日
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DayOfWeek {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<DayOfWeek> for Weekday {
+    fn from(day: DayOfWeek) -> Self {
+        match day {
+            DayOfWeek::Monday => Weekday::Mon,
+            DayOfWeek::Tuesday => Weekday::Tue,
+            DayOfWeek::Wednesday => Weekday::Wed,
+            DayOfWeek::Thursday => Weekday::Thu,
+            DayOfWeek::Friday => Weekday::Fri,
+            DayOfWeek::Saturday => Weekday::Sat,
+            DayOfWeek::Sunday => Weekday::Sun,
+        }
+    }
+}
+
+/// 推移レポートの集計単位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReportGranularity {
+    Daily,
     Monthly,
     Yearly,
 }
 
+impl ReportGranularity {
+    /// `date` を含む集計単位の期間（月は1日、年は1月1日に始まる）
+    pub fn window_containing(&self, date: NaiveDate) -> PeriodWindow {
+        match self {
+            Self::Daily => PeriodWindow::custom(date, date),
+            Self::Monthly => PeriodWindow::months(date, 1, 1),
+            Self::Yearly => PeriodWindow::months(date, 12, 1),
+        }
+    }
+
+    /// 集計範囲を指定しない場合の範囲（日次は今月、月次は直近12か月、年次は直近5年）
+    pub fn default_window(&self, today: NaiveDate) -> PeriodWindow {
+        let current = self.window_containing(today);
+        match self {
+            Self::Daily => PeriodWindow::months(today, 1, 1),
+            Self::Monthly => PeriodWindow {
+                start: current.start - Months::new(11),
+                end: current.end,
+            },
+            Self::Yearly => PeriodWindow {
+                start: current.start - Months::new(48),
+                end: current.end,
+            },
+        }
+    }
+}
+
+/// 予算期間の範囲（ユーザーのタイムゾーンでの日付。`end` の日は含まない）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodWindow {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl PeriodWindow {
+    /// `date` を含む `week_start` 曜日始まりの週
+    pub fn week(date: NaiveDate, week_start: Weekday) -> Self {
+        let start = date - Days::new(u64::from(date.weekday().days_since(week_start)));
+        Self {
+            start,
+            end: start + Days::new(7),
+        }
+    }
+
+    /// `date` を含む `months` か月の期間（1・3・12 か月。1月を起点に区切る）
+    ///
+    /// 各期間は `month_start_day` 日に始まる。月の日数を超える開始日はその月の月末として扱う
+    pub fn months(date: NaiveDate, months: u32, month_start_day: u32) -> Self {
+        let start_of =
+            |first: NaiveDate| day_of_month(first.year(), first.month(), month_start_day);
+
+        let first_month = date.month0() / months * months + 1;
+        let mut first = NaiveDate::from_ymd_opt(date.year(), first_month, 1).unwrap();
+        if date < start_of(first) {
+            first = first - Months::new(months);
+        }
+        Self {
+            start: start_of(first),
+            end: start_of(first + Months::new(months)),
+        }
+    }
+
+    /// 初日と最終日を指定した期間
+    pub fn custom(start_date: NaiveDate, end_date: NaiveDate) -> Self {
+        Self {
+            start: start_date,
+            end: end_date + Days::new(1),
+        }
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date < self.end
+    }
+
+    /// 期間の最終日
+    pub fn last_day(&self) -> NaiveDate {
+        self.end - Days::new(1)
+    }
+
+    /// 月の初日に始まり月の初日に終わる（月次集計を足し合わせて集計できる）期間かどうか
+    pub fn is_whole_months(&self) -> bool {
+        self.start.day() == 1 && self.end.day() == 1
+    }
+
+    /// 取引日が期間内の取引
+    ///
+    /// 取引日は時刻を持たない暦日のため、タイムゾーンの変換をせずにその日付で振り分ける
+    pub fn transactions_in(&self, transactions: &[Transaction]) -> Vec<Transaction> {
+        transactions
+            .iter()
+            .filter(|t| self.contains(t.transaction_date.date()))
+            .cloned()
+            .collect()
+    }
+}
+
 impl Budget {
     pub fn new(
         user_id: UserId,
-        category: TransactionCategory,
+        target: BudgetTarget,
         amount: Amount,
         period: BudgetPeriod,
         alert_threshold: f64,
@@ -180,20 +768,23 @@ impl Budget {
         Self {
             budget_id: uuid::Uuid::new_v4().to_string(),
             user_id,
-            category,
+            target,
             amount,
             period,
             alert_threshold,
+            month_start_day: None,
+            week_start: None,
+            start_date: None,
+            end_date: None,
+            rollover: false,
             created_at: now,
             updated_at: now,
         }
     }
 
     /// 予算に対する使用率を計算
-    pub fn calculate_usage_percentage(&self, spent_amount: &Amount) -> Result<f64, String> {
-        if self.amount.currency != spent_amount.currency {
-            return Err("Currency mismatch".to_string());
-        }
+    pub fn calculate_usage_percentage(&self, spent_amount: &Amount) -> DomainResult<f64> {
+        self.amount.ensure_same_currency(spent_amount)?;
 
         if self.amount.value == 0 {
             return Ok(0.0);
@@ -202,151 +793,2295 @@ impl Budget {
         Ok(spent_amount.value as f64 / self.amount.value as f64)
     }
 
+    /// 取引のうち、この予算の対象の支出額を合計する
+    ///
+    /// 収入と立て替えは含めず、返金は支出から差し引く（0未満にはしない）。
+    /// 予算と異なる通貨の取引は換算できないため数えない
+    pub fn spent_amount(
+        &self,
+        transactions: &[Transaction],
+        categories: &CategoryTree,
+    ) -> DomainResult<Amount> {
+        let mut spent = Amount::new(0, self.amount.currency.clone());
+        for transaction in transactions.iter().filter(|t| {
+            t.affects_budget()
+                && t.amount.currency == self.amount.currency
+                && self.target.matches(t, categories)
+        }) {
+            spent = spent.add(&transaction.amount)?;
+        }
+        Ok(Amount::new(spent.value.max(0), spent.currency))
+    }
+
+    /// `date`（ユーザーのタイムゾーンでの日付）を含む予算期間
+    ///
+    /// 期間指定の予算は `date` にかかわらず指定された期間を返す
+    pub fn window_containing(&self, date: NaiveDate) -> DomainResult<PeriodWindow> {
+        let month_start_day = self.month_start_day.unwrap_or(1);
+        Ok(match self.period {
+            BudgetPeriod::Weekly => {
+                PeriodWindow::week(date, self.week_start.unwrap_or(DayOfWeek::Monday).into())
+            }
+            BudgetPeriod::Monthly => PeriodWindow::months(date, 1, month_start_day),
+            BudgetPeriod::Quarterly => PeriodWindow::months(date, 3, month_start_day),
+            BudgetPeriod::Yearly => PeriodWindow::months(date, 12, month_start_day),
+            BudgetPeriod::Custom => match (self.start_date, self.end_date) {
+                (Some(start), Some(end)) => PeriodWindow::custom(start, end),
+                _ => {
+                    return Err(DomainError::Validation(
+                        "Custom budget period requires start and end dates".to_string(),
+                    ))
+                }
+            },
+        })
+    }
+
+    /// `since` を含む期間から `window` の直前の期間までの繰越額
+    ///
+    /// 各期間の使い残しを加え、超過分を差し引く。繰り越さない予算は常に0
+    pub fn carryover(
+        &self,
+        transactions: &[Transaction],
+        categories: &CategoryTree,
+        since: NaiveDate,
+        window: &PeriodWindow,
+    ) -> DomainResult<Amount> {
+        let mut carried = Amount::new(0, self.amount.currency.clone());
+        if !self.rollover {
+            return Ok(carried);
+        }
+        let mut past = self.window_containing(since)?;
+        while past.end <= window.start {
+            let spent = self.spent_amount(&past.transactions_in(transactions), categories)?;
+            carried = carried.add(&self.amount.subtract(&spent)?)?;
+            past = self.window_containing(past.end)?;
+        }
+        Ok(carried)
+    }
+
+    /// 予算を更新
+    ///
+    /// 過去の支出と比較できなくなるため、通貨の変更は認めない
+    pub fn update(
+        &mut self,
+        target: Option<BudgetTarget>,
+        amount: Option<Amount>,
+        alert_threshold: Option<f64>,
+        month_start_day: Option<u32>,
+        week_start: Option<DayOfWeek>,
+        rollover: Option<bool>,
+    ) -> DomainResult<()> {
+        if let Some(amount) = amount {
+            self.amount.ensure_same_currency(&amount)?;
+            self.amount = amount;
+        }
+        if let Some(target) = target {
+            self.target = target;
+        }
+        if let Some(threshold) = alert_threshold {
+            self.alert_threshold = threshold;
+        }
+        if let Some(day) = month_start_day {
+            self.month_start_day = Some(day);
+        }
+        if let Some(day) = week_start {
+            self.week_start = Some(day);
+        }
+        if let Some(rollover) = rollover {
+            self.rollover = rollover;
+        }
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// 予算額・対象・アラート閾値・期間の不変条件を検証
+    pub fn validate(&self) -> DomainResult<()> {
+        self.target.validate()?;
+        if self.amount.is_negative() {
+            return Err(DomainError::Validation(
+                "Budget amount must not be negative".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.alert_threshold) {
+            return Err(DomainError::Validation(
+                "Alert threshold must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+        if let Some(day) = self.month_start_day {
+            if !(1..=LAST_DAY_OF_MONTH).contains(&day) {
+                return Err(DomainError::Validation(format!(
+                    "Month start day must be between 1 and {}",
+                    LAST_DAY_OF_MONTH
+                )));
+            }
+        }
+        let is_custom = self.period == BudgetPeriod::Custom;
+        if self.month_start_day.is_some()
+            && matches!(self.period, BudgetPeriod::Weekly | BudgetPeriod::Custom)
+        {
+            return Err(DomainError::Validation(
+                "Month start day only applies to monthly, quarterly and yearly budgets".to_string(),
+            ));
+        }
+        if self.week_start.is_some() && self.period != BudgetPeriod::Weekly {
+            return Err(DomainError::Validation(
+                "Week start only applies to weekly budgets".to_string(),
+            ));
+        }
+        match (is_custom, self.start_date, self.end_date) {
+            (true, Some(start), Some(end)) if start > end => {
+                return Err(DomainError::Validation(
+                    "Start date must not be after the end date".to_string(),
+                ))
+            }
+            (true, Some(_), Some(_)) | (false, None, None) => {}
+            (true, _, _) => {
+                return Err(DomainError::Validation(
+                    "Custom budget period requires start and end dates".to_string(),
+                ))
+            }
+            (false, _, _) => {
+                return Err(DomainError::Validation(
+                    "Start and end dates only apply to custom budgets".to_string(),
+                ))
+            }
+        }
+        // 1回限りの期間には繰り越し先がない
+        if is_custom && self.rollover {
+            return Err(DomainError::Validation(
+                "Custom budgets cannot roll over".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// アラートが必要かどうかを判定
-    pub fn should_alert(&self, spent_amount: &Amount) -> Result<bool, String> {
+    pub fn should_alert(&self, spent_amount: &Amount) -> DomainResult<bool> {
         let usage = self.calculate_usage_percentage(spent_amount)?;
         Ok(usage >= self.alert_threshold)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_transaction_creation() {
-        let user_id = UserId::new("user123".to_string());
-        let amount = Amount::jpy(1000);
-        let transaction = Transaction::new(
-            user_id.clone(),
-            TransactionType::Real,
-            amount,
-            "Test transaction".to_string(),
-            TransactionCategory::Food,
-        );
+/// 予算の使用率が閾値に達したことを表すドメインイベント
+///
+/// 同じ予算・期間・閾値の組では一度だけ発生させる（`alert_id` が組から決まる）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetAlertRaised {
+    pub alert_id: String,
+    pub user_id: UserId,
+    pub budget_id: String,
+    /// 判定した期間の初日と最終日（ユーザーのタイムゾーンでの日付）
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    /// 判定に使った閾値（0.0 - 1.0）
+    pub threshold: f64,
+    /// 判定時の使用率（1.0 で予算額ちょうど）
+    pub usage: f64,
+    pub spent_amount: Amount,
+    pub budget_amount: Amount,
+    pub raised_at: DateTime<Utc>,
+    /// 通知先への送信が済んだ日時（未送信の間は `None`）
+    #[serde(default)]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
 
-        assert_eq!(transaction.user_id, user_id);
-        assert!(transaction.affects_budget());
-        assert_eq!(transaction.description, "Test transaction");
+impl BudgetAlertRaised {
+    pub fn new(
+        budget: &Budget,
+        window: &PeriodWindow,
+        usage: f64,
+        spent_amount: Amount,
+        raised_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            alert_id: Self::id_for(&budget.budget_id, window.start, budget.alert_threshold),
+            user_id: budget.user_id.clone(),
+            budget_id: budget.budget_id.clone(),
+            period_start: window.start,
+            period_end: window.last_day(),
+            threshold: budget.alert_threshold,
+            usage,
+            spent_amount,
+            budget_amount: budget.amount.clone(),
+            raised_at,
+            delivered_at: None,
+        }
     }
 
-    #[test]
-    fn test_budget_usage_calculation() {
-        let user_id = UserId::new("user123".to_string());
-        let budget_amount = Amount::jpy(10000);
-        let budget = Budget::new(
-            user_id,
-            TransactionCategory::Food,
-            budget_amount,
-            BudgetPeriod::Monthly,
-            0.8,
-        );
-
-        let spent = Amount::jpy(8000);
-        let usage = budget.calculate_usage_percentage(&spent).unwrap();
-        assert_eq!(usage, 0.8);
-
-        assert!(budget.should_alert(&spent).unwrap());
+    /// 予算・期間・閾値の組から決まるID（閾値を変えると同じ期間でも改めて発生する）
+    pub fn id_for(budget_id: &str, period_start: NaiveDate, threshold: f64) -> String {
+        format!(
+            "{}:{}:{}",
+            budget_id,
+            period_start,
+            (threshold * 1000.0).round() as i64
+        )
+    }
+}
 
-        let low_spent = Amount::jpy(5000);
-        assert!(!budget.should_alert(&low_spent).unwrap());
+/// 既定のカテゴリの名前・アイコン・色
+fn default_category_style(
+    kind: &TransactionCategory,
+) -> (&'static str, &'static str, &'static str) {
+    match kind {
+        TransactionCategory::Food => ("食費", "🍽️", "#FF6B6B"),
+        TransactionCategory::Transportation => ("交通費", "🚗", "#4ECDC4"),
+        TransactionCategory::Utilities => ("生活費", "🏠", "#96CEB4"),
+        TransactionCategory::Entertainment => ("娯楽費", "🎮", "#45B7D1"),
+        TransactionCategory::Healthcare => ("医療費", "🏥", "#FFEAA7"),
+        TransactionCategory::Shopping => ("買い物", "🛍️", "#FFB6C1"),
+        TransactionCategory::Education => ("教育費", "📚", "#DDA0DD"),
+        TransactionCategory::Other => ("その他", "📝", "#95A5A6"),
     }
 }
 
-/// グループエンティティ
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct Group {
-    pub group_id: String,
+/// ユーザー定義カテゴリ
+///
+/// 組み込みの分類（`kind`）のいずれかに属し、同じ分類のカテゴリの下に入れ子にできる。
+/// 過去の取引が参照するため削除はせず、使わなくなったカテゴリはアーカイブする
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Category {
+    pub category_id: String,
+    pub user_id: UserId,
     pub name: String,
-    pub description: String,
-    pub owner_id: UserId,
-    pub members: Vec<UserId>,
+    /// 組み込みの分類（このカテゴリの取引の `category` になる）
+    pub kind: TransactionCategory,
+    pub icon: Option<String>,
+    /// 表示色（`#RRGGBB`）
+    pub color: Option<String>,
+    pub parent_id: Option<String>,
+    /// 分類ごとに用意される既定のカテゴリ（カテゴリ未指定の取引はこれに属する）
+    pub is_default: bool,
+    pub is_archived: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-impl Group {
-    pub fn new(name: String, description: String, owner_id: UserId) -> Self {
+impl Category {
+    pub fn new(user_id: UserId, name: String, kind: TransactionCategory) -> Self {
         let now = Utc::now();
         Self {
-            group_id: uuid::Uuid::new_v4().to_string(),
+            category_id: uuid::Uuid::new_v4().to_string(),
+            user_id,
             name,
-            description,
-            owner_id: owner_id.clone(),
-            members: vec![owner_id],
+            kind,
+            icon: None,
+            color: None,
+            parent_id: None,
+            is_default: false,
+            is_archived: false,
             created_at: now,
             updated_at: now,
         }
     }
 
-    /// メンバーを追加
-    pub fn add_member(&mut self, user_id: UserId) {
-        if !self.members.contains(&user_id) {
-            self.members.push(user_id);
-            self.updated_at = Utc::now();
-        }
+    /// 組み込みの分類ごとの既定のカテゴリ
+    pub fn defaults(user_id: &UserId) -> Vec<Category> {
+        TransactionCategory::ALL
+            .into_iter()
+            .map(|kind| {
+                let (name, icon, color) = default_category_style(&kind);
+                let mut category = Category::new(user_id.clone(), name.to_string(), kind);
+                category.icon = Some(icon.to_string());
+                category.color = Some(color.to_string());
+                category.is_default = true;
+                category
+            })
+            .collect()
     }
 
-    /// メンバーを削除
-    pub fn remove_member(&mut self, user_id: &UserId) {
-        if *user_id != self.owner_id {
-            self.members.retain(|id| id != user_id);
-            self.updated_at = Utc::now();
+    /// カテゴリを更新（`parent_id` の `Some(None)` は親から外す）
+    pub fn update(
+        &mut self,
+        name: Option<String>,
+        icon: Option<String>,
+        color: Option<String>,
+        parent_id: Option<Option<String>>,
+        is_archived: Option<bool>,
+    ) {
+        if let Some(name) = name {
+            self.name = name;
         }
-    }
-
-    /// ユーザーがメンバーかどうかを確認
-    pub fn is_member(&self, user_id: &UserId) -> bool {
+        if let Some(icon) = icon {
+            self.icon = Some(icon);
+        }
+        if let Some(color) = color {
+            self.color = Some(color);
+        }
+        if let Some(parent_id) = parent_id {
+            self.parent_id = parent_id;
+        }
+        if let Some(archived) = is_archived {
+            self.is_archived = archived;
+        }
+        self.updated_at = Utc::now();
+    }
+
+    /// 名前・色・既定のカテゴリの不変条件を検証
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(DomainError::Validation(
+                "Category name must not be empty".to_string(),
+            ));
+        }
+        if let Some(color) = &self.color {
+            let hex = color.strip_prefix('#').unwrap_or_default();
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(DomainError::Validation(format!(
+                    "Color must be in #RRGGBB format: {}",
+                    color
+                )));
+            }
+        }
+        if self.is_default && self.parent_id.is_some() {
+            return Err(DomainError::Validation(
+                "Default categories cannot have a parent".to_string(),
+            ));
+        }
+        // カテゴリ未指定の取引の行き先になるため、既定のカテゴリはアーカイブできない
+        if self.is_default && self.is_archived {
+            return Err(DomainError::Validation(
+                "Default categories cannot be archived".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// ユーザーのカテゴリの親子関係
+#[derive(Debug, Clone, Default)]
+pub struct CategoryTree {
+    categories: HashMap<String, Category>,
+}
+
+impl CategoryTree {
+    pub fn new(categories: Vec<Category>) -> Self {
+        Self {
+            categories: categories
+                .into_iter()
+                .map(|c| (c.category_id.clone(), c))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, category_id: &str) -> Option<&Category> {
+        self.categories.get(category_id)
+    }
+
+    /// 取引のカテゴリ（カテゴリ未指定の取引は分類の既定のカテゴリ）
+    pub fn category_of(&self, transaction: &Transaction) -> Option<&Category> {
+        match &transaction.category_id {
+            Some(category_id) => self.get(category_id),
+            None => self
+                .categories
+                .values()
+                .find(|c| c.is_default && c.kind == transaction.category),
+        }
+    }
+
+    /// カテゴリ自身と、親をたどった祖先（近い順）
+    pub fn ancestors(&self, category_id: &str) -> Vec<&Category> {
+        let mut ancestors: Vec<&Category> = Vec::new();
+        let mut next = self.get(category_id);
+        while let Some(category) = next {
+            // 壊れたデータで親子関係が循環していても止まるようにする
+            if ancestors
+                .iter()
+                .any(|a| a.category_id == category.category_id)
+            {
+                break;
+            }
+            ancestors.push(category);
+            next = category.parent_id.as_deref().and_then(|id| self.get(id));
+        }
+        ancestors
+    }
+
+    /// `category_id` が `ancestor_id` 自身またはその子孫か
+    pub fn is_within(&self, category_id: &str, ancestor_id: &str) -> bool {
+        self.ancestors(category_id)
+            .iter()
+            .any(|c| c.category_id == ancestor_id)
+    }
+
+    /// カテゴリの親が、同じユーザーの同じ分類のアーカイブされていないカテゴリで、
+    /// 親子関係が循環しないことを確認
+    pub fn ensure_valid_parent(&self, category: &Category) -> DomainResult<()> {
+        let Some(parent_id) = &category.parent_id else {
+            return Ok(());
+        };
+        let parent = self
+            .get(parent_id)
+            .filter(|p| p.user_id == category.user_id)
+            .ok_or_else(|| {
+                DomainError::Validation(format!("Unknown parent category: {}", parent_id))
+            })?;
+        if parent.is_archived {
+            return Err(DomainError::Validation(format!(
+                "Parent category {} is archived",
+                parent_id
+            )));
+        }
+        if parent.kind != category.kind {
+            return Err(DomainError::Validation(
+                "Parent category must belong to the same kind".to_string(),
+            ));
+        }
+        if self.is_within(parent_id, &category.category_id) {
+            return Err(DomainError::Validation(
+                "A category cannot be nested under itself or its subcategories".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 口座の種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AccountType {
+    /// 現金
+    Cash,
+    /// 銀行口座
+    Bank,
+    /// クレジットカード
+    CreditCard,
+    /// 電子マネー
+    EMoney,
+    Other,
+}
+
+/// 口座エンティティ
+///
+/// 残高は保持せず、開始残高と口座に紐づく取引から計算する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+    pub account_id: String,
+    pub user_id: UserId,
+    pub name: String,
+    pub account_type: AccountType,
+    /// 記録を始めた時点の残高（クレジットカードの未払い額は負の値）
+    pub opening_balance: Amount,
+    /// クレジットカードの利用限度額
+    pub credit_limit: Option<Amount>,
+    /// クレジットカードの締め日・引き落とし日
+    #[serde(default)]
+    pub billing_cycle: Option<BillingCycle>,
+    pub is_active: bool,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// ある時点の口座残高
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountBalance {
+    pub account_id: String,
+    /// 現在残高（開始残高に取引の収支を加えたもの）
+    pub current_balance: Amount,
+    /// 利用可能額（クレジットカードは限度額の残り、限度額が未設定なら `None`）
+    pub available_balance: Option<Amount>,
+    /// この日までの取引を反映した残高（`None` はすべての取引）
+    pub as_of: Option<NaiveDate>,
+}
+
+impl Account {
+    pub fn new(
+        user_id: UserId,
+        name: String,
+        account_type: AccountType,
+        opening_balance: Amount,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            account_id: uuid::Uuid::new_v4().to_string(),
+            user_id,
+            name,
+            account_type,
+            opening_balance,
+            credit_limit: None,
+            billing_cycle: None,
+            is_active: true,
+            description: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// 口座を更新
+    ///
+    /// 過去の取引と合わせて残高を計算するため、種別と開始残高は変更できない
+    pub fn update(
+        &mut self,
+        name: Option<String>,
+        description: Option<String>,
+        credit_limit: Option<Amount>,
+        is_active: Option<bool>,
+    ) {
+        if let Some(name) = name {
+            self.name = name;
+        }
+        if let Some(description) = description {
+            self.description = Some(description);
+        }
+        if let Some(limit) = credit_limit {
+            self.credit_limit = Some(limit);
+        }
+        if let Some(active) = is_active {
+            self.is_active = active;
+        }
+        self.updated_at = Utc::now();
+    }
+
+    /// 口座名と限度額の不変条件を検証
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(DomainError::Validation(
+                "Account name must not be empty".to_string(),
+            ));
+        }
+        if let Some(limit) = &self.credit_limit {
+            if self.account_type != AccountType::CreditCard {
+                return Err(DomainError::Validation(
+                    "Only credit cards can have a credit limit".to_string(),
+                ));
+            }
+            self.opening_balance.ensure_same_currency(limit)?;
+            if limit.is_negative() {
+                return Err(DomainError::Validation(
+                    "Credit limit must not be negative".to_string(),
+                ));
+            }
+        }
+        if let Some(cycle) = &self.billing_cycle {
+            if self.account_type != AccountType::CreditCard {
+                return Err(DomainError::Validation(
+                    "Only credit cards can have a billing cycle".to_string(),
+                ));
+            }
+            if cycle.payment_account_id.as_deref() == Some(self.account_id.as_str()) {
+                return Err(DomainError::Validation(
+                    "A credit card cannot be paid from itself".to_string(),
+                ));
+            }
+            cycle.validate()?;
+        }
+        Ok(())
+    }
+
+    /// クレジットカードの引き落とし口座
+    pub fn payment_account_id(&self) -> Option<&str> {
+        self.billing_cycle
+            .as_ref()
+            .and_then(|cycle| cycle.payment_account_id.as_deref())
+    }
+
+    /// 取引がこの口座のお金を動かすかどうか
+    pub fn is_affected_by(&self, transaction: &Transaction) -> bool {
+        transaction.account_id.as_deref() == Some(self.account_id.as_str())
+    }
+
+    /// 取引から残高を計算する
+    ///
+    /// `as_of` を指定した場合は、取引日がその日以前の取引だけを反映する。取引日はユーザーの
+    /// タイムゾーンでの暦日のため、ユーザーにとってのその日の終わり時点の残高になる。
+    /// 立て替えも口座からお金が出ていくため、支出と同様に残高を減らす
+    pub fn balance(
+        &self,
+        transactions: &[Transaction],
+        as_of: Option<NaiveDate>,
+    ) -> DomainResult<AccountBalance> {
+        let mut current = self.opening_balance.clone();
+        for transaction in transactions.iter().filter(|t| {
+            self.is_affected_by(t) && as_of.is_none_or(|as_of| t.transaction_date.date() <= as_of)
+        }) {
+            current = current.add(&transaction.signed_amount())?;
+        }
+
+        let available = match self.account_type {
+            AccountType::CreditCard => match &self.credit_limit {
+                Some(limit) => Some(limit.add(&current)?),
+                None => None,
+            },
+            _ => Some(current.clone()),
+        };
+        Ok(AccountBalance {
+            account_id: self.account_id.clone(),
+            current_balance: current,
+            available_balance: available,
+            as_of,
+        })
+    }
+}
+
+/// 日付として指定できる最も遅い日（締め日・引き落とし日・月の開始日。月の日数を超える日は月末として扱う）
+pub const LAST_DAY_OF_MONTH: u32 = 31;
+
+/// 締め日から引き落とし月までの最大の月数（翌々月払い）
+pub const MAX_PAYMENT_MONTH_OFFSET: u32 = 2;
+
+/// 月の `day` 日（月の日数を超える場合は月末）
+fn day_of_month(year: i32, month: u32, day: u32) -> NaiveDate {
+    (1..=day)
+        .rev()
+        .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+        .expect("every month has a first day")
+}
+
+/// クレジットカードの締め日（締め日）と引き落とし日（引き落とし日）
+///
+/// 締め日までの利用を1枚の明細にまとめ、締め日の `payment_month_offset` か月後の
+/// `payment_day` に引き落とされる。土日祝日による引き落とし日の繰り延べは扱わない
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BillingCycle {
+    /// 締め日（1〜31）
+    pub closing_day: u32,
+    /// 引き落とし日（1〜31）
+    pub payment_day: u32,
+    /// 締め日の何か月後に引き落とされるか（翌月払いは 1）
+    pub payment_month_offset: u32,
+    /// 引き落とし口座
+    pub payment_account_id: Option<String>,
+}
+
+impl BillingCycle {
+    /// 日付と引き落とし月の不変条件を検証
+    pub fn validate(&self) -> DomainResult<()> {
+        for (name, day) in [
+            ("Closing day", self.closing_day),
+            ("Payment day", self.payment_day),
+        ] {
+            if !(1..=LAST_DAY_OF_MONTH).contains(&day) {
+                return Err(DomainError::Validation(format!(
+                    "{} must be between 1 and {}",
+                    name, LAST_DAY_OF_MONTH
+                )));
+            }
+        }
+        if self.payment_month_offset > MAX_PAYMENT_MONTH_OFFSET {
+            return Err(DomainError::Validation(format!(
+                "Payment must be due within {} months of closing",
+                MAX_PAYMENT_MONTH_OFFSET
+            )));
+        }
+        if self.payment_month_offset == 0 && self.payment_day <= self.closing_day {
+            return Err(DomainError::Validation(
+                "Payment day must come after the closing day in the same month".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 指定日の利用が載る明細の締め日（当日が締め日ならその日）
+    pub fn closing_date_for(&self, date: NaiveDate) -> NaiveDate {
+        let closing = day_of_month(date.year(), date.month(), self.closing_day);
+        if date <= closing {
+            return closing;
+        }
+        let next = date.with_day(1).unwrap() + Months::new(1);
+        day_of_month(next.year(), next.month(), self.closing_day)
+    }
+
+    /// 締め日の直前の締め日
+    pub fn previous_closing_date(&self, closing_date: NaiveDate) -> NaiveDate {
+        self.closing_date_for(closing_date.with_day(1).unwrap() - Months::new(1))
+    }
+
+    /// 締め日に対応する引き落とし日
+    pub fn due_date_for(&self, closing_date: NaiveDate) -> NaiveDate {
+        let month = closing_date.with_day(1).unwrap() + Months::new(self.payment_month_offset);
+        day_of_month(month.year(), month.month(), self.payment_day)
+    }
+}
+
+/// クレジットカードの利用明細（1回の締めの分）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardStatement {
+    pub account_id: String,
+    /// 対象期間の初日（前回の締め日の翌日）
+    pub period_start: NaiveDate,
+    pub closing_date: NaiveDate,
+    pub due_date: NaiveDate,
+    /// 請求額（利用額から返金を差し引いたもの）
+    pub total: Amount,
+    /// 明細に載る取引（取引日順）
+    pub transaction_ids: Vec<TransactionId>,
+}
+
+impl CardStatement {
+    /// 指定日の時点で締められている（請求額が確定している）かどうか
+    pub fn is_closed(&self, today: NaiveDate) -> bool {
+        self.closing_date < today
+    }
+}
+
+/// 口座間移動
+///
+/// 移動元の出金（支出）と移動先の入金（収入）の2件の `Transfer` 取引として保存する。
+/// 両方の口座残高を動かすが、家計の支出・収入には含めない
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountTransfer {
+    pub transfer_id: String,
+    pub user_id: UserId,
+    pub from_account_id: String,
+    pub to_account_id: String,
+    pub amount: Amount,
+    pub description: String,
+    pub transaction_date: TransactionDate,
+}
+
+impl AccountTransfer {
+    pub fn new(
+        user_id: UserId,
+        from_account_id: String,
+        to_account_id: String,
+        amount: Amount,
+        description: String,
+    ) -> Self {
+        Self {
+            transfer_id: uuid::Uuid::new_v4().to_string(),
+            user_id,
+            from_account_id,
+            to_account_id,
+            amount,
+            description,
+            transaction_date: TransactionDate::new(Utc::now().date_naive()),
+        }
+    }
+
+    /// 金額と口座の不変条件を検証
+    pub fn validate(&self) -> DomainResult<()> {
+        if !self.amount.is_positive() {
+            return Err(DomainError::Validation(
+                "Transfer amount must be positive".to_string(),
+            ));
+        }
+        if self.from_account_id == self.to_account_id {
+            return Err(DomainError::Validation(
+                "Cannot transfer to the same account".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 出金側・入金側の取引を作る
+    pub fn transactions(&self) -> [Transaction; 2] {
+        let info = TransferInfo {
+            transfer_id: self.transfer_id.clone(),
+            from_account_id: self.from_account_id.clone(),
+            to_account_id: self.to_account_id.clone(),
+        };
+        let side = |direction: TransactionDirection, account_id: &str| {
+            let mut transaction = Transaction::new(
+                self.user_id.clone(),
+                TransactionType::Transfer,
+                self.amount.clone(),
+                self.description.clone(),
+                TransactionCategory::Other,
+            );
+            transaction.direction = direction;
+            transaction.transaction_date = self.transaction_date;
+            transaction.account_id = Some(account_id.to_string());
+            transaction.transfer = Some(info.clone());
+            transaction
+        };
+        [
+            side(TransactionDirection::Expense, &self.from_account_id),
+            side(TransactionDirection::Income, &self.to_account_id),
+        ]
+    }
+
+    /// 保存済みの取引（片側のみでもよい）から移動を復元
+    pub fn from_transaction(transaction: &Transaction) -> Option<Self> {
+        let info = transaction.transfer.as_ref()?;
+        Some(Self {
+            transfer_id: info.transfer_id.clone(),
+            user_id: transaction.user_id.clone(),
+            from_account_id: info.from_account_id.clone(),
+            to_account_id: info.to_account_id.clone(),
+            amount: transaction.amount.clone(),
+            description: transaction.description.clone(),
+            transaction_date: transaction.transaction_date,
+        })
+    }
+}
+
+/// グループエンティティ
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Group {
+    pub group_id: String,
+    pub name: String,
+    pub description: String,
+    pub owner_id: UserId,
+    pub members: Vec<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Group {
+    pub fn new(name: String, description: String, owner_id: UserId) -> Self {
+        let now = Utc::now();
+        Self {
+            group_id: uuid::Uuid::new_v4().to_string(),
+            name,
+            description,
+            owner_id: owner_id.clone(),
+            members: vec![owner_id],
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// メンバーを追加
+    pub fn add_member(&mut self, user_id: UserId) {
+        if !self.members.contains(&user_id) {
+            self.members.push(user_id);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    /// メンバーを削除
+    pub fn remove_member(&mut self, user_id: &UserId) {
+        if *user_id != self.owner_id {
+            self.members.retain(|id| id != user_id);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    /// ユーザーがメンバーかどうかを確認
+    pub fn is_member(&self, user_id: &UserId) -> bool {
         self.members.contains(user_id)
     }
 
-    /// ユーザーがオーナーかどうかを確認
-    pub fn is_owner(&self, user_id: &UserId) -> bool {
-        self.owner_id == *user_id
+    /// ユーザーがオーナーかどうかを確認
+    pub fn is_owner(&self, user_id: &UserId) -> bool {
+        self.owner_id == *user_id
+    }
+}
+
+/// 割合指定の合計（1/100 % 単位で 100%）
+pub const FULL_SPLIT_PERCENTAGE: u32 = 10_000;
+
+/// グループ取引の割り勘方法
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    rename_all = "SCREAMING_SNAKE_CASE",
+    tag = "method",
+    content = "entries"
+)]
+pub enum SplitRule {
+    /// 対象メンバーで均等に分ける
+    Equal(Vec<UserId>),
+    /// 口数の比で分ける
+    Shares(Vec<(UserId, u64)>),
+    /// 割合で分ける（1/100 % 単位、合計は `FULL_SPLIT_PERCENTAGE`）
+    Percentages(Vec<(UserId, u32)>),
+    /// メンバーごとの金額を指定する（合計は取引額と一致すること）
+    Exact(Vec<(UserId, Amount)>),
+}
+
+impl SplitRule {
+    /// 負担するメンバー（指定順）
+    pub fn participants(&self) -> Vec<&UserId> {
+        match self {
+            Self::Equal(members) => members.iter().collect(),
+            Self::Shares(shares) => shares.iter().map(|(id, _)| id).collect(),
+            Self::Percentages(percentages) => percentages.iter().map(|(id, _)| id).collect(),
+            Self::Exact(amounts) => amounts.iter().map(|(id, _)| id).collect(),
+        }
+    }
+
+    /// 金額をメンバーごとの負担額に分ける（ユーザーID順、合計は常に `amount` と一致）
+    ///
+    /// 円未満の端数は最大剰余法で配る。剰余が同じ場合は支払者が先に負担し、
+    /// 残りはユーザーID順に1円ずつ割り当てる
+    pub fn split(
+        &self,
+        amount: &Amount,
+        paid_by: &UserId,
+    ) -> DomainResult<Vec<TransactionParticipant>> {
+        let participants = self.participants();
+        if participants.is_empty() {
+            return Err(DomainError::Validation(
+                "A split needs at least one participant".to_string(),
+            ));
+        }
+        let mut sorted = participants.clone();
+        sorted.sort_by(|a, b| a.value().cmp(b.value()));
+        sorted.dedup();
+        if sorted.len() != participants.len() {
+            return Err(DomainError::Validation(
+                "A member appears more than once in the split".to_string(),
+            ));
+        }
+
+        let weights: Vec<u64> = match self {
+            Self::Equal(members) => vec![1; members.len()],
+            Self::Shares(shares) => shares.iter().map(|&(_, weight)| weight).collect(),
+            Self::Percentages(percentages) => {
+                let total: u64 = percentages.iter().map(|&(_, p)| p as u64).sum();
+                if total != FULL_SPLIT_PERCENTAGE as u64 {
+                    return Err(DomainError::Validation(
+                        "Split percentages must add up to 100%".to_string(),
+                    ));
+                }
+                percentages.iter().map(|&(_, p)| p as u64).collect()
+            }
+            Self::Exact(amounts) => {
+                let mut total = Amount::new(0, amount.currency.clone());
+                for (_, share) in amounts {
+                    if !share.is_positive() {
+                        return Err(DomainError::Validation(
+                            "Split amounts must be positive".to_string(),
+                        ));
+                    }
+                    total = total.add(share)?;
+                }
+                if total != *amount {
+                    return Err(DomainError::Validation(format!(
+                        "Split amounts add up to {} instead of {}",
+                        total, amount
+                    )));
+                }
+                return Ok(Self::by_user_id(
+                    amounts
+                        .iter()
+                        .map(|(user_id, share)| TransactionParticipant {
+                            user_id: user_id.clone(),
+                            share_amount: share.clone(),
+                        })
+                        .collect(),
+                ));
+            }
+        };
+        if weights.contains(&0) {
+            return Err(DomainError::Validation(
+                "Split weights must be positive".to_string(),
+            ));
+        }
+
+        // 端数の優先順（支払者、ユーザーID順）に並べてから配分する
+        let mut order: Vec<usize> = (0..participants.len()).collect();
+        order.sort_by_key(|&i| (participants[i] != paid_by, participants[i].value()));
+        let shares = amount.allocate(&order.iter().map(|&i| weights[i]).collect::<Vec<_>>())?;
+
+        Ok(Self::by_user_id(
+            order
+                .into_iter()
+                .zip(shares)
+                .map(|(i, share_amount)| TransactionParticipant {
+                    user_id: participants[i].clone(),
+                    share_amount,
+                })
+                .collect(),
+        ))
+    }
+
+    fn by_user_id(mut participants: Vec<TransactionParticipant>) -> Vec<TransactionParticipant> {
+        participants.sort_by(|a, b| a.user_id.value().cmp(b.user_id.value()));
+        participants
+    }
+}
+
+/// グループ取引の負担者と負担額
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionParticipant {
+    pub user_id: UserId,
+    pub share_amount: Amount,
+}
+
+/// グループ取引エンティティ
+///
+/// メンバーの1人が支払った共有の支出を、割り勘方法に従ってメンバーに割り当てる
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupTransaction {
+    pub transaction_id: TransactionId,
+    pub group_id: String,
+    pub paid_by: UserId,
+    pub amount: Amount,
+    pub description: String,
+    pub category: TransactionCategory,
+    pub split_rule: SplitRule,
+    /// 割り勘方法から計算した負担額（ユーザーID順）
+    pub participants: Vec<TransactionParticipant>,
+    pub transaction_date: TransactionDate,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl GroupTransaction {
+    pub fn new(
+        group: &Group,
+        paid_by: UserId,
+        amount: Amount,
+        description: String,
+        category: TransactionCategory,
+        split_rule: SplitRule,
+    ) -> DomainResult<Self> {
+        if !amount.is_positive() {
+            return Err(DomainError::Validation(
+                "Transaction amount must be positive".to_string(),
+            ));
+        }
+        if let Some(outsider) = std::iter::once(&paid_by)
+            .chain(split_rule.participants())
+            .find(|user_id| !group.is_member(user_id))
+        {
+            return Err(DomainError::Validation(format!(
+                "{} is not a member of the group",
+                outsider
+            )));
+        }
+        let participants = split_rule.split(&amount, &paid_by)?;
+
+        let now = Utc::now();
+        Ok(Self {
+            transaction_id: TransactionId::generate(),
+            group_id: group.group_id.clone(),
+            paid_by,
+            amount,
+            description,
+            category,
+            split_rule,
+            participants,
+            transaction_date: TransactionDate::new(now.date_naive()),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// メンバーの負担額（負担しない場合はゼロ）
+    pub fn share_of(&self, user_id: &UserId) -> Amount {
+        self.participants
+            .iter()
+            .find(|p| p.user_id == *user_id)
+            .map(|p| p.share_amount.clone())
+            .unwrap_or_else(|| Amount::new(0, self.amount.currency.clone()))
+    }
+
+    /// 支払者が他のメンバーの分を立て替えた金額
+    pub fn fronted_amount(&self) -> Amount {
+        Amount::new(
+            self.amount.value - self.share_of(&self.paid_by).value,
+            self.amount.currency.clone(),
+        )
+    }
+
+    /// 支払者の家計簿に記録する立て替え（`Flow`）取引
+    ///
+    /// 他のメンバーの負担分だけを記録し、ここから各メンバーとの精算を作成できる。
+    /// 自分の分しか支払っていない場合は `None`
+    pub fn payer_transaction(&self) -> Option<Transaction> {
+        let fronted = self.fronted_amount();
+        if !fronted.is_positive() {
+            return None;
+        }
+        let mut transaction = Transaction::new(
+            self.paid_by.clone(),
+            TransactionType::Flow,
+            fronted,
+            self.description.clone(),
+            self.category.clone(),
+        );
+        transaction.transaction_date = self.transaction_date;
+        Some(transaction)
+    }
+}
+
+/// 月次集計の1行（取引種別・向き・分類・カテゴリ・口座・通貨の組ごとの合計）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregateEntry {
+    pub transaction_type: TransactionType,
+    pub direction: TransactionDirection,
+    pub category: TransactionCategory,
+    pub category_id: Option<String>,
+    pub account_id: Option<String>,
+    pub currency: String,
+    /// 金額の合計（返金は負の支出として差し引かれる）
+    pub amount: i64,
+    /// 取引の件数
+    pub count: i64,
+}
+
+impl AggregateEntry {
+    fn of(transaction: &Transaction) -> Self {
+        Self {
+            transaction_type: transaction.transaction_type.clone(),
+            direction: transaction.direction,
+            category: transaction.category.clone(),
+            category_id: transaction.category_id.clone(),
+            account_id: transaction.account_id.clone(),
+            currency: transaction.amount.currency.clone(),
+            amount: transaction.amount.value,
+            count: 1,
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn key(
+        &self,
+    ) -> (
+        &TransactionType,
+        TransactionDirection,
+        &TransactionCategory,
+        &Option<String>,
+        &Option<String>,
+        &str,
+    ) {
+        (
+            &self.transaction_type,
+            self.direction,
+            &self.category,
+            &self.category_id,
+            &self.account_id,
+            &self.currency,
+        )
+    }
+}
+
+/// ユーザーの月ごとの取引の集計
+///
+/// 取引の作成・更新・削除のたびに差分を加算して保つ派生データで、いつでも取引から作り直せる。
+/// 月は取引日（時刻を持たない暦日）の暦月で決める
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonthlyAggregate {
+    pub user_id: UserId,
+    /// 対象月の初日
+    pub month: NaiveDate,
+    /// 組ごとの合計（組の順に並び、件数が0の組は含まない）
+    pub entries: Vec<AggregateEntry>,
+}
+
+impl MonthlyAggregate {
+    pub fn new(user_id: UserId, month: NaiveDate) -> Self {
+        Self {
+            user_id,
+            month,
+            entries: Vec::new(),
+        }
+    }
+
+    /// 取引が集計される月の初日
+    pub fn month_of(transaction: &Transaction) -> NaiveDate {
+        transaction.transaction_date.date().with_day(1)
+            .unwrap()
+    }
+
+    /// 取引から月ごとの集計を作る（月の順）
+    pub fn from_transactions(transactions: &[Transaction]) -> Vec<Self> {
+        Self::changes(&[], transactions)
+    }
+
+    /// 取引の変更（`before` から `after` へ）による月ごとの差分（月の順。差分のない月は含まない）
+    ///
+    /// 作成は `before` を、削除は `after` を空にして求める
+    pub fn changes(before: &[Transaction], after: &[Transaction]) -> Vec<Self> {
+        let mut deltas: Vec<Self> = Vec::new();
+        let signed = before
+            .iter()
+            .map(|t| (t, -1))
+            .chain(after.iter().map(|t| (t, 1)));
+        for (transaction, sign) in signed {
+            let month = Self::month_of(transaction);
+            let index = match deltas
+                .iter()
+                .position(|d| d.user_id == transaction.user_id && d.month == month)
+            {
+                Some(index) => index,
+                None => {
+                    deltas.push(Self::new(transaction.user_id.clone(), month));
+                    deltas.len() - 1
+                }
+            };
+            let mut entry = AggregateEntry::of(transaction);
+            entry.amount *= sign;
+            entry.count *= sign;
+            deltas[index].add_entry(entry);
+        }
+        deltas.retain(|d| !d.is_empty());
+        deltas.sort_by(|a, b| (a.user_id.value(), a.month).cmp(&(b.user_id.value(), b.month)));
+        deltas
+    }
+
+    /// 差分を加算する
+    pub fn merge(&mut self, delta: &MonthlyAggregate) {
+        for entry in &delta.entries {
+            self.add_entry(entry.clone());
+        }
+    }
+
+    fn add_entry(&mut self, entry: AggregateEntry) {
+        match self
+            .entries
+            .binary_search_by(|existing| existing.key().cmp(&entry.key()))
+        {
+            Ok(index) => {
+                let existing = &mut self.entries[index];
+                existing.amount += entry.amount;
+                existing.count += entry.count;
+                if existing.count == 0 && existing.amount == 0 {
+                    self.entries.remove(index);
+                }
+            }
+            Err(index) => self.entries.insert(index, entry),
+        }
+    }
+
+    /// 集計する取引がないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// `currency` の行のうち `include` に当たるものの合計を `key` ごとにまとめる
+    fn totals_by<K: Ord>(
+        &self,
+        currency: &str,
+        include: impl Fn(&AggregateEntry) -> bool,
+        key: impl Fn(&AggregateEntry) -> K,
+    ) -> BTreeMap<K, i64> {
+        let mut totals = BTreeMap::new();
+        for entry in self
+            .entries
+            .iter()
+            .filter(|e| e.currency == currency && include(e))
+        {
+            *totals.entry(key(entry)).or_insert(0) += entry.amount;
+        }
+        totals
+    }
+
+    /// 分類ごとの支出（実支出のみ。返金を差し引いた額）
+    pub fn expenses_by_category(&self, currency: &str) -> BTreeMap<TransactionCategory, i64> {
+        self.totals_by(
+            currency,
+            |e| {
+                e.transaction_type == TransactionType::Real
+                    && e.direction == TransactionDirection::Expense
+            },
+            |e| e.category.clone(),
+        )
+    }
+
+    /// 取引種別・向きごとの合計
+    pub fn totals_by_type(
+        &self,
+        currency: &str,
+    ) -> BTreeMap<(TransactionType, TransactionDirection), i64> {
+        self.totals_by(
+            currency,
+            |_| true,
+            |e| (e.transaction_type.clone(), e.direction),
+        )
+    }
+
+    /// 口座・向きごとの合計（口座間移動を含む。口座を指定しない取引は `None`）
+    pub fn totals_by_account(
+        &self,
+        currency: &str,
+    ) -> BTreeMap<(Option<String>, TransactionDirection), i64> {
+        self.totals_by(currency, |_| true, |e| (e.account_id.clone(), e.direction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_creation() {
+        let user_id = UserId::new("user123".to_string());
+        let amount = Amount::jpy(1000);
+        let transaction = Transaction::new(
+            user_id.clone(),
+            TransactionType::Real,
+            amount,
+            "Test transaction".to_string(),
+            TransactionCategory::Food,
+        );
+
+        assert_eq!(transaction.user_id, user_id);
+        assert!(transaction.affects_budget());
+        assert_eq!(transaction.description, "Test transaction");
+    }
+
+    #[test]
+    fn test_budget_usage_calculation() {
+        let user_id = UserId::new("user123".to_string());
+        let budget_amount = Amount::jpy(10000);
+        let budget = Budget::new(
+            user_id,
+            BudgetTarget::category(TransactionCategory::Food),
+            budget_amount,
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+
+        let spent = Amount::jpy(8000);
+        let usage = budget.calculate_usage_percentage(&spent).unwrap();
+        assert_eq!(usage, 0.8);
+
+        assert!(budget.should_alert(&spent).unwrap());
+
+        let low_spent = Amount::jpy(5000);
+        assert!(!budget.should_alert(&low_spent).unwrap());
+    }
+
+    fn food(
+        direction: TransactionDirection,
+        transaction_type: TransactionType,
+        amount: i64,
+    ) -> Transaction {
+        let mut transaction = Transaction::new(
+            UserId::new("user123".to_string()),
+            transaction_type,
+            Amount::jpy(amount),
+            "テスト".to_string(),
+            TransactionCategory::Food,
+        );
+        transaction.direction = direction;
+        transaction
+    }
+
+    #[test]
+    fn test_transaction_direction() {
+        use TransactionDirection::*;
+
+        let salary = food(Income, TransactionType::Real, 300000);
+        assert!(!salary.affects_budget());
+        assert_eq!(salary.signed_amount(), Amount::jpy(300000));
+        assert!(salary.validate().is_ok());
+
+        let refund = food(Expense, TransactionType::Real, -500);
+        assert!(refund.is_refund());
+        assert!(refund.affects_budget());
+        assert_eq!(refund.signed_amount(), Amount::jpy(500));
+        assert!(refund.validate().is_ok());
+
+        assert!(food(Income, TransactionType::Real, -500)
+            .validate()
+            .is_err());
+        assert!(food(Expense, TransactionType::Real, 0).validate().is_err());
+
+        // 向きのない（導入前の）データは支出として読み込む
+        let mut json = serde_json::to_value(food(Income, TransactionType::Real, 100)).unwrap();
+        json.as_object_mut().unwrap().remove("direction");
+        let legacy: Transaction = serde_json::from_value(json).unwrap();
+        assert_eq!(legacy.direction, Expense);
+    }
+
+    #[test]
+    fn test_budget_spent_amount() {
+        use TransactionDirection::*;
+
+        let budget = Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        let mut other_category = food(Expense, TransactionType::Real, 2000);
+        other_category.category = TransactionCategory::Shopping;
+        let mut in_dollars = food(Expense, TransactionType::Real, 1500);
+        in_dollars.amount = Amount::new(1500, "USD".to_string());
+        let transactions = [
+            food(Expense, TransactionType::Real, 3000),
+            // 返金は差し引き、収入と立て替えは含めない
+            food(Expense, TransactionType::Real, -1000),
+            food(Income, TransactionType::Real, 5000),
+            food(Expense, TransactionType::Flow, 4000),
+            other_category,
+            // 予算と異なる通貨の支出は数えない
+            in_dollars,
+        ];
+        assert_eq!(
+            budget
+                .spent_amount(&transactions, &CategoryTree::default())
+                .unwrap(),
+            Amount::jpy(2000)
+        );
+        let mut all = budget.clone();
+        all.target = BudgetTarget::All;
+        assert_eq!(
+            all.spent_amount(&transactions, &CategoryTree::default())
+                .unwrap(),
+            Amount::jpy(4000)
+        );
+        assert_eq!(
+            budget
+                .spent_amount(&transactions[1..2], &CategoryTree::default())
+                .unwrap(),
+            Amount::jpy(0)
+        );
+    }
+
+    #[test]
+    fn test_account_balance() {
+        use TransactionDirection::*;
+
+        let user_id = UserId::new("user123".to_string());
+        let mut wallet = Account::new(
+            user_id.clone(),
+            "財布".to_string(),
+            AccountType::Cash,
+            Amount::jpy(10000),
+        );
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let in_wallet = |mut t: Transaction, d| {
+            t.account_id = Some(wallet.account_id.clone());
+            t.transaction_date = TransactionDate::new(day(d));
+            t
+        };
+        let transactions = [
+            in_wallet(food(Expense, TransactionType::Real, 3000), 1),
+            in_wallet(food(Income, TransactionType::Real, 5000), 2),
+            // 立て替えも口座から出ていく。返金は戻ってくる
+            in_wallet(food(Expense, TransactionType::Flow, 1000), 3),
+            in_wallet(food(Expense, TransactionType::Real, -500), 4),
+            // 別の口座・口座未指定の取引は含めない
+            food(Expense, TransactionType::Real, 9999),
+        ];
+
+        let balance = wallet.balance(&transactions, None).unwrap();
+        assert_eq!(balance.current_balance, Amount::jpy(11500));
+        assert_eq!(balance.available_balance, Some(Amount::jpy(11500)));
+        // 指定日までの取引だけを反映する
+        let balance = wallet.balance(&transactions, Some(day(2))).unwrap();
+        assert_eq!(balance.current_balance, Amount::jpy(12000));
+
+        wallet.credit_limit = Some(Amount::jpy(100000));
+        assert!(wallet.validate().is_err());
+
+        let mut card = Account::new(
+            user_id,
+            "カード".to_string(),
+            AccountType::CreditCard,
+            Amount::jpy(0),
+        );
+        assert!(card.validate().is_ok());
+        let mut used = food(Expense, TransactionType::Real, 30000);
+        used.account_id = Some(card.account_id.clone());
+        let balance = card.balance(&[used.clone()], None).unwrap();
+        assert_eq!(balance.current_balance, Amount::jpy(-30000));
+        assert_eq!(balance.available_balance, None);
+
+        card.update(None, None, Some(Amount::jpy(100000)), None);
+        assert!(card.validate().is_ok());
+        let balance = card.balance(&[used], None).unwrap();
+        assert_eq!(balance.available_balance, Some(Amount::jpy(70000)));
+
+        card.credit_limit = Some(Amount::new(100000, "USD".to_string()));
+        assert!(matches!(
+            card.validate(),
+            Err(DomainError::CurrencyMismatch { .. })
+        ));
+        card.update(Some(" ".to_string()), None, None, None);
+        card.credit_limit = None;
+        assert!(card.validate().is_err());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
 
     #[test]
-    fn test_transaction_creation() {
+    fn test_budget_targets() {
+        use TransactionDirection::*;
+
+        let mut dinner = food(Expense, TransactionType::Real, 3000);
+        dinner.add_tag("旅行".to_string());
+        let mut movie = food(Expense, TransactionType::Real, 2000);
+        movie.category = TransactionCategory::Entertainment;
+        let mut train = food(Expense, TransactionType::Real, 5000);
+        train.category = TransactionCategory::Transportation;
+        train.add_tag("旅行".to_string());
+        let transactions = [dinner, movie, train];
+
+        let budget = |target: BudgetTarget| {
+            Budget::new(
+                UserId::new("user123".to_string()),
+                target,
+                Amount::jpy(10000),
+                BudgetPeriod::Monthly,
+                0.8,
+            )
+        };
+        // 1つの取引が重なり合う複数の予算に数えられる
+        let spent = |target: BudgetTarget| {
+            budget(target)
+                .spent_amount(&transactions, &CategoryTree::default())
+                .unwrap()
+        };
+        assert_eq!(
+            spent(BudgetTarget::category(TransactionCategory::Food)),
+            Amount::jpy(3000)
+        );
+        assert_eq!(
+            spent(BudgetTarget::Categories {
+                categories: vec![
+                    TransactionCategory::Food,
+                    TransactionCategory::Entertainment
+                ]
+            }),
+            Amount::jpy(5000)
+        );
+        assert_eq!(
+            spent(BudgetTarget::Tags {
+                tags: vec!["旅行".to_string(), "出張".to_string()]
+            }),
+            Amount::jpy(8000)
+        );
+        assert_eq!(spent(BudgetTarget::All), Amount::jpy(10000));
+
+        assert!(budget(BudgetTarget::Categories { categories: vec![] })
+            .validate()
+            .is_err());
+        assert!(budget(BudgetTarget::Tags { tags: vec![] })
+            .validate()
+            .is_err());
+        assert!(budget(BudgetTarget::Tags {
+            tags: vec![" ".to_string()]
+        })
+        .validate()
+        .is_err());
+        assert!(budget(BudgetTarget::All).validate().is_ok());
+    }
+
+    #[test]
+    fn test_budget_target_serialization() {
+        let budget = Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::Tags {
+                tags: vec!["旅行".to_string()],
+            },
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        let json = serde_json::to_value(&budget).unwrap();
+        assert_eq!(
+            json["target"],
+            serde_json::json!({ "type": "TAGS", "tags": ["旅行"] })
+        );
+        let restored: Budget = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(restored.target, budget.target);
+
+        // 対象が1つのカテゴリだった頃のデータ
+        let mut legacy = json;
+        let object = legacy.as_object_mut().unwrap();
+        object.remove("target");
+        object.insert("category".to_string(), "FOOD".into());
+        let restored: Budget = serde_json::from_value(legacy).unwrap();
+        assert_eq!(
+            restored.target,
+            BudgetTarget::category(TransactionCategory::Food)
+        );
+    }
+
+    fn span(window: PeriodWindow) -> (NaiveDate, NaiveDate) {
+        (window.start, window.last_day())
+    }
+
+    /// 既定のカテゴリ「食費」の下に「外食」、その下に「カフェ」を持つカテゴリ一式
+    fn food_categories() -> (Vec<Category>, Category, Category) {
         let user_id = UserId::new("user123".to_string());
-        let amount = Amount::jpy(1000);
-        let transaction = Transaction::new(
+        let categories = Category::defaults(&user_id);
+        let food = categories
+            .iter()
+            .find(|c| c.kind == TransactionCategory::Food)
+            .unwrap()
+            .clone();
+        let mut dining = Category::new(
             user_id.clone(),
+            "外食".to_string(),
+            TransactionCategory::Food,
+        );
+        dining.parent_id = Some(food.category_id.clone());
+        let mut cafe = Category::new(user_id, "カフェ".to_string(), TransactionCategory::Food);
+        cafe.parent_id = Some(dining.category_id.clone());
+        let mut all = categories;
+        all.extend([dining.clone(), cafe.clone()]);
+        (all, dining, cafe)
+    }
+
+    #[test]
+    fn test_default_categories() {
+        let user_id = UserId::new("user123".to_string());
+        let categories = Category::defaults(&user_id);
+        assert_eq!(categories.len(), TransactionCategory::ALL.len());
+        for (category, kind) in categories.iter().zip(TransactionCategory::ALL) {
+            assert_eq!(category.kind, kind);
+            assert!(category.is_default);
+            assert!(category.validate().is_ok());
+        }
+        assert_eq!(categories[0].name, "食費");
+
+        let mut archived = categories[0].clone();
+        archived.update(None, None, None, None, Some(true));
+        assert!(archived.validate().is_err());
+        let mut nested = categories[0].clone();
+        nested.parent_id = Some(categories[1].category_id.clone());
+        assert!(nested.validate().is_err());
+
+        let mut custom = Category::new(user_id, "外食".to_string(), TransactionCategory::Food);
+        custom.color = Some("#12ab3C".to_string());
+        assert!(custom.validate().is_ok());
+        for color in ["12AB3C", "#12AB3", "#12AB3G"] {
+            custom.color = Some(color.to_string());
+            assert!(custom.validate().is_err());
+        }
+        custom.color = None;
+        custom.name = " ".to_string();
+        assert!(custom.validate().is_err());
+    }
+
+    #[test]
+    fn test_category_tree() {
+        let (categories, dining, cafe) = food_categories();
+        let tree = CategoryTree::new(categories);
+        let food_id = dining.parent_id.clone().unwrap();
+
+        let names: Vec<&str> = tree
+            .ancestors(&cafe.category_id)
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["カフェ", "外食", "食費"]);
+        assert!(tree.is_within(&cafe.category_id, &food_id));
+        assert!(tree.is_within(&dining.category_id, &dining.category_id));
+        assert!(!tree.is_within(&dining.category_id, &cafe.category_id));
+
+        // カテゴリ未指定の取引は分類の既定のカテゴリに属する
+        let mut lunch = Transaction::new(
+            UserId::new("user123".to_string()),
             TransactionType::Real,
-            amount,
-            "Test transaction".to_string(),
+            Amount::jpy(1000),
+            "ランチ".to_string(),
             TransactionCategory::Food,
         );
+        assert_eq!(tree.category_of(&lunch).unwrap().category_id, food_id);
+        lunch.assign_category(&cafe);
+        assert_eq!(tree.category_of(&lunch).unwrap().name, "カフェ");
+        lunch.update(None, Some(TransactionCategory::Other));
+        assert_eq!(lunch.category_id, None);
 
-        assert_eq!(transaction.user_id, user_id);
-        assert!(transaction.affects_budget());
-        assert_eq!(transaction.description, "Test transaction");
+        // 自分の子孫の下には移せない
+        let mut moved = dining.clone();
+        moved.parent_id = Some(cafe.category_id.clone());
+        assert!(tree.ensure_valid_parent(&moved).is_err());
+        moved.parent_id = Some(moved.category_id.clone());
+        assert!(tree.ensure_valid_parent(&moved).is_err());
+
+        let mut other_kind = cafe.clone();
+        other_kind.kind = TransactionCategory::Entertainment;
+        assert!(tree.ensure_valid_parent(&other_kind).is_err());
+        let mut unknown = cafe.clone();
+        unknown.parent_id = Some("missing".to_string());
+        assert!(tree.ensure_valid_parent(&unknown).is_err());
+        assert!(tree.ensure_valid_parent(&cafe).is_ok());
+
+        let mut archived_parent = dining.clone();
+        archived_parent.is_archived = true;
+        let tree = CategoryTree::new(vec![archived_parent, cafe.clone()]);
+        assert!(tree.ensure_valid_parent(&cafe).is_err());
     }
 
     #[test]
-    fn test_budget_usage_calculation() {
+    fn test_user_category_budget_includes_subcategories() {
+        use TransactionDirection::*;
+        let (categories, dining, cafe) = food_categories();
+        let tree = CategoryTree::new(categories);
+        let spend = |amount: i64, category: Option<&Category>| {
+            let mut transaction = food(Expense, TransactionType::Real, amount);
+            if let Some(category) = category {
+                transaction.assign_category(category);
+            }
+            transaction
+        };
+        let transactions = [
+            spend(1000, None),
+            spend(2000, Some(&dining)),
+            spend(3000, Some(&cafe)),
+        ];
+        let budget = |category: &str| {
+            Budget::new(
+                UserId::new("user123".to_string()),
+                BudgetTarget::UserCategories {
+                    category_ids: vec![category.to_string()],
+                },
+                Amount::jpy(10000),
+                BudgetPeriod::Monthly,
+                0.8,
+            )
+        };
+
+        let spent = |category: &str| budget(category).spent_amount(&transactions, &tree).unwrap();
+        assert_eq!(spent(dining.parent_id.as_ref().unwrap()), Amount::jpy(6000));
+        assert_eq!(spent(&dining.category_id), Amount::jpy(5000));
+        assert_eq!(spent(&cafe.category_id), Amount::jpy(3000));
+
+        // 分類で指定した予算は、ユーザー定義カテゴリの取引も分類で数える
+        let by_kind = Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        assert_eq!(
+            by_kind.spent_amount(&transactions, &tree).unwrap(),
+            Amount::jpy(6000)
+        );
+
+        assert!(budget("x").validate().is_ok());
+        let empty = Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::UserCategories {
+                category_ids: vec![],
+            },
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        assert!(empty.validate().is_err());
+        assert_eq!(
+            serde_json::to_value(&budget("cat-1").target).unwrap(),
+            serde_json::json!({ "type": "USER_CATEGORIES", "categoryIds": ["cat-1"] })
+        );
+    }
+
+    #[test]
+    fn test_monthly_windows() {
+        let monthly = |today, start_day| span(PeriodWindow::months(today, 1, start_day));
+
+        // 暦月
+        assert_eq!(
+            monthly(date(2025, 3, 31), 1),
+            (date(2025, 3, 1), date(2025, 3, 31))
+        );
+        assert_eq!(
+            monthly(date(2025, 12, 1), 1),
+            (date(2025, 12, 1), date(2025, 12, 31))
+        );
+        assert_eq!(
+            monthly(date(2024, 2, 29), 1),
+            (date(2024, 2, 1), date(2024, 2, 29))
+        );
+        assert_eq!(
+            monthly(date(2025, 2, 28), 1),
+            (date(2025, 2, 1), date(2025, 2, 28))
+        );
+        // 給料日（25日）始まり
+        assert_eq!(
+            monthly(date(2025, 4, 24), 25),
+            (date(2025, 3, 25), date(2025, 4, 24))
+        );
+        assert_eq!(
+            monthly(date(2025, 4, 25), 25),
+            (date(2025, 4, 25), date(2025, 5, 24))
+        );
+        assert_eq!(
+            monthly(date(2025, 1, 10), 25),
+            (date(2024, 12, 25), date(2025, 1, 24))
+        );
+        // 月の日数を超える開始日は月末（うるう年の2月を含む）
+        assert_eq!(
+            monthly(date(2025, 3, 15), 31),
+            (date(2025, 2, 28), date(2025, 3, 30))
+        );
+        assert_eq!(
+            monthly(date(2024, 3, 15), 31),
+            (date(2024, 2, 29), date(2024, 3, 30))
+        );
+        assert_eq!(
+            monthly(date(2024, 2, 29), 30),
+            (date(2024, 2, 29), date(2024, 3, 29))
+        );
+        assert_eq!(
+            monthly(date(2024, 5, 1), 31),
+            (date(2024, 4, 30), date(2024, 5, 30))
+        );
+        assert_eq!(
+            monthly(date(2100, 3, 1), 29),
+            (date(2100, 2, 28), date(2100, 3, 28))
+        );
+        assert_eq!(
+            monthly(date(2000, 3, 1), 29),
+            (date(2000, 2, 29), date(2000, 3, 28))
+        );
+
+        // どの日もちょうど1つの期間に含まれ、期間は隙間なく続く
+        for start_day in [1, 15, 28, 29, 30, 31] {
+            let mut today = date(2023, 12, 1);
+            while today < date(2025, 3, 1) {
+                let window = PeriodWindow::months(today, 1, start_day);
+                assert!(window.contains(today));
+                assert_eq!(
+                    PeriodWindow::months(window.end, 1, start_day).start,
+                    window.end
+                );
+                today = today.succ_opt().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_quarterly_and_yearly_windows() {
+        let quarterly = |today, start_day| span(PeriodWindow::months(today, 3, start_day));
+        let yearly = |today, start_day| span(PeriodWindow::months(today, 12, start_day));
+
+        assert_eq!(
+            quarterly(date(2025, 3, 31), 1),
+            (date(2025, 1, 1), date(2025, 3, 31))
+        );
+        assert_eq!(
+            quarterly(date(2025, 4, 1), 1),
+            (date(2025, 4, 1), date(2025, 6, 30))
+        );
+        assert_eq!(
+            quarterly(date(2025, 12, 31), 1),
+            (date(2025, 10, 1), date(2025, 12, 31))
+        );
+        assert_eq!(
+            quarterly(date(2025, 1, 24), 25),
+            (date(2024, 10, 25), date(2025, 1, 24))
+        );
+        assert_eq!(
+            quarterly(date(2024, 6, 15), 31),
+            (date(2024, 4, 30), date(2024, 7, 30))
+        );
+        assert_eq!(
+            quarterly(date(2024, 4, 29), 31),
+            (date(2024, 1, 31), date(2024, 4, 29))
+        );
+
+        assert_eq!(
+            yearly(date(2024, 12, 31), 1),
+            (date(2024, 1, 1), date(2024, 12, 31))
+        );
+        assert_eq!(
+            yearly(date(2025, 1, 24), 25),
+            (date(2024, 1, 25), date(2025, 1, 24))
+        );
+        assert_eq!(
+            yearly(date(2024, 2, 29), 1),
+            (date(2024, 1, 1), date(2024, 12, 31))
+        );
+    }
+
+    #[test]
+    fn test_weekly_and_custom_windows() {
+        // 2025-01-01 は水曜日
+        assert_eq!(
+            span(PeriodWindow::week(date(2025, 1, 1), Weekday::Mon)),
+            (date(2024, 12, 30), date(2025, 1, 5))
+        );
+        assert_eq!(
+            span(PeriodWindow::week(date(2025, 1, 1), Weekday::Wed)),
+            (date(2025, 1, 1), date(2025, 1, 7))
+        );
+        assert_eq!(
+            span(PeriodWindow::week(date(2025, 1, 1), Weekday::Sun)),
+            (date(2024, 12, 29), date(2025, 1, 4))
+        );
+        assert_eq!(
+            span(PeriodWindow::week(date(2024, 2, 29), Weekday::Sat)),
+            (date(2024, 2, 24), date(2024, 3, 1))
+        );
+
+        let trip = PeriodWindow::custom(date(2024, 2, 28), date(2024, 3, 1));
+        assert_eq!(span(trip), (date(2024, 2, 28), date(2024, 3, 1)));
+        assert!(trip.contains(date(2024, 2, 29)));
+        assert!(!trip.contains(date(2024, 3, 2)));
+    }
+
+    #[test]
+    fn test_report_granularity_windows() {
+        use ReportGranularity::*;
+        assert_eq!(
+            span(Daily.window_containing(date(2024, 2, 29))),
+            (date(2024, 2, 29), date(2024, 2, 29))
+        );
+        assert_eq!(
+            span(Monthly.window_containing(date(2024, 2, 29))),
+            (date(2024, 2, 1), date(2024, 2, 29))
+        );
+        assert_eq!(
+            span(Yearly.window_containing(date(2024, 2, 29))),
+            (date(2024, 1, 1), date(2024, 12, 31))
+        );
+
+        assert_eq!(
+            span(Daily.default_window(date(2025, 4, 10))),
+            (date(2025, 4, 1), date(2025, 4, 30))
+        );
+        assert_eq!(
+            span(Monthly.default_window(date(2025, 4, 10))),
+            (date(2024, 5, 1), date(2025, 4, 30))
+        );
+        assert_eq!(
+            span(Yearly.default_window(date(2025, 4, 10))),
+            (date(2021, 1, 1), date(2025, 12, 31))
+        );
+    }
+
+    fn budget_with(period: BudgetPeriod) -> Budget {
+        Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(10000),
+            period,
+            0.8,
+        )
+    }
+
+    #[test]
+    fn test_budget_window_containing() {
+        let mut weekly = budget_with(BudgetPeriod::Weekly);
+        assert_eq!(
+            weekly.window_containing(date(2025, 1, 1)).unwrap().start,
+            date(2024, 12, 30)
+        );
+        weekly
+            .update(None, None, None, None, Some(DayOfWeek::Sunday), None)
+            .unwrap();
+        assert_eq!(
+            weekly.window_containing(date(2025, 1, 1)).unwrap().start,
+            date(2024, 12, 29)
+        );
+
+        let mut monthly = budget_with(BudgetPeriod::Monthly);
+        monthly
+            .update(None, None, None, Some(25), None, None)
+            .unwrap();
+        assert_eq!(
+            monthly.window_containing(date(2025, 3, 10)).unwrap().start,
+            date(2025, 2, 25)
+        );
+
+        let mut trip = budget_with(BudgetPeriod::Custom);
+        assert!(trip.window_containing(date(2025, 3, 10)).is_err());
+        trip.start_date = Some(date(2025, 8, 10));
+        trip.end_date = Some(date(2025, 8, 15));
+        // 期間外の日でも指定された期間を返す
+        assert_eq!(
+            span(trip.window_containing(date(2025, 3, 10)).unwrap()),
+            (date(2025, 8, 10), date(2025, 8, 15))
+        );
+    }
+
+    #[test]
+    fn test_budget_period_validation() {
+        let mut monthly = budget_with(BudgetPeriod::Monthly);
+        for day in [0, 32] {
+            monthly.month_start_day = Some(day);
+            assert!(monthly.validate().is_err());
+        }
+        monthly.month_start_day = Some(31);
+        monthly.rollover = true;
+        assert!(monthly.validate().is_ok());
+        monthly.week_start = Some(DayOfWeek::Monday);
+        assert!(monthly.validate().is_err());
+
+        let mut weekly = budget_with(BudgetPeriod::Weekly);
+        weekly.week_start = Some(DayOfWeek::Friday);
+        assert!(weekly.validate().is_ok());
+        weekly.month_start_day = Some(25);
+        assert!(weekly.validate().is_err());
+        weekly.month_start_day = None;
+        weekly.start_date = Some(date(2025, 1, 1));
+        assert!(weekly.validate().is_err());
+
+        let mut trip = budget_with(BudgetPeriod::Custom);
+        assert!(trip.validate().is_err());
+        trip.start_date = Some(date(2025, 8, 15));
+        trip.end_date = Some(date(2025, 8, 10));
+        assert!(trip.validate().is_err());
+        trip.end_date = Some(date(2025, 8, 15));
+        assert!(trip.validate().is_ok());
+        trip.rollover = true;
+        assert!(trip.validate().is_err());
+    }
+
+    #[test]
+    fn test_user_local_date() {
+        let mut user = UserProfile::new(UserId::new("user123".to_string()));
+        let now = "2025-03-31T20:00:00Z".parse().unwrap();
+        assert_eq!(user.local_date(now).unwrap(), date(2025, 4, 1));
+
+        user.update(None, None, Some("America/Los_Angeles".to_string()));
+        assert_eq!(user.local_date(now).unwrap(), date(2025, 3, 31));
+
+        user.update(None, None, Some("Tokyo".to_string()));
+        assert!(user.validate().is_err());
+    }
+
+    #[test]
+    fn test_billing_cycle_dates() {
+        // 15日締め・翌月10日払い
+        let cycle = BillingCycle {
+            closing_day: 15,
+            payment_day: 10,
+            payment_month_offset: 1,
+            payment_account_id: None,
+        };
+        assert!(cycle.validate().is_ok());
+        assert_eq!(cycle.closing_date_for(date(2025, 3, 15)), date(2025, 3, 15));
+        assert_eq!(cycle.closing_date_for(date(2025, 3, 16)), date(2025, 4, 15));
+        assert_eq!(
+            cycle.closing_date_for(date(2025, 12, 20)),
+            date(2026, 1, 15)
+        );
+        assert_eq!(cycle.due_date_for(date(2025, 12, 15)), date(2026, 1, 10));
+        assert_eq!(
+            cycle.previous_closing_date(date(2025, 1, 15)),
+            date(2024, 12, 15)
+        );
+
+        // 月末締め・翌々月27日払い（月の日数を超える日は月末）
+        let cycle = BillingCycle {
+            closing_day: 31,
+            payment_day: 31,
+            payment_month_offset: 2,
+            payment_account_id: None,
+        };
+        assert!(cycle.validate().is_ok());
+        assert_eq!(cycle.closing_date_for(date(2024, 2, 10)), date(2024, 2, 29));
+        assert_eq!(cycle.closing_date_for(date(2025, 4, 30)), date(2025, 4, 30));
+        assert_eq!(cycle.due_date_for(date(2024, 12, 31)), date(2025, 2, 28));
+        assert_eq!(
+            cycle.previous_closing_date(date(2025, 3, 31)),
+            date(2025, 2, 28)
+        );
+
+        let invalid = |closing_day, payment_day, payment_month_offset| {
+            BillingCycle {
+                closing_day,
+                payment_day,
+                payment_month_offset,
+                payment_account_id: None,
+            }
+            .validate()
+            .is_err()
+        };
+        assert!(invalid(0, 10, 1));
+        assert!(invalid(15, 32, 1));
+        assert!(invalid(15, 10, 3));
+        // 当月払いは締め日より後でなければならない
+        assert!(invalid(15, 15, 0));
+        assert!(!invalid(15, 25, 0));
+    }
+
+    #[test]
+    fn test_account_billing_cycle_validation() {
+        let cycle = BillingCycle {
+            closing_day: 15,
+            payment_day: 10,
+            payment_month_offset: 1,
+            payment_account_id: Some("bank".to_string()),
+        };
+        let mut card = Account::new(
+            UserId::new("user123".to_string()),
+            "カード".to_string(),
+            AccountType::CreditCard,
+            Amount::jpy(0),
+        );
+        card.billing_cycle = Some(cycle.clone());
+        assert!(card.validate().is_ok());
+        assert_eq!(card.payment_account_id(), Some("bank"));
+
+        let mut self_paid = card.clone();
+        self_paid.billing_cycle = Some(BillingCycle {
+            payment_account_id: Some(card.account_id.clone()),
+            ..cycle.clone()
+        });
+        assert!(self_paid.validate().is_err());
+
+        let mut bank = card;
+        bank.account_type = AccountType::Bank;
+        assert!(bank.validate().is_err());
+    }
+
+    #[test]
+    fn test_account_transfer() {
+        use TransactionDirection::*;
         let user_id = UserId::new("user123".to_string());
-        let budget_amount = Amount::jpy(10000);
+        let bank = Account::new(
+            user_id.clone(),
+            "銀行".to_string(),
+            AccountType::Bank,
+            Amount::jpy(100000),
+        );
+        let wallet = Account::new(
+            user_id.clone(),
+            "財布".to_string(),
+            AccountType::Cash,
+            Amount::jpy(0),
+        );
+        let transfer = AccountTransfer::new(
+            user_id.clone(),
+            bank.account_id.clone(),
+            wallet.account_id.clone(),
+            Amount::jpy(20000),
+            "ATM引き出し".to_string(),
+        );
+        assert!(transfer.validate().is_ok());
+
+        let transactions = transfer.transactions();
+        for transaction in &transactions {
+            assert!(transaction.validate().is_ok());
+            assert!(transaction.is_transfer());
+            // 家計の支出・予算には含めない
+            assert!(!transaction.affects_budget());
+            assert_eq!(
+                AccountTransfer::from_transaction(transaction).as_ref(),
+                Some(&transfer)
+            );
+        }
+        assert_eq!(
+            bank.balance(&transactions, None).unwrap().current_balance,
+            Amount::jpy(80000)
+        );
+        assert_eq!(
+            wallet.balance(&transactions, None).unwrap().current_balance,
+            Amount::jpy(20000)
+        );
         let budget = Budget::new(
-            user_id,
-            TransactionCategory::Food,
-            budget_amount,
+            user_id.clone(),
+            BudgetTarget::category(TransactionCategory::Other),
+            Amount::jpy(10000),
             BudgetPeriod::Monthly,
             0.8,
         );
+        assert!(budget
+            .spent_amount(&transactions, &CategoryTree::default())
+            .unwrap()
+            .is_zero());
 
-        let spent = Amount::jpy(8000);
-        let usage = budget.calculate_usage_percentage(&spent).unwrap();
-        assert_eq!(usage, 0.8);
+        // 片側だけを別の口座に付け替えたり、移動に属さない移動取引は作れない
+        let [mut outgoing, _] = transfer.transactions();
+        outgoing.account_id = Some(wallet.account_id.clone());
+        assert!(outgoing.validate().is_err());
+        outgoing.transfer = None;
+        assert!(outgoing.validate().is_err());
+        let mut real = food(Expense, TransactionType::Real, 1000);
+        real.transfer = transactions[0].transfer.clone();
+        assert!(real.validate().is_err());
 
-        assert!(budget.should_alert(&spent).unwrap());
+        let mut invalid = transfer.clone();
+        invalid.to_account_id = bank.account_id.clone();
+        assert!(invalid.validate().is_err());
+        let mut invalid = transfer;
+        invalid.amount = Amount::jpy(0);
+        assert!(invalid.validate().is_err());
+    }
 
-        let low_spent = Amount::jpy(5000);
-        assert!(!budget.should_alert(&low_spent).unwrap());
+    fn flow_transaction(amount: i64) -> Transaction {
+        Transaction::new(
+            UserId::new("alice".to_string()),
+            TransactionType::Flow,
+            Amount::jpy(amount),
+            "立て替え".to_string(),
+            TransactionCategory::Food,
+        )
+    }
+
+    #[test]
+    fn test_settlement_from_transaction() {
+        let transaction = flow_transaction(3000);
+        let bob = UserId::new("bob".to_string());
+
+        let settlement =
+            Settlement::from_transaction(&transaction, bob.clone(), None, None).unwrap();
+        assert_eq!(settlement.creditor_user_id, transaction.user_id);
+        assert_eq!(settlement.amount, Amount::jpy(3000));
+        assert_eq!(settlement.description.as_deref(), Some("立て替え"));
+        assert_eq!(
+            settlement.transaction_id.as_ref(),
+            Some(&transaction.transaction_id)
+        );
+
+        let mut real = flow_transaction(3000);
+        real.transaction_type = TransactionType::Real;
+        assert!(Settlement::from_transaction(&real, bob.clone(), None, None).is_err());
+        assert!(Settlement::from_transaction(
+            &transaction,
+            bob.clone(),
+            Some(Amount::jpy(3001)),
+            None
+        )
+        .is_err());
+        assert!(matches!(
+            Settlement::from_transaction(
+                &transaction,
+                bob,
+                Some(Amount::new(10, "USD".to_string())),
+                None
+            ),
+            Err(DomainError::CurrencyMismatch { .. })
+        ));
+        // 自分自身への精算は作れない
+        assert!(Settlement::from_transaction(
+            &transaction,
+            transaction.user_id.clone(),
+            None,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_settlement_repayments() {
+        let mut settlement = Settlement::from_transaction(
+            &flow_transaction(3000),
+            UserId::new("bob".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+
+        settlement.repay(Amount::jpy(1000)).unwrap();
+        assert_eq!(settlement.status, SettlementStatus::PartiallyRepaid);
+        assert_eq!(settlement.remaining_amount(), Amount::jpy(2000));
+        assert!(settlement.repay(Amount::jpy(2001)).is_err());
+        assert!(settlement.repay(Amount::jpy(0)).is_err());
+        // 返済後は取り消せない
+        assert!(matches!(settlement.cancel(), Err(DomainError::Conflict(_))));
+
+        settlement.repay(Amount::jpy(2000)).unwrap();
+        assert_eq!(settlement.status, SettlementStatus::Completed);
+        assert!(settlement.completed_at.is_some());
+        assert!(matches!(
+            settlement.repay(Amount::jpy(1)),
+            Err(DomainError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn test_settlement_complete_and_cancel() {
+        let bob = UserId::new("bob".to_string());
+        let mut completed =
+            Settlement::from_transaction(&flow_transaction(3000), bob.clone(), None, None).unwrap();
+        completed.repay(Amount::jpy(500)).unwrap();
+        completed.complete().unwrap();
+        assert_eq!(completed.status, SettlementStatus::Completed);
+        assert_eq!(completed.repaid_amount(), Amount::jpy(3000));
+        assert!(completed.remaining_amount().is_zero());
+        assert!(matches!(
+            completed.complete(),
+            Err(DomainError::Conflict(_))
+        ));
+
+        let mut cancelled =
+            Settlement::from_transaction(&flow_transaction(3000), bob, None, None).unwrap();
+        cancelled.cancel().unwrap();
+        assert_eq!(cancelled.status, SettlementStatus::Cancelled);
+        assert!(cancelled.cancelled_at.is_some());
+        assert!(matches!(
+            cancelled.complete(),
+            Err(DomainError::Conflict(_))
+        ));
+        assert!(matches!(cancelled.cancel(), Err(DomainError::Conflict(_))));
     }
 
     #[test]
@@ -387,4 +3122,205 @@ mod tests {
         group.remove_member(&owner_id);
         assert!(group.is_member(&owner_id));
     }
+
+    fn trip() -> Group {
+        let mut group = Group::new(
+            "旅行".to_string(),
+            String::new(),
+            UserId::new("carol".to_string()),
+        );
+        group.add_member(UserId::new("alice".to_string()));
+        group.add_member(UserId::new("bob".to_string()));
+        group
+    }
+
+    fn split(rule: SplitRule, amount: i64, paid_by: &str) -> DomainResult<Vec<(String, i64)>> {
+        let transaction = GroupTransaction::new(
+            &trip(),
+            UserId::new(paid_by.to_string()),
+            Amount::jpy(amount),
+            "ホテル".to_string(),
+            TransactionCategory::Other,
+            rule,
+        )?;
+        Ok(transaction
+            .participants
+            .into_iter()
+            .map(|p| (p.user_id.0, p.share_amount.value))
+            .collect())
+    }
+
+    fn ids(ids: &[&str]) -> Vec<UserId> {
+        ids.iter().map(|id| UserId::new(id.to_string())).collect()
+    }
+
+    fn shares(result: &[(&str, i64)]) -> Vec<(String, i64)> {
+        result.iter().map(|&(id, v)| (id.to_string(), v)).collect()
+    }
+
+    #[test]
+    fn test_group_transaction_split_rules() {
+        // 端数は支払者、続いてユーザーID順に1円ずつ
+        let equal = SplitRule::Equal(ids(&["carol", "alice", "bob"]));
+        assert_eq!(
+            split(equal.clone(), 1000, "bob").unwrap(),
+            shares(&[("alice", 333), ("bob", 334), ("carol", 333)])
+        );
+        assert_eq!(
+            split(equal, 1001, "carol").unwrap(),
+            shares(&[("alice", 334), ("bob", 333), ("carol", 334)])
+        );
+
+        let weighted = SplitRule::Shares(vec![
+            (UserId::new("alice".to_string()), 2),
+            (UserId::new("bob".to_string()), 1),
+        ]);
+        assert_eq!(
+            split(weighted, 1000, "carol").unwrap(),
+            shares(&[("alice", 667), ("bob", 333)])
+        );
+
+        let percentages = SplitRule::Percentages(vec![
+            (UserId::new("alice".to_string()), 2500),
+            (UserId::new("bob".to_string()), 2500),
+            (UserId::new("carol".to_string()), 5000),
+        ]);
+        assert_eq!(
+            split(percentages, 1002, "bob").unwrap(),
+            shares(&[("alice", 250), ("bob", 251), ("carol", 501)])
+        );
+
+        let exact = SplitRule::Exact(vec![
+            (UserId::new("bob".to_string()), Amount::jpy(700)),
+            (UserId::new("alice".to_string()), Amount::jpy(300)),
+        ]);
+        assert_eq!(
+            split(exact, 1000, "alice").unwrap(),
+            shares(&[("alice", 300), ("bob", 700)])
+        );
+    }
+
+    #[test]
+    fn test_group_transaction_split_validation() {
+        let invalid = |rule: SplitRule, paid_by: &str| {
+            matches!(split(rule, 1000, paid_by), Err(DomainError::Validation(_)))
+        };
+
+        assert!(invalid(SplitRule::Equal(Vec::new()), "alice"));
+        assert!(invalid(SplitRule::Equal(ids(&["alice", "alice"])), "alice"));
+        // グループ外のメンバー
+        assert!(invalid(SplitRule::Equal(ids(&["alice", "dave"])), "alice"));
+        assert!(invalid(SplitRule::Equal(ids(&["alice"])), "dave"));
+        assert!(invalid(
+            SplitRule::Shares(vec![(UserId::new("alice".to_string()), 0)]),
+            "alice"
+        ));
+        assert!(invalid(
+            SplitRule::Percentages(vec![
+                (UserId::new("alice".to_string()), 5000),
+                (UserId::new("bob".to_string()), 4000),
+            ]),
+            "alice"
+        ));
+        assert!(invalid(
+            SplitRule::Exact(vec![
+                (UserId::new("alice".to_string()), Amount::jpy(300)),
+                (UserId::new("bob".to_string()), Amount::jpy(600)),
+            ]),
+            "alice"
+        ));
+        assert!(matches!(
+            split(
+                SplitRule::Exact(vec![(
+                    UserId::new("alice".to_string()),
+                    Amount::new(1000, "USD".to_string())
+                )]),
+                1000,
+                "alice"
+            ),
+            Err(DomainError::CurrencyMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_group_transaction_payer_transaction() {
+        let transaction = GroupTransaction::new(
+            &trip(),
+            UserId::new("alice".to_string()),
+            Amount::jpy(3000),
+            "夕食".to_string(),
+            TransactionCategory::Food,
+            SplitRule::Equal(ids(&["alice", "bob", "carol"])),
+        )
+        .unwrap();
+
+        // 自分の負担分を除いた額を立て替えとして記録する
+        let flow = transaction.payer_transaction().unwrap();
+        assert_eq!(flow.transaction_type, TransactionType::Flow);
+        assert_eq!(flow.user_id, transaction.paid_by);
+        assert_eq!(flow.amount, Amount::jpy(2000));
+
+        let own = GroupTransaction::new(
+            &trip(),
+            UserId::new("alice".to_string()),
+            Amount::jpy(3000),
+            "お土産".to_string(),
+            TransactionCategory::Shopping,
+            SplitRule::Equal(ids(&["alice"])),
+        )
+        .unwrap();
+        assert!(own.payer_transaction().is_none());
+    }
+
+    #[test]
+    fn test_monthly_aggregate_changes() {
+        use TransactionDirection::*;
+        use TransactionType::*;
+        let on = |mut transaction: Transaction, day: NaiveDate| {
+            transaction.transaction_date = TransactionDate::new(day);
+            transaction
+        };
+        let mut lunch = on(food(Expense, Real, 1000), date(2025, 4, 30));
+        lunch.account_id = Some("wallet".to_string());
+        let refund = on(food(Expense, Real, -300), date(2025, 4, 10));
+        let salary = on(food(Income, Real, 300000), date(2025, 4, 25));
+        let advance = on(food(Expense, Flow, 2000), date(2025, 5, 1));
+
+        let aggregates =
+            MonthlyAggregate::from_transactions(&[lunch.clone(), refund, salary, advance]);
+        assert_eq!(aggregates.len(), 2);
+        let april = &aggregates[0];
+        assert_eq!(april.month, date(2025, 4, 1));
+        // 口座の違う支出は別の行になる
+        assert_eq!(april.entries.len(), 3);
+        assert_eq!(
+            april.expenses_by_category("JPY")[&TransactionCategory::Food],
+            700
+        );
+        assert_eq!(april.totals_by_type("JPY")[&(Real, Income)], 300000);
+        assert_eq!(
+            april.totals_by_account("JPY")[&(Some("wallet".to_string()), Expense)],
+            1000
+        );
+        assert!(april.totals_by_type("USD").is_empty());
+        assert_eq!(aggregates[1].totals_by_type("JPY")[&(Flow, Expense)], 2000);
+
+        // 月をまたぐ変更は、元の月から差し引いて新しい月に加える
+        let mut moved = lunch.clone();
+        moved.transaction_date = TransactionDate::new(lunch.transaction_date.date() + Days::new(1));
+        let deltas = MonthlyAggregate::changes(&[lunch.clone()], &[moved]);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].month, date(2025, 4, 1));
+        assert_eq!(deltas[0].entries[0].count, -1);
+        assert_eq!(deltas[0].entries[0].amount, -1000);
+        assert_eq!(deltas[1].month, date(2025, 5, 1));
+
+        // 同じ内容への変更は差分にならない
+        assert!(MonthlyAggregate::changes(&[lunch.clone()], &[lunch.clone()]).is_empty());
+
+        let mut merged = april.clone();
+        merged.merge(&deltas[0]);
+        assert_eq!(merged.entries.len(), 2);
+        assert!(merged.entries.iter().all(|e| e.account_id.is_none()));
+    }
 }
@@ -0,0 +1,79 @@
+// ドメインエラー
+// サービス・ユースケースが返すエラーの種類と、API で返す安定したエラーコード
+
+use thiserror::Error;
+
+/// ドメイン層のエラー
+///
+/// リポジトリ等のインフラ起因の失敗は `Internal` にまとめる
+#[derive(Debug, Error)]
+pub enum DomainError {
+    #[error("{entity} {id} not found")]
+    NotFound { entity: &'static str, id: String },
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("Currency mismatch: {expected} != {actual}")]
+    CurrencyMismatch { expected: String, actual: String },
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    Forbidden(String),
+
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl DomainError {
+    pub fn not_found(entity: &'static str, id: impl Into<String>) -> Self {
+        Self::NotFound {
+            entity,
+            id: id.into(),
+        }
+    }
+
+    pub fn currency_mismatch(expected: &str, actual: &str) -> Self {
+        Self::CurrencyMismatch {
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        }
+    }
+
+    /// `ErrorResponse.code` に入る機械可読なエラーコード
+    ///
+    /// クライアントが分岐に使うため、値を変更しないこと
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound { .. } => "NOT_FOUND",
+            Self::Validation(_) => "VALIDATION_ERROR",
+            Self::CurrencyMismatch { .. } => "CURRENCY_MISMATCH",
+            Self::Conflict(_) => "CONFLICT",
+            Self::Forbidden(_) => "FORBIDDEN",
+            Self::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+}
+
+pub type DomainResult<T> = Result<T, DomainError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_and_messages() {
+        let err = DomainError::not_found("Budget", "b1");
+        assert_eq!(err.code(), "NOT_FOUND");
+        assert_eq!(err.to_string(), "Budget b1 not found");
+
+        let err = DomainError::currency_mismatch("JPY", "USD");
+        assert_eq!(err.code(), "CURRENCY_MISMATCH");
+        assert_eq!(err.to_string(), "Currency mismatch: JPY != USD");
+
+        let err: DomainError = anyhow::anyhow!("connection refused").into();
+        assert_eq!(err.code(), "INTERNAL_ERROR");
+    }
+}
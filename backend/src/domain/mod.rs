@@ -1,7 +1,10 @@
 pub mod entities;
+pub mod errors;
 pub mod value_objects;
 pub mod services;
 pub mod repositories;
+pub mod notifiers;
 
 pub use entities::*;
+pub use errors::*;
 pub use value_objects::*;
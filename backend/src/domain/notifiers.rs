@@ -0,0 +1,37 @@
+// 通知インターフェース
+// ドメインイベントの配信先の抽象化
+
+use crate::domain::entities::BudgetAlertRaised;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// 予算アラートの通知先トレイト
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait BudgetAlertNotifier: Send + Sync {
+    async fn notify(&self, alert: &BudgetAlertRaised) -> Result<()>;
+}
+
+#[async_trait]
+impl<T: BudgetAlertNotifier + ?Sized> BudgetAlertNotifier for Arc<T> {
+    async fn notify(&self, alert: &BudgetAlertRaised) -> Result<()> {
+        (**self).notify(alert).await
+    }
+}
+
+/// すべての通知先に送る（一部が失敗しても残りには送り、最初の失敗を返す）
+#[async_trait]
+impl<T: BudgetAlertNotifier> BudgetAlertNotifier for Vec<T> {
+    async fn notify(&self, alert: &BudgetAlertRaised) -> Result<()> {
+        let mut result = Ok(());
+        for notifier in self {
+            if let Err(err) = notifier.notify(alert).await {
+                if result.is_ok() {
+                    result = Err(err);
+                }
+            }
+        }
+        result
+    }
+}
@@ -2,10 +2,15 @@
 // データアクセス層の抽象化
 
 use crate::domain::entities::*;
+use crate::domain::value_objects::TransactionDate;
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// ユーザーリポジトリトレイト
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait UserRepository: Send + Sync {
     async fn find_by_id(&self, user_id: &str) -> Result<Option<UserProfile>>;
@@ -14,17 +19,125 @@ pub trait UserRepository: Send + Sync {
     async fn delete(&self, user_id: &str) -> Result<()>;
 }
 
+/// 取引一覧の読み出し位置（前ページ最後の取引）
+///
+/// 取引は (取引日時, 取引ID) の降順に並ぶため、この組より後ろから読み出す
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionCursor {
+    pub transaction_date: TransactionDate,
+    pub transaction_id: String,
+}
+
+impl TransactionCursor {
+    pub fn of(transaction: &Transaction) -> Self {
+        Self {
+            transaction_date: transaction.transaction_date,
+            transaction_id: transaction.transaction_id.value().to_string(),
+        }
+    }
+
+    /// 取引がこの位置より後ろ（古い側）にあるかどうか
+    pub fn precedes(&self, transaction: &Transaction) -> bool {
+        (
+            transaction.transaction_date,
+            transaction.transaction_id.value(),
+        ) < (self.transaction_date, self.transaction_id.as_str())
+    }
+}
+
+/// 取引一覧の検索条件
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionQuery {
+    pub transaction_type: Option<TransactionType>,
+    pub direction: Option<TransactionDirection>,
+    pub category: Option<TransactionCategory>,
+    /// この日以降の取引のみ（含む）
+    pub from: Option<TransactionDate>,
+    /// この日より前の取引のみ（含まない）
+    pub until: Option<TransactionDate>,
+    /// 1ページの最大件数
+    pub limit: usize,
+    pub after: Option<TransactionCursor>,
+}
+
+impl TransactionQuery {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            transaction_type: None,
+            direction: None,
+            category: None,
+            from: None,
+            until: None,
+            limit,
+            after: None,
+        }
+    }
+
+    /// 取引が検索条件（読み出し位置を含む）に一致するかどうか
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        self.transaction_type
+            .as_ref()
+            .is_none_or(|t| &transaction.transaction_type == t)
+            && self.direction.is_none_or(|d| transaction.direction == d)
+            && self
+                .category
+                .as_ref()
+                .is_none_or(|c| &transaction.category == c)
+            && self
+                .from
+                .is_none_or(|from| transaction.transaction_date >= from)
+            && self
+                .until
+                .is_none_or(|until| transaction.transaction_date < until)
+            && self
+                .after
+                .as_ref()
+                .is_none_or(|cursor| cursor.precedes(transaction))
+    }
+}
+
+/// 取引一覧の1ページ
+#[derive(Debug, Clone)]
+pub struct TransactionPage {
+    pub transactions: Vec<Transaction>,
+    /// 続きがある場合の次ページの読み出し位置
+    pub next: Option<TransactionCursor>,
+}
+
+impl TransactionPage {
+    /// 並び順どおりに `limit + 1` 件まで読み出した結果からページを作る
+    ///
+    /// 1件多く読むことで、追加の問い合わせなしに続きの有無が分かる
+    pub fn from_overfetch(mut transactions: Vec<Transaction>, limit: usize) -> Self {
+        let next = if transactions.len() > limit {
+            transactions.truncate(limit);
+            transactions.last().map(TransactionCursor::of)
+        } else {
+            None
+        };
+        Self { transactions, next }
+    }
+}
+
 /// 取引リポジトリトレイト
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait TransactionRepository: Send + Sync {
     async fn find_by_id(&self, transaction_id: &str) -> Result<Option<Transaction>>;
     async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Transaction>>;
+    /// 条件に一致する取引を新しい順に1ページ分取得
+    async fn find_page(&self, user_id: &str, query: &TransactionQuery) -> Result<TransactionPage>;
     async fn save(&self, transaction: Transaction) -> Result<()>;
+    /// 複数の取引をまとめて保存（すべて保存されるか、何も保存されない）
+    async fn save_all(&self, transactions: Vec<Transaction>) -> Result<()>;
     async fn update(&self, transaction: Transaction) -> Result<()>;
     async fn delete(&self, transaction_id: &str) -> Result<()>;
+    /// 複数の取引をまとめて削除（すべて削除されるか、何も削除されない）
+    async fn delete_all(&self, transaction_ids: &[String]) -> Result<()>;
 }
 
 /// 予算リポジトリトレイト
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait BudgetRepository: Send + Sync {
     async fn find_by_id(&self, budget_id: &str) -> Result<Option<Budget>>;
@@ -35,6 +148,7 @@ pub trait BudgetRepository: Send + Sync {
 }
 
 /// グループリポジトリトレイト
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait GroupRepository: Send + Sync {
     async fn find_by_id(&self, group_id: &str) -> Result<Option<Group>>;
@@ -43,3 +157,308 @@ pub trait GroupRepository: Send + Sync {
     async fn update(&self, group: Group) -> Result<()>;
     async fn delete(&self, group_id: &str) -> Result<()>;
 }
+
+/// グループ取引リポジトリトレイト
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait GroupTransactionRepository: Send + Sync {
+    /// グループの取引を取引日の古い順に取得
+    async fn find_by_group_id(&self, group_id: &str) -> Result<Vec<GroupTransaction>>;
+    /// グループ取引と、支払者の立て替え分の取引（あれば）を1回の書き込みでまとめて保存
+    async fn save(
+        &self,
+        transaction: GroupTransaction,
+        payer_transaction: Option<Transaction>,
+    ) -> Result<()>;
+}
+
+/// 口座リポジトリトレイト
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AccountRepository: Send + Sync {
+    async fn find_by_id(&self, account_id: &str) -> Result<Option<Account>>;
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Account>>;
+    async fn save(&self, account: Account) -> Result<()>;
+    async fn update(&self, account: Account) -> Result<()>;
+    async fn delete(&self, account_id: &str) -> Result<()>;
+}
+
+/// カテゴリリポジトリトレイト
+///
+/// 過去の取引が参照するため削除は提供しない（アーカイブする）
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait CategoryRepository: Send + Sync {
+    async fn find_by_id(&self, category_id: &str) -> Result<Option<Category>>;
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Category>>;
+    async fn save(&self, category: Category) -> Result<()>;
+    /// 複数のカテゴリをまとめて保存（既定のカテゴリの作成に使う。すべて保存されるか、何も保存されない）
+    async fn save_all(&self, categories: Vec<Category>) -> Result<()>;
+    async fn update(&self, category: Category) -> Result<()>;
+}
+
+/// 精算リポジトリトレイト
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SettlementRepository: Send + Sync {
+    async fn find_by_id(&self, settlement_id: &str) -> Result<Option<Settlement>>;
+    /// 債権者・債務者のいずれかとして関わる精算を取得
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Settlement>>;
+    async fn save(&self, settlement: Settlement) -> Result<()>;
+    async fn update(&self, settlement: Settlement) -> Result<()>;
+}
+
+/// 月次集計リポジトリトレイト
+///
+/// 集計は取引から作り直せる派生データで、取引の書き込みのたびに差分を加算して保つ
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AggregateRepository: Send + Sync {
+    async fn find(&self, user_id: &str, month: NaiveDate) -> Result<Option<MonthlyAggregate>>;
+    /// ユーザーの集計を月の順に取得
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<MonthlyAggregate>>;
+    /// 差分を同じユーザー・月の集計に加算する（集計がなければ差分から作る）
+    ///
+    /// 同じ月への同時の加算が失われないよう、読み出しから書き込みまでを不可分に行う
+    async fn apply(&self, delta: MonthlyAggregate) -> Result<()>;
+    /// ユーザーの集計をすべて置き換える（取引からの再構築に使う）
+    async fn replace_all(&self, user_id: &str, aggregates: Vec<MonthlyAggregate>) -> Result<()>;
+}
+
+/// 予算アラートリポジトリトレイト
+///
+/// 発生した `BudgetAlertRaised` を履歴として保持し、同じアラートの重複を防ぐ
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait BudgetAlertRepository: Send + Sync {
+    /// ユーザーのアラートを新しい順に取得
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<BudgetAlertRaised>>;
+    /// 同じ `alert_id` のアラートがなければ保存し、保存したかどうかを返す
+    ///
+    /// 同時に判定しても一方だけが保存されるよう、存在の確認と書き込みを不可分に行う
+    async fn save_if_absent(&self, alert: BudgetAlertRaised) -> Result<bool>;
+    /// 通知が済んでいないアラートを発生の古い順に取得
+    async fn find_undelivered(&self, user_id: &str) -> Result<Vec<BudgetAlertRaised>>;
+    /// 保存済みのアラートを置き換える（通知済みの記録に使う）
+    async fn update(&self, alert: BudgetAlertRaised) -> Result<()>;
+}
+
+// 共有ポインタ越しにリポジトリを利用できるようにする（`Arc<dyn UserRepository>` 等）
+
+#[async_trait]
+impl<T: UserRepository + ?Sized> UserRepository for Arc<T> {
+    async fn find_by_id(&self, user_id: &str) -> Result<Option<UserProfile>> {
+        (**self).find_by_id(user_id).await
+    }
+
+    async fn save(&self, user: UserProfile) -> Result<()> {
+        (**self).save(user).await
+    }
+
+    async fn update(&self, user: UserProfile) -> Result<()> {
+        (**self).update(user).await
+    }
+
+    async fn delete(&self, user_id: &str) -> Result<()> {
+        (**self).delete(user_id).await
+    }
+}
+
+#[async_trait]
+impl<T: TransactionRepository + ?Sized> TransactionRepository for Arc<T> {
+    async fn find_by_id(&self, transaction_id: &str) -> Result<Option<Transaction>> {
+        (**self).find_by_id(transaction_id).await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Transaction>> {
+        (**self).find_by_user_id(user_id).await
+    }
+
+    async fn find_page(&self, user_id: &str, query: &TransactionQuery) -> Result<TransactionPage> {
+        (**self).find_page(user_id, query).await
+    }
+
+    async fn save(&self, transaction: Transaction) -> Result<()> {
+        (**self).save(transaction).await
+    }
+
+    async fn save_all(&self, transactions: Vec<Transaction>) -> Result<()> {
+        (**self).save_all(transactions).await
+    }
+
+    async fn update(&self, transaction: Transaction) -> Result<()> {
+        (**self).update(transaction).await
+    }
+
+    async fn delete(&self, transaction_id: &str) -> Result<()> {
+        (**self).delete(transaction_id).await
+    }
+
+    async fn delete_all(&self, transaction_ids: &[String]) -> Result<()> {
+        (**self).delete_all(transaction_ids).await
+    }
+}
+
+#[async_trait]
+impl<T: BudgetRepository + ?Sized> BudgetRepository for Arc<T> {
+    async fn find_by_id(&self, budget_id: &str) -> Result<Option<Budget>> {
+        (**self).find_by_id(budget_id).await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Budget>> {
+        (**self).find_by_user_id(user_id).await
+    }
+
+    async fn save(&self, budget: Budget) -> Result<()> {
+        (**self).save(budget).await
+    }
+
+    async fn update(&self, budget: Budget) -> Result<()> {
+        (**self).update(budget).await
+    }
+
+    async fn delete(&self, budget_id: &str) -> Result<()> {
+        (**self).delete(budget_id).await
+    }
+}
+
+#[async_trait]
+impl<T: GroupRepository + ?Sized> GroupRepository for Arc<T> {
+    async fn find_by_id(&self, group_id: &str) -> Result<Option<Group>> {
+        (**self).find_by_id(group_id).await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Group>> {
+        (**self).find_by_user_id(user_id).await
+    }
+
+    async fn save(&self, group: Group) -> Result<()> {
+        (**self).save(group).await
+    }
+
+    async fn update(&self, group: Group) -> Result<()> {
+        (**self).update(group).await
+    }
+
+    async fn delete(&self, group_id: &str) -> Result<()> {
+        (**self).delete(group_id).await
+    }
+}
+
+#[async_trait]
+impl<T: GroupTransactionRepository + ?Sized> GroupTransactionRepository for Arc<T> {
+    async fn find_by_group_id(&self, group_id: &str) -> Result<Vec<GroupTransaction>> {
+        (**self).find_by_group_id(group_id).await
+    }
+
+    async fn save(
+        &self,
+        transaction: GroupTransaction,
+        payer_transaction: Option<Transaction>,
+    ) -> Result<()> {
+        (**self).save(transaction, payer_transaction).await
+    }
+}
+
+#[async_trait]
+impl<T: SettlementRepository + ?Sized> SettlementRepository for Arc<T> {
+    async fn find_by_id(&self, settlement_id: &str) -> Result<Option<Settlement>> {
+        (**self).find_by_id(settlement_id).await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Settlement>> {
+        (**self).find_by_user_id(user_id).await
+    }
+
+    async fn save(&self, settlement: Settlement) -> Result<()> {
+        (**self).save(settlement).await
+    }
+
+    async fn update(&self, settlement: Settlement) -> Result<()> {
+        (**self).update(settlement).await
+    }
+}
+
+#[async_trait]
+impl<T: AccountRepository + ?Sized> AccountRepository for Arc<T> {
+    async fn find_by_id(&self, account_id: &str) -> Result<Option<Account>> {
+        (**self).find_by_id(account_id).await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Account>> {
+        (**self).find_by_user_id(user_id).await
+    }
+
+    async fn save(&self, account: Account) -> Result<()> {
+        (**self).save(account).await
+    }
+
+    async fn update(&self, account: Account) -> Result<()> {
+        (**self).update(account).await
+    }
+
+    async fn delete(&self, account_id: &str) -> Result<()> {
+        (**self).delete(account_id).await
+    }
+}
+
+#[async_trait]
+impl<T: CategoryRepository + ?Sized> CategoryRepository for Arc<T> {
+    async fn find_by_id(&self, category_id: &str) -> Result<Option<Category>> {
+        (**self).find_by_id(category_id).await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Category>> {
+        (**self).find_by_user_id(user_id).await
+    }
+
+    async fn save(&self, category: Category) -> Result<()> {
+        (**self).save(category).await
+    }
+
+    async fn save_all(&self, categories: Vec<Category>) -> Result<()> {
+        (**self).save_all(categories).await
+    }
+
+    async fn update(&self, category: Category) -> Result<()> {
+        (**self).update(category).await
+    }
+}
+
+#[async_trait]
+impl<T: AggregateRepository + ?Sized> AggregateRepository for Arc<T> {
+    async fn find(&self, user_id: &str, month: NaiveDate) -> Result<Option<MonthlyAggregate>> {
+        (**self).find(user_id, month).await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<MonthlyAggregate>> {
+        (**self).find_by_user_id(user_id).await
+    }
+
+    async fn apply(&self, delta: MonthlyAggregate) -> Result<()> {
+        (**self).apply(delta).await
+    }
+
+    async fn replace_all(&self, user_id: &str, aggregates: Vec<MonthlyAggregate>) -> Result<()> {
+        (**self).replace_all(user_id, aggregates).await
+    }
+}
+
+#[async_trait]
+impl<T: BudgetAlertRepository + ?Sized> BudgetAlertRepository for Arc<T> {
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<BudgetAlertRaised>> {
+        (**self).find_by_user_id(user_id).await
+    }
+
+    async fn save_if_absent(&self, alert: BudgetAlertRaised) -> Result<bool> {
+        (**self).save_if_absent(alert).await
+    }
+
+    async fn find_undelivered(&self, user_id: &str) -> Result<Vec<BudgetAlertRaised>> {
+        (**self).find_undelivered(user_id).await
+    }
+
+    async fn update(&self, alert: BudgetAlertRaised) -> Result<()> {
+        (**self).update(alert).await
+    }
+}
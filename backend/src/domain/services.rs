@@ -2,8 +2,33 @@
 // ビジネスロジックを実装するサービス群
 
 use crate::domain::entities::*;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::notifiers::BudgetAlertNotifier;
 use crate::domain::repositories::*;
-use anyhow::Result;
+use crate::domain::value_objects::{Amount, UserId};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+/// 他のユーザーが所有するエンティティへのアクセスを拒否する
+fn ensure_owner(owner: &UserId, user_id: &UserId, entity: &str) -> DomainResult<()> {
+    if owner != user_id {
+        return Err(DomainError::Forbidden(format!(
+            "{} belongs to another user",
+            entity
+        )));
+    }
+    Ok(())
+}
+
+/// 口座間移動の片側だけを変更・削除すると両口座の残高が食い違うため、移動単位で扱わせる
+fn ensure_not_transfer(transaction: &Transaction) -> DomainResult<()> {
+    if transaction.is_transfer() {
+        return Err(DomainError::Conflict(
+            "Transfer transactions can only be changed through their transfer".to_string(),
+        ));
+    }
+    Ok(())
+}
 
 /// ユーザーサービス
 pub struct UserService<R: UserRepository> {
@@ -15,65 +40,3803 @@ impl<R: UserRepository> UserService<R> {
         Self { repository }
     }
 
-    pub async fn get_user(&self, user_id: &str) -> Result<Option<UserProfile>> {
-        self.repository.find_by_id(user_id).await
+    pub async fn get_user(&self, user_id: &str) -> DomainResult<UserProfile> {
+        self.repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found("User", user_id))
+    }
+
+    /// ユーザーIDはクライアントが指定するため、既存ユーザーの上書きを防ぐ
+    pub async fn create_user(&self, user: UserProfile) -> DomainResult<()> {
+        if self
+            .repository
+            .find_by_id(user.user_id.value())
+            .await?
+            .is_some()
+        {
+            return Err(DomainError::Conflict(format!(
+                "User {} already exists",
+                user.user_id
+            )));
+        }
+        user.validate()?;
+        Ok(self.repository.save(user).await?)
+    }
+
+    pub async fn update_user(&self, user: UserProfile) -> DomainResult<()> {
+        user.validate()?;
+        Ok(self.repository.update(user).await?)
     }
 
-    pub async fn create_user(&self, user: UserProfile) -> Result<()> {
-        self.repository.save(user).await
+    /// ユーザーのタイムゾーンでの `now` の日付（プロフィールがなければ既定のタイムゾーン）
+    pub async fn local_date(
+        &self,
+        user_id: &UserId,
+        now: DateTime<Utc>,
+    ) -> DomainResult<NaiveDate> {
+        self.repository
+            .find_by_id(user_id.value())
+            .await?
+            .unwrap_or_else(|| UserProfile::new(user_id.clone()))
+            .local_date(now)
+    }
+}
+
+/// 取引一覧の1ページの最大件数
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// 取引の変更による差分を月次集計に加算する
+async fn apply_aggregate_changes<A: AggregateRepository>(
+    aggregate_repository: &A,
+    before: &[Transaction],
+    after: &[Transaction],
+) -> DomainResult<()> {
+    for delta in MonthlyAggregate::changes(before, after) {
+        aggregate_repository.apply(delta).await?;
     }
+    Ok(())
 }
 
 /// 取引サービス
-pub struct TransactionService<R: TransactionRepository> {
+///
+/// 取引を書き込むたびに、その差分を月次集計に反映する
+pub struct TransactionService<R: TransactionRepository, A: AggregateRepository> {
     repository: R,
+    aggregate_repository: A,
+    update_aggregates: bool,
 }
 
-impl<R: TransactionRepository> TransactionService<R> {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+impl<R: TransactionRepository, A: AggregateRepository> TransactionService<R, A> {
+    pub fn new(repository: R, aggregate_repository: A) -> Self {
+        Self {
+            repository,
+            aggregate_repository,
+            update_aggregates: true,
+        }
+    }
+
+    /// 書き込み時には月次集計を更新しない（DynamoDB Streams のコンシューマーが更新する構成）
+    pub fn without_aggregate_updates(mut self) -> Self {
+        self.update_aggregates = false;
+        self
+    }
+
+    async fn apply_aggregate_changes(
+        &self,
+        before: &[Transaction],
+        after: &[Transaction],
+    ) -> DomainResult<()> {
+        if !self.update_aggregates {
+            return Ok(());
+        }
+        apply_aggregate_changes(&self.aggregate_repository, before, after).await
+    }
+
+    /// 指定ユーザーの取引を取得
+    pub async fn get_transaction(
+        &self,
+        user_id: &UserId,
+        transaction_id: &str,
+    ) -> DomainResult<Transaction> {
+        let transaction = self
+            .repository
+            .find_by_id(transaction_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Transaction", transaction_id))?;
+        ensure_owner(&transaction.user_id, user_id, "Transaction")?;
+        Ok(transaction)
+    }
+
+    pub async fn get_transactions(&self, user_id: &str) -> DomainResult<Vec<Transaction>> {
+        Ok(self.repository.find_by_user_id(user_id).await?)
+    }
+
+    /// 指定ユーザーの取引を条件で絞り込み、新しい順に1ページ分取得
+    pub async fn list_transactions(
+        &self,
+        user_id: &UserId,
+        query: &TransactionQuery,
+    ) -> DomainResult<TransactionPage> {
+        if !(1..=MAX_PAGE_SIZE).contains(&query.limit) {
+            return Err(DomainError::Validation(format!(
+                "Page size must be between 1 and {}",
+                MAX_PAGE_SIZE
+            )));
+        }
+        Ok(self.repository.find_page(user_id.value(), query).await?)
+    }
+
+    pub async fn create_transaction(&self, transaction: Transaction) -> DomainResult<()> {
+        transaction.validate()?;
+        self.repository.save(transaction.clone()).await?;
+        self.apply_aggregate_changes(&[], &[transaction]).await
     }
 
-    pub async fn get_transactions(&self, user_id: &str) -> Result<Vec<Transaction>> {
-        self.repository.find_by_user_id(user_id).await
+    /// 別の書き込みで保存済みの取引（グループ取引の立て替え分など）を月次集計に反映する
+    pub async fn record_saved_transactions(
+        &self,
+        transactions: &[Transaction],
+    ) -> DomainResult<()> {
+        self.apply_aggregate_changes(&[], transactions).await
     }
 
-    pub async fn create_transaction(&self, transaction: Transaction) -> Result<()> {
-        self.repository.save(transaction).await
+    pub async fn update_transaction(&self, transaction: Transaction) -> DomainResult<()> {
+        transaction.validate()?;
+        ensure_not_transfer(&transaction)?;
+        let before: Vec<Transaction> = self
+            .repository
+            .find_by_id(transaction.transaction_id.value())
+            .await?
+            .into_iter()
+            .collect();
+        self.repository.update(transaction.clone()).await?;
+        self.apply_aggregate_changes(&before, &[transaction]).await
     }
 
-    pub async fn update_transaction(&self, transaction: Transaction) -> Result<()> {
-        self.repository.update(transaction).await
+    pub async fn delete_transaction(
+        &self,
+        user_id: &UserId,
+        transaction_id: &str,
+    ) -> DomainResult<()> {
+        let transaction = self.get_transaction(user_id, transaction_id).await?;
+        ensure_not_transfer(&transaction)?;
+        self.repository.delete(transaction_id).await?;
+        self.apply_aggregate_changes(&[transaction], &[]).await
     }
 
-    pub async fn delete_transaction(&self, transaction_id: &str) -> Result<()> {
-        self.repository.delete(transaction_id).await
+    /// 口座間移動を出金・入金の2件の取引としてまとめて保存
+    pub async fn create_transfer(
+        &self,
+        transfer: &AccountTransfer,
+    ) -> DomainResult<Vec<Transaction>> {
+        transfer.validate()?;
+        let transactions = transfer.transactions().to_vec();
+        for transaction in &transactions {
+            transaction.validate()?;
+        }
+        self.repository.save_all(transactions.clone()).await?;
+        self.apply_aggregate_changes(&[], &transactions).await?;
+        Ok(transactions)
+    }
+
+    /// 指定ユーザーの口座間移動と、その取引（出金側、入金側の順）を取得
+    pub async fn get_transfer(
+        &self,
+        user_id: &UserId,
+        transfer_id: &str,
+    ) -> DomainResult<(AccountTransfer, Vec<Transaction>)> {
+        let mut transactions: Vec<Transaction> = self
+            .repository
+            .find_by_user_id(user_id.value())
+            .await?
+            .into_iter()
+            .filter(|t| {
+                t.transfer
+                    .as_ref()
+                    .is_some_and(|info| info.transfer_id == transfer_id)
+            })
+            .collect();
+        transactions.sort_by_key(|t| t.direction == TransactionDirection::Income);
+        let transfer = transactions
+            .first()
+            .and_then(AccountTransfer::from_transaction)
+            .ok_or_else(|| DomainError::not_found("Transfer", transfer_id))?;
+        Ok((transfer, transactions))
+    }
+
+    /// 口座間移動の両側の取引をまとめて削除
+    pub async fn delete_transfer(&self, user_id: &UserId, transfer_id: &str) -> DomainResult<()> {
+        let (_, transactions) = self.get_transfer(user_id, transfer_id).await?;
+        let transaction_ids: Vec<String> = transactions
+            .iter()
+            .map(|t| t.transaction_id.value().to_string())
+            .collect();
+        self.repository.delete_all(&transaction_ids).await?;
+        self.apply_aggregate_changes(&transactions, &[]).await
+    }
+}
+
+/// 保存済みの月次集計と、取引から求め直した集計の食い違い
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregateMismatch {
+    pub month: NaiveDate,
+    /// 保存済みの集計（ない場合は `None`）
+    pub stored: Option<MonthlyAggregate>,
+    /// 取引から求めた集計（取引がない月は `None`）
+    pub expected: Option<MonthlyAggregate>,
+}
+
+/// 月次集計サービス
+///
+/// 差分の加算は通常 `TransactionService` が行い、ここでは取引からの再構築と、
+/// 保存済みの集計と取引から求めた集計の突き合わせを行う。
+/// DynamoDB Streams のコンシューマーが集計を更新する構成では、差分の加算もここで行う
+pub struct AggregateService<A: AggregateRepository, T: TransactionRepository> {
+    aggregate_repository: A,
+    transaction_repository: T,
+}
+
+impl<A: AggregateRepository, T: TransactionRepository> AggregateService<A, T> {
+    pub fn new(aggregate_repository: A, transaction_repository: T) -> Self {
+        Self {
+            aggregate_repository,
+            transaction_repository,
+        }
+    }
+
+    pub async fn get_aggregates(&self, user_id: &UserId) -> DomainResult<Vec<MonthlyAggregate>> {
+        Ok(self
+            .aggregate_repository
+            .find_by_user_id(user_id.value())
+            .await?)
+    }
+
+    /// 取引の変更前と変更後（作成なら変更前、削除なら変更後がない）の差分を集計に加算する
+    pub async fn apply_change(
+        &self,
+        before: Option<&Transaction>,
+        after: Option<&Transaction>,
+    ) -> DomainResult<()> {
+        apply_aggregate_changes(
+            &self.aggregate_repository,
+            before.map_or(&[], std::slice::from_ref),
+            after.map_or(&[], std::slice::from_ref),
+        )
+        .await
+    }
+
+    /// 取引から月次集計を作り直し、保存済みの集計と置き換える
+    pub async fn rebuild(&self, user_id: &UserId) -> DomainResult<Vec<MonthlyAggregate>> {
+        let aggregates = self.expected(user_id).await?;
+        self.aggregate_repository
+            .replace_all(user_id.value(), aggregates.clone())
+            .await?;
+        Ok(aggregates)
+    }
+
+    /// 保存済みの月次集計を取引から求めた集計と突き合わせ、食い違う月を返す（月の順）
+    ///
+    /// 取引のない月の空の集計は、集計がないものとして扱う
+    pub async fn check(&self, user_id: &UserId) -> DomainResult<Vec<AggregateMismatch>> {
+        let mut stored: Vec<MonthlyAggregate> = self
+            .aggregate_repository
+            .find_by_user_id(user_id.value())
+            .await?
+            .into_iter()
+            .filter(|a| !a.is_empty())
+            .collect();
+        let mut mismatches = Vec::new();
+        for expected in self.expected(user_id).await? {
+            let stored = stored
+                .iter()
+                .position(|a| a.month == expected.month)
+                .map(|index| stored.remove(index));
+            if stored.as_ref() != Some(&expected) {
+                mismatches.push(AggregateMismatch {
+                    month: expected.month,
+                    stored,
+                    expected: Some(expected),
+                });
+            }
+        }
+        mismatches.extend(stored.into_iter().map(|a| AggregateMismatch {
+            month: a.month,
+            stored: Some(a),
+            expected: None,
+        }));
+        mismatches.sort_by_key(|m| m.month);
+        Ok(mismatches)
+    }
+
+    async fn expected(&self, user_id: &UserId) -> DomainResult<Vec<MonthlyAggregate>> {
+        let transactions = self
+            .transaction_repository
+            .find_by_user_id(user_id.value())
+            .await?;
+        Ok(MonthlyAggregate::from_transactions(&transactions))
+    }
+}
+
+/// 予算の現在の期間における消化状況
+#[derive(Debug, Clone)]
+pub struct BudgetStatus {
+    pub budget: Budget,
+    pub window: PeriodWindow,
+    pub spent: Amount,
+    /// 前の期間からの繰越額（超過を繰り越した場合は負）
+    pub carried_over: Amount,
+    /// 残額（超過している場合は負）
+    pub remaining: Amount,
+    pub usage: f64,
+    pub should_alert: bool,
+}
+
+impl BudgetStatus {
+    /// `today`（ユーザーのタイムゾーンでの日付）を含む期間の支出から消化状況を求める
+    ///
+    /// 繰り越す予算は `created_on`（予算を作成した日）を含む期間からの繰越額を予算額に加える。
+    /// 使用率とアラートは、支出から繰越額を差し引いた額を予算額と比べて判定する
+    pub fn evaluate(
+        budget: Budget,
+        transactions: &[Transaction],
+        categories: &CategoryTree,
+        today: NaiveDate,
+        created_on: NaiveDate,
+    ) -> DomainResult<Self> {
+        let window = budget.window_containing(today)?;
+        let spent = budget.spent_amount(&window.transactions_in(transactions), categories)?;
+        let carried_over = budget.carryover(transactions, categories, created_on, &window)?;
+        let net_spent = spent.subtract(&carried_over)?;
+        let net_spent = Amount::new(net_spent.value.max(0), net_spent.currency);
+        Ok(Self {
+            window,
+            remaining: budget.amount.add(&carried_over)?.subtract(&spent)?,
+            usage: budget.calculate_usage_percentage(&net_spent)?,
+            should_alert: budget.should_alert(&net_spent)?,
+            spent,
+            carried_over,
+            budget,
+        })
     }
 }
 
 /// 予算サービス
-pub struct BudgetService<R: BudgetRepository> {
-    repository: R,
+///
+/// 予算期間はユーザーのタイムゾーンで区切る
+pub struct BudgetService<
+    B: BudgetRepository,
+    T: TransactionRepository,
+    U: UserRepository,
+    C: CategoryRepository,
+> {
+    repository: B,
+    transaction_repository: T,
+    user_repository: U,
+    category_repository: C,
 }
 
-impl<R: BudgetRepository> BudgetService<R> {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+impl<B: BudgetRepository, T: TransactionRepository, U: UserRepository, C: CategoryRepository>
+    BudgetService<B, T, U, C>
+{
+    pub fn new(
+        repository: B,
+        transaction_repository: T,
+        user_repository: U,
+        category_repository: C,
+    ) -> Self {
+        Self {
+            repository,
+            transaction_repository,
+            user_repository,
+            category_repository,
+        }
+    }
+
+    /// ユーザー定義カテゴリを対象にする予算は、そのユーザーのカテゴリだけを指定できる
+    async fn ensure_known_categories(&self, budget: &Budget) -> DomainResult<()> {
+        let BudgetTarget::UserCategories { category_ids } = &budget.target else {
+            return Ok(());
+        };
+        let categories = self
+            .category_repository
+            .find_by_user_id(budget.user_id.value())
+            .await?;
+        for category_id in category_ids {
+            if !categories.iter().any(|c| &c.category_id == category_id) {
+                return Err(DomainError::Validation(format!(
+                    "Unknown category: {}",
+                    category_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 指定ユーザーの予算を取得
+    pub async fn get_budget(&self, user_id: &UserId, budget_id: &str) -> DomainResult<Budget> {
+        let budget = self
+            .repository
+            .find_by_id(budget_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Budget", budget_id))?;
+        ensure_owner(&budget.user_id, user_id, "Budget")?;
+        Ok(budget)
+    }
+
+    pub async fn get_budgets(&self, user_id: &str) -> DomainResult<Vec<Budget>> {
+        Ok(self.repository.find_by_user_id(user_id).await?)
+    }
+
+    pub async fn create_budget(&self, budget: Budget) -> DomainResult<()> {
+        budget.validate()?;
+        self.ensure_known_categories(&budget).await?;
+        Ok(self.repository.save(budget).await?)
+    }
+
+    pub async fn update_budget(&self, budget: Budget) -> DomainResult<()> {
+        budget.validate()?;
+        self.ensure_known_categories(&budget).await?;
+        Ok(self.repository.update(budget).await?)
+    }
+
+    pub async fn delete_budget(&self, user_id: &UserId, budget_id: &str) -> DomainResult<()> {
+        self.get_budget(user_id, budget_id).await?;
+        Ok(self.repository.delete(budget_id).await?)
+    }
+
+    /// `now` の時点での予算の消化状況を取得
+    ///
+    /// プロフィールが未作成のユーザーは既定のタイムゾーンで期間を区切る
+    pub async fn get_status(
+        &self,
+        user_id: &UserId,
+        budget_id: &str,
+        now: DateTime<Utc>,
+    ) -> DomainResult<BudgetStatus> {
+        let budget = self.get_budget(user_id, budget_id).await?;
+        let mut statuses = self.evaluate(user_id, vec![budget], now).await?;
+        Ok(statuses.remove(0))
+    }
+
+    /// `now` の時点でアラートが必要な予算の消化状況を取得
+    ///
+    /// `budget` を指定した場合はその予算だけを、指定しない場合はユーザーの全予算を判定する
+    pub async fn get_alerts(
+        &self,
+        user_id: &UserId,
+        budget: Option<Budget>,
+        now: DateTime<Utc>,
+    ) -> DomainResult<Vec<BudgetStatus>> {
+        let budgets = match budget {
+            Some(budget) => vec![budget],
+            None => self.repository.find_by_user_id(user_id.value()).await?,
+        };
+        // 予算がなければ取引を読み込まずに済ませる
+        if budgets.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(self
+            .evaluate(user_id, budgets, now)
+            .await?
+            .into_iter()
+            .filter(|status| status.should_alert)
+            .collect())
+    }
+
+    /// 指定ユーザーの予算の消化状況を、取引とカテゴリを一度だけ読み込んで求める
+    async fn evaluate(
+        &self,
+        user_id: &UserId,
+        budgets: Vec<Budget>,
+        now: DateTime<Utc>,
+    ) -> DomainResult<Vec<BudgetStatus>> {
+        let user = self
+            .user_repository
+            .find_by_id(user_id.value())
+            .await?
+            .unwrap_or_else(|| UserProfile::new(user_id.clone()));
+        let transactions = self
+            .transaction_repository
+            .find_by_user_id(user_id.value())
+            .await?;
+        let categories = CategoryTree::new(
+            self.category_repository
+                .find_by_user_id(user_id.value())
+                .await?,
+        );
+        let today = user.local_date(now)?;
+        budgets
+            .into_iter()
+            .map(|budget| {
+                let created_on = user.local_date(budget.created_at)?;
+                BudgetStatus::evaluate(budget, &transactions, &categories, today, created_on)
+            })
+            .collect()
+    }
+}
+
+/// 予算アラートサービス
+///
+/// 支出や予算が変わるたびに閾値を判定し、予算・期間・閾値の組ごとに一度だけ
+/// `BudgetAlertRaised` を記録する。記録したアラートは通知に成功するまで再送する
+pub struct BudgetAlertService<
+    B: BudgetRepository,
+    T: TransactionRepository,
+    U: UserRepository,
+    C: CategoryRepository,
+    A: BudgetAlertRepository,
+    N: BudgetAlertNotifier,
+> {
+    budget_service: BudgetService<B, T, U, C>,
+    repository: A,
+    notifier: N,
+}
+
+impl<
+        B: BudgetRepository,
+        T: TransactionRepository,
+        U: UserRepository,
+        C: CategoryRepository,
+        A: BudgetAlertRepository,
+        N: BudgetAlertNotifier,
+    > BudgetAlertService<B, T, U, C, A, N>
+{
+    pub fn new(budget_service: BudgetService<B, T, U, C>, repository: A, notifier: N) -> Self {
+        Self {
+            budget_service,
+            repository,
+            notifier,
+        }
+    }
+
+    /// `now` の時点で閾値に達した予算のアラートを記録し、新たに発生したものを返す
+    ///
+    /// `budget` を指定した場合はその予算だけを、指定しない場合はユーザーの全予算を判定する。
+    /// 記録したアラートは未送信として残り、`deliver_pending` で通知する
+    pub async fn evaluate(
+        &self,
+        user_id: &UserId,
+        budget: Option<Budget>,
+        now: DateTime<Utc>,
+    ) -> DomainResult<Vec<BudgetAlertRaised>> {
+        let mut raised = Vec::new();
+        for status in self.budget_service.get_alerts(user_id, budget, now).await? {
+            let alert = BudgetAlertRaised::new(
+                &status.budget,
+                &status.window,
+                status.usage,
+                status.spent,
+                now,
+            );
+            if self.repository.save_if_absent(alert.clone()).await? {
+                raised.push(alert);
+            }
+        }
+        Ok(raised)
+    }
+
+    /// 未送信のアラートを発生の古い順に通知し、送信できたものを通知済みにする
+    ///
+    /// 通知に失敗したアラートは未送信のまま残り、次の呼び出しで再送する（少なくとも1回は届ける）。
+    /// 失敗しても残りのアラートの通知は続け、最初の失敗を返す
+    pub async fn deliver_pending(&self, user_id: &UserId, now: DateTime<Utc>) -> DomainResult<()> {
+        let mut failure = None;
+        for mut alert in self.repository.find_undelivered(user_id.value()).await? {
+            match self.notifier.notify(&alert).await {
+                Ok(()) => {
+                    alert.delivered_at = Some(now);
+                    self.repository.update(alert).await?;
+                }
+                Err(err) => {
+                    let alert_id = alert.alert_id;
+                    failure.get_or_insert_with(|| {
+                        err.context(format!("failed to notify budget alert {}", alert_id))
+                    });
+                }
+            }
+        }
+        match failure {
+            Some(err) => Err(err.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// 発生したアラートを新しい順に取得
+    pub async fn get_history(&self, user_id: &UserId) -> DomainResult<Vec<BudgetAlertRaised>> {
+        Ok(self.repository.find_by_user_id(user_id.value()).await?)
+    }
+}
+
+/// 口座サービス
+///
+/// 残高は保存せず、口座に紐づく取引から都度計算する
+pub struct AccountService<A: AccountRepository, T: TransactionRepository> {
+    repository: A,
+    transaction_repository: T,
+}
+
+impl<A: AccountRepository, T: TransactionRepository> AccountService<A, T> {
+    pub fn new(repository: A, transaction_repository: T) -> Self {
+        Self {
+            repository,
+            transaction_repository,
+        }
+    }
+
+    /// 指定ユーザーの口座を取得
+    pub async fn get_account(&self, user_id: &UserId, account_id: &str) -> DomainResult<Account> {
+        let account = self
+            .repository
+            .find_by_id(account_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Account", account_id))?;
+        ensure_owner(&account.user_id, user_id, "Account")?;
+        Ok(account)
+    }
+
+    pub async fn get_accounts(&self, user_id: &str) -> DomainResult<Vec<Account>> {
+        Ok(self.repository.find_by_user_id(user_id).await?)
+    }
+
+    pub async fn create_account(&self, account: Account) -> DomainResult<()> {
+        account.validate()?;
+        Ok(self.repository.save(account).await?)
+    }
+
+    pub async fn update_account(&self, account: Account) -> DomainResult<()> {
+        account.validate()?;
+        Ok(self.repository.update(account).await?)
+    }
+
+    /// 口座を削除
+    ///
+    /// 取引が紐づいている口座は残高の計算に必要なため削除できない（無効化する）
+    pub async fn delete_account(&self, user_id: &UserId, account_id: &str) -> DomainResult<()> {
+        let account = self.get_account(user_id, account_id).await?;
+        let transactions = self
+            .transaction_repository
+            .find_by_user_id(user_id.value())
+            .await?;
+        if transactions.iter().any(|t| account.is_affected_by(t)) {
+            return Err(DomainError::Conflict(
+                "Account has transactions; deactivate it instead".to_string(),
+            ));
+        }
+        Ok(self.repository.delete(account_id).await?)
+    }
+
+    /// 口座の残高を計算（`as_of` を指定した場合はその日時より前の取引まで）
+    pub async fn get_balance(
+        &self,
+        user_id: &UserId,
+        account_id: &str,
+        as_of: Option<NaiveDate>,
+    ) -> DomainResult<AccountBalance> {
+        let account = self.get_account(user_id, account_id).await?;
+        let transactions = self
+            .transaction_repository
+            .find_by_user_id(user_id.value())
+            .await?;
+        account.balance(&transactions, as_of)
+    }
+
+    /// クレジットカードの明細と未確定の利用額を取得（`today` は締めの判定に使う日）
+    pub async fn get_statements(
+        &self,
+        user_id: &UserId,
+        account_id: &str,
+        today: NaiveDate,
+    ) -> DomainResult<CardBillingSummary> {
+        let account = self.get_account(user_id, account_id).await?;
+        let transactions = self
+            .transaction_repository
+            .find_by_user_id(user_id.value())
+            .await?;
+        CardStatementCalculator::summarize(&account, &transactions, today)
+    }
+
+    /// 口座から引き落とされる予定のカード請求を取得
+    pub async fn get_upcoming_debits(
+        &self,
+        user_id: &UserId,
+        account_id: &str,
+        today: NaiveDate,
+    ) -> DomainResult<DebitProjection> {
+        let account = self.get_account(user_id, account_id).await?;
+        let cards = self.repository.find_by_user_id(user_id.value()).await?;
+        let transactions = self
+            .transaction_repository
+            .find_by_user_id(user_id.value())
+            .await?;
+        CardStatementCalculator::upcoming_debits(&account, &cards, &transactions, today)
+    }
+
+    /// 取引に指定された口座が、そのユーザーの有効な口座であることを確認
+    ///
+    /// 残高を計算できなくなるため、`amount` が口座と異なる通貨の場合も認めない
+    pub async fn ensure_usable(
+        &self,
+        user_id: &UserId,
+        account_id: &str,
+        amount: &Amount,
+    ) -> DomainResult<()> {
+        let account = self
+            .repository
+            .find_by_id(account_id)
+            .await?
+            .filter(|a| a.user_id == *user_id)
+            .ok_or_else(|| DomainError::Validation(format!("Unknown account: {}", account_id)))?;
+        if !account.is_active {
+            return Err(DomainError::Validation(format!(
+                "Account {} is inactive",
+                account_id
+            )));
+        }
+        account.opening_balance.ensure_same_currency(amount)
+    }
+}
+
+/// カテゴリごとの支出額
+#[derive(Debug, Clone)]
+pub struct CategorySpending {
+    pub category: Category,
+    /// このカテゴリに直接記録された支出額
+    pub own: Amount,
+    /// 子カテゴリの支出額を含めた合計
+    pub total: Amount,
+}
+
+/// カテゴリ管理サービス
+///
+/// カテゴリを1つも持たないユーザーには、初回の取得時に既定のカテゴリを用意する
+pub struct CategoryService<C: CategoryRepository, T: TransactionRepository, U: UserRepository> {
+    repository: C,
+    transaction_repository: T,
+    user_repository: U,
+}
+
+impl<C: CategoryRepository, T: TransactionRepository, U: UserRepository> CategoryService<C, T, U> {
+    pub fn new(repository: C, transaction_repository: T, user_repository: U) -> Self {
+        Self {
+            repository,
+            transaction_repository,
+            user_repository,
+        }
+    }
+
+    /// 指定ユーザーのカテゴリを取得
+    pub async fn get_category(
+        &self,
+        user_id: &UserId,
+        category_id: &str,
+    ) -> DomainResult<Category> {
+        let category = self
+            .repository
+            .find_by_id(category_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Category", category_id))?;
+        ensure_owner(&category.user_id, user_id, "Category")?;
+        Ok(category)
+    }
+
+    /// ユーザーのカテゴリ（アーカイブ済みを含む）を取得
+    pub async fn get_categories(&self, user_id: &UserId) -> DomainResult<Vec<Category>> {
+        let categories = self.repository.find_by_user_id(user_id.value()).await?;
+        if !categories.is_empty() {
+            return Ok(categories);
+        }
+        let defaults = Category::defaults(user_id);
+        self.repository.save_all(defaults.clone()).await?;
+        Ok(defaults)
+    }
+
+    /// カテゴリを作成（子カテゴリは親の分類を引き継ぐ）
+    pub async fn create_category(&self, mut category: Category) -> DomainResult<Category> {
+        category.validate()?;
+        let tree = CategoryTree::new(self.get_categories(&category.user_id).await?);
+        if let Some(parent) = category.parent_id.as_deref().and_then(|id| tree.get(id)) {
+            category.kind = parent.kind.clone();
+        }
+        tree.ensure_valid_parent(&category)?;
+        self.repository.save(category.clone()).await?;
+        Ok(category)
+    }
+
+    pub async fn update_category(&self, category: Category) -> DomainResult<()> {
+        category.validate()?;
+        let tree = CategoryTree::new(self.get_categories(&category.user_id).await?);
+        tree.ensure_valid_parent(&category)?;
+        Ok(self.repository.update(category).await?)
+    }
+
+    /// 取引に指定されたカテゴリが、そのユーザーのアーカイブされていないカテゴリであることを確認
+    pub async fn ensure_usable(
+        &self,
+        user_id: &UserId,
+        category_id: &str,
+    ) -> DomainResult<Category> {
+        let category = self
+            .repository
+            .find_by_id(category_id)
+            .await?
+            .filter(|c| c.user_id == *user_id)
+            .ok_or_else(|| DomainError::Validation(format!("Unknown category: {}", category_id)))?;
+        if category.is_archived {
+            return Err(DomainError::Validation(format!(
+                "Category {} is archived",
+                category_id
+            )));
+        }
+        Ok(category)
+    }
+
+    /// `window` の期間のカテゴリ別の支出額（子カテゴリの支出は親にも積み上げる）
+    ///
+    /// プロフィールの通貨の取引だけを集計し、支出のあったカテゴリを合計の多い順に返す。
+    /// 予算と同じく、収入と立て替えは含めず返金は支出から差し引く
+    pub async fn get_spending(
+        &self,
+        user_id: &UserId,
+        window: &PeriodWindow,
+    ) -> DomainResult<Vec<CategorySpending>> {
+        let currency = self
+            .user_repository
+            .find_by_id(user_id.value())
+            .await?
+            .unwrap_or_else(|| UserProfile::new(user_id.clone()))
+            .currency;
+        let tree = CategoryTree::new(self.get_categories(user_id).await?);
+        let transactions = self
+            .transaction_repository
+            .find_by_user_id(user_id.value())
+            .await?;
+
+        let mut own: HashMap<String, i64> = HashMap::new();
+        let mut total: HashMap<String, i64> = HashMap::new();
+        for transaction in window
+            .transactions_in(&transactions)
+            .iter()
+            .filter(|t| t.affects_budget() && t.amount.currency == currency)
+        {
+            let Some(category) = tree.category_of(transaction) else {
+                continue;
+            };
+            *own.entry(category.category_id.clone()).or_default() += transaction.amount.value;
+            for ancestor in tree.ancestors(&category.category_id) {
+                *total.entry(ancestor.category_id.clone()).or_default() += transaction.amount.value;
+            }
+        }
+
+        let mut spending: Vec<CategorySpending> = total
+            .into_iter()
+            .filter_map(|(category_id, total)| {
+                Some(CategorySpending {
+                    category: tree.get(&category_id)?.clone(),
+                    own: Amount::new(
+                        own.get(&category_id).copied().unwrap_or_default(),
+                        currency.clone(),
+                    ),
+                    total: Amount::new(total, currency.clone()),
+                })
+            })
+            .collect();
+        // 合計が同じ場合は親カテゴリを先にする
+        let depth =
+            |spending: &CategorySpending| tree.ancestors(&spending.category.category_id).len();
+        spending.sort_by(|a, b| {
+            b.total
+                .value
+                .cmp(&a.total.value)
+                .then_with(|| depth(a).cmp(&depth(b)))
+                .then_with(|| a.category.name.cmp(&b.category.name))
+        });
+        Ok(spending)
+    }
+}
+
+/// レポートサービス
+///
+/// 集計はストレージに依存しない計算サービス（`MonthlyReportCalculator`・`TrendReportCalculator`）に任せ、
+/// 必要なデータを集めて渡す。収支と分類別支出は月次集計から求め、取引は明細が必要なとき
+/// （予算実績・未回収の立て替え・日次や月の途中で区切る推移）だけ読み込む
+pub struct ReportService<
+    T: TransactionRepository,
+    B: BudgetRepository,
+    S: SettlementRepository,
+    U: UserRepository,
+    C: CategoryRepository,
+    A: AggregateRepository,
+> {
+    transaction_repository: T,
+    budget_repository: B,
+    settlement_repository: S,
+    user_repository: U,
+    category_repository: C,
+    aggregate_repository: A,
+}
+
+impl<
+        T: TransactionRepository,
+        B: BudgetRepository,
+        S: SettlementRepository,
+        U: UserRepository,
+        C: CategoryRepository,
+        A: AggregateRepository,
+    > ReportService<T, B, S, U, C, A>
+{
+    pub fn new(
+        transaction_repository: T,
+        budget_repository: B,
+        settlement_repository: S,
+        user_repository: U,
+        category_repository: C,
+        aggregate_repository: A,
+    ) -> Self {
+        Self {
+            transaction_repository,
+            budget_repository,
+            settlement_repository,
+            user_repository,
+            category_repository,
+            aggregate_repository,
+        }
+    }
+
+    /// `month_start` から始まる月の、`now` の時点での月次レポートを取得
+    ///
+    /// プロフィールが未作成のユーザーは既定のタイムゾーン・通貨で集計する
+    pub async fn get_monthly_report(
+        &self,
+        user_id: &UserId,
+        month_start: NaiveDate,
+        now: DateTime<Utc>,
+    ) -> DomainResult<MonthlyReport> {
+        let user = self
+            .user_repository
+            .find_by_id(user_id.value())
+            .await?
+            .unwrap_or_else(|| UserProfile::new(user_id.clone()));
+        let today = user.local_date(now)?;
+        let aggregates = self
+            .aggregate_repository
+            .find_by_user_id(user_id.value())
+            .await?;
+        let budgets = self
+            .budget_repository
+            .find_by_user_id(user_id.value())
+            .await?;
+        let transactions = if MonthlyReportCalculator::needs_transactions(
+            &user,
+            month_start,
+            today,
+            &aggregates,
+            &budgets,
+        )? {
+            self.transaction_repository
+                .find_by_user_id(user_id.value())
+                .await?
+        } else {
+            Vec::new()
+        };
+        let settlements = self
+            .settlement_repository
+            .find_by_user_id(user_id.value())
+            .await?;
+        let categories = CategoryTree::new(
+            self.category_repository
+                .find_by_user_id(user_id.value())
+                .await?,
+        );
+        MonthlyReportCalculator::calculate(
+            &user,
+            month_start,
+            today,
+            &aggregates,
+            MonthlyReportDetails {
+                transactions: &transactions,
+                budgets: &budgets,
+                settlements: &settlements,
+                categories: &categories,
+            },
+        )
+    }
+
+    /// 推移レポートを取得
+    ///
+    /// `window` を省略した場合は、ユーザーのタイムゾーンでの今日を含む既定の範囲を集計する。
+    /// 月次・年次で月単位の範囲は月次集計から、それ以外（日次、月の途中で区切る範囲）は取引から集計する
+    pub async fn get_trend_report(
+        &self,
+        user_id: &UserId,
+        granularity: ReportGranularity,
+        window: Option<PeriodWindow>,
+        now: DateTime<Utc>,
+    ) -> DomainResult<TrendReport> {
+        let user = self
+            .user_repository
+            .find_by_id(user_id.value())
+            .await?
+            .unwrap_or_else(|| UserProfile::new(user_id.clone()));
+        let window = match window {
+            Some(window) => window,
+            None => granularity.default_window(user.local_date(now)?),
+        };
+        if granularity != ReportGranularity::Daily && window.is_whole_months() {
+            let aggregates = self
+                .aggregate_repository
+                .find_by_user_id(user_id.value())
+                .await?;
+            return TrendReportCalculator::from_aggregates(
+                &user.currency,
+                granularity,
+                window,
+                &aggregates,
+            );
+        }
+        let transactions = self
+            .transaction_repository
+            .find_by_user_id(user_id.value())
+            .await?;
+        TrendReportCalculator::calculate(&user.currency, granularity, window, &transactions)
+    }
+}
+
+/// 精算サービス
+///
+/// 精算は債権者・債務者の双方が参照でき、返済の記録・完了・取り消しは債権者のみが行える
+pub struct SettlementService<S: SettlementRepository, T: TransactionRepository> {
+    repository: S,
+    transaction_repository: T,
+}
+
+impl<S: SettlementRepository, T: TransactionRepository> SettlementService<S, T> {
+    pub fn new(repository: S, transaction_repository: T) -> Self {
+        Self {
+            repository,
+            transaction_repository,
+        }
+    }
+
+    /// 指定ユーザーが関わる精算を取得
+    pub async fn get_settlement(
+        &self,
+        user_id: &UserId,
+        settlement_id: &str,
+    ) -> DomainResult<Settlement> {
+        let settlement = self
+            .repository
+            .find_by_id(settlement_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Settlement", settlement_id))?;
+        if !settlement.involves(user_id) {
+            return Err(DomainError::Forbidden(
+                "Settlement belongs to other users".to_string(),
+            ));
+        }
+        Ok(settlement)
+    }
+
+    pub async fn get_settlements(&self, user_id: &str) -> DomainResult<Vec<Settlement>> {
+        Ok(self.repository.find_by_user_id(user_id).await?)
+    }
+
+    pub async fn create_settlement(&self, settlement: Settlement) -> DomainResult<()> {
+        Ok(self.repository.save(settlement).await?)
+    }
+
+    /// 自分の立て替え取引から精算を作成
+    ///
+    /// 同じ取引の精算（取り消し済みを除く）の合計が取引額を超えないようにする。
+    /// 金額を省略した場合は、まだ精算していない残りの全額とする
+    pub async fn create_from_transaction(
+        &self,
+        user_id: &UserId,
+        transaction_id: &str,
+        debtor_user_id: UserId,
+        amount: Option<Amount>,
+        description: Option<String>,
+    ) -> DomainResult<Settlement> {
+        let transaction = self
+            .transaction_repository
+            .find_by_id(transaction_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Transaction", transaction_id))?;
+        ensure_owner(&transaction.user_id, user_id, "Transaction")?;
+
+        let settled: i64 = self
+            .repository
+            .find_by_user_id(user_id.value())
+            .await?
+            .iter()
+            .filter(|s| s.transaction_id.as_ref() == Some(&transaction.transaction_id))
+            .filter(|s| s.status != SettlementStatus::Cancelled)
+            .map(|s| s.amount.value)
+            .sum();
+        let unsettled = transaction.amount.value - settled;
+        if unsettled <= 0 {
+            return Err(DomainError::Conflict(
+                "Transaction is already fully settled".to_string(),
+            ));
+        }
+        let amount =
+            amount.unwrap_or_else(|| Amount::new(unsettled, transaction.amount.currency.clone()));
+
+        let settlement =
+            Settlement::from_transaction(&transaction, debtor_user_id, Some(amount), description)?;
+        if settlement.amount.value > unsettled {
+            return Err(DomainError::Conflict(format!(
+                "Only {} of the transaction remains unsettled",
+                Amount::new(unsettled, transaction.amount.currency.clone())
+            )));
+        }
+        self.repository.save(settlement.clone()).await?;
+        Ok(settlement)
+    }
+
+    /// 返済を記録
+    pub async fn repay(
+        &self,
+        user_id: &UserId,
+        settlement_id: &str,
+        amount: Amount,
+    ) -> DomainResult<Settlement> {
+        self.modify(user_id, settlement_id, |settlement| {
+            settlement.repay(amount)
+        })
+        .await
+    }
+
+    /// 精算を完了
+    pub async fn complete(
+        &self,
+        user_id: &UserId,
+        settlement_id: &str,
+    ) -> DomainResult<Settlement> {
+        self.modify(user_id, settlement_id, Settlement::complete)
+            .await
+    }
+
+    /// 精算を取り消し
+    pub async fn cancel(&self, user_id: &UserId, settlement_id: &str) -> DomainResult<Settlement> {
+        self.modify(user_id, settlement_id, Settlement::cancel)
+            .await
+    }
+
+    /// 債権者であることを確認してから精算を変更し、保存する
+    async fn modify(
+        &self,
+        user_id: &UserId,
+        settlement_id: &str,
+        change: impl FnOnce(&mut Settlement) -> DomainResult<()>,
+    ) -> DomainResult<Settlement> {
+        let mut settlement = self.get_settlement(user_id, settlement_id).await?;
+        if settlement.creditor_user_id != *user_id {
+            return Err(DomainError::Forbidden(
+                "Only the creditor can update a settlement".to_string(),
+            ));
+        }
+        change(&mut settlement)?;
+        self.repository.update(settlement.clone()).await?;
+        Ok(settlement)
+    }
+}
+
+/// グループ取引サービス
+///
+/// グループ取引はグループのメンバーだけが参照・記録できる
+pub struct GroupTransactionService<G: GroupRepository, T: GroupTransactionRepository> {
+    group_repository: G,
+    repository: T,
+}
+
+impl<G: GroupRepository, T: GroupTransactionRepository> GroupTransactionService<G, T> {
+    pub fn new(group_repository: G, repository: T) -> Self {
+        Self {
+            group_repository,
+            repository,
+        }
+    }
+
+    /// 指定ユーザーが所属するグループを取得
+    pub async fn get_group(&self, user_id: &UserId, group_id: &str) -> DomainResult<Group> {
+        let group = self
+            .group_repository
+            .find_by_id(group_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Group", group_id))?;
+        if !group.is_member(user_id) {
+            return Err(DomainError::Forbidden(
+                "Group belongs to other users".to_string(),
+            ));
+        }
+        Ok(group)
+    }
+
+    pub async fn get_transactions(
+        &self,
+        user_id: &UserId,
+        group_id: &str,
+    ) -> DomainResult<Vec<GroupTransaction>> {
+        self.get_group(user_id, group_id).await?;
+        Ok(self.repository.find_by_group_id(group_id).await?)
+    }
+
+    /// メンバーが記録したグループ取引を、支払者の立て替え分の取引とまとめて保存
+    ///
+    /// 保存した立て替え分の取引（支払者が自分の負担分しか払っていなければ `None`）を返す
+    pub async fn create_transaction(
+        &self,
+        user_id: &UserId,
+        transaction: GroupTransaction,
+    ) -> DomainResult<Option<Transaction>> {
+        self.get_group(user_id, &transaction.group_id).await?;
+        let payer_transaction = transaction.payer_transaction();
+        if let Some(payer_transaction) = &payer_transaction {
+            payer_transaction.validate()?;
+        }
+        self.repository
+            .save(transaction, payer_transaction.clone())
+            .await?;
+        Ok(payer_transaction)
+    }
+
+    /// グループのこれまでの取引から精算プランを計算
+    pub async fn calculate_settlements(
+        &self,
+        user_id: &UserId,
+        group_id: &str,
+    ) -> DomainResult<SettlementPlan> {
+        let group = self.get_group(user_id, group_id).await?;
+        let transactions = self.repository.find_by_group_id(group_id).await?;
+        GroupSettlementCalculator::calculate(&group, &transactions)
+    }
+}
+
+/// グループ精算におけるメンバーの差引残高（正: 受け取る側、負: 支払う側）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberBalance {
+    pub user_id: UserId,
+    pub balance: Amount,
+}
+
+/// 精算のための送金（`from` が `to` に `amount` を支払う）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub from: UserId,
+    pub to: UserId,
+    pub amount: Amount,
+}
+
+/// グループの精算プラン
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettlementPlan {
+    pub group_id: String,
+    /// メンバーごとの差引残高（ユーザーID順）
+    pub balances: Vec<MemberBalance>,
+    pub transfers: Vec<Transfer>,
+}
+
+/// 送金回数を厳密に最小化するメンバー数の上限（超える場合は貪欲法で近似する）
+const EXACT_SETTLEMENT_MEMBER_LIMIT: usize = 16;
+
+/// グループ精算計算サービス
+///
+/// 共有取引を支払ったメンバーと全メンバーの負担額から差引残高を求め、
+/// 送金回数ができるだけ少なくなる精算プランを作る
+pub struct GroupSettlementCalculator;
+
+impl GroupSettlementCalculator {
+    pub fn calculate(
+        group: &Group,
+        transactions: &[GroupTransaction],
+    ) -> DomainResult<SettlementPlan> {
+        let balances = Self::balances(group, transactions)?;
+        let transfers = Self::transfers(&balances);
+        Ok(SettlementPlan {
+            group_id: group.group_id.clone(),
+            balances,
+            transfers,
+        })
+    }
+
+    /// メンバーごとの差引残高を計算（合計は常にゼロ）
+    ///
+    /// 支払者は取引額を受け取る側、各負担者は負担額を支払う側になる。負担額は取引の作成時に
+    /// 端数まで確定しているため、結果は取引やメンバーの並び順に依存しない。
+    /// 取引後にグループを抜けたメンバーも、取引に関わっていれば残高に含める
+    pub fn balances(
+        group: &Group,
+        transactions: &[GroupTransaction],
+    ) -> DomainResult<Vec<MemberBalance>> {
+        if let Some(other) = transactions.iter().find(|t| t.group_id != group.group_id) {
+            return Err(DomainError::Validation(format!(
+                "Transaction {} belongs to another group",
+                other.transaction_id.value()
+            )));
+        }
+
+        let mut members: Vec<&UserId> = group
+            .members
+            .iter()
+            .chain(transactions.iter().flat_map(|t| {
+                std::iter::once(&t.paid_by).chain(t.participants.iter().map(|p| &p.user_id))
+            }))
+            .collect();
+        members.sort_by(|a, b| a.value().cmp(b.value()));
+        members.dedup();
+        let index = |user_id: &UserId| members.iter().position(|&m| m == user_id).unwrap();
+
+        // 通貨は最初の取引に合わせる（異なる通貨の取引があれば加算時にエラー）
+        let zero = transactions
+            .first()
+            .map(|t| Amount::new(0, t.amount.currency.clone()))
+            .unwrap_or_else(|| Amount::jpy(0));
+        let mut balances = vec![zero; members.len()];
+
+        for transaction in transactions {
+            let payer = index(&transaction.paid_by);
+            balances[payer] = balances[payer].add(&transaction.amount)?;
+            for participant in &transaction.participants {
+                let i = index(&participant.user_id);
+                balances[i] = balances[i].subtract(&participant.share_amount)?;
+            }
+        }
+
+        Ok(members
+            .into_iter()
+            .zip(balances)
+            .map(|(user_id, balance)| MemberBalance {
+                user_id: user_id.clone(),
+                balance,
+            })
+            .collect())
+    }
+
+    /// 差引残高（合計ゼロ）を清算する送金の一覧を作る
+    ///
+    /// 残高の合計がゼロになるメンバーの組はその中だけで清算でき、k 人の組は k - 1 回の
+    /// 送金で済む。そのため送金回数の最小化は、組の数が最大になる分割を探すことに等しい。
+    /// メンバーが多い場合は全員を1つの組として扱う
+    pub fn transfers(balances: &[MemberBalance]) -> Vec<Transfer> {
+        let open: Vec<&MemberBalance> = balances.iter().filter(|b| !b.balance.is_zero()).collect();
+        let groups = if open.len() <= EXACT_SETTLEMENT_MEMBER_LIMIT {
+            Self::zero_sum_partition(&open)
+        } else {
+            vec![(0..open.len()).collect()]
+        };
+
+        groups
+            .into_iter()
+            .flat_map(|group| {
+                Self::settle_group(&group.into_iter().map(|i| open[i]).collect::<Vec<_>>())
+            })
+            .collect()
+    }
+
+    /// 合計ゼロの組の数が最大になるように残高を分割する（ビットDP）
+    ///
+    /// `best[mask]` は `mask` の要素を1つずつ取り除いていく順序のうち、途中に現れる
+    /// 合計ゼロの集合の数の最大値。同点のときは番号の小さい要素を先に取り除く
+    fn zero_sum_partition(open: &[&MemberBalance]) -> Vec<Vec<usize>> {
+        let n = open.len();
+        let full = (1usize << n) - 1;
+
+        let mut sum = vec![0i64; full + 1];
+        let mut best = vec![0usize; full + 1];
+        for mask in 1..=full {
+            let low = mask.trailing_zeros() as usize;
+            sum[mask] = sum[mask & (mask - 1)] + open[low].balance.value;
+            let removed = (0..n)
+                .filter(|&i| mask & (1 << i) != 0)
+                .map(|i| best[mask ^ (1 << i)])
+                .max()
+                .unwrap_or(0);
+            best[mask] = removed + usize::from(sum[mask] == 0);
+        }
+
+        let mut groups = Vec::new();
+        let mut current = Vec::new();
+        let mut mask = full;
+        while mask != 0 {
+            let gain = usize::from(sum[mask] == 0);
+            let i = (0..n)
+                .find(|&i| mask & (1 << i) != 0 && best[mask ^ (1 << i)] + gain == best[mask])
+                .expect("best[mask] is attained by removing some element");
+            current.push(i);
+            mask ^= 1 << i;
+            if sum[mask] == 0 {
+                current.sort_unstable();
+                groups.push(std::mem::take(&mut current));
+            }
+        }
+        groups.sort();
+        groups
+    }
+
+    /// 合計ゼロの組の中で、支払う側と受け取る側をユーザーID順に突き合わせて送金を作る
+    ///
+    /// 送金のたびに少なくとも1人の残高がゼロになるため、k 人なら k - 1 回以内で済む
+    fn settle_group(group: &[&MemberBalance]) -> Vec<Transfer> {
+        let mut debtors: Vec<(&UserId, i64)> = group
+            .iter()
+            .filter(|b| b.balance.is_negative())
+            .map(|b| (&b.user_id, -b.balance.value))
+            .collect();
+        let mut creditors: Vec<(&UserId, i64)> = group
+            .iter()
+            .filter(|b| b.balance.is_positive())
+            .map(|b| (&b.user_id, b.balance.value))
+            .collect();
+        let currency = match group.first() {
+            Some(b) => b.balance.currency.clone(),
+            None => return Vec::new(),
+        };
+
+        let mut transfers = Vec::new();
+        let (mut d, mut c) = (0, 0);
+        while d < debtors.len() && c < creditors.len() {
+            let value = debtors[d].1.min(creditors[c].1);
+            transfers.push(Transfer {
+                from: debtors[d].0.clone(),
+                to: creditors[c].0.clone(),
+                amount: Amount::new(value, currency.clone()),
+            });
+            debtors[d].1 -= value;
+            creditors[c].1 -= value;
+            if debtors[d].1 == 0 {
+                d += 1;
+            }
+            if creditors[c].1 == 0 {
+                c += 1;
+            }
+        }
+        transfers
+    }
+}
+
+/// クレジットカードの明細の集計結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardBillingSummary {
+    pub account_id: String,
+    /// 締め日の古い順
+    pub statements: Vec<CardStatement>,
+    /// まだ締められていない利用額（次回以降の請求に載る分）
+    pub unbilled: Amount,
+}
+
+/// 引き落とし口座からの引き落とし予定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledDebit {
+    pub card_account_id: String,
+    pub closing_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub amount: Amount,
+    /// 締め済みで金額が確定しているか（未確定の場合は現時点までの利用額）
+    pub is_confirmed: bool,
+}
+
+/// 口座の引き落とし予定の見通し
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebitProjection {
+    pub account_id: String,
+    /// 引き落とし日順
+    pub debits: Vec<ScheduledDebit>,
+    pub total: Amount,
+}
+
+/// クレジットカード明細計算サービス
+///
+/// カードの取引を取引日から締め日ごとの明細に振り分け、請求額と引き落とし日を求める
+pub struct CardStatementCalculator;
+
+impl CardStatementCalculator {
+    /// カードの取引を明細にまとめる（利用のない締めの明細は作らない）
+    ///
+    /// カードへの支払い（口座間移動）は明細に含めない。返金は請求額から差し引く
+    pub fn statements(
+        account: &Account,
+        transactions: &[Transaction],
+    ) -> DomainResult<Vec<CardStatement>> {
+        let cycle = account.billing_cycle.as_ref().ok_or_else(|| {
+            DomainError::Validation(format!(
+                "Account {} has no billing cycle",
+                account.account_id
+            ))
+        })?;
+
+        let mut usages: Vec<&Transaction> = transactions
+            .iter()
+            .filter(|t| account.is_affected_by(t) && !t.is_transfer())
+            .collect();
+        usages.sort_by(|a, b| {
+            (a.transaction_date, a.transaction_id.value())
+                .cmp(&(b.transaction_date, b.transaction_id.value()))
+        });
+
+        let zero = Amount::new(0, account.opening_balance.currency.clone());
+        let mut statements: Vec<CardStatement> = Vec::new();
+        for transaction in usages {
+            let closing_date = cycle.closing_date_for(transaction.transaction_date.date());
+            if statements
+                .last()
+                .is_none_or(|s| s.closing_date != closing_date)
+            {
+                statements.push(CardStatement {
+                    account_id: account.account_id.clone(),
+                    period_start: cycle.previous_closing_date(closing_date) + chrono::Days::new(1),
+                    closing_date,
+                    due_date: cycle.due_date_for(closing_date),
+                    total: zero.clone(),
+                    transaction_ids: Vec::new(),
+                });
+            }
+            let statement = statements.last_mut().unwrap();
+            // 利用は残高を減らす（負の収支）ため、符号を反転して請求額に加える
+            statement.total = statement.total.subtract(&transaction.signed_amount())?;
+            statement
+                .transaction_ids
+                .push(transaction.transaction_id.clone());
+        }
+        Ok(statements)
+    }
+
+    /// 明細と、指定日の時点でまだ締められていない利用額を求める
+    pub fn summarize(
+        account: &Account,
+        transactions: &[Transaction],
+        today: NaiveDate,
+    ) -> DomainResult<CardBillingSummary> {
+        let statements = Self::statements(account, transactions)?;
+        let mut unbilled = Amount::new(0, account.opening_balance.currency.clone());
+        for statement in statements.iter().filter(|s| !s.is_closed(today)) {
+            unbilled = unbilled.add(&statement.total)?;
+        }
+        Ok(CardBillingSummary {
+            account_id: account.account_id.clone(),
+            statements,
+            unbilled,
+        })
+    }
+
+    /// 口座から引き落とされる予定のカード請求を引き落とし日順に並べる
+    ///
+    /// この口座を引き落とし口座にしているカードの明細のうち、引き落とし日が `today` 以降で
+    /// 請求額が正のものを対象にする
+    pub fn upcoming_debits(
+        payment_account: &Account,
+        cards: &[Account],
+        transactions: &[Transaction],
+        today: NaiveDate,
+    ) -> DomainResult<DebitProjection> {
+        let mut debits = Vec::new();
+        for card in cards
+            .iter()
+            .filter(|card| card.payment_account_id() == Some(payment_account.account_id.as_str()))
+        {
+            for statement in Self::statements(card, transactions)? {
+                if statement.due_date >= today && statement.total.is_positive() {
+                    debits.push(ScheduledDebit {
+                        card_account_id: card.account_id.clone(),
+                        closing_date: statement.closing_date,
+                        due_date: statement.due_date,
+                        is_confirmed: statement.is_closed(today),
+                        amount: statement.total,
+                    });
+                }
+            }
+        }
+        debits.sort_by(|a, b| {
+            (a.due_date, &a.card_account_id).cmp(&(b.due_date, &b.card_account_id))
+        });
+
+        let mut total = Amount::new(0, payment_account.opening_balance.currency.clone());
+        for debit in &debits {
+            total = total.add(&debit.amount)?;
+        }
+        Ok(DebitProjection {
+            account_id: payment_account.account_id.clone(),
+            debits,
+            total,
+        })
     }
+}
+
+/// 収支に数える取引（口座間移動と立て替えを除く、`currency` の取引）
+fn counted<'a>(
+    currency: &'a str,
+    transactions: &'a [Transaction],
+) -> impl Iterator<Item = &'a Transaction> {
+    transactions.iter().filter(move |t| {
+        t.transaction_type == TransactionType::Real && t.amount.currency == currency
+    })
+}
+
+fn period_totals(currency: &str, transactions: &[Transaction]) -> PeriodTotals {
+    let mut income = 0;
+    let mut expense = 0;
+    for transaction in counted(currency, transactions) {
+        match transaction.direction {
+            TransactionDirection::Income => income += transaction.amount.value,
+            TransactionDirection::Expense => expense += transaction.amount.value,
+        }
+    }
+    PeriodTotals {
+        income: Amount::new(income, currency.to_string()),
+        expense: Amount::new(expense, currency.to_string()),
+    }
+}
+
+/// 分類の支出額（返金を差し引いた額）
+fn category_expense(
+    currency: &str,
+    transactions: &[Transaction],
+    category: &TransactionCategory,
+) -> i64 {
+    counted(currency, transactions)
+        .filter(|t| t.affects_budget() && t.category == *category)
+        .map(|t| t.amount.value)
+        .sum()
+}
+
+/// 月次集計の収支（口座間移動と立て替えを除く、`currency` の行）
+fn aggregate_totals<'a>(
+    currency: &str,
+    aggregates: impl IntoIterator<Item = &'a MonthlyAggregate>,
+) -> PeriodTotals {
+    let mut income = 0;
+    let mut expense = 0;
+    for aggregate in aggregates {
+        for ((transaction_type, direction), amount) in aggregate.totals_by_type(currency) {
+            match (transaction_type, direction) {
+                (TransactionType::Real, TransactionDirection::Income) => income += amount,
+                (TransactionType::Real, TransactionDirection::Expense) => expense += amount,
+                _ => {}
+            }
+        }
+    }
+    PeriodTotals {
+        income: Amount::new(income, currency.to_string()),
+        expense: Amount::new(expense, currency.to_string()),
+    }
+}
+
+/// 月次集計の分類ごとの支出（返金を差し引いた額）
+fn aggregate_expenses<'a>(
+    currency: &str,
+    aggregates: impl IntoIterator<Item = &'a MonthlyAggregate>,
+) -> BTreeMap<TransactionCategory, i64> {
+    let mut expenses = BTreeMap::new();
+    for aggregate in aggregates {
+        for (category, amount) in aggregate.expenses_by_category(currency) {
+            *expenses.entry(category).or_insert(0) += amount;
+        }
+    }
+    expenses
+}
+
+/// 支出全体に占める割合（1.0 で全額）
+fn expense_share(amount: i64, totals: &PeriodTotals) -> f64 {
+    if totals.expense.value > 0 {
+        amount as f64 / totals.expense.value as f64
+    } else {
+        0.0
+    }
+}
+
+/// 期間の収入と支出
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodTotals {
+    pub income: Amount,
+    /// 支出（返金を差し引いた額）
+    pub expense: Amount,
+}
+
+impl PeriodTotals {
+    /// 収支（収入 − 支出）
+    pub fn net(&self) -> DomainResult<Amount> {
+        self.income.subtract(&self.expense)
+    }
+}
+
+/// 分類ごとの月の支出
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryExpense {
+    pub category: TransactionCategory,
+    pub amount: Amount,
+    /// 前月の支出
+    pub previous_amount: Amount,
+    /// 月の支出全体に占める割合（1.0 で全額）
+    pub share: f64,
+}
+
+/// 月次レポート
+#[derive(Debug, Clone)]
+pub struct MonthlyReport {
+    pub month: PeriodWindow,
+    pub totals: PeriodTotals,
+    /// 前月の収支（前月比の基準）
+    pub previous: PeriodTotals,
+    /// 支出のあった分類（支出の多い順）
+    pub categories: Vec<CategoryExpense>,
+    /// 予算ごとの予算と実績
+    pub budgets: Vec<BudgetStatus>,
+    /// 月末時点で返済されていない立て替えの合計
+    pub outstanding_flow: Amount,
+}
+
+/// 月次レポートのうち、取引の明細から求める部分（予算実績・未回収の立て替え）の入力
+pub struct MonthlyReportDetails<'a> {
+    pub transactions: &'a [Transaction],
+    pub budgets: &'a [Budget],
+    pub settlements: &'a [Settlement],
+    pub categories: &'a CategoryTree,
+}
+
+/// 月次レポート計算サービス
+///
+/// ストレージに依存せず、ユーザーのタイムゾーンで月の収支・分類別支出・予算実績・
+/// 未回収の立て替えを求める。プロフィールの通貨の取引だけを集計する
+pub struct MonthlyReportCalculator;
+
+impl MonthlyReportCalculator {
+    /// `month_start` から始まる月のレポートを求める（`today` はユーザーのタイムゾーンでの今日）
+    ///
+    /// 収支と分類別支出は月次集計から求める。予算は月末（今月であれば今日）を含む期間で評価し、
+    /// 月末より後に作成した予算と月と重ならない期間指定の予算は含めない
+    pub fn calculate(
+        user: &UserProfile,
+        month_start: NaiveDate,
+        today: NaiveDate,
+        aggregates: &[MonthlyAggregate],
+        details: MonthlyReportDetails,
+    ) -> DomainResult<MonthlyReport> {
+        let month = PeriodWindow::months(month_start, 1, 1);
+        let previous_month = PeriodWindow::months(month.start - chrono::Days::new(1), 1, 1);
+        let current: Vec<&MonthlyAggregate> = aggregates
+            .iter()
+            .filter(|a| month.contains(a.month))
+            .collect();
+        let previous: Vec<&MonthlyAggregate> = aggregates
+            .iter()
+            .filter(|a| previous_month.contains(a.month))
+            .collect();
+        let totals = aggregate_totals(&user.currency, current.iter().copied());
+
+        let reference = Self::reference_date(&month, today);
+        let mut statuses = Vec::new();
+        for (budget, created_on) in
+            Self::budgets_in_month(user, &month, reference, details.budgets)?
+        {
+            statuses.push(BudgetStatus::evaluate(
+                budget.clone(),
+                details.transactions,
+                details.categories,
+                reference,
+                created_on,
+            )?);
+        }
+
+        Ok(MonthlyReport {
+            categories: Self::category_expenses(
+                &user.currency,
+                &aggregate_expenses(&user.currency, current),
+                &aggregate_expenses(&user.currency, previous.iter().copied()),
+                &totals,
+            ),
+            previous: aggregate_totals(&user.currency, previous),
+            outstanding_flow: Self::outstanding_flow(
+                user,
+                month.last_day(),
+                details.transactions,
+                details.settlements,
+            )?,
+            budgets: statuses,
+            totals,
+            month,
+        })
+    }
+
+    /// レポートに取引の明細（`MonthlyReportDetails::transactions`）が必要かどうか
+    ///
+    /// 月に評価する予算がなく、月末までに立て替えもなければ、月次集計だけでレポートを求められる
+    pub fn needs_transactions(
+        user: &UserProfile,
+        month_start: NaiveDate,
+        today: NaiveDate,
+        aggregates: &[MonthlyAggregate],
+        budgets: &[Budget],
+    ) -> DomainResult<bool> {
+        let month = PeriodWindow::months(month_start, 1, 1);
+        let reference = Self::reference_date(&month, today);
+        let has_flows = aggregates.iter().filter(|a| a.month < month.end).any(|a| {
+            a.totals_by_type(&user.currency)
+                .keys()
+                .any(|(transaction_type, _)| *transaction_type == TransactionType::Flow)
+        });
+        Ok(has_flows || !Self::budgets_in_month(user, &month, reference, budgets)?.is_empty())
+    }
+
+    /// 予算を評価する基準日（今月であれば今日、それ以外は月末）
+    fn reference_date(month: &PeriodWindow, today: NaiveDate) -> NaiveDate {
+        if month.contains(today) {
+            today
+        } else {
+            month.last_day()
+        }
+    }
+
+    /// 月のレポートに含める予算と、ユーザーのタイムゾーンでの作成日
+    fn budgets_in_month<'a>(
+        user: &UserProfile,
+        month: &PeriodWindow,
+        reference: NaiveDate,
+        budgets: &'a [Budget],
+    ) -> DomainResult<Vec<(&'a Budget, NaiveDate)>> {
+        let mut included = Vec::new();
+        for budget in budgets {
+            let created_on = user.local_date(budget.created_at)?;
+            let window = budget.window_containing(reference)?;
+            if created_on > month.last_day()
+                || window.end <= month.start
+                || window.start >= month.end
+            {
+                continue;
+            }
+            included.push((budget, created_on));
+        }
+        Ok(included)
+    }
+
+    fn category_expenses(
+        currency: &str,
+        current: &BTreeMap<TransactionCategory, i64>,
+        previous: &BTreeMap<TransactionCategory, i64>,
+        totals: &PeriodTotals,
+    ) -> Vec<CategoryExpense> {
+        let amount_of = |expenses: &BTreeMap<TransactionCategory, i64>,
+                         category: &TransactionCategory| {
+            expenses.get(category).copied().unwrap_or(0)
+        };
+        let mut expenses: Vec<CategoryExpense> = TransactionCategory::ALL
+            .into_iter()
+            .map(|category| {
+                let amount = amount_of(current, &category);
+                CategoryExpense {
+                    previous_amount: Amount::new(
+                        amount_of(previous, &category),
+                        currency.to_string(),
+                    ),
+                    share: expense_share(amount, totals),
+                    amount: Amount::new(amount, currency.to_string()),
+                    category,
+                }
+            })
+            .filter(|e| !e.amount.is_zero() || !e.previous_amount.is_zero())
+            .collect();
+        expenses.sort_by_key(|e| std::cmp::Reverse(e.amount.value));
+        expenses
+    }
+
+    /// `last_day` の終わりの時点で返済されていない立て替えの合計
+    ///
+    /// 立て替え取引の額から、その取引の精算で `last_day` までに返済された額を差し引く
+    fn outstanding_flow(
+        user: &UserProfile,
+        last_day: NaiveDate,
+        transactions: &[Transaction],
+        settlements: &[Settlement],
+    ) -> DomainResult<Amount> {
+        let mut outstanding = 0;
+        for flow in transactions.iter().filter(|t| {
+            t.transaction_type == TransactionType::Flow
+                && t.amount.currency == user.currency
+                && t.transaction_date.date() <= last_day
+        }) {
+            let mut repaid = 0;
+            for settlement in settlements.iter().filter(|s| {
+                s.creditor_user_id == user.user_id
+                    && s.transaction_id.as_ref() == Some(&flow.transaction_id)
+            }) {
+                for repayment in &settlement.repayments {
+                    if user.local_date(repayment.repaid_at)? <= last_day {
+                        repaid += repayment.amount.value;
+                    }
+                }
+            }
+            outstanding += (flow.amount.value - repaid).max(0);
+        }
+        Ok(Amount::new(outstanding, user.currency.clone()))
+    }
+}
+
+/// 分類ごとの支出と割合
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryShare {
+    pub category: TransactionCategory,
+    pub amount: Amount,
+    /// 区間の支出全体に占める割合（1.0 で全額）
+    pub share: f64,
+}
+
+/// 推移レポートの1区間
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendPoint {
+    /// 区間（集計範囲の端の区間は範囲内に切り詰める）
+    pub window: PeriodWindow,
+    pub totals: PeriodTotals,
+    /// 支出のあった分類（支出の多い順）
+    pub categories: Vec<CategoryShare>,
+}
+
+/// 推移レポート
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendReport {
+    pub granularity: ReportGranularity,
+    pub window: PeriodWindow,
+    /// 集計範囲全体の収支
+    pub totals: PeriodTotals,
+    /// 区間ごとの収支（古い順。取引のない区間も含む）
+    pub points: Vec<TrendPoint>,
+}
+
+/// 推移レポートの区間数の上限（日次で1年分）
+pub const MAX_TREND_POINTS: usize = 366;
+
+/// 推移レポート計算サービス
+///
+/// ストレージに依存せず、集計範囲を日・月・年の区間に分けて区間ごとの収支と分類別の割合を求める
+pub struct TrendReportCalculator;
+
+impl TrendReportCalculator {
+    /// `window` を `granularity` の区間に分けて `currency` の取引を集計する
+    pub fn calculate(
+        currency: &str,
+        granularity: ReportGranularity,
+        window: PeriodWindow,
+        transactions: &[Transaction],
+    ) -> DomainResult<TrendReport> {
+        let windows = Self::split(granularity, window)?;
+        let in_range = window.transactions_in(transactions);
+        let points = windows
+            .into_iter()
+            .map(|point| {
+                let transactions = point.transactions_in(&in_range);
+                let totals = period_totals(currency, &transactions);
+                TrendPoint {
+                    categories: Self::category_shares(currency, &totals, |category| {
+                        category_expense(currency, &transactions, category)
+                    }),
+                    window: point,
+                    totals,
+                }
+            })
+            .collect();
+        Ok(TrendReport {
+            granularity,
+            window,
+            totals: period_totals(currency, &in_range),
+            points,
+        })
+    }
+
+    /// 月単位の `window` を `granularity`（月次・年次）の区間に分け、月次集計を足し合わせて集計する
+    ///
+    /// 月の途中で区切る区間は月次集計から求められないため、日次と月単位でない範囲はエラーとする
+    pub fn from_aggregates(
+        currency: &str,
+        granularity: ReportGranularity,
+        window: PeriodWindow,
+        aggregates: &[MonthlyAggregate],
+    ) -> DomainResult<TrendReport> {
+        if granularity == ReportGranularity::Daily || !window.is_whole_months() {
+            return Err(DomainError::Validation(
+                "Monthly aggregates can only be split into whole months".to_string(),
+            ));
+        }
+        let windows = Self::split(granularity, window)?;
+        let months_in = |window: PeriodWindow| {
+            aggregates
+                .iter()
+                .filter(move |aggregate| window.contains(aggregate.month))
+        };
+        let points = windows
+            .into_iter()
+            .map(|point| {
+                let totals = aggregate_totals(currency, months_in(point));
+                let expenses = aggregate_expenses(currency, months_in(point));
+                TrendPoint {
+                    categories: Self::category_shares(currency, &totals, |category| {
+                        expenses.get(category).copied().unwrap_or(0)
+                    }),
+                    window: point,
+                    totals,
+                }
+            })
+            .collect();
+        Ok(TrendReport {
+            granularity,
+            window,
+            totals: aggregate_totals(currency, months_in(window)),
+            points,
+        })
+    }
+
+    /// 集計範囲を区間に分ける（区間数が上限を超える場合はエラー）
+    fn split(
+        granularity: ReportGranularity,
+        window: PeriodWindow,
+    ) -> DomainResult<Vec<PeriodWindow>> {
+        let mut windows = Vec::new();
+        let mut start = window.start;
+        while start < window.end {
+            if windows.len() == MAX_TREND_POINTS {
+                return Err(DomainError::Validation(format!(
+                    "Too many periods: at most {} points can be requested",
+                    MAX_TREND_POINTS
+                )));
+            }
+            let bucket = granularity.window_containing(start);
+            let point = PeriodWindow {
+                start,
+                end: bucket.end.min(window.end),
+            };
+            windows.push(point);
+            start = point.end;
+        }
+        Ok(windows)
+    }
+
+    /// 支出のあった分類と割合（`expense_of` は分類ごとの支出）
+    fn category_shares(
+        currency: &str,
+        totals: &PeriodTotals,
+        expense_of: impl Fn(&TransactionCategory) -> i64,
+    ) -> Vec<CategoryShare> {
+        let mut shares: Vec<CategoryShare> = TransactionCategory::ALL
+            .into_iter()
+            .filter_map(|category| {
+                let amount = expense_of(&category);
+                (amount != 0).then(|| CategoryShare {
+                    share: expense_share(amount, totals),
+                    amount: Amount::new(amount, currency.to_string()),
+                    category,
+                })
+            })
+            .collect();
+        shares.sort_by_key(|s| std::cmp::Reverse(s.amount.value));
+        shares
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::*;
+    use crate::infrastructure::memory::*;
+    use std::sync::Arc;
+
+    fn transaction(user_id: &str, amount: i64) -> Transaction {
+        Transaction::new(
+            UserId::new(user_id.to_string()),
+            TransactionType::Real,
+            Amount::jpy(amount),
+            "テスト".to_string(),
+            TransactionCategory::Food,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_user_service() {
+        let service = UserService::new(InMemoryUserRepository::new());
+        let mut user = UserProfile::new(UserId::new("user123".to_string()));
+        service.create_user(user.clone()).await.unwrap();
+
+        user.update(Some("花子".to_string()), None, None);
+        service.update_user(user).await.unwrap();
+
+        let found = service.get_user("user123").await.unwrap();
+        assert_eq!(found.display_name.as_deref(), Some("花子"));
+        assert!(matches!(
+            service.get_user("missing").await,
+            Err(DomainError::NotFound { entity: "User", .. })
+        ));
+        assert!(matches!(
+            service.create_user(found.clone()).await,
+            Err(DomainError::Conflict(_))
+        ));
 
-    pub async fn get_budgets(&self, user_id: &str) -> Result<Vec<Budget>> {
-        self.repository.find_by_user_id(user_id).await
+        let mut unknown_zone = found;
+        unknown_zone.update(None, None, Some("Mars/Olympus".to_string()));
+        assert!(matches!(
+            service.update_user(unknown_zone).await,
+            Err(DomainError::Validation(_))
+        ));
     }
 
-    pub async fn create_budget(&self, budget: Budget) -> Result<()> {
-        self.repository.save(budget).await
+    #[tokio::test]
+    async fn test_user_local_date() {
+        let service = UserService::new(InMemoryUserRepository::new());
+        let mut user = UserProfile::new(UserId::new("user123".to_string()));
+        user.update(None, None, Some("America/New_York".to_string()));
+        service.create_user(user.clone()).await.unwrap();
+        // 東京では4月1日、UTC とニューヨークではまだ3月31日
+        let now = "2025-03-31T23:00:00Z".parse().unwrap();
+
+        assert_eq!(
+            service.local_date(&user.user_id, now).await.unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap()
+        );
+        // プロフィールがなければ既定の東京
+        assert_eq!(
+            service
+                .local_date(&UserId::new("unknown".to_string()), now)
+                .await
+                .unwrap(),
+            NaiveDate::from_ymd_opt(2025, 4, 1).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transaction_service_lifecycle() {
+        let service = TransactionService::new(
+            InMemoryTransactionRepository::new(),
+            InMemoryAggregateRepository::new(),
+        );
+        let mut lunch = transaction("user123", 1000);
+        service.create_transaction(lunch.clone()).await.unwrap();
+        service
+            .create_transaction(transaction("other-user", 500))
+            .await
+            .unwrap();
+
+        lunch.update(Some("ランチ".to_string()), None);
+        service.update_transaction(lunch.clone()).await.unwrap();
+        let found = service
+            .get_transaction(&lunch.user_id, lunch.transaction_id.value())
+            .await
+            .unwrap();
+        let other = UserId::new("other-user".to_string());
+        assert!(matches!(
+            service
+                .get_transaction(&other, lunch.transaction_id.value())
+                .await,
+            Err(DomainError::Forbidden(_))
+        ));
+        assert_eq!(found.description, "ランチ");
+        assert_eq!(service.get_transactions("user123").await.unwrap().len(), 1);
+
+        service
+            .delete_transaction(&lunch.user_id, lunch.transaction_id.value())
+            .await
+            .unwrap();
+        assert!(service
+            .get_transactions("user123")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            service
+                .delete_transaction(&lunch.user_id, lunch.transaction_id.value())
+                .await,
+            Err(DomainError::NotFound { .. })
+        ));
+
+        // 負の収入は保存できない（返金は負の支出として記録する）
+        let mut refund = transaction("user123", -300);
+        service.create_transaction(refund.clone()).await.unwrap();
+        refund.direction = TransactionDirection::Income;
+        assert!(matches!(
+            service.update_transaction(refund).await,
+            Err(DomainError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_transaction_service_maintains_aggregates() {
+        let transactions = Arc::new(InMemoryTransactionRepository::new());
+        let aggregates = Arc::new(InMemoryAggregateRepository::new());
+        let service = TransactionService::new(transactions.clone(), aggregates.clone());
+        let user_id = UserId::new("user123".to_string());
+        // 取引がなくなった月の空の集計は、集計がないものとして比べる
+        let stored = || async {
+            let mut stored = aggregates.find_by_user_id("user123").await.unwrap();
+            stored.retain(|a| !a.is_empty());
+            stored
+        };
+        let expected = || async {
+            MonthlyAggregate::from_transactions(
+                &transactions.find_by_user_id("user123").await.unwrap(),
+            )
+        };
+
+        let mut lunch = food_on(1000, "2025-04-30");
+        service.create_transaction(lunch.clone()).await.unwrap();
+        service
+            .create_transaction(food_on(500, "2025-04-01"))
+            .await
+            .unwrap();
+        assert_eq!(stored().await, expected().await);
+        assert_eq!(stored().await[0].entries[0].amount, 1500);
+
+        // 翌月に移した取引は元の月から差し引かれる
+        lunch.transaction_date = TransactionDate::new("2025-05-01".parse().unwrap());
+        lunch.category = TransactionCategory::Entertainment;
+        service.update_transaction(lunch.clone()).await.unwrap();
+        assert_eq!(stored().await, expected().await);
+        assert_eq!(stored().await[0].entries[0].amount, 500);
+
+        let transfer = AccountTransfer::new(
+            user_id.clone(),
+            "bank".to_string(),
+            "wallet".to_string(),
+            Amount::jpy(20000),
+            "ATM引き出し".to_string(),
+        );
+        service.create_transfer(&transfer).await.unwrap();
+        service
+            .delete_transaction(&user_id, lunch.transaction_id.value())
+            .await
+            .unwrap();
+        assert_eq!(stored().await, expected().await);
+        service
+            .delete_transfer(&user_id, &transfer.transfer_id)
+            .await
+            .unwrap();
+        assert_eq!(stored().await, expected().await);
     }
 
-    pub async fn update_budget(&self, budget: Budget) -> Result<()> {
-        self.repository.update(budget).await
+    #[tokio::test]
+    async fn test_aggregate_rebuild_and_check() {
+        let transactions = Arc::new(InMemoryTransactionRepository::new());
+        let aggregates = Arc::new(InMemoryAggregateRepository::new());
+        let service = AggregateService::new(aggregates.clone(), transactions.clone());
+        let user_id = UserId::new("user123".to_string());
+        assert!(service.check(&user_id).await.unwrap().is_empty());
+
+        // 集計を通さずに書き込まれた取引と、取引のない月の集計
+        transactions
+            .save(food_on(1000, "2025-04-10"))
+            .await
+            .unwrap();
+        let stale = MonthlyAggregate::from_transactions(&[food_on(300, "2025-03-10")]);
+        aggregates.replace_all("user123", stale).await.unwrap();
+
+        let mismatches = service.check(&user_id).await.unwrap();
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!(mismatches[0].month, day("2025-03-01"));
+        assert_eq!(mismatches[0].expected, None);
+        assert_eq!(mismatches[1].month, day("2025-04-01"));
+        assert_eq!(mismatches[1].stored, None);
+
+        let rebuilt = service.rebuild(&user_id).await.unwrap();
+        assert_eq!(rebuilt.len(), 1);
+        assert_eq!(service.get_aggregates(&user_id).await.unwrap(), rebuilt);
+        assert!(service.check(&user_id).await.unwrap().is_empty());
     }
 
-    pub async fn delete_budget(&self, budget_id: &str) -> Result<()> {
-        self.repository.delete(budget_id).await
+    #[tokio::test]
+    async fn test_list_transactions_page_size() {
+        let service = TransactionService::new(
+            InMemoryTransactionRepository::new(),
+            InMemoryAggregateRepository::new(),
+        );
+        let user_id = UserId::new("user123".to_string());
+        for amount in [100, 200, 300] {
+            service
+                .create_transaction(transaction("user123", amount))
+                .await
+                .unwrap();
+        }
+
+        let page = service
+            .list_transactions(&user_id, &TransactionQuery::new(2))
+            .await
+            .unwrap();
+        assert_eq!(page.transactions.len(), 2);
+        assert!(page.next.is_some());
+
+        for limit in [0, MAX_PAGE_SIZE + 1] {
+            assert!(matches!(
+                service
+                    .list_transactions(&user_id, &TransactionQuery::new(limit))
+                    .await,
+                Err(DomainError::Validation(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_lifecycle() {
+        let service = TransactionService::new(
+            InMemoryTransactionRepository::new(),
+            InMemoryAggregateRepository::new(),
+        );
+        let user_id = UserId::new("user123".to_string());
+        let transfer = AccountTransfer::new(
+            user_id.clone(),
+            "bank".to_string(),
+            "wallet".to_string(),
+            Amount::jpy(20000),
+            "ATM引き出し".to_string(),
+        );
+        let saved = service.create_transfer(&transfer).await.unwrap();
+        assert_eq!(saved.len(), 2);
+
+        let (found, sides) = service
+            .get_transfer(&user_id, &transfer.transfer_id)
+            .await
+            .unwrap();
+        assert_eq!(found, transfer);
+        assert_eq!(sides[0].account_id.as_deref(), Some("bank"));
+        assert_eq!(sides[1].direction, TransactionDirection::Income);
+        let other = UserId::new("other-user".to_string());
+        assert!(matches!(
+            service.get_transfer(&other, &transfer.transfer_id).await,
+            Err(DomainError::NotFound { .. })
+        ));
+
+        // 片側だけは変更・削除できない
+        let mut outgoing = sides[0].clone();
+        outgoing.update(Some("変更".to_string()), None);
+        assert!(matches!(
+            service.update_transaction(outgoing).await,
+            Err(DomainError::Conflict(_))
+        ));
+        assert!(matches!(
+            service
+                .delete_transaction(&user_id, sides[1].transaction_id.value())
+                .await,
+            Err(DomainError::Conflict(_))
+        ));
+
+        service
+            .delete_transfer(&user_id, &transfer.transfer_id)
+            .await
+            .unwrap();
+        assert!(service
+            .get_transactions("user123")
+            .await
+            .unwrap()
+            .is_empty());
+
+        let mut same_account = transfer;
+        same_account.to_account_id = "bank".to_string();
+        assert!(matches!(
+            service.create_transfer(&same_account).await,
+            Err(DomainError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_budget_service_lifecycle() {
+        let service = BudgetService::new(
+            InMemoryBudgetRepository::new(),
+            InMemoryTransactionRepository::new(),
+            InMemoryUserRepository::new(),
+            InMemoryCategoryRepository::new(),
+        );
+        let mut budget = Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(30000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        service.create_budget(budget.clone()).await.unwrap();
+
+        budget
+            .update(None, Some(Amount::jpy(40000)), None, None, None, None)
+            .unwrap();
+        service.update_budget(budget.clone()).await.unwrap();
+        let found = service
+            .get_budget(&budget.user_id, &budget.budget_id)
+            .await
+            .unwrap();
+        assert_eq!(found.amount, Amount::jpy(40000));
+
+        budget.alert_threshold = 1.5;
+        assert!(matches!(
+            service.update_budget(budget.clone()).await,
+            Err(DomainError::Validation(_))
+        ));
+
+        service
+            .delete_budget(&budget.user_id, &budget.budget_id)
+            .await
+            .unwrap();
+        assert!(service.get_budgets("user123").await.unwrap().is_empty());
+    }
+
+    fn food_on(amount: i64, date: &str) -> Transaction {
+        let mut food = transaction("user123", amount);
+        food.transaction_date = TransactionDate::new(date.parse().unwrap());
+        food
+    }
+
+    #[test]
+    fn test_budget_status_evaluate() {
+        let mut budget = Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        budget.month_start_day = Some(25);
+        let transactions = [
+            food_on(3000, "2025-03-24"),
+            food_on(4000, "2025-03-25"),
+            food_on(5000, "2025-04-24"),
+            food_on(6000, "2025-04-25"),
+        ];
+
+        let created_on = day("2025-01-01");
+        let categories = CategoryTree::default();
+
+        let status = BudgetStatus::evaluate(
+            budget,
+            &transactions,
+            &categories,
+            day("2025-04-10"),
+            created_on,
+        )
+        .unwrap();
+        assert_eq!(status.window.start, day("2025-03-25"));
+        assert_eq!(status.window.end, day("2025-04-25"));
+        assert_eq!(status.spent, Amount::jpy(9000));
+        assert_eq!(status.remaining, Amount::jpy(1000));
+        assert_eq!(status.usage, 0.9);
+        assert!(status.should_alert);
+
+        // 超過分は負の残額になる
+        let status = BudgetStatus::evaluate(
+            status.budget,
+            &transactions,
+            &categories,
+            day("2025-05-01"),
+            created_on,
+        )
+        .unwrap();
+        assert_eq!(status.spent, Amount::jpy(6000));
+        assert!(!status.should_alert);
+        let status = BudgetStatus::evaluate(
+            status.budget,
+            &[food_on(12000, "2025-05-01")],
+            &categories,
+            day("2025-05-01"),
+            created_on,
+        )
+        .unwrap();
+        assert_eq!(status.remaining, Amount::jpy(-2000));
+        assert_eq!(status.carried_over, Amount::jpy(0));
+    }
+
+    #[test]
+    fn test_budget_status_rollover() {
+        let mut budget = Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(10000),
+            BudgetPeriod::Weekly,
+            0.8,
+        );
+        budget.rollover = true;
+        // 2025-03-03 は月曜日
+        let transactions = [
+            // 作成した週より前の支出は繰り越しに含めない
+            food_on(50000, "2025-02-28"),
+            food_on(4000, "2025-03-03"),
+            food_on(13000, "2025-03-12"),
+            food_on(2000, "2025-03-17"),
+        ];
+        let evaluate = |budget: &Budget, today: &str| {
+            BudgetStatus::evaluate(
+                budget.clone(),
+                &transactions,
+                &CategoryTree::default(),
+                day(today),
+                day("2025-03-05"),
+            )
+            .unwrap()
+        };
+
+        let status = evaluate(&budget, "2025-03-09");
+        assert_eq!(status.window.start, day("2025-03-03"));
+        assert_eq!(status.carried_over, Amount::jpy(0));
+        assert_eq!(status.remaining, Amount::jpy(6000));
+
+        // 1週目の使い残し 6000 を繰り越す
+        let status = evaluate(&budget, "2025-03-12");
+        assert_eq!(status.carried_over, Amount::jpy(6000));
+        assert_eq!(status.remaining, Amount::jpy(3000));
+        assert_eq!(status.usage, 0.7);
+        assert!(!status.should_alert);
+
+        // 2週目の超過 3000 を差し引く
+        let status = evaluate(&budget, "2025-03-17");
+        assert_eq!(status.carried_over, Amount::jpy(3000));
+        assert_eq!(status.remaining, Amount::jpy(11000));
+        assert_eq!(status.usage, 0.0);
+
+        budget.rollover = false;
+        let status = evaluate(&budget, "2025-03-17");
+        assert_eq!(status.carried_over, Amount::jpy(0));
+        assert_eq!(status.remaining, Amount::jpy(8000));
+    }
+
+    #[tokio::test]
+    async fn test_budget_status_uses_user_timezone() {
+        let users = std::sync::Arc::new(InMemoryUserRepository::new());
+        let transactions = std::sync::Arc::new(InMemoryTransactionRepository::new());
+        let service = BudgetService::new(
+            InMemoryBudgetRepository::new(),
+            transactions.clone(),
+            users.clone(),
+            InMemoryCategoryRepository::new(),
+        );
+        let user_id = UserId::new("user123".to_string());
+        let budget = Budget::new(
+            user_id.clone(),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        service.create_budget(budget.clone()).await.unwrap();
+        transactions
+            .save(food_on(3000, "2025-03-31"))
+            .await
+            .unwrap();
+        transactions
+            .save(food_on(5000, "2025-04-01"))
+            .await
+            .unwrap();
+
+        // UTCでは3月31日だが、東京（既定）では4月1日
+        let now = "2025-03-31T20:00:00Z".parse().unwrap();
+        let status = service
+            .get_status(&user_id, &budget.budget_id, now)
+            .await
+            .unwrap();
+        assert_eq!(status.window.start, day("2025-04-01"));
+        assert_eq!(status.spent, Amount::jpy(5000));
+
+        let mut user = UserProfile::new(user_id.clone());
+        user.update(None, None, Some("America/New_York".to_string()));
+        users.save(user).await.unwrap();
+        let status = service
+            .get_status(&user_id, &budget.budget_id, now)
+            .await
+            .unwrap();
+        assert_eq!(status.window.start, day("2025-03-01"));
+        assert_eq!(status.spent, Amount::jpy(3000));
+
+        let other = UserId::new("other-user".to_string());
+        assert!(matches!(
+            service.get_status(&other, &budget.budget_id, now).await,
+            Err(DomainError::Forbidden(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_budget_alerts() {
+        let transactions = std::sync::Arc::new(InMemoryTransactionRepository::new());
+        let service = BudgetService::new(
+            InMemoryBudgetRepository::new(),
+            transactions.clone(),
+            InMemoryUserRepository::new(),
+            InMemoryCategoryRepository::new(),
+        );
+        let user_id = UserId::new("user123".to_string());
+        let food = Budget::new(
+            user_id.clone(),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        let all = Budget::new(
+            user_id.clone(),
+            BudgetTarget::All,
+            Amount::jpy(100000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        service.create_budget(food.clone()).await.unwrap();
+        service.create_budget(all.clone()).await.unwrap();
+        transactions
+            .save(food_on(8000, "2025-04-10"))
+            .await
+            .unwrap();
+        // 予算と異なる通貨の支出があっても判定できる
+        let mut in_dollars = food_on(120, "2025-04-11");
+        in_dollars.amount = Amount::new(120, "USD".to_string());
+        transactions.save(in_dollars).await.unwrap();
+
+        let now = "2025-04-15T00:00:00Z".parse().unwrap();
+        let alerts = service.get_alerts(&user_id, None, now).await.unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].budget.budget_id, food.budget_id);
+
+        // 保存前の予算も判定できる
+        let mut lowered = all.clone();
+        lowered.amount = Amount::jpy(9000);
+        let alerts = service
+            .get_alerts(&user_id, Some(lowered), now)
+            .await
+            .unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].budget.budget_id, all.budget_id);
+
+        // 次の月には支出がない
+        let later = "2025-05-15T00:00:00Z".parse().unwrap();
+        assert!(service
+            .get_alerts(&user_id, None, later)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_budget_alert_service() {
+        let budgets = Arc::new(InMemoryBudgetRepository::new());
+        let transactions = Arc::new(InMemoryTransactionRepository::new());
+        // 通知先が復旧するまでは失敗し、送信できたアラートの閾値を順に記録する
+        let available = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let delivered = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut notifier = crate::domain::notifiers::MockBudgetAlertNotifier::new();
+        let (up, sent) = (available.clone(), delivered.clone());
+        notifier.expect_notify().times(3).returning(move |alert| {
+            if !up.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(anyhow::anyhow!("webhook is unavailable"));
+            }
+            sent.lock().unwrap().push(alert.threshold);
+            Ok(())
+        });
+        let service = BudgetAlertService::new(
+            BudgetService::new(
+                budgets.clone(),
+                transactions.clone(),
+                InMemoryUserRepository::new(),
+                InMemoryCategoryRepository::new(),
+            ),
+            InMemoryBudgetAlertRepository::new(),
+            notifier,
+        );
+        let user_id = UserId::new("user123".to_string());
+        let mut food = Budget::new(
+            user_id.clone(),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        budgets.save(food.clone()).await.unwrap();
+        transactions
+            .save(food_on(8500, "2025-04-10"))
+            .await
+            .unwrap();
+
+        let now = "2025-04-15T00:00:00Z".parse().unwrap();
+        let raised = service.evaluate(&user_id, None, now).await.unwrap();
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].budget_id, food.budget_id);
+        assert_eq!(
+            raised[0].period_start,
+            "2025-04-01".parse::<NaiveDate>().unwrap()
+        );
+        assert_eq!(
+            raised[0].period_end,
+            "2025-04-30".parse::<NaiveDate>().unwrap()
+        );
+        assert_eq!(raised[0].usage, 0.85);
+
+        // 通知に失敗したアラートは未送信のまま残る
+        assert!(service.deliver_pending(&user_id, now).await.is_err());
+        assert_eq!(
+            service.get_history(&user_id).await.unwrap()[0].delivered_at,
+            None
+        );
+
+        // 同じ期間・閾値では再び発生しない
+        transactions.save(food_on(500, "2025-04-12")).await.unwrap();
+        assert!(service
+            .evaluate(&user_id, None, now)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // 閾値を変えると新しい閾値で改めて発生する
+        food.alert_threshold = 0.7;
+        let later = "2025-04-20T00:00:00Z".parse().unwrap();
+        let raised = service
+            .evaluate(&user_id, Some(food.clone()), later)
+            .await
+            .unwrap();
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].usage, 0.9);
+
+        let history = service.get_history(&user_id).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].threshold, 0.7);
+        assert_eq!(history[1].threshold, 0.8);
+
+        // 復旧後に未送信のアラートを古い順に送り、送信済みのものは送り直さない
+        available.store(true, std::sync::atomic::Ordering::SeqCst);
+        service.deliver_pending(&user_id, later).await.unwrap();
+        service.deliver_pending(&user_id, later).await.unwrap();
+        assert_eq!(*delivered.lock().unwrap(), [0.8, 0.7]);
+        assert!(service
+            .get_history(&user_id)
+            .await
+            .unwrap()
+            .iter()
+            .all(|alert| alert.delivered_at == Some(later)));
+        assert!(service
+            .get_history(&UserId::new("other".to_string()))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    fn category_service() -> CategoryService<
+        InMemoryCategoryRepository,
+        std::sync::Arc<InMemoryTransactionRepository>,
+        InMemoryUserRepository,
+    > {
+        category_service_with(std::sync::Arc::new(InMemoryTransactionRepository::new()))
+    }
+
+    fn category_service_with(
+        transactions: std::sync::Arc<InMemoryTransactionRepository>,
+    ) -> CategoryService<
+        InMemoryCategoryRepository,
+        std::sync::Arc<InMemoryTransactionRepository>,
+        InMemoryUserRepository,
+    > {
+        CategoryService::new(
+            InMemoryCategoryRepository::new(),
+            transactions,
+            InMemoryUserRepository::new(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_category_service_lifecycle() {
+        let service = category_service();
+        let user_id = UserId::new("user123".to_string());
+
+        // 初回の取得で既定のカテゴリが用意され、2回目以降は同じものを返す
+        let defaults = service.get_categories(&user_id).await.unwrap();
+        assert_eq!(defaults.len(), TransactionCategory::ALL.len());
+        assert_eq!(
+            service.get_categories(&user_id).await.unwrap().len(),
+            defaults.len()
+        );
+        let food = defaults
+            .iter()
+            .find(|c| c.kind == TransactionCategory::Food)
+            .unwrap();
+
+        // 子カテゴリは親の分類を引き継ぐ
+        let mut dining = Category::new(
+            user_id.clone(),
+            "外食".to_string(),
+            TransactionCategory::Other,
+        );
+        dining.parent_id = Some(food.category_id.clone());
+        let dining = service.create_category(dining).await.unwrap();
+        assert_eq!(dining.kind, TransactionCategory::Food);
+
+        let mut cafe = Category::new(
+            user_id.clone(),
+            "カフェ".to_string(),
+            TransactionCategory::Food,
+        );
+        cafe.parent_id = Some(dining.category_id.clone());
+        let cafe = service.create_category(cafe).await.unwrap();
+
+        let mut moved = dining.clone();
+        moved.update(None, None, None, Some(Some(cafe.category_id.clone())), None);
+        assert!(matches!(
+            service.update_category(moved).await,
+            Err(DomainError::Validation(_))
+        ));
+
+        let other = UserId::new("other-user".to_string());
+        assert!(matches!(
+            service.get_category(&other, &dining.category_id).await,
+            Err(DomainError::Forbidden(_))
+        ));
+        let mut foreign_parent =
+            Category::new(other.clone(), "外食".to_string(), TransactionCategory::Food);
+        foreign_parent.parent_id = Some(food.category_id.clone());
+        assert!(matches!(
+            service.create_category(foreign_parent).await,
+            Err(DomainError::Validation(_))
+        ));
+
+        // アーカイブしたカテゴリは新しい取引に使えない
+        assert!(service
+            .ensure_usable(&user_id, &cafe.category_id)
+            .await
+            .is_ok());
+        let mut archived = cafe.clone();
+        archived.update(None, None, None, None, Some(true));
+        service.update_category(archived).await.unwrap();
+        assert!(matches!(
+            service.ensure_usable(&user_id, &cafe.category_id).await,
+            Err(DomainError::Validation(_))
+        ));
+        assert!(matches!(
+            service.ensure_usable(&other, &dining.category_id).await,
+            Err(DomainError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_category_spending_rolls_up_subcategories() {
+        let transactions = std::sync::Arc::new(InMemoryTransactionRepository::new());
+        let service = category_service_with(transactions.clone());
+        let user_id = UserId::new("user123".to_string());
+        let defaults = service.get_categories(&user_id).await.unwrap();
+        let food = defaults
+            .iter()
+            .find(|c| c.kind == TransactionCategory::Food)
+            .unwrap();
+        let mut dining = Category::new(
+            user_id.clone(),
+            "外食".to_string(),
+            TransactionCategory::Food,
+        );
+        dining.parent_id = Some(food.category_id.clone());
+        let dining = service.create_category(dining).await.unwrap();
+
+        let mut lunch = food_on(1200, "2025-04-03");
+        lunch.assign_category(&dining);
+        let mut income = food_on(50000, "2025-04-10");
+        income.direction = TransactionDirection::Income;
+        let mut dollars = food_on(30, "2025-04-10");
+        dollars.amount = Amount::new(30, "USD".to_string());
+        let mut bus = food_on(230, "2025-04-05");
+        bus.category = TransactionCategory::Transportation;
+        for transaction in [
+            food_on(800, "2025-04-01"),
+            lunch,
+            income,
+            dollars,
+            bus,
+            food_on(9999, "2025-05-01"),
+        ] {
+            transactions.save(transaction).await.unwrap();
+        }
+
+        let window = PeriodWindow::custom(day("2025-04-01"), day("2025-04-30"));
+        let spending = service.get_spending(&user_id, &window).await.unwrap();
+        let summary: Vec<(&str, i64, i64)> = spending
+            .iter()
+            .map(|s| (s.category.name.as_str(), s.own.value, s.total.value))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("食費", 800, 2000),
+                ("外食", 1200, 1200),
+                ("交通費", 230, 230)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_budget_for_user_categories() {
+        let categories = std::sync::Arc::new(InMemoryCategoryRepository::new());
+        let transactions = std::sync::Arc::new(InMemoryTransactionRepository::new());
+        let service = BudgetService::new(
+            InMemoryBudgetRepository::new(),
+            transactions.clone(),
+            InMemoryUserRepository::new(),
+            categories.clone(),
+        );
+        let user_id = UserId::new("user123".to_string());
+        let defaults = Category::defaults(&user_id);
+        let food = defaults[0].clone();
+        categories.save_all(defaults).await.unwrap();
+        let mut dining = Category::new(
+            user_id.clone(),
+            "外食".to_string(),
+            TransactionCategory::Food,
+        );
+        dining.parent_id = Some(food.category_id.clone());
+        categories.save(dining.clone()).await.unwrap();
+
+        let mut budget = Budget::new(
+            user_id.clone(),
+            BudgetTarget::UserCategories {
+                category_ids: vec!["missing".to_string()],
+            },
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        assert!(matches!(
+            service.create_budget(budget.clone()).await,
+            Err(DomainError::Validation(_))
+        ));
+        budget.target = BudgetTarget::UserCategories {
+            category_ids: vec![food.category_id.clone()],
+        };
+        service.create_budget(budget.clone()).await.unwrap();
+
+        let mut lunch = food_on(1500, "2025-04-03");
+        lunch.assign_category(&dining);
+        transactions.save(lunch).await.unwrap();
+        transactions.save(food_on(500, "2025-04-04")).await.unwrap();
+        let status = service
+            .get_status(
+                &user_id,
+                &budget.budget_id,
+                "2025-04-10T00:00:00Z".parse().unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(status.spent, Amount::jpy(2000));
+    }
+
+    #[tokio::test]
+    async fn test_settlement_service_lifecycle() {
+        let transactions = InMemoryTransactionRepository::new();
+        let mut flow = transaction("alice", 3000);
+        flow.transaction_type = TransactionType::Flow;
+        transactions.save(flow.clone()).await.unwrap();
+        let service = SettlementService::new(InMemorySettlementRepository::new(), transactions);
+        let alice = UserId::new("alice".to_string());
+        let bob = UserId::new("bob".to_string());
+        let carol = UserId::new("carol".to_string());
+        let flow_id = flow.transaction_id.value();
+
+        // 他人の取引からは作れない
+        assert!(matches!(
+            service
+                .create_from_transaction(&bob, flow_id, alice.clone(), None, None)
+                .await,
+            Err(DomainError::Forbidden(_))
+        ));
+
+        let settlement = service
+            .create_from_transaction(&alice, flow_id, bob.clone(), Some(Amount::jpy(1000)), None)
+            .await
+            .unwrap();
+        // 金額を省略すると残りの全額になる
+        let rest = service
+            .create_from_transaction(&alice, flow_id, carol.clone(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(rest.amount, Amount::jpy(2000));
+        assert!(matches!(
+            service
+                .create_from_transaction(&alice, flow_id, carol.clone(), None, None)
+                .await,
+            Err(DomainError::Conflict(_))
+        ));
+
+        // 債務者は参照できるが変更できない。無関係のユーザーは参照もできない
+        let id = &settlement.settlement_id;
+        assert!(service.get_settlement(&bob, id).await.is_ok());
+        assert!(matches!(
+            service.complete(&bob, id).await,
+            Err(DomainError::Forbidden(_))
+        ));
+        assert!(matches!(
+            service.get_settlement(&carol, id).await,
+            Err(DomainError::Forbidden(_))
+        ));
+
+        let repaid = service.repay(&alice, id, Amount::jpy(400)).await.unwrap();
+        assert_eq!(repaid.status, SettlementStatus::PartiallyRepaid);
+        let completed = service.complete(&alice, id).await.unwrap();
+        assert_eq!(completed.status, SettlementStatus::Completed);
+        assert_eq!(service.get_settlement(&bob, id).await.unwrap(), completed);
+
+        // 取り消した分は再び精算できる
+        service.cancel(&alice, &rest.settlement_id).await.unwrap();
+        service
+            .create_from_transaction(&alice, flow_id, carol, None, None)
+            .await
+            .unwrap();
+        assert_eq!(service.get_settlements("alice").await.unwrap().len(), 3);
+        assert_eq!(service.get_settlements("bob").await.unwrap().len(), 1);
+    }
+
+    fn group(members: &[&str]) -> Group {
+        let mut group = Group::new(
+            "旅行".to_string(),
+            String::new(),
+            UserId::new(members[0].to_string()),
+        );
+        for member in &members[1..] {
+            group.add_member(UserId::new(member.to_string()));
+        }
+        group
+    }
+
+    fn group_transaction(group: &Group, paid_by: &str, amount: Amount) -> GroupTransaction {
+        GroupTransaction::new(
+            group,
+            UserId::new(paid_by.to_string()),
+            amount,
+            "テスト".to_string(),
+            TransactionCategory::Food,
+            SplitRule::Equal(group.members.clone()),
+        )
+        .unwrap()
+    }
+
+    fn balance(user_id: &str, value: i64) -> MemberBalance {
+        MemberBalance {
+            user_id: UserId::new(user_id.to_string()),
+            balance: Amount::jpy(value),
+        }
+    }
+
+    #[test]
+    fn test_group_settlement_balances() {
+        let group = group(&["carol", "alice", "bob"]);
+        let plan = GroupSettlementCalculator::calculate(
+            &group,
+            &[
+                group_transaction(&group, "alice", Amount::jpy(1000)),
+                group_transaction(&group, "bob", Amount::jpy(300)),
+            ],
+        )
+        .unwrap();
+
+        // 1000円の端数1円は支払者の alice、300円は割り切れる
+        assert_eq!(
+            plan.balances,
+            vec![
+                balance("alice", 566),
+                balance("bob", -133),
+                balance("carol", -433)
+            ]
+        );
+        assert_eq!(plan.transfers.len(), 2);
+        assert!(plan.transfers.iter().all(|t| t.to.value() == "alice"));
+
+        // 他のグループの取引・通貨の異なる取引は計算できない
+        let other = group_transaction(&self::group(&["alice"]), "alice", Amount::jpy(100));
+        assert!(matches!(
+            GroupSettlementCalculator::calculate(&group, &[other]),
+            Err(DomainError::Validation(_))
+        ));
+        assert!(matches!(
+            GroupSettlementCalculator::calculate(
+                &group,
+                &[
+                    group_transaction(&group, "alice", Amount::jpy(100)),
+                    group_transaction(&group, "bob", Amount::new(100, "USD".to_string())),
+                ]
+            ),
+            Err(DomainError::CurrencyMismatch { .. })
+        ));
+
+        // 取引後にグループを抜けたメンバーの残高も残る
+        let mut left = group.clone();
+        left.remove_member(&UserId::new("bob".to_string()));
+        let plan = GroupSettlementCalculator::calculate(
+            &left,
+            &[group_transaction(&group, "alice", Amount::jpy(300))],
+        )
+        .unwrap();
+        assert_eq!(plan.balances[1], balance("bob", -100));
+    }
+
+    #[test]
+    fn test_group_settlement_minimizes_transfers() {
+        // ID順に突き合わせると3回になるが、(a, d) と (b, c) に分ければ2回で済む
+        let balances = [
+            balance("a", -3),
+            balance("b", -4),
+            balance("c", 4),
+            balance("d", 3),
+        ];
+        let transfers = GroupSettlementCalculator::transfers(&balances);
+        assert_eq!(
+            transfers,
+            vec![
+                Transfer {
+                    from: UserId::new("a".to_string()),
+                    to: UserId::new("d".to_string()),
+                    amount: Amount::jpy(3),
+                },
+                Transfer {
+                    from: UserId::new("b".to_string()),
+                    to: UserId::new("c".to_string()),
+                    amount: Amount::jpy(4),
+                },
+            ]
+        );
+        assert!(GroupSettlementCalculator::transfers(&[balance("a", 0)]).is_empty());
+    }
+
+    fn card(closing_day: u32, payment_day: u32, payment_account_id: &str) -> Account {
+        let mut card = Account::new(
+            UserId::new("user123".to_string()),
+            "カード".to_string(),
+            AccountType::CreditCard,
+            Amount::jpy(0),
+        );
+        card.billing_cycle = Some(BillingCycle {
+            closing_day,
+            payment_day,
+            payment_month_offset: 1,
+            payment_account_id: Some(payment_account_id.to_string()),
+        });
+        card
+    }
+
+    fn card_usage(card: &Account, amount: i64, date: &str) -> Transaction {
+        let mut usage = transaction("user123", amount);
+        usage.account_id = Some(card.account_id.clone());
+        usage.transaction_date = TransactionDate::new(date.parse().unwrap());
+        usage
+    }
+
+    fn day(date: &str) -> NaiveDate {
+        date.parse().unwrap()
+    }
+
+    #[test]
+    fn test_card_statements() {
+        let card = card(15, 10, "bank");
+        let mut payoff = AccountTransfer::new(
+            card.user_id.clone(),
+            "bank".to_string(),
+            card.account_id.clone(),
+            Amount::jpy(5000),
+            "カードの引き落とし".to_string(),
+        );
+        payoff.transaction_date = TransactionDate::new("2025-03-10".parse().unwrap());
+        let mut transactions = vec![
+            card_usage(&card, 3000, "2025-03-15"),
+            card_usage(&card, 2000, "2025-03-01"),
+            card_usage(&card, 4000, "2025-03-16"),
+            // 返金は請求額から差し引く
+            card_usage(&card, -1000, "2025-04-02"),
+            // 他の口座の取引
+            transaction("user123", 9999),
+        ];
+        transactions.extend(payoff.transactions());
+
+        let summary =
+            CardStatementCalculator::summarize(&card, &transactions, day("2025-03-20")).unwrap();
+        let statements = &summary.statements;
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0].period_start, day("2025-02-16"));
+        assert_eq!(statements[0].closing_date, day("2025-03-15"));
+        assert_eq!(statements[0].due_date, day("2025-04-10"));
+        assert_eq!(statements[0].total, Amount::jpy(5000));
+        assert_eq!(
+            statements[0].transaction_ids,
+            vec![
+                transactions[1].transaction_id.clone(),
+                transactions[0].transaction_id.clone()
+            ]
+        );
+        assert_eq!(statements[1].closing_date, day("2025-04-15"));
+        assert_eq!(statements[1].total, Amount::jpy(3000));
+        assert!(statements[0].is_closed(day("2025-03-20")));
+        assert!(!statements[1].is_closed(day("2025-03-20")));
+        assert_eq!(summary.unbilled, Amount::jpy(3000));
+
+        // 締め日当日はまだ締められていない
+        let summary =
+            CardStatementCalculator::summarize(&card, &transactions, day("2025-03-15")).unwrap();
+        assert_eq!(summary.unbilled, Amount::jpy(8000));
+
+        let mut wallet = card.clone();
+        wallet.billing_cycle = None;
+        assert!(matches!(
+            CardStatementCalculator::statements(&wallet, &transactions),
+            Err(DomainError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_upcoming_debits_from_one_bank_account() {
+        let bank = Account::new(
+            UserId::new("user123".to_string()),
+            "銀行".to_string(),
+            AccountType::Bank,
+            Amount::jpy(100000),
+        );
+        let first = card(15, 10, &bank.account_id);
+        let second = card(31, 27, &bank.account_id);
+        let other_bank = card(15, 10, "other-bank");
+        let transactions = vec![
+            card_usage(&first, 5000, "2025-02-20"),
+            card_usage(&first, 3000, "2025-03-20"),
+            card_usage(&second, 7000, "2025-03-05"),
+            card_usage(&other_bank, 1000, "2025-03-05"),
+        ];
+
+        let projection = CardStatementCalculator::upcoming_debits(
+            &bank,
+            &[first.clone(), second.clone(), other_bank],
+            &transactions,
+            day("2025-03-20"),
+        )
+        .unwrap();
+        let debits: Vec<_> = projection
+            .debits
+            .iter()
+            .map(|d| {
+                (
+                    d.card_account_id.as_str(),
+                    d.due_date,
+                    d.amount.value,
+                    d.is_confirmed,
+                )
+            })
+            .collect();
+        assert_eq!(
+            debits,
+            vec![
+                (first.account_id.as_str(), day("2025-04-10"), 5000, true),
+                (second.account_id.as_str(), day("2025-04-27"), 7000, false),
+                (first.account_id.as_str(), day("2025-05-10"), 3000, false),
+            ]
+        );
+        assert_eq!(projection.total, Amount::jpy(15000));
+
+        // 引き落とし日を過ぎた請求は含めない
+        let projection = CardStatementCalculator::upcoming_debits(
+            &bank,
+            &[first, second],
+            &transactions,
+            day("2025-04-11"),
+        )
+        .unwrap();
+        assert_eq!(projection.debits.len(), 2);
+        assert_eq!(projection.total, Amount::jpy(10000));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_group_settlement_nets_to_zero(
+            member_count in 1usize..8,
+            payments in proptest::collection::vec(
+                (
+                    0usize..8,
+                    100i64..1_000_000,
+                    0usize..4,
+                    1u32..256,
+                    proptest::collection::vec(1u64..10, 8),
+                ),
+                0..20,
+            ),
+        ) {
+            let members: Vec<String> = (0..member_count).map(|i| format!("user{}", i)).collect();
+            let group = group(&members.iter().map(String::as_str).collect::<Vec<_>>());
+            let transactions: Vec<GroupTransaction> = payments
+                .iter()
+                .map(|(payer, value, method, mask, weights)| {
+                    // mask のビットが立っているメンバーが負担する（いなければ全員）
+                    let mut participants: Vec<(UserId, u64)> = group
+                        .members
+                        .iter()
+                        .zip(weights)
+                        .enumerate()
+                        .filter(|(i, _)| mask & (1 << i) != 0)
+                        .map(|(_, (id, &w))| (id.clone(), w))
+                        .collect();
+                    if participants.is_empty() {
+                        participants = group
+                            .members
+                            .iter()
+                            .cloned()
+                            .zip(weights.iter().copied())
+                            .collect();
+                    }
+                    let amount = Amount::jpy(*value);
+                    let weights: Vec<u64> = participants.iter().map(|(_, w)| *w).collect();
+                    let split_rule = match method {
+                        0 => SplitRule::Equal(participants.into_iter().map(|(id, _)| id).collect()),
+                        1 => SplitRule::Shares(participants),
+                        2 => {
+                            let percentages = Amount::jpy(FULL_SPLIT_PERCENTAGE as i64)
+                                .allocate(&weights)
+                                .unwrap();
+                            SplitRule::Percentages(
+                                participants
+                                    .into_iter()
+                                    .zip(percentages)
+                                    .map(|((id, _), p)| (id, p.value as u32))
+                                    .collect(),
+                            )
+                        }
+                        _ => {
+                            let amounts = amount.allocate(&weights).unwrap();
+                            SplitRule::Exact(
+                                participants.into_iter().map(|(id, _)| id).zip(amounts).collect(),
+                            )
+                        }
+                    };
+                    GroupTransaction::new(
+                        &group,
+                        UserId::new(members[payer % member_count].clone()),
+                        amount,
+                        "テスト".to_string(),
+                        TransactionCategory::Food,
+                        split_rule,
+                    )
+                    .unwrap()
+                })
+                .collect();
+
+            // 各取引の負担額の合計は取引額と一致する
+            for transaction in &transactions {
+                proptest::prop_assert_eq!(
+                    transaction.participants.iter().map(|p| p.share_amount.value).sum::<i64>(),
+                    transaction.amount.value
+                );
+            }
+
+            let plan = GroupSettlementCalculator::calculate(&group, &transactions).unwrap();
+            proptest::prop_assert_eq!(
+                plan.balances.iter().map(|b| b.balance.value).sum::<i64>(),
+                0
+            );
+
+            // 送金を反映すると全員の残高がゼロになる
+            let mut remaining: std::collections::HashMap<&UserId, i64> = plan
+                .balances
+                .iter()
+                .map(|b| (&b.user_id, b.balance.value))
+                .collect();
+            for transfer in &plan.transfers {
+                proptest::prop_assert!(transfer.amount.is_positive());
+                proptest::prop_assert_ne!(&transfer.from, &transfer.to);
+                *remaining.get_mut(&transfer.from).unwrap() += transfer.amount.value;
+                *remaining.get_mut(&transfer.to).unwrap() -= transfer.amount.value;
+            }
+            proptest::prop_assert!(remaining.values().all(|&v| v == 0));
+
+            let open = plan.balances.iter().filter(|b| !b.balance.is_zero()).count();
+            proptest::prop_assert!(plan.transfers.len() < open.max(1));
+
+            // メンバーや取引の並び順に依存しない
+            let mut reordered = group.clone();
+            reordered.members.reverse();
+            let reversed: Vec<GroupTransaction> = transactions.iter().rev().cloned().collect();
+            proptest::prop_assert_eq!(
+                GroupSettlementCalculator::calculate(&reordered, &reversed).unwrap(),
+                plan
+            );
+        }
+    }
+
+    fn report_transaction(
+        transaction_type: TransactionType,
+        direction: TransactionDirection,
+        category: TransactionCategory,
+        amount: i64,
+        date: &str,
+    ) -> Transaction {
+        let mut transaction = food_on(amount, date);
+        transaction.transaction_type = transaction_type;
+        transaction.direction = direction;
+        transaction.category = category;
+        transaction
+    }
+
+    #[test]
+    fn test_monthly_report_totals() {
+        use TransactionCategory::*;
+        use TransactionDirection::*;
+        use TransactionType::*;
+        let user = UserProfile::new(UserId::new("user123".to_string()));
+        let mut dollars = food_on(30, "2025-04-02");
+        dollars.amount = Amount::new(30, "USD".to_string());
+        let transactions = [
+            report_transaction(Real, Income, Other, 300000, "2025-04-25"),
+            food_on(5000, "2025-04-01"),
+            food_on(-1000, "2025-04-30"),
+            report_transaction(Real, Expense, Transportation, 2000, "2025-04-10"),
+            // 立て替え・口座間移動・他の通貨の取引は収支に含めない
+            report_transaction(Flow, Expense, Food, 3000, "2025-04-12"),
+            report_transaction(Transfer, Expense, Other, 10000, "2025-04-12"),
+            dollars,
+            report_transaction(Real, Income, Other, 250000, "2025-03-25"),
+            food_on(2000, "2025-03-31"),
+            report_transaction(Real, Expense, Shopping, 8000, "2025-03-15"),
+            food_on(9000, "2025-05-01"),
+        ];
+
+        let report = MonthlyReportCalculator::calculate(
+            &user,
+            day("2025-04-01"),
+            day("2025-05-10"),
+            &MonthlyAggregate::from_transactions(&transactions),
+            MonthlyReportDetails {
+                transactions: &transactions,
+                budgets: &[],
+                settlements: &[],
+                categories: &CategoryTree::default(),
+            },
+        )
+        .unwrap();
+        assert_eq!(report.month.start, day("2025-04-01"));
+        assert_eq!(report.month.last_day(), day("2025-04-30"));
+        assert_eq!(report.totals.income, Amount::jpy(300000));
+        assert_eq!(report.totals.expense, Amount::jpy(6000));
+        assert_eq!(report.totals.net().unwrap(), Amount::jpy(294000));
+        assert_eq!(report.previous.income, Amount::jpy(250000));
+        assert_eq!(report.previous.expense, Amount::jpy(10000));
+
+        let categories: Vec<(TransactionCategory, i64, i64)> = report
+            .categories
+            .iter()
+            .map(|c| (c.category.clone(), c.amount.value, c.previous_amount.value))
+            .collect();
+        assert_eq!(
+            categories,
+            vec![
+                (Food, 4000, 2000),
+                (Transportation, 2000, 0),
+                (Shopping, 0, 8000)
+            ]
+        );
+        assert!((report.categories[0].share - 4000.0 / 6000.0).abs() < 1e-9);
+        assert_eq!(report.categories[2].share, 0.0);
+        assert_eq!(report.outstanding_flow, Amount::jpy(3000));
+    }
+
+    #[test]
+    fn test_monthly_report_budgets_and_flows() {
+        let user = UserProfile::new(UserId::new("user123".to_string()));
+        let budget = |period: BudgetPeriod, created_at: &str| {
+            let mut budget = Budget::new(
+                user.user_id.clone(),
+                BudgetTarget::category(TransactionCategory::Food),
+                Amount::jpy(10000),
+                period,
+                0.8,
+            );
+            budget.created_at = created_at.parse().unwrap();
+            budget
+        };
+        let monthly = budget(BudgetPeriod::Monthly, "2025-03-01T00:00:00Z");
+        let weekly = budget(BudgetPeriod::Weekly, "2025-03-01T00:00:00Z");
+        // 東京では5月1日に作成した予算
+        let later = budget(BudgetPeriod::Monthly, "2025-04-30T16:00:00Z");
+        let mut january = budget(BudgetPeriod::Custom, "2025-01-01T00:00:00Z");
+        january.start_date = Some(day("2025-01-01"));
+        january.end_date = Some(day("2025-01-31"));
+        let budgets = [monthly.clone(), weekly.clone(), later, january];
+
+        let mut flow = food_on(3000, "2025-04-12");
+        flow.transaction_type = TransactionType::Flow;
+        let mut may_flow = food_on(5000, "2025-05-02");
+        may_flow.transaction_type = TransactionType::Flow;
+        let transactions = [
+            food_on(4000, "2025-04-08"),
+            food_on(1000, "2025-04-29"),
+            flow.clone(),
+            may_flow,
+        ];
+        let mut settlement =
+            Settlement::from_transaction(&flow, UserId::new("bob".to_string()), None, None)
+                .unwrap();
+        // 2回目の返済は東京では5月1日
+        for (amount, repaid_at) in [
+            (1000, "2025-04-20T00:00:00Z"),
+            (500, "2025-04-30T16:00:00Z"),
+        ] {
+            settlement.repayments.push(Repayment {
+                amount: Amount::jpy(amount),
+                repaid_at: repaid_at.parse().unwrap(),
+            });
+        }
+        let settlements = [settlement];
+
+        let aggregates = MonthlyAggregate::from_transactions(&transactions);
+        let calculate = |today: &str| {
+            MonthlyReportCalculator::calculate(
+                &user,
+                day("2025-04-01"),
+                day(today),
+                &aggregates,
+                MonthlyReportDetails {
+                    transactions: &transactions,
+                    budgets: &budgets,
+                    settlements: &settlements,
+                    categories: &CategoryTree::default(),
+                },
+            )
+            .unwrap()
+        };
+
+        // 過去の月は月末を含む期間で評価する
+        let report = calculate("2025-05-10");
+        let evaluated: Vec<(&str, NaiveDate, i64)> = report
+            .budgets
+            .iter()
+            .map(|s| (s.budget.budget_id.as_str(), s.window.start, s.spent.value))
+            .collect();
+        assert_eq!(
+            evaluated,
+            vec![
+                (monthly.budget_id.as_str(), day("2025-04-01"), 5000),
+                (weekly.budget_id.as_str(), day("2025-04-28"), 1000),
+            ]
+        );
+        assert_eq!(report.outstanding_flow, Amount::jpy(2000));
+
+        // 今月は今日を含む期間で評価する
+        let report = calculate("2025-04-10");
+        assert_eq!(report.budgets[1].window.start, day("2025-04-07"));
+        assert_eq!(report.budgets[1].spent, Amount::jpy(4000));
+
+        // 予算実績と立て替えのない月は月次集計だけで求められる
+        let needs_transactions = |month: &str, budgets: &[Budget]| {
+            MonthlyReportCalculator::needs_transactions(
+                &user,
+                day(month),
+                day("2025-05-10"),
+                &aggregates,
+                budgets,
+            )
+            .unwrap()
+        };
+        assert!(needs_transactions("2025-04-01", &budgets));
+        assert!(needs_transactions("2025-04-01", &[]));
+        assert!(!needs_transactions("2025-03-01", &[]));
+        assert!(needs_transactions("2025-03-01", &budgets[..1]));
+        assert!(!needs_transactions("2025-03-01", &budgets[2..3]));
+    }
+
+    #[tokio::test]
+    async fn test_report_service_uses_user_timezone() {
+        let users = InMemoryUserRepository::new();
+        let mut user = UserProfile::new(UserId::new("user123".to_string()));
+        user.update(None, None, Some("America/New_York".to_string()));
+        users.save(user.clone()).await.unwrap();
+        let transactions = InMemoryTransactionRepository::new();
+        let aggregates = InMemoryAggregateRepository::new();
+        let mut salary = food_on(300000, "2025-04-25");
+        salary.direction = TransactionDirection::Income;
+        aggregates
+            .replace_all(
+                "user123",
+                MonthlyAggregate::from_transactions(&[salary.clone()]),
+            )
+            .await
+            .unwrap();
+        transactions.save(salary).await.unwrap();
+        let budgets = InMemoryBudgetRepository::new();
+        let mut budget = Budget::new(
+            user.user_id.clone(),
+            BudgetTarget::All,
+            Amount::jpy(10000),
+            BudgetPeriod::Weekly,
+            0.8,
+        );
+        // UTCでは5月1日だが、ニューヨークではまだ4月30日に作成した予算
+        budget.created_at = "2025-05-01T01:00:00Z".parse().unwrap();
+        budgets.save(budget.clone()).await.unwrap();
+        let service = ReportService::new(
+            transactions,
+            budgets,
+            InMemorySettlementRepository::new(),
+            users,
+            InMemoryCategoryRepository::new(),
+            aggregates,
+        );
+
+        let report = service
+            .get_monthly_report(
+                &user.user_id,
+                day("2025-04-01"),
+                "2025-05-01T02:00:00Z".parse().unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(report.totals.income, Amount::jpy(300000));
+        assert_eq!(report.budgets.len(), 1);
+        assert_eq!(report.budgets[0].budget.budget_id, budget.budget_id);
+    }
+
+    #[test]
+    fn test_trend_report_points() {
+        use TransactionCategory::*;
+        use TransactionDirection::*;
+        use TransactionType::*;
+        let transactions = [
+            report_transaction(Real, Income, Other, 300000, "2025-03-25"),
+            food_on(3000, "2025-03-03"),
+            report_transaction(Real, Expense, Transportation, 1000, "2025-03-31"),
+            food_on(2000, "2025-04-01"),
+            // 集計範囲外
+            food_on(9000, "2025-05-01"),
+        ];
+
+        let report = TrendReportCalculator::calculate(
+            "JPY",
+            ReportGranularity::Monthly,
+            PeriodWindow::custom(day("2025-02-15"), day("2025-04-30")),
+            &transactions,
+        )
+        .unwrap();
+        assert_eq!(report.totals.expense, Amount::jpy(6000));
+        let spans: Vec<_> = report
+            .points
+            .iter()
+            .map(|p| (p.window.start, p.window.last_day()))
+            .collect();
+        // 範囲の端の区間は範囲内に切り詰める
+        assert_eq!(
+            spans,
+            [
+                (day("2025-02-15"), day("2025-02-28")),
+                (day("2025-03-01"), day("2025-03-31")),
+                (day("2025-04-01"), day("2025-04-30")),
+            ]
+        );
+        assert_eq!(report.points[0].totals.expense, Amount::jpy(0));
+        assert!(report.points[0].categories.is_empty());
+        let march = &report.points[1];
+        assert_eq!(march.totals.net().unwrap(), Amount::jpy(296000));
+        assert_eq!(march.categories.len(), 2);
+        assert_eq!(march.categories[0].category, Food);
+        assert_eq!(march.categories[0].share, 0.75);
+        assert_eq!(march.categories[1].share, 0.25);
+
+        let daily = TrendReportCalculator::calculate(
+            "JPY",
+            ReportGranularity::Daily,
+            PeriodWindow::months(day("2025-03-01"), 1, 1),
+            &transactions,
+        )
+        .unwrap();
+        assert_eq!(daily.points.len(), 31);
+        assert_eq!(daily.points[2].totals.expense, Amount::jpy(3000));
+        assert_eq!(daily.points[30].totals.expense, Amount::jpy(1000));
+
+        let yearly = TrendReportCalculator::calculate(
+            "JPY",
+            ReportGranularity::Yearly,
+            PeriodWindow::custom(day("2024-01-01"), day("2025-12-31")),
+            &transactions,
+        )
+        .unwrap();
+        assert_eq!(yearly.points.len(), 2);
+        assert_eq!(yearly.points[1].totals.expense, Amount::jpy(15000));
+    }
+
+    #[test]
+    fn test_trend_report_limits_points() {
+        let daily = |end: &str| {
+            TrendReportCalculator::calculate(
+                "JPY",
+                ReportGranularity::Daily,
+                PeriodWindow::custom(day("2024-01-01"), day(end)),
+                &[],
+            )
+        };
+        assert_eq!(daily("2024-12-31").unwrap().points.len(), MAX_TREND_POINTS);
+        assert!(matches!(
+            daily("2025-01-01"),
+            Err(DomainError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_trend_report_from_aggregates() {
+        let mut dollars = food_on(30, "2025-03-02");
+        dollars.amount = Amount::new(30, "USD".to_string());
+        let mut flow = food_on(4000, "2025-03-10");
+        flow.transaction_type = TransactionType::Flow;
+        let mut salary = food_on(300000, "2025-03-25");
+        salary.direction = TransactionDirection::Income;
+        let mut bus = food_on(1000, "2025-03-31");
+        bus.category = TransactionCategory::Transportation;
+        let transactions = [
+            salary,
+            food_on(3000, "2025-03-03"),
+            bus,
+            food_on(-500, "2025-04-01"),
+            food_on(2000, "2025-04-15"),
+            dollars,
+            flow,
+            food_on(9000, "2024-12-31"),
+        ];
+        let aggregates = MonthlyAggregate::from_transactions(&transactions);
+
+        // 月単位の範囲では、月次集計から取引と同じ推移を求める
+        for (granularity, window) in [
+            (
+                ReportGranularity::Monthly,
+                PeriodWindow::custom(day("2025-02-01"), day("2025-04-30")),
+            ),
+            (
+                ReportGranularity::Yearly,
+                PeriodWindow::custom(day("2024-01-01"), day("2025-12-31")),
+            ),
+        ] {
+            assert_eq!(
+                TrendReportCalculator::from_aggregates("JPY", granularity, window, &aggregates)
+                    .unwrap(),
+                TrendReportCalculator::calculate("JPY", granularity, window, &transactions)
+                    .unwrap()
+            );
+        }
+        let monthly = TrendReportCalculator::from_aggregates(
+            "JPY",
+            ReportGranularity::Monthly,
+            PeriodWindow::custom(day("2025-03-01"), day("2025-04-30")),
+            &aggregates,
+        )
+        .unwrap();
+        assert_eq!(monthly.points[0].totals.expense, Amount::jpy(4000));
+        assert_eq!(monthly.points[1].totals.expense, Amount::jpy(1500));
+
+        // 月の途中で区切る範囲と日次は月次集計からは求めない
+        for (granularity, window) in [
+            (
+                ReportGranularity::Monthly,
+                PeriodWindow::custom(day("2025-02-15"), day("2025-04-30")),
+            ),
+            (
+                ReportGranularity::Daily,
+                PeriodWindow::months(day("2025-03-01"), 1, 1),
+            ),
+        ] {
+            assert!(matches!(
+                TrendReportCalculator::from_aggregates("JPY", granularity, window, &aggregates),
+                Err(DomainError::Validation(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trend_report_defaults_to_recent_months() {
+        let users = InMemoryUserRepository::new();
+        let mut user = UserProfile::new(UserId::new("user123".to_string()));
+        user.update(None, None, Some("America/New_York".to_string()));
+        users.save(user.clone()).await.unwrap();
+        let transactions = InMemoryTransactionRepository::new();
+        let aggregates = InMemoryAggregateRepository::new();
+        let saved = [food_on(1000, "2024-05-10"), food_on(2000, "2025-04-30")];
+        for transaction in &saved {
+            transactions.save(transaction.clone()).await.unwrap();
+        }
+        aggregates
+            .replace_all("user123", MonthlyAggregate::from_transactions(&saved))
+            .await
+            .unwrap();
+        let service = ReportService::new(
+            transactions,
+            InMemoryBudgetRepository::new(),
+            InMemorySettlementRepository::new(),
+            users,
+            InMemoryCategoryRepository::new(),
+            aggregates,
+        );
+
+        // UTCでは5月1日だが、ニューヨークではまだ4月30日
+        let report = service
+            .get_trend_report(
+                &user.user_id,
+                ReportGranularity::Monthly,
+                None,
+                "2025-05-01T02:00:00Z".parse().unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(report.points.len(), 12);
+        assert_eq!(report.window.start, day("2024-05-01"));
+        assert_eq!(report.points[0].totals.expense, Amount::jpy(1000));
+        assert_eq!(report.points[11].totals.expense, Amount::jpy(2000));
     }
 }
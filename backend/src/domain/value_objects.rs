@@ -1,4 +1,6 @@
-use serde::{Deserialize, Serialize};
+use crate::domain::errors::{DomainError, DomainResult};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt;
 
 /// 金額を表す値オブジェクト
@@ -19,32 +21,33 @@ impl Amount {
         Self::new(value, "JPY".to_string())
     }
 
-    pub fn add(&self, other: &Amount) -> Result<Amount, String> {
-        if self.currency != other.currency {
-            return Err(format!(
-                "Currency mismatch: {} != {}",
-                self.currency, other.currency
-            ));
-        }
+    pub fn add(&self, other: &Amount) -> DomainResult<Amount> {
+        self.ensure_same_currency(other)?;
         Ok(Amount::new(
             self.value + other.value,
             self.currency.clone(),
         ))
     }
 
-    pub fn subtract(&self, other: &Amount) -> Result<Amount, String> {
-        if self.currency != other.currency {
-            return Err(format!(
-                "Currency mismatch: {} != {}",
-                self.currency, other.currency
-            ));
-        }
+    pub fn subtract(&self, other: &Amount) -> DomainResult<Amount> {
+        self.ensure_same_currency(other)?;
         Ok(Amount::new(
             self.value - other.value,
             self.currency.clone(),
         ))
     }
 
+    /// 通貨が一致しない金額同士の演算を防ぐ
+    pub fn ensure_same_currency(&self, other: &Amount) -> DomainResult<()> {
+        if self.currency != other.currency {
+            return Err(DomainError::currency_mismatch(
+                &self.currency,
+                &other.currency,
+            ));
+        }
+        Ok(())
+    }
+
     pub fn is_positive(&self) -> bool {
         self.value > 0
     }
@@ -56,6 +59,44 @@ impl Amount {
     pub fn is_zero(&self) -> bool {
         self.value == 0
     }
+
+    /// 金額を重みに比例して配分する（合計は常に元の金額と一致する）
+    ///
+    /// 最小単位未満の端数は最大剰余法で配る。剰余が同じ場合は先の要素を優先するため、
+    /// 結果は重みの並び順だけで決まる
+    pub fn allocate(&self, weights: &[u64]) -> DomainResult<Vec<Amount>> {
+        let total_weight: u128 = weights.iter().map(|&w| w as u128).sum();
+        if total_weight == 0 {
+            return Err(DomainError::Validation(
+                "Allocation weights must not all be zero".to_string(),
+            ));
+        }
+
+        // 負の金額は絶対値を配分してから符号を戻す
+        let magnitude = self.value.unsigned_abs() as u128;
+        let mut shares: Vec<u128> = weights
+            .iter()
+            .map(|&w| magnitude * w as u128 / total_weight)
+            .collect();
+        let mut remainder = magnitude - shares.iter().sum::<u128>();
+
+        let mut order: Vec<usize> = (0..weights.len()).collect();
+        // 安定ソートなので、剰余が同じ要素は元の順序のまま
+        order.sort_by_key(|&i| std::cmp::Reverse(magnitude * weights[i] as u128 % total_weight));
+        for i in order {
+            if remainder == 0 {
+                break;
+            }
+            shares[i] += 1;
+            remainder -= 1;
+        }
+
+        let sign = self.value.signum();
+        Ok(shares
+            .into_iter()
+            .map(|share| Amount::new(sign * share as i64, self.currency.clone()))
+            .collect())
+    }
 }
 
 impl fmt::Display for Amount {
@@ -124,9 +165,99 @@ impl GroupId {
     }
 }
 
+/// 取引日を表す値オブジェクト
+///
+/// 取引日はユーザーのタイムゾーンでの暦日で、時刻を持たない。保存や並べ替えには
+/// その日の UTC の0時の日時を使い、時刻付きの値を読み込んだ場合もその日付に切り詰める。
+/// 日付を取り出すときにタイムゾーンの変換をしないため、どこで判定しても同じ日になる
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(transparent)]
+pub struct TransactionDate(DateTime<Utc>);
+
+impl TransactionDate {
+    pub fn new(date: NaiveDate) -> Self {
+        Self(date.and_time(NaiveTime::MIN).and_utc())
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.0.date_naive()
+    }
+
+    /// 取引日の UTC の0時
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+impl From<NaiveDate> for TransactionDate {
+    fn from(date: NaiveDate) -> Self {
+        Self::new(date)
+    }
+}
+
+impl<'de> Deserialize<'de> for TransactionDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let timestamp = DateTime::<Utc>::deserialize(deserializer)?;
+        Ok(Self::new(timestamp.date_naive()))
+    }
+}
+
+impl fmt::Display for TransactionDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.date())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_allocate() {
+        let values =
+            |amounts: Vec<Amount>| amounts.into_iter().map(|a| a.value).collect::<Vec<_>>();
+
+        // 端数は先のメンバーから1円ずつ
+        assert_eq!(
+            values(Amount::jpy(1000).allocate(&[1, 1, 1]).unwrap()),
+            vec![334, 333, 333]
+        );
+        assert_eq!(
+            values(Amount::jpy(-1000).allocate(&[1, 1, 1]).unwrap()),
+            vec![-334, -333, -333]
+        );
+        // 剰余の大きいものが優先される
+        assert_eq!(
+            values(Amount::jpy(100).allocate(&[1, 2]).unwrap()),
+            vec![33, 67]
+        );
+        assert_eq!(
+            values(Amount::jpy(10).allocate(&[0, 3]).unwrap()),
+            vec![0, 10]
+        );
+        assert!(Amount::jpy(10).allocate(&[0, 0]).is_err());
+        assert!(Amount::jpy(10).allocate(&[]).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_allocate_preserves_total(
+            value in -10_000_000i64..10_000_000,
+            weights in proptest::collection::vec(0u64..1000, 1..10),
+        ) {
+            prop_assume!(weights.iter().any(|&w| w > 0));
+            let shares = Amount::jpy(value).allocate(&weights).unwrap();
+            let total_weight: u64 = weights.iter().sum();
+
+            prop_assert_eq!(shares.iter().map(|s| s.value).sum::<i64>(), value);
+            for (share, &weight) in shares.iter().zip(&weights) {
+                // 各配分は比例配分した値との差が1円未満
+                let exact = value as f64 * weight as f64 / total_weight as f64;
+                prop_assert!((share.value as f64 - exact).abs() < 1.0);
+            }
+        }
+    }
 
     #[test]
     fn test_amount_operations() {
@@ -152,7 +283,25 @@ mod tests {
         let jpy = Amount::jpy(1000);
         let usd = Amount::new(1000, "USD".to_string());
 
-        assert!(jpy.add(&usd).is_err());
+        assert!(matches!(
+            jpy.add(&usd),
+            Err(DomainError::CurrencyMismatch { .. })
+        ));
         assert!(jpy.subtract(&usd).is_err());
     }
+
+    #[test]
+    fn test_transaction_date_has_no_time_of_day() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let transaction_date = TransactionDate::new(date);
+        assert_eq!(
+            serde_json::to_value(transaction_date).unwrap(),
+            "2025-03-01T00:00:00Z"
+        );
+
+        // 時刻付きで保存された値は UTC での日付に切り詰める
+        let stored: TransactionDate = serde_json::from_str("\"2025-03-01T09:30:00Z\"").unwrap();
+        assert_eq!(stored, transaction_date);
+        assert_eq!(stored.date(), date);
+    }
 }
@@ -1,32 +1,256 @@
 // HTTPハンドラー
 // API エンドポイントの実装
 
+use crate::application::dto::*;
+use crate::application::AppState;
+use crate::auth::{authenticate, AuthError, AuthUser};
+use crate::domain::entities::Budget;
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::UserId;
 use axum::{
-    extract::{Path, Query},
-    http::StatusCode,
-    response::Json,
-    routing::{get, post, put, delete},
+    async_trait,
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        FromRequest, FromRequestParts, Path, Query, Request, State,
+    },
+    http::request::Parts,
+    http::{header, StatusCode},
+    middleware,
+    response::{IntoResponse, Json, Response},
+    routing::{get, post, put},
     Router,
 };
+use chrono::Utc;
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use validator::{Validate, ValidationErrors};
 
 /// ルーターの作成
-pub fn create_router() -> Router {
+///
+/// `/health` 以外のエンドポイントは Bearer JWT による認証が必要
+pub fn create_router(state: AppState) -> Router {
+    let api = Router::new()
+        .route(
+            "/api/users/profile",
+            get(get_user_profile)
+                .post(create_user_profile)
+                .put(update_user_profile),
+        )
+        .route(
+            "/api/transactions",
+            get(get_transactions).post(create_transaction),
+        )
+        .route(
+            "/api/transactions/:transaction_id",
+            get(get_transaction)
+                .put(update_transaction)
+                .delete(delete_transaction),
+        )
+        .route("/api/transfers", post(create_transfer))
+        .route(
+            "/api/transfers/:transfer_id",
+            get(get_transfer).delete(delete_transfer),
+        )
+        .route("/api/budgets", get(get_budgets).post(create_budget))
+        .route(
+            "/api/budgets/:budget_id",
+            put(update_budget).delete(delete_budget),
+        )
+        .route("/api/budgets/:budget_id/status", get(get_budget_status))
+        .route("/api/budget-alerts", get(get_budget_alerts))
+        .route("/api/categories", get(get_categories).post(create_category))
+        .route(
+            "/api/categories/:category_id",
+            get(get_category).put(update_category),
+        )
+        .route("/api/reports/monthly", get(get_monthly_report))
+        .route("/api/reports/trends", get(get_trend_report))
+        .route("/api/reports/category-spending", get(get_category_spending))
+        .route("/api/accounts", get(get_accounts).post(create_account))
+        .route(
+            "/api/accounts/:account_id",
+            get(get_account).put(update_account).delete(delete_account),
+        )
+        .route(
+            "/api/accounts/:account_id/balance",
+            get(get_account_balance),
+        )
+        .route(
+            "/api/accounts/:account_id/statements",
+            get(get_card_statements),
+        )
+        .route(
+            "/api/accounts/:account_id/upcoming-debits",
+            get(get_upcoming_debits),
+        )
+        .route(
+            "/api/settlements",
+            get(get_settlements).post(create_settlement),
+        )
+        .route("/api/settlements/:settlement_id", get(get_settlement))
+        .route(
+            "/api/settlements/:settlement_id/repayments",
+            post(repay_settlement),
+        )
+        .route(
+            "/api/settlements/:settlement_id/complete",
+            post(complete_settlement),
+        )
+        .route(
+            "/api/settlements/:settlement_id/cancel",
+            post(cancel_settlement),
+        )
+        .route(
+            "/api/groups/:group_id/transactions",
+            get(get_group_transactions).post(create_group_transaction),
+        )
+        .route(
+            "/api/groups/:group_id/settlements/calculate",
+            post(calculate_group_settlements),
+        )
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate));
+
     Router::new()
         .route("/health", get(health_check))
-        .route("/api/users/:user_id", get(get_user))
-        .route("/api/users", post(create_user))
-        .route("/api/users/:user_id/transactions", get(get_transactions))
-        .route("/api/transactions", post(create_transaction))
-        .route("/api/transactions/:transaction_id", put(update_transaction))
-        .route("/api/transactions/:transaction_id", delete(delete_transaction))
-        .route("/api/users/:user_id/budgets", get(get_budgets))
-        .route("/api/budgets", post(create_budget))
-        .route("/api/budgets/:budget_id", put(update_budget))
-        .route("/api/budgets/:budget_id", delete(delete_budget))
+        .merge(api)
+        .with_state(state)
+}
+
+/// APIエラー
+///
+/// `ErrorResponse` スキーマ（`code` / `message` / `details`）の JSON として返す
+#[derive(Debug)]
+pub enum ApiError {
+    /// ドメイン層のエラー
+    Domain(DomainError),
+    /// リクエストの入力検証エラー（項目ごとの内容を `details` に入れる）
+    InvalidRequest(ValidationErrors),
+    /// JSON として解釈できないリクエストボディ
+    MalformedBody(JsonRejection),
+    /// 解釈できないクエリパラメーター
+    MalformedQuery(QueryRejection),
+    /// Bearer トークンが無い、または検証に失敗した
+    Unauthorized(AuthError),
+}
+
+/// ドメインエラーに対応する HTTP ステータス
+fn status_of(err: &DomainError) -> StatusCode {
+    match err {
+        DomainError::NotFound { .. } => StatusCode::NOT_FOUND,
+        DomainError::Validation(_) => StatusCode::BAD_REQUEST,
+        DomainError::CurrencyMismatch { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+        DomainError::Conflict(_) => StatusCode::CONFLICT,
+        DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
+        DomainError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, body) = match self {
+            ApiError::Domain(DomainError::Internal(err)) => {
+                eprintln!("Internal server error: {:?}", err);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "code": "INTERNAL_ERROR", "message": "Internal server error" }),
+                )
+            }
+            ApiError::Domain(err) => (
+                status_of(&err),
+                json!({ "code": err.code(), "message": err.to_string() }),
+            ),
+            ApiError::InvalidRequest(errors) => (
+                StatusCode::BAD_REQUEST,
+                json!({
+                    "code": "VALIDATION_ERROR",
+                    "message": "Request validation failed",
+                    "details": errors,
+                }),
+            ),
+            ApiError::MalformedBody(rejection) => (
+                rejection.status(),
+                json!({ "code": "MALFORMED_REQUEST", "message": rejection.body_text() }),
+            ),
+            ApiError::MalformedQuery(rejection) => (
+                rejection.status(),
+                json!({ "code": "MALFORMED_REQUEST", "message": rejection.body_text() }),
+            ),
+            ApiError::Unauthorized(err) => {
+                return (
+                    StatusCode::UNAUTHORIZED,
+                    [(header::WWW_AUTHENTICATE, "Bearer")],
+                    Json(json!({ "code": "UNAUTHORIZED", "message": err.to_string() })),
+                )
+                    .into_response();
+            }
+        };
+        (status, Json(body)).into_response()
+    }
 }
 
+impl From<DomainError> for ApiError {
+    fn from(err: DomainError) -> Self {
+        ApiError::Domain(err)
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        ApiError::Domain(DomainError::Internal(err))
+    }
+}
+
+impl From<AuthError> for ApiError {
+    fn from(err: AuthError) -> Self {
+        ApiError::Unauthorized(err)
+    }
+}
+
+impl From<ValidationErrors> for ApiError {
+    fn from(err: ValidationErrors) -> Self {
+        ApiError::InvalidRequest(err)
+    }
+}
+
+/// `Json` の拒否レスポンスも `ErrorResponse` 形式で返す JSON エクストラクター
+pub struct ApiJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(ApiError::MalformedBody)?;
+        Ok(Self(value))
+    }
+}
+
+/// `Query` の拒否レスポンスも `ErrorResponse` 形式で返すクエリエクストラクター
+pub struct ApiQuery<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    Query<T>: FromRequestParts<S, Rejection = QueryRejection>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(ApiError::MalformedQuery)?;
+        Ok(Self(value))
+    }
+}
+
+type ApiResult<T> = Result<T, ApiError>;
+
 /// ヘルスチェック
 async fn health_check() -> Json<Value> {
     Json(json!({
@@ -35,108 +259,2488 @@ async fn health_check() -> Json<Value> {
     }))
 }
 
-/// ユーザー取得
-async fn get_user(Path(user_id): Path<String>) -> Result<Json<Value>, StatusCode> {
-    // 実装予定
-    Ok(Json(json!({
-        "user_id": user_id,
-        "message": "User retrieval not implemented yet"
-    })))
+/// ユーザープロフィール取得
+async fn get_user_profile(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+) -> ApiResult<Json<UserProfileResponse>> {
+    let user = state
+        .user_use_case
+        .get_user_profile(user_id.value())
+        .await?;
+    Ok(Json(user.into()))
 }
 
-/// ユーザー作成
-async fn create_user(Json(payload): Json<Value>) -> Result<Json<Value>, StatusCode> {
-    // 実装予定
-    Ok(Json(json!({
-        "message": "User creation not implemented yet",
-        "data": payload
-    })))
+/// ユーザープロフィール作成（初回ログイン時）
+async fn create_user_profile(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiJson(payload): ApiJson<UpdateUserProfileRequest>,
+) -> ApiResult<(StatusCode, Json<UserProfileResponse>)> {
+    payload.validate()?;
+    let user = payload.into_entity(user_id);
+    state.user_use_case.create_user(user.clone()).await?;
+    Ok((StatusCode::CREATED, Json(user.into())))
+}
+
+/// ユーザープロフィール更新
+async fn update_user_profile(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiJson(payload): ApiJson<UpdateUserProfileRequest>,
+) -> ApiResult<Json<UserProfileResponse>> {
+    payload.validate()?;
+    let mut user = state
+        .user_use_case
+        .get_user_profile(user_id.value())
+        .await?;
+    payload.apply(&mut user);
+    state
+        .user_use_case
+        .update_user_profile(user.clone())
+        .await?;
+    Ok(Json(user.into()))
 }
 
 /// 取引一覧取得
 async fn get_transactions(
-    Path(user_id): Path<String>,
-    Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<Value>, StatusCode> {
-    // 実装予定
-    Ok(Json(json!({
-        "user_id": user_id,
-        "params": params,
-        "message": "Transaction listing not implemented yet"
-    })))
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiQuery(params): ApiQuery<ListTransactionsParams>,
+) -> ApiResult<Json<TransactionListResponse>> {
+    params.validate()?;
+    let (transactions, next_cursor) = state
+        .transaction_use_case
+        .list_transactions(&user_id, params.to_query(), params.cursor.as_deref())
+        .await?;
+    Ok(Json(TransactionListResponse::new(
+        transactions,
+        next_cursor,
+    )))
+}
+
+/// 取引取得
+async fn get_transaction(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(transaction_id): Path<String>,
+) -> ApiResult<Json<TransactionResponse>> {
+    let transaction = state
+        .transaction_use_case
+        .get_transaction(&user_id, &transaction_id)
+        .await?;
+    Ok(Json(transaction.into()))
 }
 
 /// 取引作成
-async fn create_transaction(Json(payload): Json<Value>) -> Result<Json<Value>, StatusCode> {
-    // 実装予定
-    Ok(Json(json!({
-        "message": "Transaction creation not implemented yet",
-        "data": payload
-    })))
+async fn create_transaction(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiJson(payload): ApiJson<CreateTransactionRequest>,
+) -> ApiResult<(StatusCode, Json<TransactionResponse>)> {
+    payload.validate()?;
+    let today = state.user_use_case.local_date(&user_id, Utc::now()).await?;
+    let mut transaction = payload.into_entity(user_id.clone(), today)?;
+    if let Some(account_id) = &transaction.account_id {
+        state
+            .account_use_case
+            .ensure_usable(&user_id, account_id, &transaction.amount)
+            .await?;
+    }
+    if let Some(category_id) = &transaction.category_id {
+        let category = state
+            .category_use_case
+            .ensure_usable(&user_id, category_id)
+            .await?;
+        transaction.assign_category(&category);
+    }
+    state
+        .transaction_use_case
+        .create_transaction(transaction.clone())
+        .await?;
+    evaluate_budget_alerts(&state, &user_id, None).await;
+    Ok((StatusCode::CREATED, Json(transaction.into())))
 }
 
 /// 取引更新
 async fn update_transaction(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
     Path(transaction_id): Path<String>,
-    Json(payload): Json<Value>,
-) -> Result<Json<Value>, StatusCode> {
-    // 実装予定
-    Ok(Json(json!({
-        "transaction_id": transaction_id,
-        "message": "Transaction update not implemented yet",
-        "data": payload
-    })))
+    ApiJson(payload): ApiJson<UpdateTransactionRequest>,
+) -> ApiResult<Json<TransactionResponse>> {
+    payload.validate()?;
+    let mut transaction = state
+        .transaction_use_case
+        .get_transaction(&user_id, &transaction_id)
+        .await?;
+    let previous_account_id = transaction.account_id.clone();
+    let previous_currency = transaction.amount.currency.clone();
+    let previous_category_id = transaction.category_id.clone();
+    payload.apply(&mut transaction);
+    // 無効化した口座の取引も編集できるよう、口座か通貨を変えた場合だけ確認する
+    if let Some(account_id) = &transaction.account_id {
+        if transaction.account_id != previous_account_id
+            || transaction.amount.currency != previous_currency
+        {
+            state
+                .account_use_case
+                .ensure_usable(&user_id, account_id, &transaction.amount)
+                .await?;
+        }
+    }
+    // カテゴリも同様に、アーカイブ済みのカテゴリの取引を編集できるよう付け替えた場合だけ確認する
+    if let Some(category_id) = &transaction.category_id {
+        if transaction.category_id != previous_category_id {
+            let category = state
+                .category_use_case
+                .ensure_usable(&user_id, category_id)
+                .await?;
+            transaction.assign_category(&category);
+        }
+    }
+    state
+        .transaction_use_case
+        .update_transaction(transaction.clone())
+        .await?;
+    evaluate_budget_alerts(&state, &user_id, None).await;
+    Ok(Json(transaction.into()))
 }
 
 /// 取引削除
-async fn delete_transaction(Path(transaction_id): Path<String>) -> Result<Json<Value>, StatusCode> {
-    // 実装予定
-    Ok(Json(json!({
-        "transaction_id": transaction_id,
-        "message": "Transaction deletion not implemented yet"
-    })))
+async fn delete_transaction(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(transaction_id): Path<String>,
+) -> ApiResult<StatusCode> {
+    state
+        .transaction_use_case
+        .delete_transaction(&user_id, &transaction_id)
+        .await?;
+    evaluate_budget_alerts(&state, &user_id, None).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 口座間移動の作成（出金・入金の2件の取引をまとめて保存）
+async fn create_transfer(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiJson(payload): ApiJson<CreateTransferRequest>,
+) -> ApiResult<(StatusCode, Json<TransferResponse>)> {
+    payload.validate()?;
+    let today = state.user_use_case.local_date(&user_id, Utc::now()).await?;
+    let transfer = payload.into_entity(user_id.clone(), today);
+    for account_id in [&transfer.from_account_id, &transfer.to_account_id] {
+        state
+            .account_use_case
+            .ensure_usable(&user_id, account_id, &transfer.amount)
+            .await?;
+    }
+    let transactions = state
+        .transaction_use_case
+        .create_transfer(&transfer)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(TransferResponse::new(transfer, transactions)),
+    ))
+}
+
+/// 口座間移動取得
+async fn get_transfer(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(transfer_id): Path<String>,
+) -> ApiResult<Json<TransferResponse>> {
+    let (transfer, transactions) = state
+        .transaction_use_case
+        .get_transfer(&user_id, &transfer_id)
+        .await?;
+    Ok(Json(TransferResponse::new(transfer, transactions)))
+}
+
+/// 口座間移動削除（両側の取引をまとめて削除）
+async fn delete_transfer(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(transfer_id): Path<String>,
+) -> ApiResult<StatusCode> {
+    state
+        .transaction_use_case
+        .delete_transfer(&user_id, &transfer_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
 }
 
 /// 予算一覧取得
 async fn get_budgets(
-    Path(user_id): Path<String>,
-    Query(params): Query<HashMap<String, String>>,
-) -> Result<Json<Value>, StatusCode> {
-    // 実装予定
-    Ok(Json(json!({
-        "user_id": user_id,
-        "params": params,
-        "message": "Budget listing not implemented yet"
-    })))
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+) -> ApiResult<Json<Vec<BudgetResponse>>> {
+    let budgets = state.budget_use_case.get_budgets(user_id.value()).await?;
+    Ok(Json(budgets.into_iter().map(Into::into).collect()))
 }
 
 /// 予算作成
-async fn create_budget(Json(payload): Json<Value>) -> Result<Json<Value>, StatusCode> {
-    // 実装予定
-    Ok(Json(json!({
-        "message": "Budget creation not implemented yet",
-        "data": payload
-    })))
+async fn create_budget(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiJson(payload): ApiJson<CreateBudgetRequest>,
+) -> ApiResult<(StatusCode, Json<BudgetResponse>)> {
+    payload.validate()?;
+    let budget = payload.into_entity(user_id.clone())?;
+    state.budget_use_case.create_budget(budget.clone()).await?;
+    evaluate_budget_alerts(&state, &user_id, Some(budget.clone())).await;
+    Ok((StatusCode::CREATED, Json(budget.into())))
 }
 
 /// 予算更新
 async fn update_budget(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
     Path(budget_id): Path<String>,
-    Json(payload): Json<Value>,
-) -> Result<Json<Value>, StatusCode> {
-    // 実装予定
-    Ok(Json(json!({
-        "budget_id": budget_id,
-        "message": "Budget update not implemented yet",
-        "data": payload
-    })))
+    ApiJson(payload): ApiJson<UpdateBudgetRequest>,
+) -> ApiResult<Json<BudgetResponse>> {
+    payload.validate()?;
+    let mut budget = state
+        .budget_use_case
+        .get_budget(&user_id, &budget_id)
+        .await?;
+    payload.apply(&mut budget)?;
+    state.budget_use_case.update_budget(budget.clone()).await?;
+    evaluate_budget_alerts(&state, &user_id, Some(budget.clone())).await;
+    Ok(Json(budget.into()))
 }
 
 /// 予算削除
-async fn delete_budget(Path(budget_id): Path<String>) -> Result<Json<Value>, StatusCode> {
-    // 実装予定
-    Ok(Json(json!({
-        "budget_id": budget_id,
-        "message": "Budget deletion not implemented yet"
-    })))
+async fn delete_budget(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(budget_id): Path<String>,
+) -> ApiResult<StatusCode> {
+    state
+        .budget_use_case
+        .delete_budget(&user_id, &budget_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 予算の現在の期間の消化状況取得
+async fn get_budget_status(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(budget_id): Path<String>,
+) -> ApiResult<Json<BudgetStatusResponse>> {
+    let status = state
+        .budget_use_case
+        .get_budget_status(&user_id, &budget_id, Utc::now())
+        .await?;
+    Ok(Json(status.into()))
+}
+
+/// 予算アラートの履歴取得（新しい順）
+async fn get_budget_alerts(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+) -> ApiResult<Json<Vec<BudgetAlertResponse>>> {
+    let alerts = state.budget_alert_use_case.get_alerts(&user_id).await?;
+    Ok(Json(alerts.into_iter().map(Into::into).collect()))
+}
+
+/// 支出や予算の変更後に予算アラートを判定し、未送信のアラートを通知する
+///
+/// 変更自体は保存済みのため、判定や通知に失敗してもログに残すだけでリクエストは失敗させない
+/// （通知できなかったアラートは次の判定の後に再送される）
+async fn evaluate_budget_alerts(state: &AppState, user_id: &UserId, budget: Option<Budget>) {
+    if !state.evaluate_budget_alerts {
+        return;
+    }
+    let now = Utc::now();
+    if let Err(err) = state
+        .budget_alert_use_case
+        .evaluate(user_id, budget, now)
+        .await
+    {
+        eprintln!("Failed to evaluate budget alerts: {:?}", err);
+    }
+    if let Err(err) = state
+        .budget_alert_use_case
+        .deliver_pending(user_id, now)
+        .await
+    {
+        eprintln!("Failed to deliver budget alerts: {:?}", err);
+    }
+}
+
+/// カテゴリ一覧取得（初回は既定のカテゴリを用意する）
+async fn get_categories(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+) -> ApiResult<Json<Vec<CategoryResponse>>> {
+    let categories = state.category_use_case.get_categories(&user_id).await?;
+    Ok(Json(categories.into_iter().map(Into::into).collect()))
+}
+
+/// カテゴリ取得
+async fn get_category(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(category_id): Path<String>,
+) -> ApiResult<Json<CategoryResponse>> {
+    let category = state
+        .category_use_case
+        .get_category(&user_id, &category_id)
+        .await?;
+    Ok(Json(category.into()))
+}
+
+/// カテゴリ作成
+async fn create_category(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiJson(payload): ApiJson<CreateCategoryRequest>,
+) -> ApiResult<(StatusCode, Json<CategoryResponse>)> {
+    payload.validate()?;
+    let category = state
+        .category_use_case
+        .create_category(payload.into_entity(user_id)?)
+        .await?;
+    Ok((StatusCode::CREATED, Json(category.into())))
+}
+
+/// カテゴリ更新（使わなくなったカテゴリは削除せずアーカイブする）
+async fn update_category(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(category_id): Path<String>,
+    ApiJson(payload): ApiJson<UpdateCategoryRequest>,
+) -> ApiResult<Json<CategoryResponse>> {
+    payload.validate()?;
+    let mut category = state
+        .category_use_case
+        .get_category(&user_id, &category_id)
+        .await?;
+    payload.apply(&mut category);
+    state
+        .category_use_case
+        .update_category(category.clone())
+        .await?;
+    Ok(Json(category.into()))
+}
+
+/// 月次レポート取得（ユーザーのタイムゾーンで集計する）
+async fn get_monthly_report(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiQuery(params): ApiQuery<MonthlyReportParams>,
+) -> ApiResult<Json<MonthlyReportResponse>> {
+    params.validate()?;
+    let report = state
+        .report_use_case
+        .get_monthly_report(&user_id, params.month_start()?, Utc::now())
+        .await?;
+    Ok(Json(MonthlyReportResponse::new(report)?))
+}
+
+/// 推移レポート取得（日・月・年ごとの収支と分類別の割合）
+async fn get_trend_report(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiQuery(params): ApiQuery<TrendReportParams>,
+) -> ApiResult<Json<TrendReportResponse>> {
+    let report = state
+        .report_use_case
+        .get_trend_report(&user_id, params.granularity, params.window()?, Utc::now())
+        .await?;
+    Ok(Json(TrendReportResponse::new(report)?))
+}
+
+/// カテゴリ別支出レポート取得（子カテゴリの支出は親カテゴリにも積み上げる）
+async fn get_category_spending(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiQuery(params): ApiQuery<CategorySpendingParams>,
+) -> ApiResult<Json<CategorySpendingReportResponse>> {
+    let spending = state
+        .category_use_case
+        .get_spending(&user_id, &params.window()?)
+        .await?;
+    Ok(Json(CategorySpendingReportResponse::new(&params, spending)))
+}
+
+/// 口座一覧取得
+async fn get_accounts(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+) -> ApiResult<Json<Vec<AccountResponse>>> {
+    let accounts = state.account_use_case.get_accounts(user_id.value()).await?;
+    Ok(Json(accounts.into_iter().map(Into::into).collect()))
+}
+
+/// 口座取得
+async fn get_account(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(account_id): Path<String>,
+) -> ApiResult<Json<AccountResponse>> {
+    let account = state
+        .account_use_case
+        .get_account(&user_id, &account_id)
+        .await?;
+    Ok(Json(account.into()))
+}
+
+/// 口座作成
+async fn create_account(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiJson(payload): ApiJson<CreateAccountRequest>,
+) -> ApiResult<(StatusCode, Json<AccountResponse>)> {
+    payload.validate()?;
+    let account = payload.into_entity(user_id.clone());
+    // カードの利用額は引き落とし口座から支払うため、通貨も揃える
+    if let Some(payment_account_id) = account.payment_account_id() {
+        state
+            .account_use_case
+            .ensure_usable(&user_id, payment_account_id, &account.opening_balance)
+            .await?;
+    }
+    state
+        .account_use_case
+        .create_account(account.clone())
+        .await?;
+    Ok((StatusCode::CREATED, Json(account.into())))
+}
+
+/// 口座更新
+async fn update_account(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(account_id): Path<String>,
+    ApiJson(payload): ApiJson<UpdateAccountRequest>,
+) -> ApiResult<Json<AccountResponse>> {
+    payload.validate()?;
+    let mut account = state
+        .account_use_case
+        .get_account(&user_id, &account_id)
+        .await?;
+    let previous_payment_account_id = account.payment_account_id().map(str::to_string);
+    payload.apply(&mut account);
+    // 引き落とし口座を付け替えた場合だけ確認する
+    if let Some(payment_account_id) = account.payment_account_id() {
+        if previous_payment_account_id.as_deref() != Some(payment_account_id) {
+            state
+                .account_use_case
+                .ensure_usable(&user_id, payment_account_id, &account.opening_balance)
+                .await?;
+        }
+    }
+    state
+        .account_use_case
+        .update_account(account.clone())
+        .await?;
+    Ok(Json(account.into()))
+}
+
+/// 口座削除（取引が紐づく口座は削除できない）
+async fn delete_account(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(account_id): Path<String>,
+) -> ApiResult<StatusCode> {
+    state
+        .account_use_case
+        .delete_account(&user_id, &account_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 口座残高取得（`asOf` を指定するとその日の終わり時点の残高）
+async fn get_account_balance(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(account_id): Path<String>,
+    ApiQuery(params): ApiQuery<AccountBalanceParams>,
+) -> ApiResult<Json<AccountBalanceResponse>> {
+    let balance = state
+        .account_use_case
+        .get_balance(&user_id, &account_id, params.as_of)
+        .await?;
+    Ok(Json(AccountBalanceResponse::new(balance, params.as_of)))
+}
+
+/// クレジットカードの明細一覧と未確定の利用額の取得
+async fn get_card_statements(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(account_id): Path<String>,
+    ApiQuery(params): ApiQuery<CardStatementParams>,
+) -> ApiResult<Json<CardStatementListResponse>> {
+    let today = match params.as_of {
+        Some(as_of) => as_of,
+        None => state.user_use_case.local_date(&user_id, Utc::now()).await?,
+    };
+    let summary = state
+        .account_use_case
+        .get_statements(&user_id, &account_id, today)
+        .await?;
+    Ok(Json(CardStatementListResponse::new(summary, today)))
+}
+
+/// 口座から引き落とされる予定のカード請求の取得
+async fn get_upcoming_debits(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(account_id): Path<String>,
+    ApiQuery(params): ApiQuery<CardStatementParams>,
+) -> ApiResult<Json<UpcomingDebitsResponse>> {
+    let today = match params.as_of {
+        Some(as_of) => as_of,
+        None => state.user_use_case.local_date(&user_id, Utc::now()).await?,
+    };
+    let projection = state
+        .account_use_case
+        .get_upcoming_debits(&user_id, &account_id, today)
+        .await?;
+    Ok(Json(UpcomingDebitsResponse::new(projection, today)))
+}
+
+/// 精算一覧取得（債権者・債務者どちらの立場のものも含む）
+async fn get_settlements(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+) -> ApiResult<Json<Vec<SettlementResponse>>> {
+    let settlements = state
+        .settlement_use_case
+        .get_settlements(user_id.value())
+        .await?;
+    Ok(Json(settlements.into_iter().map(Into::into).collect()))
+}
+
+/// 精算取得
+async fn get_settlement(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(settlement_id): Path<String>,
+) -> ApiResult<Json<SettlementResponse>> {
+    let settlement = state
+        .settlement_use_case
+        .get_settlement(&user_id, &settlement_id)
+        .await?;
+    Ok(Json(settlement.into()))
+}
+
+/// 精算作成（認証済みユーザーが債権者）
+async fn create_settlement(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    ApiJson(payload): ApiJson<CreateSettlementRequest>,
+) -> ApiResult<(StatusCode, Json<SettlementResponse>)> {
+    payload.validate()?;
+    let settlement = match payload.transaction_id {
+        Some(transaction_id) => {
+            state
+                .settlement_use_case
+                .create_from_transaction(
+                    &user_id,
+                    &transaction_id,
+                    UserId::new(payload.debtor_user_id),
+                    payload.amount,
+                    payload.description,
+                )
+                .await?
+        }
+        None => {
+            let settlement = payload.into_entity(user_id)?;
+            state
+                .settlement_use_case
+                .create_settlement(settlement.clone())
+                .await?;
+            settlement
+        }
+    };
+    Ok((StatusCode::CREATED, Json(settlement.into())))
+}
+
+/// 返済の記録
+async fn repay_settlement(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(settlement_id): Path<String>,
+    ApiJson(payload): ApiJson<RepaySettlementRequest>,
+) -> ApiResult<Json<SettlementResponse>> {
+    let settlement = state
+        .settlement_use_case
+        .repay(&user_id, &settlement_id, payload.amount)
+        .await?;
+    Ok(Json(settlement.into()))
+}
+
+/// 精算完了
+async fn complete_settlement(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(settlement_id): Path<String>,
+) -> ApiResult<Json<SettlementResponse>> {
+    let settlement = state
+        .settlement_use_case
+        .complete(&user_id, &settlement_id)
+        .await?;
+    Ok(Json(settlement.into()))
+}
+
+/// 精算取り消し
+async fn cancel_settlement(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(settlement_id): Path<String>,
+) -> ApiResult<Json<SettlementResponse>> {
+    let settlement = state
+        .settlement_use_case
+        .cancel(&user_id, &settlement_id)
+        .await?;
+    Ok(Json(settlement.into()))
+}
+
+/// グループ取引一覧取得（グループのメンバーのみ）
+async fn get_group_transactions(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(group_id): Path<String>,
+) -> ApiResult<Json<Vec<GroupTransactionResponse>>> {
+    let transactions = state
+        .group_transaction_use_case
+        .get_transactions(&user_id, &group_id)
+        .await?;
+    Ok(Json(transactions.into_iter().map(Into::into).collect()))
+}
+
+/// グループ取引作成（負担額は割り勘方法から計算し、支払者の取引に立て替え分を記録する）
+async fn create_group_transaction(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(group_id): Path<String>,
+    ApiJson(payload): ApiJson<CreateGroupTransactionRequest>,
+) -> ApiResult<(StatusCode, Json<GroupTransactionResponse>)> {
+    payload.validate()?;
+    let group = state
+        .group_transaction_use_case
+        .get_group(&user_id, &group_id)
+        .await?;
+    let today = state.user_use_case.local_date(&user_id, Utc::now()).await?;
+    let transaction = payload.into_entity(&group, user_id.clone(), today)?;
+    let payer_transaction = state
+        .group_transaction_use_case
+        .create_transaction(&user_id, transaction.clone())
+        .await?;
+    if let Some(payer_transaction) = payer_transaction {
+        evaluate_budget_alerts(&state, &payer_transaction.user_id, None).await;
+    }
+    Ok((StatusCode::CREATED, Json(transaction.into())))
+}
+
+/// グループ精算計算（送金回数ができるだけ少ない精算プラン。グループのメンバーのみ）
+async fn calculate_group_settlements(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Path(group_id): Path<String>,
+) -> ApiResult<Json<OptimalSettlementPlanResponse>> {
+    let plan = state
+        .group_transaction_use_case
+        .calculate_settlements(&user_id, &group_id)
+        .await?;
+    Ok(Json(plan.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::cursor::CursorCodec;
+    use crate::application::{DynBudgetAlertNotifier, DynTransactionRepository, Repositories};
+    use crate::auth::testing;
+    use crate::config::AggregateUpdates;
+    use crate::domain::repositories::*;
+    use crate::domain::*;
+    use crate::infrastructure::memory::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn app(
+        users: MockUserRepository,
+        transactions: MockTransactionRepository,
+        budgets: MockBudgetRepository,
+    ) -> Router {
+        router(
+            users,
+            transactions,
+            budgets,
+            InMemoryAggregateRepository::new(),
+            InMemoryGroupRepository::new(),
+        )
+    }
+
+    /// 取引から求めた月次集計が保存済みの構成（レポートは月次集計から求める）
+    async fn app_with_aggregates(
+        users: MockUserRepository,
+        transactions: MockTransactionRepository,
+        budgets: MockBudgetRepository,
+        aggregated: &[Transaction],
+    ) -> Router {
+        let aggregates = InMemoryAggregateRepository::new();
+        aggregates
+            .replace_all("user123", MonthlyAggregate::from_transactions(aggregated))
+            .await
+            .unwrap();
+        router(
+            users,
+            transactions,
+            budgets,
+            aggregates,
+            InMemoryGroupRepository::new(),
+        )
+    }
+
+    /// グループが保存済みの構成（グループ取引を記録できる）
+    async fn app_with_group(users: MockUserRepository, group: Group) -> Router {
+        let groups = InMemoryGroupRepository::new();
+        groups.save(group).await.unwrap();
+        router(
+            users,
+            InMemoryTransactionRepository::new(),
+            no_budgets(),
+            InMemoryAggregateRepository::new(),
+            groups,
+        )
+    }
+
+    fn router(
+        users: MockUserRepository,
+        transactions: impl TransactionRepository + 'static,
+        budgets: MockBudgetRepository,
+        aggregates: InMemoryAggregateRepository,
+        groups: InMemoryGroupRepository,
+    ) -> Router {
+        let transactions: DynTransactionRepository = Arc::new(transactions);
+        create_router(AppState::new(
+            Arc::new(testing::verifier()),
+            CursorCodec::new(b"test-secret"),
+            Repositories {
+                users: Arc::new(users),
+                transactions: transactions.clone(),
+                budgets: Arc::new(budgets),
+                groups: Arc::new(groups),
+                group_transactions: Arc::new(InMemoryGroupTransactionRepository::new(transactions)),
+                settlements: Arc::new(InMemorySettlementRepository::new()),
+                accounts: Arc::new(InMemoryAccountRepository::new()),
+                categories: Arc::new(InMemoryCategoryRepository::new()),
+                aggregates: Arc::new(aggregates),
+                budget_alerts: Arc::new(InMemoryBudgetAlertRepository::new()),
+            },
+            AggregateUpdates::Inline,
+            // 通知先なし
+            Arc::new(Vec::<DynBudgetAlertNotifier>::new()),
+        ))
+    }
+
+    /// プロフィール未作成のユーザー（既定のタイムゾーンで日付を決める）
+    fn no_profile() -> MockUserRepository {
+        let mut users = MockUserRepository::new();
+        users.expect_find_by_id().returning(|_| Ok(None));
+        users
+    }
+
+    /// 予算のないユーザー（取引を書き込むと予算アラートの判定で予算一覧を読む）
+    fn no_budgets() -> MockBudgetRepository {
+        let mut budgets = MockBudgetRepository::new();
+        budgets.expect_find_by_user_id().returning(|_| Ok(vec![]));
+        budgets
+    }
+
+    async fn send(app: Router, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json = if body.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&body).unwrap()
+        };
+        (status, json)
+    }
+
+    /// user123 として認証されたリクエスト
+    fn request(method: &str, uri: &str) -> axum::http::request::Builder {
+        Request::builder().method(method).uri(uri).header(
+            "authorization",
+            format!("Bearer {}", testing::token("user123")),
+        )
+    }
+
+    fn json_request(method: &str, uri: &str, body: Value) -> Request<Body> {
+        request(method, uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_user_returns_profile() {
+        let mut users = MockUserRepository::new();
+        users
+            .expect_find_by_id()
+            .withf(|id| id == "user123")
+            .returning(|id| Ok(Some(UserProfile::new(UserId::new(id.to_string())))));
+
+        let (status, body) = send(
+            app(
+                users,
+                MockTransactionRepository::new(),
+                MockBudgetRepository::new(),
+            ),
+            request("GET", "/api/users/profile")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["userId"], "user123");
+        assert_eq!(body["timezone"], "Asia/Tokyo");
+    }
+
+    #[tokio::test]
+    async fn test_get_user_not_found() {
+        let mut users = MockUserRepository::new();
+        users.expect_find_by_id().returning(|_| Ok(None));
+
+        let (status, body) = send(
+            app(
+                users,
+                MockTransactionRepository::new(),
+                MockBudgetRepository::new(),
+            ),
+            request("GET", "/api/users/profile")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_list_transactions_pushes_filters_down() {
+        let mut transactions = MockTransactionRepository::new();
+        transactions
+            .expect_find_page()
+            .withf(|user_id, query| {
+                user_id == "user123"
+                    && query.limit == 1
+                    && query.category == Some(TransactionCategory::Food)
+                    && query.transaction_type.is_none()
+            })
+            .returning(|user_id, _| {
+                let transaction = Transaction::new(
+                    UserId::new(user_id.to_string()),
+                    TransactionType::Real,
+                    Amount::jpy(1000),
+                    "ランチ".to_string(),
+                    TransactionCategory::Food,
+                );
+                Ok(TransactionPage {
+                    next: Some(TransactionCursor::of(&transaction)),
+                    transactions: vec![transaction],
+                })
+            });
+
+        let (status, body) = send(
+            app(
+                MockUserRepository::new(),
+                transactions,
+                MockBudgetRepository::new(),
+            ),
+            request("GET", "/api/transactions?limit=1&category=FOOD")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["transactions"].as_array().unwrap().len(), 1);
+        assert_eq!(body["hasMore"], true);
+        assert!(body["nextCursor"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_list_transactions_rejects_bad_parameters() {
+        for (uri, code) in [
+            ("/api/transactions?limit=0", "VALIDATION_ERROR"),
+            ("/api/transactions?cursor=forged", "VALIDATION_ERROR"),
+            ("/api/transactions?category=UNKNOWN", "MALFORMED_REQUEST"),
+        ] {
+            let (status, body) = send(
+                app(
+                    MockUserRepository::new(),
+                    MockTransactionRepository::new(),
+                    MockBudgetRepository::new(),
+                ),
+                request("GET", uri).body(Body::empty()).unwrap(),
+            )
+            .await;
+
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+            assert_eq!(body["code"], code, "{}", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_transaction_saves_entity() {
+        let mut transactions = MockTransactionRepository::new();
+        transactions
+            .expect_save()
+            .withf(|t| t.description == "ランチ" && t.amount == Amount::jpy(1200))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let (status, body) = send(
+            app(no_profile(), transactions, no_budgets()),
+            json_request(
+                "POST",
+                "/api/transactions",
+                json!({
+                    "type": "REAL",
+                    "amount": { "value": 1200, "currency": "JPY" },
+                    "description": "ランチ",
+                    "category": "FOOD"
+                }),
+            ),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["description"], "ランチ");
+        assert!(body["transactionId"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_create_transaction_rejects_invalid_payload() {
+        let (status, body) = send(
+            app(
+                MockUserRepository::new(),
+                MockTransactionRepository::new(),
+                MockBudgetRepository::new(),
+            ),
+            json_request(
+                "POST",
+                "/api/transactions",
+                json!({
+                    "type": "REAL",
+                    "amount": { "value": 1200, "currency": "JPY" },
+                    "description": "",
+                    "category": "FOOD"
+                }),
+            ),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "VALIDATION_ERROR");
+        assert!(body["details"]["description"].is_array());
+    }
+
+    #[tokio::test]
+    async fn test_update_transaction_applies_changes() {
+        let existing = Transaction::new(
+            UserId::new("user123".to_string()),
+            TransactionType::Real,
+            Amount::jpy(1000),
+            "before".to_string(),
+            TransactionCategory::Food,
+        );
+        let id = existing.transaction_id.value().to_string();
+
+        let mut transactions = MockTransactionRepository::new();
+        transactions
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(existing.clone())));
+        transactions
+            .expect_update()
+            .withf(|t| t.description == "after" && t.category == TransactionCategory::Other)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let (status, body) = send(
+            app(MockUserRepository::new(), transactions, no_budgets()),
+            json_request(
+                "PUT",
+                &format!("/api/transactions/{}", id),
+                json!({ "description": "after", "category": "OTHER" }),
+            ),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["transactionId"], id);
+        assert_eq!(body["amount"]["value"], 1000);
+    }
+
+    #[tokio::test]
+    async fn test_delete_budget_not_found() {
+        let mut budgets = MockBudgetRepository::new();
+        budgets.expect_find_by_id().returning(|_| Ok(None));
+        budgets.expect_delete().never();
+
+        let (status, _) = send(
+            app(
+                MockUserRepository::new(),
+                MockTransactionRepository::new(),
+                budgets,
+            ),
+            request("DELETE", "/api/budgets/missing")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_create_existing_user_is_conflict() {
+        let mut users = MockUserRepository::new();
+        users
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(UserProfile::new(UserId::new(id.to_string())))));
+        users.expect_save().never();
+
+        let (status, body) = send(
+            app(
+                users,
+                MockTransactionRepository::new(),
+                MockBudgetRepository::new(),
+            ),
+            json_request("POST", "/api/users/profile", json!({})),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "CONFLICT");
+    }
+
+    #[tokio::test]
+    async fn test_update_budget_currency_mismatch() {
+        let existing = Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(30000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        let mut budgets = MockBudgetRepository::new();
+        budgets
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(existing.clone())));
+        budgets.expect_update().never();
+
+        let (status, body) = send(
+            app(
+                MockUserRepository::new(),
+                MockTransactionRepository::new(),
+                budgets,
+            ),
+            json_request(
+                "PUT",
+                "/api/budgets/budget1",
+                json!({ "amount": { "value": 300, "currency": "USD" } }),
+            ),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "CURRENCY_MISMATCH");
+    }
+
+    #[tokio::test]
+    async fn test_create_budget_periods() {
+        let mut budgets = MockBudgetRepository::new();
+        budgets.expect_save().times(1).returning(|_| Ok(()));
+        // 作成した予算のアラートを判定する
+        let mut users = MockUserRepository::new();
+        users.expect_find_by_id().returning(|_| Ok(None));
+        let mut transactions = MockTransactionRepository::new();
+        transactions
+            .expect_find_by_user_id()
+            .returning(|_| Ok(vec![]));
+        let app = app(users, transactions, budgets);
+        let create = |body: Value| json_request("POST", "/api/budgets", body);
+
+        let (status, body) = send(
+            app.clone(),
+            create(json!({
+                "category": "FOOD",
+                "amount": { "value": 10000, "currency": "JPY" },
+                "period": "WEEKLY",
+                "weekStart": "SUNDAY",
+                "rollover": true
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["weekStart"], "SUNDAY");
+        assert_eq!(body["rollover"], true);
+
+        // 期間指定の予算は初日と最終日が必要
+        let (status, body) = send(
+            app,
+            create(json!({
+                "category": "ENTERTAINMENT",
+                "amount": { "value": 50000, "currency": "JPY" },
+                "period": "CUSTOM",
+                "startDate": "2025-08-10"
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_budget_status() {
+        let mut existing = Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.5,
+        );
+        existing.month_start_day = Some(25);
+        let mut budgets = MockBudgetRepository::new();
+        budgets
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(existing.clone())));
+        let mut users = MockUserRepository::new();
+        users.expect_find_by_id().returning(|_| Ok(None));
+
+        // 取引日はユーザーのタイムゾーン（既定の東京）での今日
+        let today = Utc::now()
+            .with_timezone(&chrono_tz::Asia::Tokyo)
+            .date_naive();
+        let on = |date: chrono::NaiveDate, amount: i64| {
+            let mut food = Transaction::new(
+                UserId::new("user123".to_string()),
+                TransactionType::Real,
+                Amount::jpy(amount),
+                "食費".to_string(),
+                TransactionCategory::Food,
+            );
+            food.transaction_date = TransactionDate::new(date);
+            food
+        };
+        let history = vec![on(today, 6000), on(today - chrono::Months::new(2), 3000)];
+        let mut transactions = MockTransactionRepository::new();
+        transactions
+            .expect_find_by_user_id()
+            .returning(move |_| Ok(history.clone()));
+
+        let (status, body) = send(
+            app(users, transactions, budgets),
+            request("GET", "/api/budgets/budget1/status")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["spentAmount"]["value"], 6000);
+        assert_eq!(body["remainingAmount"]["value"], 4000);
+        assert_eq!(body["usage"], 0.6);
+        assert_eq!(body["shouldAlert"], true);
+        let start: chrono::NaiveDate = body["periodStart"].as_str().unwrap().parse().unwrap();
+        let end: chrono::NaiveDate = body["periodEnd"].as_str().unwrap().parse().unwrap();
+        assert!(body["periodStart"].as_str().unwrap().ends_with("-25"));
+        assert!(start <= today && today <= end);
+    }
+
+    #[tokio::test]
+    async fn test_budget_alert_is_raised_once_per_period() {
+        let budget = Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::category(TransactionCategory::Food),
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.5,
+        );
+        let budget_id = budget.budget_id.clone();
+        let mut budgets = MockBudgetRepository::new();
+        budgets
+            .expect_find_by_user_id()
+            .returning(move |_| Ok(vec![budget.clone()]));
+        let mut users = MockUserRepository::new();
+        users.expect_find_by_id().returning(|_| Ok(None));
+        let saved = Arc::new(std::sync::Mutex::new(Vec::<Transaction>::new()));
+        let mut transactions = MockTransactionRepository::new();
+        let store = saved.clone();
+        transactions.expect_save().returning(move |t| {
+            store.lock().unwrap().push(t);
+            Ok(())
+        });
+        transactions
+            .expect_find_by_user_id()
+            .returning(move |_| Ok(saved.lock().unwrap().clone()));
+        let app = app(users, transactions, budgets);
+        let lunch = |amount: i64| {
+            json_request(
+                "POST",
+                "/api/transactions",
+                json!({
+                    "type": "REAL",
+                    "amount": { "value": amount, "currency": "JPY" },
+                    "description": "ランチ",
+                    "category": "FOOD"
+                }),
+            )
+        };
+
+        // 閾値（50%）を超えた後の支出では、同じ期間のアラートは再び発生しない
+        let (status, _) = send(app.clone(), lunch(6000)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, _) = send(app.clone(), lunch(2000)).await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, body) = send(
+            app,
+            request("GET", "/api/budget-alerts")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let alerts = body.as_array().unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0]["budgetId"], budget_id);
+        assert_eq!(alerts[0]["threshold"], 0.5);
+        assert_eq!(alerts[0]["usage"], 0.6);
+        assert_eq!(alerts[0]["spentAmount"]["value"], 6000);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_body_uses_error_response() {
+        let malformed = request("POST", "/api/budgets")
+            .header("content-type", "application/json")
+            .body(Body::from("{not json"))
+            .unwrap();
+
+        let (status, body) = send(
+            app(
+                MockUserRepository::new(),
+                MockTransactionRepository::new(),
+                MockBudgetRepository::new(),
+            ),
+            malformed,
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "MALFORMED_REQUEST");
+        assert!(body["message"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_requests_without_valid_token_are_unauthorized() {
+        let app = app(
+            MockUserRepository::new(),
+            MockTransactionRepository::new(),
+            MockBudgetRepository::new(),
+        );
+
+        let (status, body) = send(
+            app.clone(),
+            Request::get("/api/transactions")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "UNAUTHORIZED");
+
+        let (status, _) = send(
+            app.clone(),
+            Request::get("/api/transactions")
+                .header("authorization", "Bearer not-a-jwt")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // ヘルスチェックは認証不要
+        let (status, _) = send(app, Request::get("/health").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_other_users_transaction_is_forbidden() {
+        let existing = Transaction::new(
+            UserId::new("someone-else".to_string()),
+            TransactionType::Real,
+            Amount::jpy(1000),
+            "private".to_string(),
+            TransactionCategory::Food,
+        );
+        let mut transactions = MockTransactionRepository::new();
+        transactions
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(existing.clone())));
+        transactions.expect_delete().never();
+
+        let (status, body) = send(
+            app(
+                MockUserRepository::new(),
+                transactions,
+                MockBudgetRepository::new(),
+            ),
+            request("DELETE", "/api/transactions/tx1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], "FORBIDDEN");
+    }
+
+    #[tokio::test]
+    async fn test_repository_failure_is_internal_error() {
+        let mut budgets = MockBudgetRepository::new();
+        budgets
+            .expect_find_by_user_id()
+            .returning(|_| Err(anyhow::anyhow!("connection refused")));
+
+        let (status, body) = send(
+            app(
+                MockUserRepository::new(),
+                MockTransactionRepository::new(),
+                budgets,
+            ),
+            request("GET", "/api/budgets").body(Body::empty()).unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["code"], "INTERNAL_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_settlement_lifecycle() {
+        let app = app(
+            MockUserRepository::new(),
+            MockTransactionRepository::new(),
+            MockBudgetRepository::new(),
+        );
+        let (status, created) = send(
+            app.clone(),
+            json_request(
+                "POST",
+                "/api/settlements",
+                json!({
+                    "debtorUserId": "bob",
+                    "amount": { "value": 3000, "currency": "JPY" },
+                    "description": "ランチ代"
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["creditorUserId"], "user123");
+        assert_eq!(created["status"], "PENDING");
+        let id = created["settlementId"].as_str().unwrap();
+
+        // 債務者は参照できるが完了にはできない
+        let as_bob = |method: &str, uri: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", testing::token("bob")))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let (status, _) = send(
+            app.clone(),
+            as_bob("GET", &format!("/api/settlements/{}", id)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, body) = send(
+            app.clone(),
+            as_bob("POST", &format!("/api/settlements/{}/complete", id)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], "FORBIDDEN");
+
+        let (status, body) = send(
+            app.clone(),
+            json_request(
+                "POST",
+                &format!("/api/settlements/{}/repayments", id),
+                json!({ "amount": { "value": 1000, "currency": "JPY" } }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "PARTIALLY_REPAID");
+        assert_eq!(body["remainingAmount"]["value"], 2000);
+
+        let (status, body) = send(
+            app.clone(),
+            request("POST", &format!("/api/settlements/{}/complete", id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "COMPLETED");
+        assert!(body["completedAt"].is_string());
+
+        // 完了後は取り消せない
+        let (status, body) = send(
+            app,
+            request("POST", &format!("/api/settlements/{}/cancel", id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "CONFLICT");
+    }
+
+    #[tokio::test]
+    async fn test_group_transactions() {
+        let mut group = Group::new(
+            "旅行".to_string(),
+            String::new(),
+            UserId::new("user123".to_string()),
+        );
+        group.add_member(UserId::new("bob".to_string()));
+        let group_id = group.group_id.clone();
+        let app = app_with_group(no_profile(), group).await;
+        let uri = format!("/api/groups/{}/transactions", group_id);
+
+        let (status, created) = send(
+            app.clone(),
+            json_request(
+                "POST",
+                &uri,
+                json!({
+                    "amount": { "value": 10001, "currency": "JPY" },
+                    "description": "ホテル代",
+                    "splitRule": {
+                        "method": "SHARES",
+                        "members": [
+                            { "userId": "user123", "shares": 1 },
+                            { "userId": "bob", "shares": 1 }
+                        ]
+                    },
+                    "transactionDate": "2024-03-10"
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["paidBy"], "user123");
+        assert_eq!(created["category"], "OTHER");
+        assert_eq!(created["transactionDate"], "2024-03-10");
+        assert_eq!(created["splitRule"]["method"], "SHARES");
+        // 端数は支払者が負担する
+        assert_eq!(created["participants"][0]["userId"], "bob");
+        assert_eq!(created["participants"][0]["shareAmount"]["value"], 5000);
+        assert_eq!(created["participants"][1]["shareAmount"]["value"], 5001);
+
+        let (status, body) = send(
+            app.clone(),
+            request("GET", &uri).body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), 1);
+        assert_eq!(body[0]["transactionId"], created["transactionId"]);
+
+        // 割り勘方法に必要な項目がない
+        let (status, body) = send(
+            app.clone(),
+            json_request(
+                "POST",
+                &uri,
+                json!({
+                    "amount": { "value": 3000, "currency": "JPY" },
+                    "description": "夕食",
+                    "splitRule": {
+                        "method": "EXACT",
+                        "members": [{ "userId": "bob" }]
+                    }
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "VALIDATION_ERROR");
+
+        // メンバー以外は参照できない
+        let (status, body) = send(
+            app.clone(),
+            Request::builder()
+                .uri(&uri)
+                .header(
+                    "authorization",
+                    format!("Bearer {}", testing::token("carol")),
+                )
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], "FORBIDDEN");
+
+        let (status, body) = send(
+            app,
+            request("GET", "/api/groups/unknown/transactions")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_group_transaction_records_payers_share() {
+        let mut group = Group::new(
+            "旅行".to_string(),
+            String::new(),
+            UserId::new("user123".to_string()),
+        );
+        group.add_member(UserId::new("bob".to_string()));
+        let group_id = group.group_id.clone();
+        let app = app_with_group(no_profile(), group).await;
+
+        let (status, _) = send(
+            app.clone(),
+            json_request(
+                "POST",
+                &format!("/api/groups/{}/transactions", group_id),
+                json!({
+                    "amount": { "value": 10001, "currency": "JPY" },
+                    "description": "ホテル代",
+                    "category": "ENTERTAINMENT",
+                    "splitRule": {
+                        "method": "SHARES",
+                        "members": [
+                            { "userId": "user123", "shares": 1 },
+                            { "userId": "bob", "shares": 1 }
+                        ]
+                    },
+                    "transactionDate": "2024-03-10"
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        // 支払者の取引一覧に、他のメンバーの分を立て替えた額が記録される
+        let (status, body) = send(
+            app.clone(),
+            request("GET", "/api/transactions")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let transactions = body["transactions"].as_array().unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0]["type"], "FLOW");
+        assert_eq!(transactions[0]["amount"]["value"], 5000);
+        assert_eq!(transactions[0]["description"], "ホテル代");
+        assert_eq!(transactions[0]["category"], "ENTERTAINMENT");
+        assert_eq!(transactions[0]["transactionDate"], "2024-03-10");
+
+        // 支払っていないメンバーの取引は増えない
+        let (status, body) = send(
+            app,
+            Request::builder()
+                .uri("/api/transactions")
+                .header("authorization", format!("Bearer {}", testing::token("bob")))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["transactions"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_group_settlement_calculation() {
+        let mut group = Group::new(
+            "旅行".to_string(),
+            String::new(),
+            UserId::new("user123".to_string()),
+        );
+        group.add_member(UserId::new("bob".to_string()));
+        group.add_member(UserId::new("carol".to_string()));
+        let group_id = group.group_id.clone();
+        let app = app_with_group(no_profile(), group).await;
+        let calculate_uri = format!("/api/groups/{}/settlements/calculate", group_id);
+        let calculate = || request("POST", &calculate_uri).body(Body::empty()).unwrap();
+
+        // 取引がなければ送金もない
+        let (status, body) = send(app.clone(), calculate()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["groupId"], group_id.as_str());
+        assert_eq!(body["totalTransfers"], 0);
+
+        // 3人で割り勘した 3000円を user123 と bob がそれぞれ支払った
+        for paid_by in ["user123", "bob"] {
+            let (status, _) = send(
+                app.clone(),
+                json_request(
+                    "POST",
+                    &format!("/api/groups/{}/transactions", group_id),
+                    json!({
+                        "paidBy": paid_by,
+                        "amount": { "value": 3000, "currency": "JPY" },
+                        "description": "夕食",
+                        "splitRule": {
+                            "method": "EQUAL",
+                            "members": [
+                                { "userId": "user123" },
+                                { "userId": "bob" },
+                                { "userId": "carol" }
+                            ]
+                        },
+                        "transactionDate": "2024-03-10"
+                    }),
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+
+        let (status, body) = send(app.clone(), calculate()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["totalTransfers"], 2);
+        assert_eq!(
+            body["settlements"],
+            json!([
+                {
+                    "fromUserId": "carol",
+                    "toUserId": "bob",
+                    "amount": { "value": 1000, "currency": "JPY" }
+                },
+                {
+                    "fromUserId": "carol",
+                    "toUserId": "user123",
+                    "amount": { "value": 1000, "currency": "JPY" }
+                }
+            ])
+        );
+
+        // メンバー以外は計算できない
+        let (status, _) = send(
+            app,
+            Request::builder()
+                .method("POST")
+                .uri(&calculate_uri)
+                .header(
+                    "authorization",
+                    format!("Bearer {}", testing::token("dave")),
+                )
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_account_lifecycle() {
+        // 保存された取引を残高の計算に使う
+        let saved = Arc::new(std::sync::Mutex::new(Vec::<Transaction>::new()));
+        let mut transactions = MockTransactionRepository::new();
+        let store = saved.clone();
+        transactions.expect_save().returning(move |t| {
+            store.lock().unwrap().push(t);
+            Ok(())
+        });
+        let store = saved.clone();
+        transactions
+            .expect_find_by_user_id()
+            .returning(move |_| Ok(store.lock().unwrap().clone()));
+        let app = app(no_profile(), transactions, no_budgets());
+
+        let (status, account) = send(
+            app.clone(),
+            json_request(
+                "POST",
+                "/api/accounts",
+                json!({
+                    "name": "財布",
+                    "type": "CASH",
+                    "openingBalance": { "value": 10000, "currency": "JPY" }
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(account["isActive"], true);
+        let id = account["accountId"].as_str().unwrap();
+
+        for (direction, value, date) in [
+            ("EXPENSE", 3000, "2025-03-01"),
+            ("INCOME", 5000, "2025-03-05"),
+        ] {
+            let (status, _) = send(
+                app.clone(),
+                json_request(
+                    "POST",
+                    "/api/transactions",
+                    json!({
+                        "type": "REAL",
+                        "direction": direction,
+                        "amount": { "value": value, "currency": "JPY" },
+                        "description": "テスト",
+                        "category": "FOOD",
+                        "transactionDate": date,
+                        "accountId": id
+                    }),
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+
+        let balance_uri = format!("/api/accounts/{}/balance", id);
+        let (status, body) = send(
+            app.clone(),
+            request("GET", &balance_uri).body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["currentBalance"]["value"], 12000);
+        assert_eq!(body["availableBalance"]["value"], 12000);
+        // 指定日の終わり時点の残高
+        let (_, body) = send(
+            app.clone(),
+            request("GET", &format!("{}?asOf=2025-03-01", balance_uri))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(body["currentBalance"]["value"], 7000);
+        assert_eq!(body["asOf"], "2025-03-01");
+
+        // 取引がある口座は削除できない
+        let (status, _) = send(
+            app.clone(),
+            request("DELETE", &format!("/api/accounts/{}", id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        // 無効化した口座・存在しない口座には取引を記録できない
+        let (status, body) = send(
+            app.clone(),
+            json_request(
+                "PUT",
+                &format!("/api/accounts/{}", id),
+                json!({ "isActive": false }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["isActive"], false);
+        for account_id in [id, "missing"] {
+            let (status, body) = send(
+                app.clone(),
+                json_request(
+                    "POST",
+                    "/api/transactions",
+                    json!({
+                        "type": "REAL",
+                        "amount": { "value": 100, "currency": "JPY" },
+                        "description": "テスト",
+                        "category": "FOOD",
+                        "accountId": account_id
+                    }),
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"], "VALIDATION_ERROR");
+        }
+        assert_eq!(saved.lock().unwrap().len(), 2);
+
+        let (status, body) = send(
+            app,
+            request("GET", "/api/accounts").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_moves_money_between_accounts() {
+        let saved = Arc::new(std::sync::Mutex::new(Vec::<Transaction>::new()));
+        let mut transactions = MockTransactionRepository::new();
+        let store = saved.clone();
+        transactions.expect_save_all().times(1).returning(move |t| {
+            store.lock().unwrap().extend(t);
+            Ok(())
+        });
+        let store = saved.clone();
+        transactions
+            .expect_find_by_user_id()
+            .returning(move |_| Ok(store.lock().unwrap().clone()));
+        let app = app(no_profile(), transactions, MockBudgetRepository::new());
+
+        let mut ids = Vec::new();
+        for (name, account_type, opening) in [("銀行", "BANK", 50000), ("財布", "CASH", 0)] {
+            let (_, account) = send(
+                app.clone(),
+                json_request(
+                    "POST",
+                    "/api/accounts",
+                    json!({
+                        "name": name,
+                        "type": account_type,
+                        "openingBalance": { "value": opening, "currency": "JPY" }
+                    }),
+                ),
+            )
+            .await;
+            ids.push(account["accountId"].as_str().unwrap().to_string());
+        }
+
+        let transfer = |to: &str| {
+            json_request(
+                "POST",
+                "/api/transfers",
+                json!({
+                    "fromAccountId": ids[0],
+                    "toAccountId": to,
+                    "amount": { "value": 20000, "currency": "JPY" },
+                    "description": "ATM引き出し",
+                    "transactionDate": "2025-03-01"
+                }),
+            )
+        };
+        let (status, _) = send(app.clone(), transfer("missing")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, body) = send(app.clone(), transfer(&ids[1])).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["transactions"][0]["type"], "TRANSFER");
+        assert_eq!(body["transactions"][1]["direction"], "INCOME");
+        assert_eq!(body["transactions"][1]["transfer"]["fromAccountId"], ids[0]);
+
+        for (id, expected) in [(&ids[0], 30000), (&ids[1], 20000)] {
+            let (_, balance) = send(
+                app.clone(),
+                request("GET", &format!("/api/accounts/{}/balance", id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await;
+            assert_eq!(balance["currentBalance"]["value"], expected);
+        }
+
+        let uri = format!("/api/transfers/{}", body["transferId"].as_str().unwrap());
+        let (status, found) = send(app, request("GET", &uri).body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(found["amount"]["value"], 20000);
+        assert_eq!(found["transactionDate"], "2025-03-01");
+    }
+
+    #[tokio::test]
+    async fn test_account_balance_as_of_users_local_day() {
+        let mut users = MockUserRepository::new();
+        users.expect_find_by_id().returning(|id| {
+            let mut profile = UserProfile::new(UserId::new(id.to_string()));
+            profile.timezone = "America/New_York".to_string();
+            Ok(Some(profile))
+        });
+        let saved = Arc::new(std::sync::Mutex::new(Vec::<Transaction>::new()));
+        let mut transactions = MockTransactionRepository::new();
+        let store = saved.clone();
+        transactions.expect_save().returning(move |t| {
+            store.lock().unwrap().push(t);
+            Ok(())
+        });
+        let store = saved.clone();
+        transactions
+            .expect_find_by_user_id()
+            .returning(move |_| Ok(store.lock().unwrap().clone()));
+        let app = app(users, transactions, no_budgets());
+
+        let (_, account) = send(
+            app.clone(),
+            json_request(
+                "POST",
+                "/api/accounts",
+                json!({
+                    "name": "財布",
+                    "type": "CASH",
+                    "openingBalance": { "value": 10000, "currency": "JPY" }
+                }),
+            ),
+        )
+        .await;
+        let id = account["accountId"].as_str().unwrap();
+
+        // 取引日を省略した取引はユーザーのタイムゾーンでの今日になる
+        let today = Utc::now()
+            .with_timezone(&chrono_tz::America::New_York)
+            .date_naive();
+        for (value, date) in [
+            (3000, Some(today - chrono::Days::new(1))),
+            (2000, None),
+            (1000, Some(today + chrono::Days::new(1))),
+        ] {
+            let mut body = json!({
+                "type": "REAL",
+                "amount": { "value": value, "currency": "JPY" },
+                "description": "テスト",
+                "category": "FOOD",
+                "accountId": id
+            });
+            if let Some(date) = date {
+                body["transactionDate"] = json!(date);
+            }
+            let (status, _) =
+                send(app.clone(), json_request("POST", "/api/transactions", body)).await;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+
+        // UTC より遅れたタイムゾーンでも、指定日の翌日の取引は含めない
+        let balance_on = |date: chrono::NaiveDate| {
+            request(
+                "GET",
+                &format!("/api/accounts/{}/balance?asOf={}", id, date),
+            )
+            .body(Body::empty())
+            .unwrap()
+        };
+        let (status, body) = send(app.clone(), balance_on(today - chrono::Days::new(1))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["currentBalance"]["value"], 7000);
+        let (_, body) = send(app, balance_on(today)).await;
+        assert_eq!(body["currentBalance"]["value"], 5000);
+    }
+
+    #[tokio::test]
+    async fn test_account_rejects_other_currency() {
+        // 通貨の異なる取引は保存しない
+        let app = app(
+            no_profile(),
+            MockTransactionRepository::new(),
+            MockBudgetRepository::new(),
+        );
+        let mut ids = Vec::new();
+        for name in ["銀行", "財布"] {
+            let (_, account) = send(
+                app.clone(),
+                json_request(
+                    "POST",
+                    "/api/accounts",
+                    json!({
+                        "name": name,
+                        "type": "CASH",
+                        "openingBalance": { "value": 10000, "currency": "JPY" }
+                    }),
+                ),
+            )
+            .await;
+            ids.push(account["accountId"].as_str().unwrap().to_string());
+        }
+        let dollars = json!({ "value": 30, "currency": "USD" });
+
+        let (status, body) = send(
+            app.clone(),
+            json_request(
+                "POST",
+                "/api/transactions",
+                json!({
+                    "type": "REAL",
+                    "amount": dollars,
+                    "description": "空港のカフェ",
+                    "category": "FOOD",
+                    "accountId": ids[0]
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "CURRENCY_MISMATCH");
+
+        let (status, body) = send(
+            app,
+            json_request(
+                "POST",
+                "/api/transfers",
+                json!({
+                    "fromAccountId": ids[0],
+                    "toAccountId": ids[1],
+                    "amount": dollars,
+                    "description": "両替",
+                    "transactionDate": "2025-03-01"
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "CURRENCY_MISMATCH");
+    }
+
+    #[tokio::test]
+    async fn test_card_statements_and_upcoming_debits() {
+        let saved = Arc::new(std::sync::Mutex::new(Vec::<Transaction>::new()));
+        let mut transactions = MockTransactionRepository::new();
+        let store = saved.clone();
+        transactions.expect_save().returning(move |t| {
+            store.lock().unwrap().push(t);
+            Ok(())
+        });
+        let store = saved.clone();
+        transactions
+            .expect_find_by_user_id()
+            .returning(move |_| Ok(store.lock().unwrap().clone()));
+        let app = app(no_profile(), transactions, no_budgets());
+
+        let create = |body: Value| json_request("POST", "/api/accounts", body);
+        let (_, bank) = send(
+            app.clone(),
+            create(json!({ "name": "銀行", "type": "BANK" })),
+        )
+        .await;
+        let bank_id = bank["accountId"].as_str().unwrap();
+        let card = |payment_account_id: &str| {
+            create(json!({
+                "name": "カード",
+                "type": "CREDIT_CARD",
+                "billingCycle": {
+                    "closingDay": 15,
+                    "paymentDay": 10,
+                    "paymentAccountId": payment_account_id
+                }
+            }))
+        };
+        let (status, _) = send(app.clone(), card("missing")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, card) = send(app.clone(), card(bank_id)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(card["billingCycle"]["paymentMonthOffset"], 1);
+        let card_id = card["accountId"].as_str().unwrap();
+
+        for date in ["2025-03-01", "2025-03-20"] {
+            let (status, _) = send(
+                app.clone(),
+                json_request(
+                    "POST",
+                    "/api/transactions",
+                    json!({
+                        "type": "REAL",
+                        "amount": { "value": 2000, "currency": "JPY" },
+                        "description": "買い物",
+                        "category": "SHOPPING",
+                        "transactionDate": date,
+                        "accountId": card_id
+                    }),
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+
+        let (status, body) = send(
+            app.clone(),
+            request(
+                "GET",
+                &format!("/api/accounts/{}/statements?asOf=2025-03-20", card_id),
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["statements"][0]["closingDate"], "2025-03-15");
+        assert_eq!(body["statements"][0]["dueDate"], "2025-04-10");
+        assert_eq!(body["statements"][0]["isClosed"], true);
+        assert_eq!(body["unbilledAmount"]["value"], 2000);
+
+        let (status, body) = send(
+            app.clone(),
+            request(
+                "GET",
+                &format!("/api/accounts/{}/upcoming-debits?asOf=2025-03-20", bank_id),
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["debits"].as_array().unwrap().len(), 2);
+        assert_eq!(body["debits"][0]["cardAccountId"], card_id);
+        assert_eq!(body["totalAmount"]["value"], 4000);
+
+        // 締め日のない口座の明細は求められない
+        let (status, _) = send(
+            app,
+            request("GET", &format!("/api/accounts/{}/statements", bank_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_card_statements_default_to_users_local_date() {
+        // UTC+14 と UTC-11 のユーザーの今日は、いつ実行しても UTC の日付をまたいで食い違う
+        let mut dates = Vec::new();
+        for timezone in ["Pacific/Kiritimati", "Pacific/Pago_Pago"] {
+            let mut users = MockUserRepository::new();
+            users.expect_find_by_id().returning(move |id| {
+                let mut profile = UserProfile::new(UserId::new(id.to_string()));
+                profile.timezone = timezone.to_string();
+                Ok(Some(profile))
+            });
+            let mut transactions = MockTransactionRepository::new();
+            transactions
+                .expect_find_by_user_id()
+                .returning(|_| Ok(vec![]));
+            let app = app(users, transactions, no_budgets());
+
+            let (_, card) = send(
+                app.clone(),
+                json_request(
+                    "POST",
+                    "/api/accounts",
+                    json!({
+                        "name": "カード",
+                        "type": "CREDIT_CARD",
+                        "billingCycle": { "closingDay": 15, "paymentDay": 10 }
+                    }),
+                ),
+            )
+            .await;
+            let card_id = card["accountId"].as_str().unwrap();
+
+            let tz: chrono_tz::Tz = timezone.parse().unwrap();
+            let before = Utc::now().with_timezone(&tz).date_naive();
+            let (status, body) = send(
+                app,
+                request("GET", &format!("/api/accounts/{}/statements", card_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await;
+            let after = Utc::now().with_timezone(&tz).date_naive();
+            assert_eq!(status, StatusCode::OK);
+            let as_of: chrono::NaiveDate = body["asOf"].as_str().unwrap().parse().unwrap();
+            assert!(as_of == before || as_of == after);
+            dates.push(as_of);
+        }
+        assert_ne!(dates[0], dates[1]);
+    }
+
+    #[tokio::test]
+    async fn test_categories_and_spending_report() {
+        let saved = Arc::new(std::sync::Mutex::new(Vec::<Transaction>::new()));
+        let mut transactions = MockTransactionRepository::new();
+        let store = saved.clone();
+        transactions.expect_save().returning(move |t| {
+            store.lock().unwrap().push(t);
+            Ok(())
+        });
+        let store = saved.clone();
+        transactions
+            .expect_find_by_user_id()
+            .returning(move |_| Ok(store.lock().unwrap().clone()));
+        let mut users = MockUserRepository::new();
+        users.expect_find_by_id().returning(|_| Ok(None));
+        let app = app(users, transactions, no_budgets());
+
+        let (status, defaults) = send(
+            app.clone(),
+            request("GET", "/api/categories")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let food = defaults
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["kind"] == "FOOD")
+            .unwrap();
+        assert_eq!(food["isDefault"], true);
+        let food_id = food["categoryId"].as_str().unwrap();
+
+        let (status, dining) = send(
+            app.clone(),
+            json_request(
+                "POST",
+                "/api/categories",
+                json!({ "name": "外食", "parentId": food_id, "color": "#FF8800" }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(dining["kind"], "FOOD");
+        let dining_id = dining["categoryId"].as_str().unwrap();
+
+        // 分類を省略しても、カテゴリの分類で記録される
+        let (status, body) = send(
+            app.clone(),
+            json_request(
+                "POST",
+                "/api/transactions",
+                json!({
+                    "type": "REAL",
+                    "amount": { "value": 1500, "currency": "JPY" },
+                    "description": "ランチ",
+                    "categoryId": dining_id,
+                    "transactionDate": "2025-04-03"
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["category"], "FOOD");
+        assert_eq!(body["categoryId"], dining_id);
+        let (status, _) = send(
+            app.clone(),
+            json_request(
+                "POST",
+                "/api/transactions",
+                json!({
+                    "type": "REAL",
+                    "amount": { "value": 500, "currency": "JPY" },
+                    "description": "テスト",
+                    "transactionDate": "2025-04-04"
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, report) = send(
+            app.clone(),
+            request(
+                "GET",
+                "/api/reports/category-spending?startDate=2025-04-01&endDate=2025-04-30",
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(report["categories"][0]["categoryId"], food_id);
+        assert_eq!(report["categories"][0]["ownAmount"]["value"], 0);
+        assert_eq!(report["categories"][0]["totalAmount"]["value"], 1500);
+        assert_eq!(report["categories"][1]["parentId"], food_id);
+        let (status, _) = send(
+            app.clone(),
+            request(
+                "GET",
+                "/api/reports/category-spending?startDate=2025-04-30&endDate=2025-04-01",
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // 親から外してアーカイブすると、新しい取引には使えない
+        let (status, body) = send(
+            app.clone(),
+            json_request(
+                "PUT",
+                &format!("/api/categories/{}", dining_id),
+                json!({ "parentId": null, "isArchived": true }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.get("parentId").is_none());
+        assert_eq!(body["isArchived"], true);
+        let (status, _) = send(
+            app,
+            json_request(
+                "POST",
+                "/api/transactions",
+                json!({
+                    "type": "REAL",
+                    "amount": { "value": 500, "currency": "JPY" },
+                    "description": "テスト",
+                    "categoryId": dining_id
+                }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(saved.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_monthly_report() {
+        let mut users = MockUserRepository::new();
+        users.expect_find_by_id().returning(|_| Ok(None));
+        let transaction = |direction, amount: i64, date: &str| {
+            let mut transaction = Transaction::new(
+                UserId::new("user123".to_string()),
+                TransactionType::Real,
+                Amount::jpy(amount),
+                "テスト".to_string(),
+                TransactionCategory::Food,
+            );
+            transaction.direction = direction;
+            transaction.transaction_date = TransactionDate::new(date.parse().unwrap());
+            transaction
+        };
+        let aggregated = [
+            transaction(TransactionDirection::Income, 300000, "2025-04-25"),
+            transaction(TransactionDirection::Expense, 3000, "2025-04-03"),
+            transaction(TransactionDirection::Expense, 1000, "2025-03-03"),
+        ];
+        // 予算も立て替えもなければ取引を読まずに月次集計だけで求める
+        let transactions = MockTransactionRepository::new();
+        let mut budgets = MockBudgetRepository::new();
+        budgets.expect_find_by_user_id().returning(|_| Ok(vec![]));
+        let app = app_with_aggregates(users, transactions, budgets, &aggregated).await;
+
+        let (status, body) = send(
+            app.clone(),
+            request("GET", "/api/reports/monthly?year=2025&month=4")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["year"], 2025);
+        assert_eq!(body["month"], 4);
+        assert_eq!(body["totalIncome"]["value"], 300000);
+        assert_eq!(body["totalExpense"]["value"], 3000);
+        assert_eq!(body["netAmount"]["value"], 297000);
+        assert_eq!(body["categoryBreakdown"][0]["category"], "FOOD");
+        assert_eq!(body["categoryBreakdown"][0]["percentage"], 100.0);
+        assert_eq!(body["categoryBreakdown"][0]["change"]["value"], 2000);
+        assert_eq!(body["previousMonth"]["totalExpense"]["value"], 1000);
+        assert_eq!(body["monthOverMonth"]["netAmount"]["value"], 298000);
+        assert_eq!(body["outstandingFlowAmount"]["value"], 0);
+
+        let (status, body) = send(
+            app,
+            request("GET", "/api/reports/monthly?year=2025&month=13")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "VALIDATION_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_trend_report() {
+        let mut users = MockUserRepository::new();
+        users.expect_find_by_id().returning(|_| Ok(None));
+        let transaction = |category, amount: i64, date: &str| {
+            let mut transaction = Transaction::new(
+                UserId::new("user123".to_string()),
+                TransactionType::Real,
+                Amount::jpy(amount),
+                "テスト".to_string(),
+                category,
+            );
+            transaction.transaction_date = TransactionDate::new(date.parse().unwrap());
+            transaction
+        };
+        let saved = vec![
+            transaction(TransactionCategory::Food, 3000, "2025-04-03"),
+            transaction(TransactionCategory::Transportation, 1000, "2025-04-03"),
+            transaction(TransactionCategory::Food, 2000, "2025-04-20"),
+        ];
+        // 取引から求めるのは日次の推移（区間数が上限を超える範囲を含む）だけ
+        let mut transactions = MockTransactionRepository::new();
+        let scanned = saved.clone();
+        transactions
+            .expect_find_by_user_id()
+            .times(2)
+            .returning(move |_| Ok(scanned.clone()));
+        let budgets = MockBudgetRepository::new();
+        let app = app_with_aggregates(users, transactions, budgets, &saved).await;
+
+        let (status, body) = send(
+            app.clone(),
+            request(
+                "GET",
+                "/api/reports/trends?granularity=DAILY&startDate=2025-04-01&endDate=2025-04-30",
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["granularity"], "DAILY");
+        assert_eq!(body["totalExpense"]["value"], 6000);
+        assert_eq!(body["points"].as_array().unwrap().len(), 30);
+        let third = &body["points"][2];
+        assert_eq!(third["periodStart"], "2025-04-03");
+        assert_eq!(third["periodEnd"], "2025-04-03");
+        assert_eq!(third["totalExpense"]["value"], 4000);
+        assert_eq!(third["categoryBreakdown"][0]["category"], "FOOD");
+        assert_eq!(third["categoryBreakdown"][0]["percentage"], 75.0);
+
+        let (status, body) = send(
+            app.clone(),
+            request(
+                "GET",
+                "/api/reports/trends?granularity=MONTHLY&startDate=2025-03-01&endDate=2025-04-30",
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["points"].as_array().unwrap().len(), 2);
+        assert_eq!(body["points"][0]["totalExpense"]["value"], 0);
+        assert_eq!(body["points"][1]["totalExpense"]["value"], 6000);
+        assert_eq!(
+            body["points"][1]["categoryBreakdown"][0]["category"],
+            "FOOD"
+        );
+
+        for (query, code) in [
+            ("granularity=DAILY&startDate=2025-04-01", "VALIDATION_ERROR"),
+            (
+                "granularity=DAILY&startDate=2024-01-01&endDate=2025-12-31",
+                "VALIDATION_ERROR",
+            ),
+            ("granularity=WEEKLY", "MALFORMED_REQUEST"),
+        ] {
+            let (status, body) = send(
+                app.clone(),
+                request("GET", &format!("/api/reports/trends?{}", query))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", query);
+            assert_eq!(body["code"], code, "{}", query);
+        }
+    }
 }
@@ -1,12 +1,320 @@
 // DynamoDB リポジトリ実装
+// シングルテーブル設計（docs/design-docs.md 5.3.1）に従い、
+// 全エンティティを PK/SK と `type` 属性で区別して1つのテーブルに格納する
 
 use crate::domain::entities::*;
 use crate::domain::repositories::*;
-use anyhow::Result;
+use crate::domain::value_objects::{TransactionDate, UserId};
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use aws_sdk_dynamodb::types::{AttributeValue, Delete, KeysAndAttributes, Put, TransactWriteItem};
 use aws_sdk_dynamodb::Client;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 逆引き用のグローバルセカンダリインデックス名
+///
+/// - `GSI1PK=TX#<TxID>` / `BUDGET#<BudgetID>`: IDのみからの取引・予算の検索
+/// - `GSI1PK=USER#<UserID>`, `GSI1SK=GROUP#<GroupID>`: ユーザーが所属するグループの検索
+pub const GSI1_NAME: &str = "GSI1";
+
+/// TransactWriteItems 1回あたりの最大アイテム数
+const MAX_TRANSACT_ITEMS: usize = 100;
+
+/// BatchGetItem 1回あたりの最大キー数
+const MAX_BATCH_GET_KEYS: usize = 100;
+
+/// DynamoDB アイテム
+type Item = HashMap<String, AttributeValue>;
+
+/// パーティションキー・ソートキーの組み立て
+pub mod keys {
+    use super::*;
+
+    pub fn user_pk(user_id: &str) -> String {
+        format!("USER#{}", user_id)
+    }
+
+    /// 取引のソートキー（`TX#<Timestamp>#<TxID>`）
+    ///
+    /// タイムスタンプは桁数固定のUTC表記にして、文字列順が時系列順になるようにする
+    pub fn transaction_sk(transaction_date: &TransactionDate, transaction_id: &str) -> String {
+        format!(
+            "TX#{}#{}",
+            transaction_date
+                .timestamp()
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+            transaction_id
+        )
+    }
+
+    pub fn transaction_gsi1pk(transaction_id: &str) -> String {
+        format!("TX#{}", transaction_id)
+    }
+
+    pub const TRANSACTION_SK_PREFIX: &str = "TX#";
+
+    /// 取引ソートキーの範囲の境界
+    ///
+    /// `TX#<Timestamp>` は同時刻のどの取引のソートキーよりも小さく、
+    /// `TX$` はすべての取引のソートキーより大きい
+    pub fn transaction_sk_bound(transaction_date: Option<&TransactionDate>, upper: bool) -> String {
+        match transaction_date {
+            Some(date) => format!(
+                "TX#{}",
+                date.timestamp()
+                    .to_rfc3339_opts(SecondsFormat::Millis, true)
+            ),
+            None if upper => "TX$".to_string(),
+            None => TRANSACTION_SK_PREFIX.to_string(),
+        }
+    }
+
+    pub fn budget_sk(budget_id: &str) -> String {
+        format!("BUDGET#{}", budget_id)
+    }
+
+    pub const BUDGET_SK_PREFIX: &str = "BUDGET#";
+
+    pub fn account_sk(account_id: &str) -> String {
+        format!("ACCOUNT#{}", account_id)
+    }
+
+    pub const ACCOUNT_SK_PREFIX: &str = "ACCOUNT#";
+
+    pub fn category_sk(category_id: &str) -> String {
+        format!("CATEGORY#{}", category_id)
+    }
+
+    pub const CATEGORY_SK_PREFIX: &str = "CATEGORY#";
+
+    /// 月次集計のソートキー（`AGG#<YYYY-MM>`）
+    pub fn aggregate_sk(month: NaiveDate) -> String {
+        format!("AGG#{}", month.format("%Y-%m"))
+    }
+
+    pub const AGGREGATE_SK_PREFIX: &str = "AGG#";
+
+    /// 予算アラートのソートキー（`ALERT#<AlertID>`）
+    pub fn budget_alert_sk(alert_id: &str) -> String {
+        format!("ALERT#{}", alert_id)
+    }
+
+    pub const BUDGET_ALERT_SK_PREFIX: &str = "ALERT#";
+
+    /// 未配信の予算アラートの GSI1 ソートキー（`PENDING_ALERT#<Timestamp>#<AlertID>`）
+    ///
+    /// 文字列順が発生日時の古い順になる
+    pub fn pending_alert_gsi1sk(raised_at: &DateTime<Utc>, alert_id: &str) -> String {
+        format!(
+            "PENDING_ALERT#{}#{}",
+            raised_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+            alert_id
+        )
+    }
+
+    pub const PENDING_ALERT_GSI1SK_PREFIX: &str = "PENDING_ALERT#";
+
+    pub fn settlement_sk(settlement_id: &str) -> String {
+        format!("SETTLEMENT#{}", settlement_id)
+    }
+
+    pub const SETTLEMENT_SK_PREFIX: &str = "SETTLEMENT#";
+
+    pub fn group_pk(group_id: &str) -> String {
+        format!("GROUP#{}", group_id)
+    }
+
+    pub fn member_sk(user_id: &str) -> String {
+        format!("MEMBER#{}", user_id)
+    }
+
+    pub const MEMBER_SK_PREFIX: &str = "MEMBER#";
+
+    /// ユーザー・グループのプロフィールアイテムのソートキー
+    pub const PROFILE_SK: &str = "PROFILE";
+}
+
+/// アイテム種別（`type` 属性の値）
+pub mod item_types {
+    pub const USER_PROFILE: &str = "UserProfile";
+    pub const TRANSACTION: &str = "Transaction";
+    pub const BUDGET: &str = "Budget";
+    pub const ACCOUNT: &str = "Account";
+    pub const CATEGORY: &str = "Category";
+    pub const MONTHLY_AGGREGATE: &str = "MonthlyAggregate";
+    pub const BUDGET_ALERT: &str = "BudgetAlert";
+    pub const GROUP_PROFILE: &str = "GroupProfile";
+    pub const GROUP_MEMBER: &str = "GroupMember";
+    pub const GROUP_TRANSACTION: &str = "GroupTransaction";
+    pub const SETTLEMENT: &str = "Settlement";
+}
+
+/// エンティティをキー属性と `type` 属性付きのアイテムに変換
+fn to_item<T: Serialize>(entity: &T, pk: String, sk: String, item_type: &str) -> Result<Item> {
+    let mut item: Item = serde_dynamo::to_item(entity).context("failed to serialize item")?;
+    item.insert("PK".to_string(), AttributeValue::S(pk));
+    item.insert("SK".to_string(), AttributeValue::S(sk));
+    item.insert("type".to_string(), AttributeValue::S(item_type.to_string()));
+    Ok(item)
+}
+
+/// アイテムをエンティティに変換（キー属性等の余分な属性は無視される）
+fn from_item<T: DeserializeOwned>(item: Item) -> Result<T> {
+    serde_dynamo::from_item(item).context("failed to deserialize item")
+}
+
+/// GSI1 のキー属性を付与
+fn with_gsi1(mut item: Item, gsi1pk: String, gsi1sk: String) -> Item {
+    item.insert("GSI1PK".to_string(), AttributeValue::S(gsi1pk));
+    item.insert("GSI1SK".to_string(), AttributeValue::S(gsi1sk));
+    item
+}
+
+fn string_attr(item: &Item, name: &str) -> Result<String> {
+    match item.get(name) {
+        Some(AttributeValue::S(value)) => Ok(value.clone()),
+        _ => anyhow::bail!("item has no string attribute `{}`", name),
+    }
+}
+
+/// 主キー属性を組み立てる
+fn primary_key(pk: String, sk: String) -> Item {
+    HashMap::from([
+        ("PK".to_string(), AttributeValue::S(pk)),
+        ("SK".to_string(), AttributeValue::S(sk)),
+    ])
+}
+
+/// 格納済みアイテムから主キー（PK/SK）のみを取り出す
+fn primary_key_of(item: &Item) -> Result<Item> {
+    Ok(primary_key(
+        string_attr(item, "PK")?,
+        string_attr(item, "SK")?,
+    ))
+}
+
+/// GSI1PK が一致する最初のアイテムを取得
+async fn find_by_gsi1pk(client: &Client, table_name: &str, gsi1pk: String) -> Result<Option<Item>> {
+    let output = client
+        .query()
+        .table_name(table_name)
+        .index_name(GSI1_NAME)
+        .key_condition_expression("GSI1PK = :pk")
+        .expression_attribute_values(":pk", AttributeValue::S(gsi1pk))
+        .limit(1)
+        .send()
+        .await
+        .context("failed to query GSI1")?;
+
+    Ok(output.items.unwrap_or_default().into_iter().next())
+}
+
+/// パーティション内でソートキーが前方一致するアイテムを全件取得
+async fn query_by_sk_prefix(
+    client: &Client,
+    table_name: &str,
+    pk: String,
+    sk_prefix: &str,
+    scan_forward: bool,
+) -> Result<Vec<Item>> {
+    client
+        .query()
+        .table_name(table_name)
+        .key_condition_expression("PK = :pk AND begins_with(SK, :sk)")
+        .expression_attribute_values(":pk", AttributeValue::S(pk))
+        .expression_attribute_values(":sk", AttributeValue::S(sk_prefix.to_string()))
+        .scan_index_forward(scan_forward)
+        .into_paginator()
+        .items()
+        .send()
+        .try_collect()
+        .await
+        .context("failed to query items by sort key prefix")
+}
+
+fn put(table_name: &str, item: Item) -> Result<TransactWriteItem> {
+    let put = Put::builder()
+        .table_name(table_name)
+        .set_item(Some(item))
+        .build()?;
+    Ok(TransactWriteItem::builder().put(put).build())
+}
+
+fn delete(table_name: &str, key: Item) -> Result<TransactWriteItem> {
+    let delete = Delete::builder()
+        .table_name(table_name)
+        .set_key(Some(key))
+        .build()?;
+    Ok(TransactWriteItem::builder().delete(delete).build())
+}
+
+/// 複数アイテムの書き込みを1トランザクションで実行
+async fn transact_write(client: &Client, items: Vec<TransactWriteItem>) -> Result<()> {
+    if items.len() > MAX_TRANSACT_ITEMS {
+        anyhow::bail!(
+            "transaction has {} items, exceeding the limit of {}",
+            items.len(),
+            MAX_TRANSACT_ITEMS
+        );
+    }
+
+    client
+        .transact_write_items()
+        .set_transact_items(Some(items))
+        .send()
+        .await
+        .context("failed to write items transactionally")?;
+    Ok(())
+}
+
+/// 書き込みを `MAX_TRANSACT_ITEMS` ごとのトランザクションに分けて順に実行
+///
+/// 上限以内なら1トランザクションで実行する。超える場合は分けたトランザクションごとにしか
+/// 原子的にならないため、途中で失敗しても読み出しが壊れない順に `items` を並べておくこと
+async fn transact_write_chunked(client: &Client, mut items: Vec<TransactWriteItem>) -> Result<()> {
+    while !items.is_empty() {
+        let rest = items.split_off(items.len().min(MAX_TRANSACT_ITEMS));
+        transact_write(client, items).await?;
+        items = rest;
+    }
+    Ok(())
+}
+
+/// 主キーを指定して複数アイテムを取得（存在しないキーは結果に含まれず、順序は保証されない）
+async fn batch_get(client: &Client, table_name: &str, keys: Vec<Item>) -> Result<Vec<Item>> {
+    let mut items = Vec::with_capacity(keys.len());
+    for chunk in keys.chunks(MAX_BATCH_GET_KEYS) {
+        let mut pending = Some(
+            KeysAndAttributes::builder()
+                .set_keys(Some(chunk.to_vec()))
+                .build()?,
+        );
+        // 未処理として返されたキーは取得できるまで再要求する
+        while let Some(request) = pending.take() {
+            let output = client
+                .batch_get_item()
+                .request_items(table_name, request)
+                .send()
+                .await
+                .context("failed to batch get items")?;
+            if let Some(mut responses) = output.responses {
+                items.extend(responses.remove(table_name).unwrap_or_default());
+            }
+            pending = output
+                .unprocessed_keys
+                .and_then(|mut unprocessed| unprocessed.remove(table_name))
+                .filter(|request| !request.keys.is_empty());
+        }
+    }
+    Ok(items)
+}
 
 /// DynamoDB ユーザーリポジトリ
+///
+/// `PK=USER#<UserID>`, `SK=PROFILE` に格納する
 pub struct DynamoUserRepository {
     client: Client,
     table_name: String,
@@ -21,27 +329,62 @@ impl DynamoUserRepository {
 #[async_trait]
 impl UserRepository for DynamoUserRepository {
     async fn find_by_id(&self, user_id: &str) -> Result<Option<UserProfile>> {
-        // DynamoDB実装（簡略版）
-        Ok(None)
+        let output = self
+            .client
+            .get_item()
+            .table_name(&self.table_name)
+            .set_key(Some(primary_key(
+                keys::user_pk(user_id),
+                keys::PROFILE_SK.to_string(),
+            )))
+            .send()
+            .await
+            .context("failed to get user profile")?;
+
+        output.item.map(from_item).transpose()
     }
 
     async fn save(&self, user: UserProfile) -> Result<()> {
-        // DynamoDB実装（簡略版）
+        let item = to_item(
+            &user,
+            keys::user_pk(user.user_id.value()),
+            keys::PROFILE_SK.to_string(),
+            item_types::USER_PROFILE,
+        )?;
+
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .send()
+            .await
+            .context("failed to put user profile")?;
         Ok(())
     }
 
     async fn update(&self, user: UserProfile) -> Result<()> {
-        // DynamoDB実装（簡略版）
-        Ok(())
+        self.save(user).await
     }
 
     async fn delete(&self, user_id: &str) -> Result<()> {
-        // DynamoDB実装（簡略版）
+        self.client
+            .delete_item()
+            .table_name(&self.table_name)
+            .set_key(Some(primary_key(
+                keys::user_pk(user_id),
+                keys::PROFILE_SK.to_string(),
+            )))
+            .send()
+            .await
+            .context("failed to delete user profile")?;
         Ok(())
     }
 }
 
 /// DynamoDB 取引リポジトリ
+///
+/// `PK=USER#<UserID>`, `SK=TX#<Timestamp>#<TxID>` に格納し、
+/// 取引IDのみでの検索は `GSI1PK=TX#<TxID>` で GSI1 を引く
 pub struct DynamoTransactionRepository {
     client: Client,
     table_name: String,
@@ -51,37 +394,225 @@ impl DynamoTransactionRepository {
     pub fn new(client: Client, table_name: String) -> Self {
         Self { client, table_name }
     }
+
+    fn to_item(transaction: &Transaction) -> Result<Item> {
+        let user_pk = keys::user_pk(transaction.user_id.value());
+        let item = to_item(
+            transaction,
+            user_pk.clone(),
+            keys::transaction_sk(
+                &transaction.transaction_date,
+                transaction.transaction_id.value(),
+            ),
+            item_types::TRANSACTION,
+        )?;
+        Ok(with_gsi1(
+            item,
+            keys::transaction_gsi1pk(transaction.transaction_id.value()),
+            user_pk,
+        ))
+    }
+
+    /// 取引IDから GSI1 経由で格納済みアイテムを取得
+    async fn find_item(&self, transaction_id: &str) -> Result<Option<Item>> {
+        find_by_gsi1pk(
+            &self.client,
+            &self.table_name,
+            keys::transaction_gsi1pk(transaction_id),
+        )
+        .await
+    }
 }
 
 #[async_trait]
 impl TransactionRepository for DynamoTransactionRepository {
     async fn find_by_id(&self, transaction_id: &str) -> Result<Option<Transaction>> {
-        // DynamoDB実装（簡略版）
-        Ok(None)
+        self.find_item(transaction_id)
+            .await?
+            .map(from_item)
+            .transpose()
     }
 
     async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Transaction>> {
-        // DynamoDB実装（簡略版）
-        Ok(vec![])
+        // 新しい取引から順に返す
+        query_by_sk_prefix(
+            &self.client,
+            &self.table_name,
+            keys::user_pk(user_id),
+            keys::TRANSACTION_SK_PREFIX,
+            false,
+        )
+        .await?
+        .into_iter()
+        .map(from_item)
+        .collect()
+    }
+
+    async fn find_page(&self, user_id: &str, query: &TransactionQuery) -> Result<TransactionPage> {
+        let user_pk = keys::user_pk(user_id);
+        let mut request = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .key_condition_expression("PK = :pk AND SK BETWEEN :lower AND :upper")
+            .expression_attribute_values(":pk", AttributeValue::S(user_pk.clone()))
+            .expression_attribute_values(
+                ":lower",
+                AttributeValue::S(keys::transaction_sk_bound(query.from.as_ref(), false)),
+            )
+            .expression_attribute_values(
+                ":upper",
+                AttributeValue::S(keys::transaction_sk_bound(query.until.as_ref(), true)),
+            )
+            .scan_index_forward(false);
+
+        let mut filters = Vec::new();
+        if let Some(transaction_type) = &query.transaction_type {
+            filters.push("#tt = :tt");
+            request = request
+                .expression_attribute_names("#tt", "transaction_type")
+                .expression_attribute_values(
+                    ":tt",
+                    serde_dynamo::to_attribute_value(transaction_type)?,
+                );
+        }
+        if let Some(direction) = &query.direction {
+            // 向きの導入前に保存された取引は属性を持たず、支出として扱う
+            filters.push(match direction {
+                TransactionDirection::Expense => "(attribute_not_exists(#dir) OR #dir = :dir)",
+                TransactionDirection::Income => "#dir = :dir",
+            });
+            request = request
+                .expression_attribute_names("#dir", "direction")
+                .expression_attribute_values(":dir", serde_dynamo::to_attribute_value(direction)?);
+        }
+        if let Some(category) = &query.category {
+            filters.push("#cat = :cat");
+            request = request
+                .expression_attribute_names("#cat", "category")
+                .expression_attribute_values(":cat", serde_dynamo::to_attribute_value(category)?);
+        }
+        if !filters.is_empty() {
+            request = request.filter_expression(filters.join(" AND "));
+        }
+
+        // ソートキーはミリ秒精度のため、範囲の端はエンティティの日時で改めて判定する
+        let range = TransactionQuery {
+            after: None,
+            ..query.clone()
+        };
+        let mut start_key = query.after.as_ref().map(|cursor| {
+            primary_key(
+                user_pk.clone(),
+                keys::transaction_sk(&cursor.transaction_date, &cursor.transaction_id),
+            )
+        });
+        let mut transactions = Vec::new();
+        // フィルタは読み出し後に適用されるため、続きの有無が分かるまで読み進める
+        loop {
+            let output = request
+                .clone()
+                .set_exclusive_start_key(start_key.take())
+                .limit((query.limit + 1 - transactions.len()) as i32)
+                .send()
+                .await
+                .context("failed to query transactions")?;
+            for item in output.items.unwrap_or_default() {
+                let transaction: Transaction = from_item(item)?;
+                if range.matches(&transaction) {
+                    transactions.push(transaction);
+                }
+            }
+            start_key = output.last_evaluated_key;
+            if transactions.len() > query.limit || start_key.is_none() {
+                break;
+            }
+        }
+        transactions.truncate(query.limit + 1);
+        Ok(TransactionPage::from_overfetch(transactions, query.limit))
     }
 
     async fn save(&self, transaction: Transaction) -> Result<()> {
-        // DynamoDB実装（簡略版）
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(Self::to_item(&transaction)?))
+            .send()
+            .await
+            .context("failed to put transaction")?;
         Ok(())
     }
 
+    async fn save_all(&self, transactions: Vec<Transaction>) -> Result<()> {
+        if transactions.is_empty() {
+            return Ok(());
+        }
+        let items = transactions
+            .iter()
+            .map(|transaction| put(&self.table_name, Self::to_item(transaction)?))
+            .collect::<Result<Vec<_>>>()?;
+        transact_write(&self.client, items)
+            .await
+            .context("failed to put transactions")
+    }
+
     async fn update(&self, transaction: Transaction) -> Result<()> {
-        // DynamoDB実装（簡略版）
-        Ok(())
+        let item = Self::to_item(&transaction)?;
+
+        // 取引日が変わるとソートキーも変わるため、旧アイテムの削除と新アイテムの書き込みを
+        // 1トランザクションで行う
+        let existing = match self.find_item(transaction.transaction_id.value()).await? {
+            Some(existing) if existing.get("SK") != item.get("SK") => existing,
+            _ => return self.save(transaction).await,
+        };
+
+        transact_write(
+            &self.client,
+            vec![
+                delete(&self.table_name, primary_key_of(&existing)?)?,
+                put(&self.table_name, item)?,
+            ],
+        )
+        .await
+        .context("failed to move transaction to new sort key")
     }
 
     async fn delete(&self, transaction_id: &str) -> Result<()> {
-        // DynamoDB実装（簡略版）
+        let Some(existing) = self.find_item(transaction_id).await? else {
+            return Ok(());
+        };
+
+        self.client
+            .delete_item()
+            .table_name(&self.table_name)
+            .set_key(Some(primary_key_of(&existing)?))
+            .send()
+            .await
+            .context("failed to delete transaction")?;
         Ok(())
     }
+
+    async fn delete_all(&self, transaction_ids: &[String]) -> Result<()> {
+        // ソートキーに取引日時を含むため、格納済みアイテムから主キーを引いてから削除する
+        let mut items = Vec::new();
+        for transaction_id in transaction_ids {
+            if let Some(existing) = self.find_item(transaction_id).await? {
+                items.push(delete(&self.table_name, primary_key_of(&existing)?)?);
+            }
+        }
+        if items.is_empty() {
+            return Ok(());
+        }
+        transact_write(&self.client, items)
+            .await
+            .context("failed to delete transactions")
+    }
 }
 
 /// DynamoDB 予算リポジトリ
+///
+/// `PK=USER#<UserID>`, `SK=BUDGET#<BudgetID>` に格納し、
+/// 予算IDのみでの検索は `GSI1PK=BUDGET#<BudgetID>` で GSI1 を引く
 pub struct DynamoBudgetRepository {
     client: Client,
     table_name: String,
@@ -91,37 +622,406 @@ impl DynamoBudgetRepository {
     pub fn new(client: Client, table_name: String) -> Self {
         Self { client, table_name }
     }
+
+    fn to_item(budget: &Budget) -> Result<Item> {
+        let user_pk = keys::user_pk(budget.user_id.value());
+        let item = to_item(
+            budget,
+            user_pk.clone(),
+            keys::budget_sk(&budget.budget_id),
+            item_types::BUDGET,
+        )?;
+        Ok(with_gsi1(item, keys::budget_sk(&budget.budget_id), user_pk))
+    }
 }
 
 #[async_trait]
 impl BudgetRepository for DynamoBudgetRepository {
     async fn find_by_id(&self, budget_id: &str) -> Result<Option<Budget>> {
-        // DynamoDB実装（簡略版）
-        Ok(None)
+        find_by_gsi1pk(&self.client, &self.table_name, keys::budget_sk(budget_id))
+            .await?
+            .map(from_item)
+            .transpose()
     }
 
     async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Budget>> {
-        // DynamoDB実装（簡略版）
-        Ok(vec![])
+        query_by_sk_prefix(
+            &self.client,
+            &self.table_name,
+            keys::user_pk(user_id),
+            keys::BUDGET_SK_PREFIX,
+            true,
+        )
+        .await?
+        .into_iter()
+        .map(from_item)
+        .collect()
     }
 
     async fn save(&self, budget: Budget) -> Result<()> {
-        // DynamoDB実装（簡略版）
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(Self::to_item(&budget)?))
+            .send()
+            .await
+            .context("failed to put budget")?;
         Ok(())
     }
 
     async fn update(&self, budget: Budget) -> Result<()> {
-        // DynamoDB実装（簡略版）
-        Ok(())
+        self.save(budget).await
     }
 
     async fn delete(&self, budget_id: &str) -> Result<()> {
-        // DynamoDB実装（簡略版）
+        let Some(existing) =
+            find_by_gsi1pk(&self.client, &self.table_name, keys::budget_sk(budget_id)).await?
+        else {
+            return Ok(());
+        };
+
+        self.client
+            .delete_item()
+            .table_name(&self.table_name)
+            .set_key(Some(primary_key_of(&existing)?))
+            .send()
+            .await
+            .context("failed to delete budget")?;
+        Ok(())
+    }
+}
+
+/// DynamoDB 口座リポジトリ
+///
+/// `USER#<UserID>` / `ACCOUNT#<AccountID>` に保存し、GSI1 で口座IDから引けるようにする
+pub struct DynamoAccountRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DynamoAccountRepository {
+    pub fn new(client: Client, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+
+    fn to_item(account: &Account) -> Result<Item> {
+        let user_pk = keys::user_pk(account.user_id.value());
+        let item = to_item(
+            account,
+            user_pk.clone(),
+            keys::account_sk(&account.account_id),
+            item_types::ACCOUNT,
+        )?;
+        Ok(with_gsi1(
+            item,
+            keys::account_sk(&account.account_id),
+            user_pk,
+        ))
+    }
+}
+
+#[async_trait]
+impl AccountRepository for DynamoAccountRepository {
+    async fn find_by_id(&self, account_id: &str) -> Result<Option<Account>> {
+        find_by_gsi1pk(&self.client, &self.table_name, keys::account_sk(account_id))
+            .await?
+            .map(from_item)
+            .transpose()
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Account>> {
+        query_by_sk_prefix(
+            &self.client,
+            &self.table_name,
+            keys::user_pk(user_id),
+            keys::ACCOUNT_SK_PREFIX,
+            true,
+        )
+        .await?
+        .into_iter()
+        .map(from_item)
+        .collect()
+    }
+
+    async fn save(&self, account: Account) -> Result<()> {
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(Self::to_item(&account)?))
+            .send()
+            .await
+            .context("failed to put account")?;
+        Ok(())
+    }
+
+    async fn update(&self, account: Account) -> Result<()> {
+        self.save(account).await
+    }
+
+    async fn delete(&self, account_id: &str) -> Result<()> {
+        let Some(existing) =
+            find_by_gsi1pk(&self.client, &self.table_name, keys::account_sk(account_id)).await?
+        else {
+            return Ok(());
+        };
+
+        self.client
+            .delete_item()
+            .table_name(&self.table_name)
+            .set_key(Some(primary_key_of(&existing)?))
+            .send()
+            .await
+            .context("failed to delete account")?;
+        Ok(())
+    }
+}
+
+/// DynamoDB カテゴリリポジトリ
+///
+/// `USER#<UserID>` / `CATEGORY#<CategoryID>` に保存し、GSI1 でカテゴリIDから引けるようにする
+pub struct DynamoCategoryRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DynamoCategoryRepository {
+    pub fn new(client: Client, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+
+    fn to_item(category: &Category) -> Result<Item> {
+        let user_pk = keys::user_pk(category.user_id.value());
+        let item = to_item(
+            category,
+            user_pk.clone(),
+            keys::category_sk(&category.category_id),
+            item_types::CATEGORY,
+        )?;
+        Ok(with_gsi1(
+            item,
+            keys::category_sk(&category.category_id),
+            user_pk,
+        ))
+    }
+}
+
+#[async_trait]
+impl CategoryRepository for DynamoCategoryRepository {
+    async fn find_by_id(&self, category_id: &str) -> Result<Option<Category>> {
+        find_by_gsi1pk(
+            &self.client,
+            &self.table_name,
+            keys::category_sk(category_id),
+        )
+        .await?
+        .map(from_item)
+        .transpose()
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Category>> {
+        query_by_sk_prefix(
+            &self.client,
+            &self.table_name,
+            keys::user_pk(user_id),
+            keys::CATEGORY_SK_PREFIX,
+            true,
+        )
+        .await?
+        .into_iter()
+        .map(from_item)
+        .collect()
+    }
+
+    async fn save(&self, category: Category) -> Result<()> {
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(Self::to_item(&category)?))
+            .send()
+            .await
+            .context("failed to put category")?;
         Ok(())
     }
+
+    async fn save_all(&self, categories: Vec<Category>) -> Result<()> {
+        if categories.is_empty() {
+            return Ok(());
+        }
+        let items = categories
+            .iter()
+            .map(|category| put(&self.table_name, Self::to_item(category)?))
+            .collect::<Result<Vec<_>>>()?;
+        transact_write(&self.client, items)
+            .await
+            .context("failed to put categories")
+    }
+
+    async fn update(&self, category: Category) -> Result<()> {
+        self.save(category).await
+    }
+}
+
+/// DynamoDB グループ取引リポジトリ
+///
+/// `PK=GROUP#<GroupID>`, `SK=TX#<Timestamp>#<TxID>` に格納し、グループの取引を取引日順に引く
+pub struct DynamoGroupTransactionRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DynamoGroupTransactionRepository {
+    pub fn new(client: Client, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+}
+
+#[async_trait]
+impl GroupTransactionRepository for DynamoGroupTransactionRepository {
+    async fn find_by_group_id(&self, group_id: &str) -> Result<Vec<GroupTransaction>> {
+        query_by_sk_prefix(
+            &self.client,
+            &self.table_name,
+            keys::group_pk(group_id),
+            keys::TRANSACTION_SK_PREFIX,
+            true,
+        )
+        .await?
+        .into_iter()
+        .map(from_item)
+        .collect()
+    }
+
+    async fn save(
+        &self,
+        transaction: GroupTransaction,
+        payer_transaction: Option<Transaction>,
+    ) -> Result<()> {
+        let item = to_item(
+            &transaction,
+            keys::group_pk(&transaction.group_id),
+            keys::transaction_sk(
+                &transaction.transaction_date,
+                transaction.transaction_id.value(),
+            ),
+            item_types::GROUP_TRANSACTION,
+        )?;
+        let mut items = vec![put(&self.table_name, item)?];
+        // 立て替え分の取引はユーザーの取引と同じ形で書き込み、取引一覧や月次集計に現れるようにする
+        if let Some(payer_transaction) = &payer_transaction {
+            items.push(put(
+                &self.table_name,
+                DynamoTransactionRepository::to_item(payer_transaction)?,
+            )?);
+        }
+        transact_write(&self.client, items)
+            .await
+            .context("failed to put group transaction")
+    }
+}
+
+/// DynamoDB 精算リポジトリ
+///
+/// 債権者・債務者それぞれの `PK=USER#<UserID>`, `SK=SETTLEMENT#<SettlementID>` に
+/// 同じ内容のアイテムを1トランザクションで書き込み、どちらの立場からも1回のクエリで引けるようにする。
+/// 精算IDのみでの検索は、債権者側のアイテムに付与した `GSI1PK=SETTLEMENT#<SettlementID>` で GSI1 を引く
+pub struct DynamoSettlementRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DynamoSettlementRepository {
+    pub fn new(client: Client, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+
+    fn items(settlement: &Settlement) -> Result<[Item; 2]> {
+        let sk = keys::settlement_sk(&settlement.settlement_id);
+        let creditor_pk = keys::user_pk(settlement.creditor_user_id.value());
+        let creditor = to_item(
+            settlement,
+            creditor_pk.clone(),
+            sk.clone(),
+            item_types::SETTLEMENT,
+        )?;
+        let debtor = to_item(
+            settlement,
+            keys::user_pk(settlement.debtor_user_id.value()),
+            sk.clone(),
+            item_types::SETTLEMENT,
+        )?;
+        Ok([with_gsi1(creditor, sk, creditor_pk), debtor])
+    }
+}
+
+#[async_trait]
+impl SettlementRepository for DynamoSettlementRepository {
+    async fn find_by_id(&self, settlement_id: &str) -> Result<Option<Settlement>> {
+        find_by_gsi1pk(
+            &self.client,
+            &self.table_name,
+            keys::settlement_sk(settlement_id),
+        )
+        .await?
+        .map(from_item)
+        .transpose()
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Settlement>> {
+        query_by_sk_prefix(
+            &self.client,
+            &self.table_name,
+            keys::user_pk(user_id),
+            keys::SETTLEMENT_SK_PREFIX,
+            true,
+        )
+        .await?
+        .into_iter()
+        .map(from_item)
+        .collect()
+    }
+
+    async fn save(&self, settlement: Settlement) -> Result<()> {
+        let items = Self::items(&settlement)?
+            .into_iter()
+            .map(|item| put(&self.table_name, item))
+            .collect::<Result<Vec<_>>>()?;
+        transact_write(&self.client, items)
+            .await
+            .context("failed to put settlement")
+    }
+
+    async fn update(&self, settlement: Settlement) -> Result<()> {
+        // 債権者・債務者は変更されないため、同じキーへの上書きで済む
+        self.save(settlement).await
+    }
+}
+
+/// グループプロフィールアイテム（メンバー一覧は別アイテムに持つ）
+#[derive(Serialize, Deserialize)]
+struct GroupProfileItem {
+    group_id: String,
+    name: String,
+    description: String,
+    owner_id: UserId,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+/// グループメンバーアイテム
+#[derive(Serialize, Deserialize)]
+struct GroupMemberItem {
+    group_id: String,
+    user_id: UserId,
+    /// `Group.members` 内での並び順
+    position: usize,
 }
 
 /// DynamoDB グループリポジトリ
+///
+/// `PK=GROUP#<GroupID>` に `SK=PROFILE` のプロフィールと `SK=MEMBER#<UserID>` の
+/// メンバーアイテムを格納する。メンバーアイテムには `GSI1PK=USER#<UserID>`,
+/// `GSI1SK=GROUP#<GroupID>` を付与し、ユーザーの所属グループを GSI1 で引けるようにする。
+/// メンバーが増えても書き込めるよう、保存時は追加・並び替え・削除されたメンバーのアイテムだけを書き込む
 pub struct DynamoGroupRepository {
     client: Client,
     table_name: String,
@@ -131,32 +1031,507 @@ impl DynamoGroupRepository {
     pub fn new(client: Client, table_name: String) -> Self {
         Self { client, table_name }
     }
+
+    fn profile_item(group: &Group) -> Result<Item> {
+        let profile = GroupProfileItem {
+            group_id: group.group_id.clone(),
+            name: group.name.clone(),
+            description: group.description.clone(),
+            owner_id: group.owner_id.clone(),
+            created_at: group.created_at,
+            updated_at: group.updated_at,
+        };
+        to_item(
+            &profile,
+            keys::group_pk(&group.group_id),
+            keys::PROFILE_SK.to_string(),
+            item_types::GROUP_PROFILE,
+        )
+    }
+
+    fn member_item(group_id: &str, user_id: &UserId, position: usize) -> Result<Item> {
+        let member = GroupMemberItem {
+            group_id: group_id.to_string(),
+            user_id: user_id.clone(),
+            position,
+        };
+        let item = to_item(
+            &member,
+            keys::group_pk(group_id),
+            keys::member_sk(user_id.value()),
+            item_types::GROUP_MEMBER,
+        )?;
+        Ok(with_gsi1(
+            item,
+            keys::user_pk(user_id.value()),
+            keys::group_pk(group_id),
+        ))
+    }
+
+    async fn find_members(&self, group_id: &str) -> Result<Vec<GroupMemberItem>> {
+        let mut members = query_by_sk_prefix(
+            &self.client,
+            &self.table_name,
+            keys::group_pk(group_id),
+            keys::MEMBER_SK_PREFIX,
+            true,
+        )
+        .await?
+        .into_iter()
+        .map(from_item::<GroupMemberItem>)
+        .collect::<Result<Vec<_>>>()?;
+        members.sort_by_key(|member| member.position);
+        Ok(members)
+    }
+
+    /// 変更のあったメンバーアイテムとプロフィールを書き込み、不要になったメンバーアイテムを削除
+    ///
+    /// 並び順（`position`）は昇順でさえあればよいため、既存メンバーの位置は並びが崩れない限り保ち、
+    /// 追加や並び替えのあったメンバーだけに直前のメンバーより大きい位置を割り当てる。
+    /// 書き込みが1トランザクションに収まらない場合は、メンバーの変更を先に、プロフィールを最後に書き込む
+    async fn write(&self, group: &Group) -> Result<()> {
+        let existing: HashMap<UserId, usize> = self
+            .find_members(&group.group_id)
+            .await?
+            .into_iter()
+            .map(|member| (member.user_id, member.position))
+            .collect();
+
+        let mut items = Vec::new();
+        let mut last: Option<usize> = None;
+        for user_id in &group.members {
+            let position = match existing.get(user_id) {
+                Some(&position) if last.is_none_or(|last| position > last) => position,
+                _ => {
+                    let position = last.map_or(0, |last| last + 1);
+                    items.push(put(
+                        &self.table_name,
+                        Self::member_item(&group.group_id, user_id, position)?,
+                    )?);
+                    position
+                }
+            };
+            last = Some(position);
+        }
+        for user_id in existing.keys() {
+            if !group.members.contains(user_id) {
+                items.push(delete(
+                    &self.table_name,
+                    primary_key(
+                        keys::group_pk(&group.group_id),
+                        keys::member_sk(user_id.value()),
+                    ),
+                )?);
+            }
+        }
+        items.push(put(&self.table_name, Self::profile_item(group)?)?);
+
+        transact_write_chunked(&self.client, items).await
+    }
+
+    fn into_group(profile: GroupProfileItem, members: Vec<GroupMemberItem>) -> Group {
+        Group {
+            group_id: profile.group_id,
+            name: profile.name,
+            description: profile.description,
+            owner_id: profile.owner_id,
+            members: members.into_iter().map(|member| member.user_id).collect(),
+            created_at: profile.created_at,
+            updated_at: profile.updated_at,
+        }
+    }
 }
 
 #[async_trait]
 impl GroupRepository for DynamoGroupRepository {
     async fn find_by_id(&self, group_id: &str) -> Result<Option<Group>> {
-        // DynamoDB実装（簡略版）
-        Ok(None)
+        let output = self
+            .client
+            .get_item()
+            .table_name(&self.table_name)
+            .set_key(Some(primary_key(
+                keys::group_pk(group_id),
+                keys::PROFILE_SK.to_string(),
+            )))
+            .send()
+            .await
+            .context("failed to get group profile")?;
+
+        let Some(item) = output.item else {
+            return Ok(None);
+        };
+        let profile: GroupProfileItem = from_item(item)?;
+        let members = self.find_members(group_id).await?;
+
+        Ok(Some(Self::into_group(profile, members)))
     }
 
     async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Group>> {
-        // DynamoDB実装（簡略版）
-        Ok(vec![])
+        let memberships: Vec<Item> = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .index_name(GSI1_NAME)
+            .key_condition_expression("GSI1PK = :pk AND begins_with(GSI1SK, :sk)")
+            .expression_attribute_values(":pk", AttributeValue::S(keys::user_pk(user_id)))
+            .expression_attribute_values(":sk", AttributeValue::S(keys::group_pk("")))
+            .into_paginator()
+            .items()
+            .send()
+            .try_collect()
+            .await
+            .context("failed to query group memberships")?;
+
+        let group_ids = memberships
+            .into_iter()
+            .map(|membership| Ok(from_item::<GroupMemberItem>(membership)?.group_id))
+            .collect::<Result<Vec<_>>>()?;
+
+        // プロフィールはまとめて取得し、メンバー一覧はグループごとの問い合わせを並行して行う
+        let mut members = tokio::task::JoinSet::new();
+        for group_id in &group_ids {
+            let repository = Self::new(self.client.clone(), self.table_name.clone());
+            let group_id = group_id.clone();
+            members.spawn(async move {
+                let members = repository.find_members(&group_id).await;
+                (group_id, members)
+            });
+        }
+        let profile_keys = group_ids
+            .iter()
+            .map(|group_id| primary_key(keys::group_pk(group_id), keys::PROFILE_SK.to_string()))
+            .collect();
+        let mut profiles: HashMap<String, GroupProfileItem> =
+            batch_get(&self.client, &self.table_name, profile_keys)
+                .await?
+                .into_iter()
+                .map(|item| {
+                    let profile: GroupProfileItem = from_item(item)?;
+                    Ok((profile.group_id.clone(), profile))
+                })
+                .collect::<Result<_>>()?;
+
+        // GSI1 の並び（グループIDの昇順）で返す
+        let mut groups: Vec<Group> = Vec::with_capacity(group_ids.len());
+        while let Some(joined) = members.join_next().await {
+            let (group_id, group_members) = joined.context("group member query panicked")?;
+            if let Some(profile) = profiles.remove(&group_id) {
+                groups.push(Self::into_group(profile, group_members?));
+            }
+        }
+        groups.sort_by(|a, b| a.group_id.cmp(&b.group_id));
+        Ok(groups)
     }
 
     async fn save(&self, group: Group) -> Result<()> {
-        // DynamoDB実装（簡略版）
-        Ok(())
+        self.write(&group).await
     }
 
     async fn update(&self, group: Group) -> Result<()> {
-        // DynamoDB実装（簡略版）
-        Ok(())
+        self.write(&group).await
     }
 
     async fn delete(&self, group_id: &str) -> Result<()> {
-        // DynamoDB実装（簡略版）
+        let mut items = vec![delete(
+            &self.table_name,
+            primary_key(keys::group_pk(group_id), keys::PROFILE_SK.to_string()),
+        )?];
+        for member in self.find_members(group_id).await? {
+            items.push(delete(
+                &self.table_name,
+                primary_key(
+                    keys::group_pk(group_id),
+                    keys::member_sk(member.user_id.value()),
+                ),
+            )?);
+        }
+
+        // プロフィールを先に消し、分割された場合も途中でグループが見えるままにならないようにする
+        transact_write_chunked(&self.client, items).await
+    }
+}
+
+/// 月次集計の書き込みが競合したときに読み直して再試行する回数
+const MAX_AGGREGATE_ATTEMPTS: usize = 10;
+
+/// DynamoDB 月次集計リポジトリ
+///
+/// `PK=USER#<UserID>`, `SK=AGG#<YYYY-MM>` に格納する。差分の加算は `version` 属性による
+/// 楽観ロックで行い、読み出し後に他から書き込まれていれば読み直して再試行する
+pub struct DynamoAggregateRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DynamoAggregateRepository {
+    pub fn new(client: Client, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+
+    fn to_item(aggregate: &MonthlyAggregate, version: u64) -> Result<Item> {
+        let mut item = to_item(
+            aggregate,
+            keys::user_pk(aggregate.user_id.value()),
+            keys::aggregate_sk(aggregate.month),
+            item_types::MONTHLY_AGGREGATE,
+        )?;
+        item.insert(
+            "version".to_string(),
+            AttributeValue::N(version.to_string()),
+        );
+        Ok(item)
+    }
+
+    fn version_of(item: &Item) -> Result<u64> {
+        match item.get("version") {
+            Some(AttributeValue::N(value)) => value.parse().context("invalid version attribute"),
+            _ => Ok(0),
+        }
+    }
+}
+
+#[async_trait]
+impl AggregateRepository for DynamoAggregateRepository {
+    async fn find(&self, user_id: &str, month: NaiveDate) -> Result<Option<MonthlyAggregate>> {
+        let output = self
+            .client
+            .get_item()
+            .table_name(&self.table_name)
+            .set_key(Some(primary_key(
+                keys::user_pk(user_id),
+                keys::aggregate_sk(month),
+            )))
+            .send()
+            .await
+            .context("failed to get monthly aggregate")?;
+        output.item.map(from_item).transpose()
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<MonthlyAggregate>> {
+        query_by_sk_prefix(
+            &self.client,
+            &self.table_name,
+            keys::user_pk(user_id),
+            keys::AGGREGATE_SK_PREFIX,
+            true,
+        )
+        .await?
+        .into_iter()
+        .map(from_item)
+        .collect()
+    }
+
+    async fn apply(&self, delta: MonthlyAggregate) -> Result<()> {
+        let key = primary_key(
+            keys::user_pk(delta.user_id.value()),
+            keys::aggregate_sk(delta.month),
+        );
+        for _ in 0..MAX_AGGREGATE_ATTEMPTS {
+            let output = self
+                .client
+                .get_item()
+                .table_name(&self.table_name)
+                .set_key(Some(key.clone()))
+                .consistent_read(true)
+                .send()
+                .await
+                .context("failed to get monthly aggregate")?;
+            let (mut aggregate, version) = match output.item {
+                Some(item) => {
+                    let version = Self::version_of(&item)?;
+                    (from_item::<MonthlyAggregate>(item)?, Some(version))
+                }
+                None => (
+                    MonthlyAggregate::new(delta.user_id.clone(), delta.month),
+                    None,
+                ),
+            };
+            aggregate.merge(&delta);
+
+            let request = self
+                .client
+                .put_item()
+                .table_name(&self.table_name)
+                .set_item(Some(Self::to_item(&aggregate, version.unwrap_or(0) + 1)?));
+            let request = match version {
+                Some(version) => request
+                    .condition_expression("#version = :version")
+                    .expression_attribute_names("#version", "version")
+                    .expression_attribute_values(
+                        ":version",
+                        AttributeValue::N(version.to_string()),
+                    ),
+                None => request.condition_expression("attribute_not_exists(PK)"),
+            };
+            match request.send().await {
+                Ok(_) => return Ok(()),
+                Err(err)
+                    if err
+                        .as_service_error()
+                        .is_some_and(|e| e.is_conditional_check_failed_exception()) =>
+                {
+                    continue
+                }
+                Err(err) => return Err(err).context("failed to put monthly aggregate"),
+            }
+        }
+        anyhow::bail!(
+            "monthly aggregate was updated concurrently {} times in a row",
+            MAX_AGGREGATE_ATTEMPTS
+        )
+    }
+
+    /// 既存の集計を消して書き直す
+    ///
+    /// `MAX_TRANSACT_ITEMS` 件ずつ書き込むため、月の数が多いと途中の状態が見えることがある
+    async fn replace_all(&self, user_id: &str, aggregates: Vec<MonthlyAggregate>) -> Result<()> {
+        let existing = query_by_sk_prefix(
+            &self.client,
+            &self.table_name,
+            keys::user_pk(user_id),
+            keys::AGGREGATE_SK_PREFIX,
+            true,
+        )
+        .await?;
+        let mut versions = HashMap::new();
+        for item in &existing {
+            versions.insert(string_attr(item, "SK")?, Self::version_of(item)?);
+        }
+
+        let mut writes = Vec::new();
+        for aggregate in &aggregates {
+            // 書き込み中の加算が古い版のまま成功しないよう、版を進める
+            let version = versions
+                .remove(&keys::aggregate_sk(aggregate.month))
+                .unwrap_or(0);
+            writes.push(put(
+                &self.table_name,
+                Self::to_item(aggregate, version + 1)?,
+            )?);
+        }
+        for sk in versions.into_keys() {
+            writes.push(delete(
+                &self.table_name,
+                primary_key(keys::user_pk(user_id), sk),
+            )?);
+        }
+
+        for chunk in writes.chunks(MAX_TRANSACT_ITEMS) {
+            transact_write(&self.client, chunk.to_vec())
+                .await
+                .context("failed to replace monthly aggregates")?;
+        }
+        Ok(())
+    }
+}
+
+/// DynamoDB 予算アラートリポジトリ
+///
+/// `PK=USER#<UserID>`, `SK=ALERT#<AlertID>` に格納する。アラートIDは予算・期間・閾値から
+/// 決まるため、アイテムがないことを条件に書き込んで重複を防ぐ。
+/// 未配信のアラートにだけ `GSI1PK=USER#<UserID>`, `GSI1SK=PENDING_ALERT#<Timestamp>#<AlertID>`
+/// を付与し、配信済みにするとキー属性ごと書き換えて GSI1 から外す
+pub struct DynamoBudgetAlertRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DynamoBudgetAlertRepository {
+    pub fn new(client: Client, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+
+    fn alert_item(alert: &BudgetAlertRaised) -> Result<Item> {
+        let item = to_item(
+            alert,
+            keys::user_pk(alert.user_id.value()),
+            keys::budget_alert_sk(&alert.alert_id),
+            item_types::BUDGET_ALERT,
+        )?;
+        Ok(match alert.delivered_at {
+            Some(_) => item,
+            None => with_gsi1(
+                item,
+                keys::user_pk(alert.user_id.value()),
+                keys::pending_alert_gsi1sk(&alert.raised_at, &alert.alert_id),
+            ),
+        })
+    }
+}
+
+#[async_trait]
+impl BudgetAlertRepository for DynamoBudgetAlertRepository {
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<BudgetAlertRaised>> {
+        let mut alerts = query_by_sk_prefix(
+            &self.client,
+            &self.table_name,
+            keys::user_pk(user_id),
+            keys::BUDGET_ALERT_SK_PREFIX,
+            true,
+        )
+        .await?
+        .into_iter()
+        .map(from_item)
+        .collect::<Result<Vec<BudgetAlertRaised>>>()?;
+        // ソートキーはアラートIDの順のため、発生日時の新しい順に並べ直す
+        alerts.sort_by(|a, b| (b.raised_at, &b.alert_id).cmp(&(a.raised_at, &a.alert_id)));
+        Ok(alerts)
+    }
+
+    async fn save_if_absent(&self, alert: BudgetAlertRaised) -> Result<bool> {
+        let item = Self::alert_item(&alert)?;
+        match self
+            .client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(PK)")
+            .send()
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(err)
+                if err
+                    .as_service_error()
+                    .is_some_and(|e| e.is_conditional_check_failed_exception()) =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err).context("failed to put budget alert"),
+        }
+    }
+
+    async fn find_undelivered(&self, user_id: &str) -> Result<Vec<BudgetAlertRaised>> {
+        // 配信済みのアラートは GSI1 に載らないため、未配信のものだけを発生日時の古い順に引ける
+        let items: Vec<Item> = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .index_name(GSI1_NAME)
+            .key_condition_expression("GSI1PK = :pk AND begins_with(GSI1SK, :sk)")
+            .expression_attribute_values(":pk", AttributeValue::S(keys::user_pk(user_id)))
+            .expression_attribute_values(
+                ":sk",
+                AttributeValue::S(keys::PENDING_ALERT_GSI1SK_PREFIX.to_string()),
+            )
+            .into_paginator()
+            .items()
+            .send()
+            .try_collect()
+            .await
+            .context("failed to query undelivered budget alerts")?;
+        items.into_iter().map(from_item).collect()
+    }
+
+    async fn update(&self, alert: BudgetAlertRaised) -> Result<()> {
+        let item = Self::alert_item(&alert)?;
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .send()
+            .await
+            .context("failed to update budget alert")?;
         Ok(())
     }
 }
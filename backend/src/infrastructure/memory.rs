@@ -0,0 +1,672 @@
+// インメモリ リポジトリ実装
+// AWS を使わないローカル実行やテストのため、プロセス内のマップにエンティティを保持する
+// 並び順は DynamoDB 実装（ソートキー順）と揃える
+
+use crate::domain::entities::*;
+use crate::domain::repositories::*;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// 読み書きロック付きのマップ
+///
+/// ロックは await をまたいで保持しないため、標準ライブラリの `RwLock` で十分
+struct Store<T> {
+    items: RwLock<HashMap<String, T>>,
+}
+
+impl<T: Clone> Store<T> {
+    fn new() -> Self {
+        Self {
+            items: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, id: &str) -> Option<T> {
+        self.items.read().unwrap().get(id).cloned()
+    }
+
+    fn filter(&self, predicate: impl Fn(&T) -> bool) -> Vec<T> {
+        self.items
+            .read()
+            .unwrap()
+            .values()
+            .filter(|item| predicate(item))
+            .cloned()
+            .collect()
+    }
+
+    fn put(&self, id: String, item: T) {
+        self.items.write().unwrap().insert(id, item);
+    }
+
+    fn remove(&self, id: &str) {
+        self.items.write().unwrap().remove(id);
+    }
+
+    /// 1回のロックでまとめて書き込む（途中の状態は他から見えない）
+    fn put_all(&self, items: impl IntoIterator<Item = (String, T)>) {
+        self.items.write().unwrap().extend(items);
+    }
+
+    fn remove_all(&self, ids: &[String]) {
+        let mut items = self.items.write().unwrap();
+        for id in ids {
+            items.remove(id);
+        }
+    }
+
+    /// 1回のロックで読み出して書き換える（読み出しと書き込みの間に他の書き込みが入らない）
+    fn modify(&self, id: String, f: impl FnOnce(Option<T>) -> T) {
+        let mut items = self.items.write().unwrap();
+        let item = f(items.remove(&id));
+        items.insert(id, item);
+    }
+
+    /// 同じIDの要素がなければ追加し、追加したかどうかを返す
+    fn put_if_absent(&self, id: String, item: T) -> bool {
+        let mut items = self.items.write().unwrap();
+        if items.contains_key(&id) {
+            return false;
+        }
+        items.insert(id, item);
+        true
+    }
+
+    /// 1回のロックで `predicate` に当たる要素を `replacement` に置き換える
+    fn replace_where(
+        &self,
+        predicate: impl Fn(&T) -> bool,
+        replacement: impl IntoIterator<Item = (String, T)>,
+    ) {
+        let mut items = self.items.write().unwrap();
+        items.retain(|_, item| !predicate(item));
+        items.extend(replacement);
+    }
+}
+
+/// インメモリ ユーザーリポジトリ
+pub struct InMemoryUserRepository {
+    store: Store<UserProfile>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self {
+            store: Store::new(),
+        }
+    }
+}
+
+impl Default for InMemoryUserRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_by_id(&self, user_id: &str) -> Result<Option<UserProfile>> {
+        Ok(self.store.get(user_id))
+    }
+
+    async fn save(&self, user: UserProfile) -> Result<()> {
+        self.store.put(user.user_id.value().to_string(), user);
+        Ok(())
+    }
+
+    async fn update(&self, user: UserProfile) -> Result<()> {
+        self.save(user).await
+    }
+
+    async fn delete(&self, user_id: &str) -> Result<()> {
+        self.store.remove(user_id);
+        Ok(())
+    }
+}
+
+/// インメモリ 取引リポジトリ
+pub struct InMemoryTransactionRepository {
+    store: Store<Transaction>,
+}
+
+impl InMemoryTransactionRepository {
+    pub fn new() -> Self {
+        Self {
+            store: Store::new(),
+        }
+    }
+}
+
+impl Default for InMemoryTransactionRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TransactionRepository for InMemoryTransactionRepository {
+    async fn find_by_id(&self, transaction_id: &str) -> Result<Option<Transaction>> {
+        Ok(self.store.get(transaction_id))
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Transaction>> {
+        let mut transactions = self.store.filter(|t| t.user_id.value() == user_id);
+        // 新しい取引から順に返す
+        transactions.sort_by(|a, b| {
+            (b.transaction_date, b.transaction_id.value())
+                .cmp(&(a.transaction_date, a.transaction_id.value()))
+        });
+        Ok(transactions)
+    }
+
+    async fn find_page(&self, user_id: &str, query: &TransactionQuery) -> Result<TransactionPage> {
+        let mut transactions = self.find_by_user_id(user_id).await?;
+        transactions.retain(|t| query.matches(t));
+        transactions.truncate(query.limit + 1);
+        Ok(TransactionPage::from_overfetch(transactions, query.limit))
+    }
+
+    async fn save(&self, transaction: Transaction) -> Result<()> {
+        self.store
+            .put(transaction.transaction_id.value().to_string(), transaction);
+        Ok(())
+    }
+
+    async fn save_all(&self, transactions: Vec<Transaction>) -> Result<()> {
+        self.store.put_all(
+            transactions
+                .into_iter()
+                .map(|t| (t.transaction_id.value().to_string(), t)),
+        );
+        Ok(())
+    }
+
+    async fn update(&self, transaction: Transaction) -> Result<()> {
+        self.save(transaction).await
+    }
+
+    async fn delete(&self, transaction_id: &str) -> Result<()> {
+        self.store.remove(transaction_id);
+        Ok(())
+    }
+
+    async fn delete_all(&self, transaction_ids: &[String]) -> Result<()> {
+        self.store.remove_all(transaction_ids);
+        Ok(())
+    }
+}
+
+/// インメモリ 予算リポジトリ
+pub struct InMemoryBudgetRepository {
+    store: Store<Budget>,
+}
+
+impl InMemoryBudgetRepository {
+    pub fn new() -> Self {
+        Self {
+            store: Store::new(),
+        }
+    }
+}
+
+impl Default for InMemoryBudgetRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl BudgetRepository for InMemoryBudgetRepository {
+    async fn find_by_id(&self, budget_id: &str) -> Result<Option<Budget>> {
+        Ok(self.store.get(budget_id))
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Budget>> {
+        let mut budgets = self.store.filter(|b| b.user_id.value() == user_id);
+        budgets.sort_by(|a, b| a.budget_id.cmp(&b.budget_id));
+        Ok(budgets)
+    }
+
+    async fn save(&self, budget: Budget) -> Result<()> {
+        self.store.put(budget.budget_id.clone(), budget);
+        Ok(())
+    }
+
+    async fn update(&self, budget: Budget) -> Result<()> {
+        self.save(budget).await
+    }
+
+    async fn delete(&self, budget_id: &str) -> Result<()> {
+        self.store.remove(budget_id);
+        Ok(())
+    }
+}
+
+/// インメモリ グループリポジトリ
+pub struct InMemoryGroupRepository {
+    store: Store<Group>,
+}
+
+impl InMemoryGroupRepository {
+    pub fn new() -> Self {
+        Self {
+            store: Store::new(),
+        }
+    }
+}
+
+impl Default for InMemoryGroupRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl GroupRepository for InMemoryGroupRepository {
+    async fn find_by_id(&self, group_id: &str) -> Result<Option<Group>> {
+        Ok(self.store.get(group_id))
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Group>> {
+        let mut groups = self
+            .store
+            .filter(|g| g.members.iter().any(|m| m.value() == user_id));
+        groups.sort_by(|a, b| a.group_id.cmp(&b.group_id));
+        Ok(groups)
+    }
+
+    async fn save(&self, group: Group) -> Result<()> {
+        self.store.put(group.group_id.clone(), group);
+        Ok(())
+    }
+
+    async fn update(&self, group: Group) -> Result<()> {
+        self.save(group).await
+    }
+
+    async fn delete(&self, group_id: &str) -> Result<()> {
+        self.store.remove(group_id);
+        Ok(())
+    }
+}
+
+/// インメモリ グループ取引リポジトリ
+///
+/// 支払者の立て替え分の取引は `transactions` に書き込む
+pub struct InMemoryGroupTransactionRepository {
+    store: Store<GroupTransaction>,
+    transactions: Arc<dyn TransactionRepository>,
+}
+
+impl InMemoryGroupTransactionRepository {
+    pub fn new(transactions: Arc<dyn TransactionRepository>) -> Self {
+        Self {
+            store: Store::new(),
+            transactions,
+        }
+    }
+}
+
+#[async_trait]
+impl GroupTransactionRepository for InMemoryGroupTransactionRepository {
+    async fn find_by_group_id(&self, group_id: &str) -> Result<Vec<GroupTransaction>> {
+        let mut transactions = self.store.filter(|t| t.group_id == group_id);
+        transactions.sort_by(|a, b| {
+            (a.transaction_date, a.transaction_id.value())
+                .cmp(&(b.transaction_date, b.transaction_id.value()))
+        });
+        Ok(transactions)
+    }
+
+    async fn save(
+        &self,
+        transaction: GroupTransaction,
+        payer_transaction: Option<Transaction>,
+    ) -> Result<()> {
+        if let Some(payer_transaction) = payer_transaction {
+            self.transactions.save(payer_transaction).await?;
+        }
+        self.store
+            .put(transaction.transaction_id.value().to_string(), transaction);
+        Ok(())
+    }
+}
+
+/// インメモリ精算リポジトリ
+pub struct InMemorySettlementRepository {
+    store: Store<Settlement>,
+}
+
+impl InMemorySettlementRepository {
+    pub fn new() -> Self {
+        Self {
+            store: Store::new(),
+        }
+    }
+}
+
+impl Default for InMemorySettlementRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SettlementRepository for InMemorySettlementRepository {
+    async fn find_by_id(&self, settlement_id: &str) -> Result<Option<Settlement>> {
+        Ok(self.store.get(settlement_id))
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Settlement>> {
+        let mut settlements = self.store.filter(|s| {
+            s.creditor_user_id.value() == user_id || s.debtor_user_id.value() == user_id
+        });
+        settlements.sort_by(|a, b| a.settlement_id.cmp(&b.settlement_id));
+        Ok(settlements)
+    }
+
+    async fn save(&self, settlement: Settlement) -> Result<()> {
+        self.store.put(settlement.settlement_id.clone(), settlement);
+        Ok(())
+    }
+
+    async fn update(&self, settlement: Settlement) -> Result<()> {
+        self.save(settlement).await
+    }
+}
+
+/// インメモリ口座リポジトリ
+pub struct InMemoryAccountRepository {
+    store: Store<Account>,
+}
+
+impl InMemoryAccountRepository {
+    pub fn new() -> Self {
+        Self {
+            store: Store::new(),
+        }
+    }
+}
+
+impl Default for InMemoryAccountRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl AccountRepository for InMemoryAccountRepository {
+    async fn find_by_id(&self, account_id: &str) -> Result<Option<Account>> {
+        Ok(self.store.get(account_id))
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Account>> {
+        let mut accounts = self.store.filter(|a| a.user_id.value() == user_id);
+        accounts.sort_by(|a, b| a.account_id.cmp(&b.account_id));
+        Ok(accounts)
+    }
+
+    async fn save(&self, account: Account) -> Result<()> {
+        self.store.put(account.account_id.clone(), account);
+        Ok(())
+    }
+
+    async fn update(&self, account: Account) -> Result<()> {
+        self.save(account).await
+    }
+
+    async fn delete(&self, account_id: &str) -> Result<()> {
+        self.store.remove(account_id);
+        Ok(())
+    }
+}
+
+/// インメモリ カテゴリリポジトリ
+pub struct InMemoryCategoryRepository {
+    store: Store<Category>,
+}
+
+impl InMemoryCategoryRepository {
+    pub fn new() -> Self {
+        Self {
+            store: Store::new(),
+        }
+    }
+}
+
+impl Default for InMemoryCategoryRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl CategoryRepository for InMemoryCategoryRepository {
+    async fn find_by_id(&self, category_id: &str) -> Result<Option<Category>> {
+        Ok(self.store.get(category_id))
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Category>> {
+        let mut categories = self.store.filter(|c| c.user_id.value() == user_id);
+        categories.sort_by(|a, b| a.category_id.cmp(&b.category_id));
+        Ok(categories)
+    }
+
+    async fn save(&self, category: Category) -> Result<()> {
+        self.store.put(category.category_id.clone(), category);
+        Ok(())
+    }
+
+    async fn save_all(&self, categories: Vec<Category>) -> Result<()> {
+        self.store
+            .put_all(categories.into_iter().map(|c| (c.category_id.clone(), c)));
+        Ok(())
+    }
+
+    async fn update(&self, category: Category) -> Result<()> {
+        self.save(category).await
+    }
+}
+
+/// インメモリ 月次集計リポジトリ
+pub struct InMemoryAggregateRepository {
+    store: Store<MonthlyAggregate>,
+}
+
+impl InMemoryAggregateRepository {
+    pub fn new() -> Self {
+        Self {
+            store: Store::new(),
+        }
+    }
+
+    fn key(user_id: &str, month: NaiveDate) -> String {
+        format!("{}#{}", user_id, month)
+    }
+}
+
+impl Default for InMemoryAggregateRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl AggregateRepository for InMemoryAggregateRepository {
+    async fn find(&self, user_id: &str, month: NaiveDate) -> Result<Option<MonthlyAggregate>> {
+        Ok(self.store.get(&Self::key(user_id, month)))
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<MonthlyAggregate>> {
+        let mut aggregates = self.store.filter(|a| a.user_id.value() == user_id);
+        aggregates.sort_by_key(|a| a.month);
+        Ok(aggregates)
+    }
+
+    async fn apply(&self, delta: MonthlyAggregate) -> Result<()> {
+        let key = Self::key(delta.user_id.value(), delta.month);
+        self.store.modify(key, |current| {
+            let mut aggregate = current
+                .unwrap_or_else(|| MonthlyAggregate::new(delta.user_id.clone(), delta.month));
+            aggregate.merge(&delta);
+            aggregate
+        });
+        Ok(())
+    }
+
+    async fn replace_all(&self, user_id: &str, aggregates: Vec<MonthlyAggregate>) -> Result<()> {
+        self.store.replace_where(
+            |a| a.user_id.value() == user_id,
+            aggregates
+                .into_iter()
+                .map(|a| (Self::key(a.user_id.value(), a.month), a)),
+        );
+        Ok(())
+    }
+}
+
+/// インメモリ 予算アラートリポジトリ
+pub struct InMemoryBudgetAlertRepository {
+    store: Store<BudgetAlertRaised>,
+}
+
+impl InMemoryBudgetAlertRepository {
+    pub fn new() -> Self {
+        Self {
+            store: Store::new(),
+        }
+    }
+}
+
+impl Default for InMemoryBudgetAlertRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl BudgetAlertRepository for InMemoryBudgetAlertRepository {
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<BudgetAlertRaised>> {
+        let mut alerts = self.store.filter(|a| a.user_id.value() == user_id);
+        alerts.sort_by(|a, b| (b.raised_at, &b.alert_id).cmp(&(a.raised_at, &a.alert_id)));
+        Ok(alerts)
+    }
+
+    async fn save_if_absent(&self, alert: BudgetAlertRaised) -> Result<bool> {
+        Ok(self.store.put_if_absent(alert.alert_id.clone(), alert))
+    }
+
+    async fn find_undelivered(&self, user_id: &str) -> Result<Vec<BudgetAlertRaised>> {
+        let mut alerts = self
+            .store
+            .filter(|a| a.user_id.value() == user_id && a.delivered_at.is_none());
+        alerts.sort_by(|a, b| (a.raised_at, &a.alert_id).cmp(&(b.raised_at, &b.alert_id)));
+        Ok(alerts)
+    }
+
+    async fn update(&self, alert: BudgetAlertRaised) -> Result<()> {
+        self.store.put(alert.alert_id.clone(), alert);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::*;
+    use chrono::Duration;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_transactions_are_returned_newest_first() {
+        let repository = InMemoryTransactionRepository::new();
+        let user_id = UserId::new("user123".to_string());
+
+        for day in 0..3 {
+            let mut transaction = Transaction::new(
+                user_id.clone(),
+                TransactionType::Real,
+                Amount::jpy(100),
+                format!("day {}", day),
+                TransactionCategory::Food,
+            );
+            transaction.transaction_date =
+                TransactionDate::new(transaction.transaction_date.date() + Duration::days(day));
+            repository.save(transaction).await.unwrap();
+        }
+
+        let descriptions: Vec<_> = repository
+            .find_by_user_id("user123")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.description)
+            .collect();
+        assert_eq!(descriptions, vec!["day 2", "day 1", "day 0"]);
+        assert!(repository
+            .find_by_user_id("other")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_group_lookup_by_member() {
+        let repository = InMemoryGroupRepository::new();
+        let mut group = Group::new(
+            "旅行".to_string(),
+            String::new(),
+            UserId::new("alice".to_string()),
+        );
+        group.add_member(UserId::new("bob".to_string()));
+        repository.save(group.clone()).await.unwrap();
+
+        assert_eq!(
+            repository.find_by_user_id("bob").await.unwrap(),
+            vec![group.clone()]
+        );
+
+        group.remove_member(&UserId::new("bob".to_string()));
+        repository.update(group.clone()).await.unwrap();
+        assert!(repository.find_by_user_id("bob").await.unwrap().is_empty());
+
+        repository.delete(&group.group_id).await.unwrap();
+        assert!(repository
+            .find_by_id(&group.group_id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_writes() {
+        let repository = Arc::new(InMemoryBudgetRepository::new());
+
+        let handles: Vec<_> = (0..16)
+            .map(|i| {
+                let repository = repository.clone();
+                tokio::spawn(async move {
+                    let budget = Budget::new(
+                        UserId::new("user123".to_string()),
+                        BudgetTarget::category(TransactionCategory::Food),
+                        Amount::jpy(1000 * i),
+                        BudgetPeriod::Monthly,
+                        0.8,
+                    );
+                    repository.save(budget).await.unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(
+            repository.find_by_user_id("user123").await.unwrap().len(),
+            16
+        );
+    }
+}
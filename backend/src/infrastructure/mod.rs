@@ -1,12 +1,16 @@
 // インフラストラクチャ層
-// 外部システムとの統合（DynamoDB、AWS等）
+// 外部システムとの統合（DynamoDB、SQLite、AWS等）
 
 pub mod dynamodb;
-
-use crate::domain::entities::*;
-use crate::domain::repositories::*;
-use anyhow::Result;
-use async_trait::async_trait;
+pub mod memory;
+pub mod notifiers;
+pub mod sqlite;
 
 /// DynamDBリポジトリ実装のモジュール
 pub use dynamodb::*;
+/// インメモリリポジトリ実装のモジュール
+pub use memory::*;
+/// 予算アラートの通知先のモジュール
+pub use notifiers::*;
+/// SQLiteリポジトリ実装のモジュール
+pub use sqlite::*;
@@ -0,0 +1,173 @@
+// 予算アラートの通知先の実装
+// ログ出力と、外部システムへの Webhook 送信
+
+use crate::domain::entities::BudgetAlertRaised;
+use crate::domain::notifiers::BudgetAlertNotifier;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use ring::hmac;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Webhook の送信を打ち切るまでの時間
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 署名ヘッダー（`sha256=<本文の HMAC-SHA256 の16進表記>`）
+pub const SIGNATURE_HEADER: &str = "x-axi-budget-signature";
+
+/// 通知するイベントの JSON 表現
+fn payload(alert: &BudgetAlertRaised) -> Value {
+    json!({
+        "type": "BudgetAlertRaised",
+        "alertId": alert.alert_id,
+        "userId": alert.user_id.value(),
+        "budgetId": alert.budget_id,
+        "periodStart": alert.period_start,
+        "periodEnd": alert.period_end,
+        "threshold": alert.threshold,
+        "usage": alert.usage,
+        "spentAmount": alert.spent_amount,
+        "budgetAmount": alert.budget_amount,
+        "raisedAt": alert.raised_at,
+    })
+}
+
+/// 1件1行の JSON として標準出力に書き込む（Lambda 上では CloudWatch Logs に送られる）
+pub struct LogNotifier;
+
+#[async_trait]
+impl BudgetAlertNotifier for LogNotifier {
+    async fn notify(&self, alert: &BudgetAlertRaised) -> Result<()> {
+        println!("{}", payload(alert));
+        Ok(())
+    }
+}
+
+/// 指定した URL にイベントを JSON で POST する
+///
+/// 鍵を指定した場合は、受信側が送信元を確かめられるよう本文の署名をヘッダーに付ける
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+    key: Option<hmac::Key>,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String, secret: Option<&[u8]>) -> Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(WEBHOOK_TIMEOUT)
+                .build()?,
+            url,
+            key: secret.map(|secret| hmac::Key::new(hmac::HMAC_SHA256, secret)),
+        })
+    }
+}
+
+/// 本文の署名ヘッダーの値
+pub fn signature(key: &hmac::Key, body: &[u8]) -> String {
+    let tag = hmac::sign(key, body);
+    let hex: String = tag
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("sha256={}", hex)
+}
+
+#[async_trait]
+impl BudgetAlertNotifier for WebhookNotifier {
+    async fn notify(&self, alert: &BudgetAlertRaised) -> Result<()> {
+        let body = serde_json::to_vec(&payload(alert))?;
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(key) = &self.key {
+            request = request.header(SIGNATURE_HEADER, signature(key, &body));
+        }
+        request
+            .body(body)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("failed to post budget alert to {}", self.url))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::*;
+    use crate::domain::value_objects::*;
+    use axum::body::Bytes;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use tokio::sync::mpsc;
+
+    fn alert() -> BudgetAlertRaised {
+        let budget = Budget::new(
+            UserId::new("user123".to_string()),
+            BudgetTarget::All,
+            Amount::jpy(10000),
+            BudgetPeriod::Monthly,
+            0.8,
+        );
+        let window = PeriodWindow {
+            start: "2025-04-01".parse().unwrap(),
+            end: "2025-05-01".parse().unwrap(),
+        };
+        BudgetAlertRaised::new(
+            &budget,
+            &window,
+            0.85,
+            Amount::jpy(8500),
+            "2025-04-15T00:00:00Z".parse().unwrap(),
+        )
+    }
+
+    /// 受け取ったリクエストを送り返す Webhook の受信側を起動し、その URL を返す
+    async fn receiver(status: StatusCode) -> (String, mpsc::UnboundedReceiver<(HeaderMap, Bytes)>) {
+        let (sender, requests) = mpsc::unbounded_channel();
+        let app = Router::new().route(
+            "/hooks/alerts",
+            post(move |headers: HeaderMap, body: Bytes| async move {
+                sender.send((headers, body)).unwrap();
+                status
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks/alerts", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, requests)
+    }
+
+    #[tokio::test]
+    async fn test_webhook_posts_signed_event() {
+        let (url, mut requests) = receiver(StatusCode::NO_CONTENT).await;
+        let notifier = WebhookNotifier::new(url, Some(b"webhook-secret")).unwrap();
+        let alert = alert();
+
+        notifier.notify(&alert).await.unwrap();
+
+        let (headers, body) = requests.recv().await.unwrap();
+        let event: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(event["type"], "BudgetAlertRaised");
+        assert_eq!(event["alertId"], alert.alert_id);
+        assert_eq!(event["periodStart"], "2025-04-01");
+        assert_eq!(event["periodEnd"], "2025-04-30");
+        assert_eq!(event["spentAmount"]["value"], 8500);
+        let key = hmac::Key::new(hmac::HMAC_SHA256, b"webhook-secret");
+        assert_eq!(headers[SIGNATURE_HEADER], signature(&key, &body).as_str());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_error_status_fails() {
+        let (url, _requests) = receiver(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let notifier = WebhookNotifier::new(url, None).unwrap();
+
+        assert!(notifier.notify(&alert()).await.is_err());
+    }
+}
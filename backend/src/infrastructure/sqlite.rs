@@ -0,0 +1,1115 @@
+// SQLite リポジトリ実装
+// AWS を使わないセルフホスト環境向けに、組み込み SQLite ファイルへ永続化する
+// エンティティ本体は JSON で保持し、検索に使う列だけを別カラムに持つ
+// 並び順は DynamoDB 実装（ソートキー順）と揃える
+
+use crate::domain::entities::*;
+use crate::domain::repositories::*;
+use crate::domain::value_objects::UserId;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// スキーママイグレーション
+///
+/// 適用済みの数を `PRAGMA user_version` に記録し、起動時に未適用分のみを実行する。
+/// 既存の要素は変更せず、末尾に追加していくこと
+const MIGRATIONS: &[&str] = &[
+    // 1: 初期スキーマ
+    "CREATE TABLE users (
+        user_id TEXT PRIMARY KEY,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE transactions (
+        transaction_id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        transaction_date TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX transactions_user_date ON transactions (user_id, transaction_date);
+    CREATE TABLE budgets (
+        budget_id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX budgets_user ON budgets (user_id);
+    CREATE TABLE user_groups (
+        group_id TEXT PRIMARY KEY,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE group_members (
+        group_id TEXT NOT NULL REFERENCES user_groups (group_id) ON DELETE CASCADE,
+        user_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        PRIMARY KEY (group_id, user_id)
+    );
+    CREATE INDEX group_members_user ON group_members (user_id);",
+    // 2: 取引一覧の絞り込み用カラム
+    "ALTER TABLE transactions ADD COLUMN transaction_type TEXT;
+    ALTER TABLE transactions ADD COLUMN category TEXT;
+    UPDATE transactions SET
+        transaction_type = json_extract(data, '$.transaction_type'),
+        category = json_extract(data, '$.category');",
+    // 3: 精算
+    "CREATE TABLE settlements (
+        settlement_id TEXT PRIMARY KEY,
+        creditor_user_id TEXT NOT NULL,
+        debtor_user_id TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX settlements_creditor ON settlements (creditor_user_id);
+    CREATE INDEX settlements_debtor ON settlements (debtor_user_id);",
+    // 4: 取引の向き（既存の取引は支出）
+    "ALTER TABLE transactions ADD COLUMN direction TEXT NOT NULL DEFAULT 'EXPENSE';",
+    // 5: 口座
+    "CREATE TABLE accounts (
+        account_id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX accounts_user ON accounts (user_id);",
+    // 6: ユーザー定義カテゴリ
+    "CREATE TABLE categories (
+        category_id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX categories_user ON categories (user_id);",
+    // 7: 月次集計
+    "CREATE TABLE monthly_aggregates (
+        user_id TEXT NOT NULL,
+        month TEXT NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (user_id, month)
+    );",
+    // 8: 予算アラートの履歴
+    "CREATE TABLE budget_alerts (
+        alert_id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        raised_at TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX budget_alerts_user ON budget_alerts (user_id, raised_at);",
+    // 9: 予算アラートの通知済み日時（未送信のアラートを再送する）
+    "ALTER TABLE budget_alerts ADD COLUMN delivered_at TEXT;
+    CREATE INDEX budget_alerts_undelivered ON budget_alerts (user_id, raised_at)
+        WHERE delivered_at IS NULL;",
+    // 10: グループ取引
+    "CREATE TABLE group_transactions (
+        transaction_id TEXT PRIMARY KEY,
+        group_id TEXT NOT NULL REFERENCES user_groups (group_id) ON DELETE CASCADE,
+        transaction_date TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX group_transactions_group_date
+        ON group_transactions (group_id, transaction_date);",
+];
+
+/// マイグレーション済みの SQLite 接続
+///
+/// 各リポジトリで共有し、ブロッキング処理は `spawn_blocking` 上で実行する
+#[derive(Clone)]
+pub struct SqliteDatabase {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteDatabase {
+    /// ファイルを開き（なければ作成し）、マイグレーションを適用する
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let connection = Connection::open(path.as_ref()).with_context(|| {
+            format!("failed to open SQLite database {}", path.as_ref().display())
+        })?;
+        Self::initialize(connection)
+    }
+
+    /// メモリ上のデータベースを開く（テスト用）
+    pub fn open_in_memory() -> Result<Self> {
+        Self::initialize(Connection::open_in_memory()?)
+    }
+
+    fn initialize(mut connection: Connection) -> Result<Self> {
+        connection.pragma_update(None, "foreign_keys", true)?;
+        migrate(&mut connection)?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// 接続を使う処理をブロッキングスレッドで実行
+    async fn call<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    {
+        let connection = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = connection
+                .lock()
+                .map_err(|_| anyhow::anyhow!("SQLite connection mutex poisoned"))?;
+            f(&mut connection)
+        })
+        .await?
+    }
+}
+
+/// 未適用のマイグレーションを順に適用
+fn migrate(connection: &mut Connection) -> Result<()> {
+    let applied: usize = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+        let tx = connection.transaction()?;
+        tx.execute_batch(migration)
+            .with_context(|| format!("failed to apply migration {}", index + 1))?;
+        tx.pragma_update(None, "user_version", index + 1)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+fn to_json<T: Serialize>(entity: &T) -> Result<String> {
+    serde_json::to_string(entity).context("failed to serialize entity")
+}
+
+fn from_json<T: DeserializeOwned>(data: &str) -> Result<T> {
+    serde_json::from_str(data).context("failed to deserialize entity")
+}
+
+/// 文字列順が時系列順になる桁数固定のUTC表記
+fn sortable_timestamp(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+/// 列挙型をシリアライズ後の文字列（`"FOOD"` 等）で列に保存する
+fn enum_text<T: Serialize>(value: &T) -> Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(text) => Ok(text),
+        other => anyhow::bail!("expected a unit enum variant, got {}", other),
+    }
+}
+
+/// `data` 列を持つ行をすべてエンティティに変換
+fn query_entities<T: DeserializeOwned>(
+    connection: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<T>> {
+    let mut statement = connection.prepare(sql)?;
+    let rows = statement.query_map(params, |row| row.get::<_, String>(0))?;
+    rows.map(|data| from_json(&data?)).collect()
+}
+
+/// `data` 列を持つ行を1件だけエンティティに変換
+fn query_entity<T: DeserializeOwned>(
+    connection: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Option<T>> {
+    connection
+        .query_row(sql, params, |row| row.get::<_, String>(0))
+        .optional()?
+        .map(|data| from_json(&data))
+        .transpose()
+}
+
+/// SQLite ユーザーリポジトリ
+pub struct SqliteUserRepository {
+    db: SqliteDatabase,
+}
+
+impl SqliteUserRepository {
+    pub fn new(db: SqliteDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl UserRepository for SqliteUserRepository {
+    async fn find_by_id(&self, user_id: &str) -> Result<Option<UserProfile>> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entity(conn, "SELECT data FROM users WHERE user_id = ?1", [user_id])
+            })
+            .await
+    }
+
+    async fn save(&self, user: UserProfile) -> Result<()> {
+        let data = to_json(&user)?;
+        self.db
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO users (user_id, data) VALUES (?1, ?2)",
+                    params![user.user_id.value(), data],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn update(&self, user: UserProfile) -> Result<()> {
+        self.save(user).await
+    }
+
+    async fn delete(&self, user_id: &str) -> Result<()> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                conn.execute("DELETE FROM users WHERE user_id = ?1", [user_id])?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// SQLite 取引リポジトリ
+pub struct SqliteTransactionRepository {
+    db: SqliteDatabase,
+}
+
+impl SqliteTransactionRepository {
+    pub fn new(db: SqliteDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl TransactionRepository for SqliteTransactionRepository {
+    async fn find_by_id(&self, transaction_id: &str) -> Result<Option<Transaction>> {
+        let transaction_id = transaction_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entity(
+                    conn,
+                    "SELECT data FROM transactions WHERE transaction_id = ?1",
+                    [transaction_id],
+                )
+            })
+            .await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Transaction>> {
+        let user_id = user_id.to_string();
+        // 新しい取引から順に返す
+        self.db
+            .call(move |conn| {
+                query_entities(
+                    conn,
+                    "SELECT data FROM transactions WHERE user_id = ?1
+                     ORDER BY transaction_date DESC, transaction_id DESC",
+                    [user_id],
+                )
+            })
+            .await
+    }
+
+    async fn find_page(&self, user_id: &str, query: &TransactionQuery) -> Result<TransactionPage> {
+        let mut sql = String::from("SELECT data FROM transactions WHERE user_id = ?");
+        let mut values = vec![Value::Text(user_id.to_string())];
+        if let Some(transaction_type) = &query.transaction_type {
+            sql.push_str(" AND transaction_type = ?");
+            values.push(Value::Text(enum_text(transaction_type)?));
+        }
+        if let Some(direction) = &query.direction {
+            sql.push_str(" AND direction = ?");
+            values.push(Value::Text(enum_text(direction)?));
+        }
+        if let Some(category) = &query.category {
+            sql.push_str(" AND category = ?");
+            values.push(Value::Text(enum_text(category)?));
+        }
+        if let Some(from) = &query.from {
+            sql.push_str(" AND transaction_date >= ?");
+            values.push(Value::Text(sortable_timestamp(&from.timestamp())));
+        }
+        if let Some(until) = &query.until {
+            sql.push_str(" AND transaction_date < ?");
+            values.push(Value::Text(sortable_timestamp(&until.timestamp())));
+        }
+        if let Some(cursor) = &query.after {
+            sql.push_str(" AND (transaction_date, transaction_id) < (?, ?)");
+            values.push(Value::Text(sortable_timestamp(
+                &cursor.transaction_date.timestamp(),
+            )));
+            values.push(Value::Text(cursor.transaction_id.clone()));
+        }
+        sql.push_str(" ORDER BY transaction_date DESC, transaction_id DESC LIMIT ?");
+        values.push(Value::Integer(query.limit as i64 + 1));
+
+        let limit = query.limit;
+        let transactions = self
+            .db
+            .call(move |conn| query_entities(conn, &sql, params_from_iter(values)))
+            .await?;
+        Ok(TransactionPage::from_overfetch(transactions, limit))
+    }
+
+    async fn save(&self, transaction: Transaction) -> Result<()> {
+        self.db
+            .call(move |conn| write_transaction(conn, &transaction))
+            .await
+    }
+
+    async fn save_all(&self, transactions: Vec<Transaction>) -> Result<()> {
+        self.db
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                for transaction in &transactions {
+                    write_transaction(&tx, transaction)?;
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn update(&self, transaction: Transaction) -> Result<()> {
+        self.save(transaction).await
+    }
+
+    async fn delete(&self, transaction_id: &str) -> Result<()> {
+        let transaction_id = transaction_id.to_string();
+        self.db
+            .call(move |conn| {
+                conn.execute(
+                    "DELETE FROM transactions WHERE transaction_id = ?1",
+                    [transaction_id],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn delete_all(&self, transaction_ids: &[String]) -> Result<()> {
+        let transaction_ids = transaction_ids.to_vec();
+        self.db
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                for transaction_id in &transaction_ids {
+                    tx.execute(
+                        "DELETE FROM transactions WHERE transaction_id = ?1",
+                        [transaction_id],
+                    )?;
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// 取引を1行書き込む（同じ取引IDの行は置き換える）
+fn write_transaction(conn: &Connection, transaction: &Transaction) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO transactions
+         (transaction_id, user_id, transaction_date, transaction_type, direction,
+          category, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            transaction.transaction_id.value(),
+            transaction.user_id.value(),
+            sortable_timestamp(&transaction.transaction_date.timestamp()),
+            enum_text(&transaction.transaction_type)?,
+            enum_text(&transaction.direction)?,
+            enum_text(&transaction.category)?,
+            to_json(transaction)?
+        ],
+    )?;
+    Ok(())
+}
+
+/// SQLite 予算リポジトリ
+pub struct SqliteBudgetRepository {
+    db: SqliteDatabase,
+}
+
+impl SqliteBudgetRepository {
+    pub fn new(db: SqliteDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl BudgetRepository for SqliteBudgetRepository {
+    async fn find_by_id(&self, budget_id: &str) -> Result<Option<Budget>> {
+        let budget_id = budget_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entity(
+                    conn,
+                    "SELECT data FROM budgets WHERE budget_id = ?1",
+                    [budget_id],
+                )
+            })
+            .await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Budget>> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entities(
+                    conn,
+                    "SELECT data FROM budgets WHERE user_id = ?1 ORDER BY budget_id",
+                    [user_id],
+                )
+            })
+            .await
+    }
+
+    async fn save(&self, budget: Budget) -> Result<()> {
+        let data = to_json(&budget)?;
+        self.db
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO budgets (budget_id, user_id, data) VALUES (?1, ?2, ?3)",
+                    params![budget.budget_id, budget.user_id.value(), data],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn update(&self, budget: Budget) -> Result<()> {
+        self.save(budget).await
+    }
+
+    async fn delete(&self, budget_id: &str) -> Result<()> {
+        let budget_id = budget_id.to_string();
+        self.db
+            .call(move |conn| {
+                conn.execute("DELETE FROM budgets WHERE budget_id = ?1", [budget_id])?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// グループ本体（メンバー一覧は `group_members` テーブルに持つ）
+#[derive(Serialize, Deserialize)]
+struct GroupRow {
+    group_id: String,
+    name: String,
+    description: String,
+    owner_id: UserId,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+/// SQLite グループリポジトリ
+pub struct SqliteGroupRepository {
+    db: SqliteDatabase,
+}
+
+impl SqliteGroupRepository {
+    pub fn new(db: SqliteDatabase) -> Self {
+        Self { db }
+    }
+}
+
+fn find_group(conn: &Connection, group_id: &str) -> Result<Option<Group>> {
+    let Some(row) = query_entity::<GroupRow>(
+        conn,
+        "SELECT data FROM user_groups WHERE group_id = ?1",
+        [group_id],
+    )?
+    else {
+        return Ok(None);
+    };
+
+    let mut statement =
+        conn.prepare("SELECT user_id FROM group_members WHERE group_id = ?1 ORDER BY position")?;
+    let members = statement
+        .query_map([group_id], |row| row.get::<_, String>(0))?
+        .map(|user_id| Ok(UserId::new(user_id?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(Group {
+        group_id: row.group_id,
+        name: row.name,
+        description: row.description,
+        owner_id: row.owner_id,
+        members,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }))
+}
+
+/// グループ本体とメンバーを1トランザクションで書き込む
+fn write_group(conn: &mut Connection, group: &Group) -> Result<()> {
+    let row = GroupRow {
+        group_id: group.group_id.clone(),
+        name: group.name.clone(),
+        description: group.description.clone(),
+        owner_id: group.owner_id.clone(),
+        created_at: group.created_at,
+        updated_at: group.updated_at,
+    };
+
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT INTO user_groups (group_id, data) VALUES (?1, ?2)
+         ON CONFLICT (group_id) DO UPDATE SET data = excluded.data",
+        params![group.group_id, to_json(&row)?],
+    )?;
+    tx.execute(
+        "DELETE FROM group_members WHERE group_id = ?1",
+        [&group.group_id],
+    )?;
+    for (position, user_id) in group.members.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO group_members (group_id, user_id, position) VALUES (?1, ?2, ?3)",
+            params![group.group_id, user_id.value(), position],
+        )?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+#[async_trait]
+impl GroupRepository for SqliteGroupRepository {
+    async fn find_by_id(&self, group_id: &str) -> Result<Option<Group>> {
+        let group_id = group_id.to_string();
+        self.db.call(move |conn| find_group(conn, &group_id)).await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Group>> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                let mut statement = conn.prepare(
+                    "SELECT group_id FROM group_members WHERE user_id = ?1 ORDER BY group_id",
+                )?;
+                let group_ids = statement
+                    .query_map([user_id], |row| row.get::<_, String>(0))?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+
+                let mut groups = Vec::with_capacity(group_ids.len());
+                for group_id in group_ids {
+                    if let Some(group) = find_group(conn, &group_id)? {
+                        groups.push(group);
+                    }
+                }
+                Ok(groups)
+            })
+            .await
+    }
+
+    async fn save(&self, group: Group) -> Result<()> {
+        self.db.call(move |conn| write_group(conn, &group)).await
+    }
+
+    async fn update(&self, group: Group) -> Result<()> {
+        self.save(group).await
+    }
+
+    async fn delete(&self, group_id: &str) -> Result<()> {
+        let group_id = group_id.to_string();
+        self.db
+            .call(move |conn| {
+                // メンバーは外部キーの ON DELETE CASCADE で削除される
+                conn.execute("DELETE FROM user_groups WHERE group_id = ?1", [group_id])?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// SQLite グループ取引リポジトリ
+pub struct SqliteGroupTransactionRepository {
+    db: SqliteDatabase,
+}
+
+impl SqliteGroupTransactionRepository {
+    pub fn new(db: SqliteDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl GroupTransactionRepository for SqliteGroupTransactionRepository {
+    async fn find_by_group_id(&self, group_id: &str) -> Result<Vec<GroupTransaction>> {
+        let group_id = group_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entities(
+                    conn,
+                    "SELECT data FROM group_transactions WHERE group_id = ?1
+                     ORDER BY transaction_date, transaction_id",
+                    [group_id],
+                )
+            })
+            .await
+    }
+
+    async fn save(
+        &self,
+        transaction: GroupTransaction,
+        payer_transaction: Option<Transaction>,
+    ) -> Result<()> {
+        self.db
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                tx.execute(
+                    "INSERT INTO group_transactions (transaction_id, group_id, transaction_date, data)
+                     VALUES (?1, ?2, ?3, ?4)
+                     ON CONFLICT (transaction_id) DO UPDATE SET
+                        transaction_date = excluded.transaction_date,
+                        data = excluded.data",
+                    params![
+                        transaction.transaction_id.value(),
+                        transaction.group_id,
+                        sortable_timestamp(&transaction.transaction_date.timestamp()),
+                        to_json(&transaction)?
+                    ],
+                )?;
+                if let Some(payer_transaction) = &payer_transaction {
+                    write_transaction(&tx, payer_transaction)?;
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// SQLite 精算リポジトリ
+pub struct SqliteSettlementRepository {
+    db: SqliteDatabase,
+}
+
+impl SqliteSettlementRepository {
+    pub fn new(db: SqliteDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl SettlementRepository for SqliteSettlementRepository {
+    async fn find_by_id(&self, settlement_id: &str) -> Result<Option<Settlement>> {
+        let settlement_id = settlement_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entity(
+                    conn,
+                    "SELECT data FROM settlements WHERE settlement_id = ?1",
+                    [settlement_id],
+                )
+            })
+            .await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Settlement>> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entities(
+                    conn,
+                    "SELECT data FROM settlements
+                     WHERE creditor_user_id = ?1 OR debtor_user_id = ?1
+                     ORDER BY settlement_id",
+                    [user_id],
+                )
+            })
+            .await
+    }
+
+    async fn save(&self, settlement: Settlement) -> Result<()> {
+        let data = to_json(&settlement)?;
+        self.db
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO settlements
+                     (settlement_id, creditor_user_id, debtor_user_id, data) VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        settlement.settlement_id,
+                        settlement.creditor_user_id.value(),
+                        settlement.debtor_user_id.value(),
+                        data
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn update(&self, settlement: Settlement) -> Result<()> {
+        self.save(settlement).await
+    }
+}
+
+/// SQLite 口座リポジトリ
+pub struct SqliteAccountRepository {
+    db: SqliteDatabase,
+}
+
+impl SqliteAccountRepository {
+    pub fn new(db: SqliteDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl AccountRepository for SqliteAccountRepository {
+    async fn find_by_id(&self, account_id: &str) -> Result<Option<Account>> {
+        let account_id = account_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entity(
+                    conn,
+                    "SELECT data FROM accounts WHERE account_id = ?1",
+                    [account_id],
+                )
+            })
+            .await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Account>> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entities(
+                    conn,
+                    "SELECT data FROM accounts WHERE user_id = ?1 ORDER BY account_id",
+                    [user_id],
+                )
+            })
+            .await
+    }
+
+    async fn save(&self, account: Account) -> Result<()> {
+        let data = to_json(&account)?;
+        self.db
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO accounts (account_id, user_id, data) VALUES (?1, ?2, ?3)",
+                    params![account.account_id, account.user_id.value(), data],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn update(&self, account: Account) -> Result<()> {
+        self.save(account).await
+    }
+
+    async fn delete(&self, account_id: &str) -> Result<()> {
+        let account_id = account_id.to_string();
+        self.db
+            .call(move |conn| {
+                conn.execute("DELETE FROM accounts WHERE account_id = ?1", [account_id])?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// SQLite カテゴリリポジトリ
+pub struct SqliteCategoryRepository {
+    db: SqliteDatabase,
+}
+
+impl SqliteCategoryRepository {
+    pub fn new(db: SqliteDatabase) -> Self {
+        Self { db }
+    }
+}
+
+fn write_category(conn: &Connection, category: &Category) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO categories (category_id, user_id, data) VALUES (?1, ?2, ?3)",
+        params![
+            category.category_id,
+            category.user_id.value(),
+            to_json(category)?
+        ],
+    )?;
+    Ok(())
+}
+
+#[async_trait]
+impl CategoryRepository for SqliteCategoryRepository {
+    async fn find_by_id(&self, category_id: &str) -> Result<Option<Category>> {
+        let category_id = category_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entity(
+                    conn,
+                    "SELECT data FROM categories WHERE category_id = ?1",
+                    [category_id],
+                )
+            })
+            .await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<Category>> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entities(
+                    conn,
+                    "SELECT data FROM categories WHERE user_id = ?1 ORDER BY category_id",
+                    [user_id],
+                )
+            })
+            .await
+    }
+
+    async fn save(&self, category: Category) -> Result<()> {
+        self.db
+            .call(move |conn| write_category(conn, &category))
+            .await
+    }
+
+    async fn save_all(&self, categories: Vec<Category>) -> Result<()> {
+        self.db
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                for category in &categories {
+                    write_category(&tx, category)?;
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn update(&self, category: Category) -> Result<()> {
+        self.save(category).await
+    }
+}
+
+/// SQLite 月次集計リポジトリ
+pub struct SqliteAggregateRepository {
+    db: SqliteDatabase,
+}
+
+impl SqliteAggregateRepository {
+    pub fn new(db: SqliteDatabase) -> Self {
+        Self { db }
+    }
+}
+
+fn write_aggregate(conn: &Connection, aggregate: &MonthlyAggregate) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO monthly_aggregates (user_id, month, data) VALUES (?1, ?2, ?3)",
+        params![
+            aggregate.user_id.value(),
+            aggregate.month.to_string(),
+            to_json(aggregate)?
+        ],
+    )?;
+    Ok(())
+}
+
+#[async_trait]
+impl AggregateRepository for SqliteAggregateRepository {
+    async fn find(&self, user_id: &str, month: NaiveDate) -> Result<Option<MonthlyAggregate>> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entity(
+                    conn,
+                    "SELECT data FROM monthly_aggregates WHERE user_id = ?1 AND month = ?2",
+                    params![user_id, month.to_string()],
+                )
+            })
+            .await
+    }
+
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<MonthlyAggregate>> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entities(
+                    conn,
+                    "SELECT data FROM monthly_aggregates WHERE user_id = ?1 ORDER BY month",
+                    [user_id],
+                )
+            })
+            .await
+    }
+
+    async fn apply(&self, delta: MonthlyAggregate) -> Result<()> {
+        self.db
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let mut aggregate: MonthlyAggregate = query_entity(
+                    &tx,
+                    "SELECT data FROM monthly_aggregates WHERE user_id = ?1 AND month = ?2",
+                    params![delta.user_id.value(), delta.month.to_string()],
+                )?
+                .unwrap_or_else(|| MonthlyAggregate::new(delta.user_id.clone(), delta.month));
+                aggregate.merge(&delta);
+                write_aggregate(&tx, &aggregate)?;
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn replace_all(&self, user_id: &str, aggregates: Vec<MonthlyAggregate>) -> Result<()> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                tx.execute(
+                    "DELETE FROM monthly_aggregates WHERE user_id = ?1",
+                    [&user_id],
+                )?;
+                for aggregate in &aggregates {
+                    write_aggregate(&tx, aggregate)?;
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// SQLite 予算アラートリポジトリ
+pub struct SqliteBudgetAlertRepository {
+    db: SqliteDatabase,
+}
+
+impl SqliteBudgetAlertRepository {
+    pub fn new(db: SqliteDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl BudgetAlertRepository for SqliteBudgetAlertRepository {
+    async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<BudgetAlertRaised>> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entities(
+                    conn,
+                    "SELECT data FROM budget_alerts WHERE user_id = ?1
+                     ORDER BY raised_at DESC, alert_id DESC",
+                    [user_id],
+                )
+            })
+            .await
+    }
+
+    async fn save_if_absent(&self, alert: BudgetAlertRaised) -> Result<bool> {
+        self.db
+            .call(move |conn| {
+                let inserted = conn.execute(
+                    "INSERT OR IGNORE INTO budget_alerts (alert_id, user_id, raised_at, data)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        alert.alert_id,
+                        alert.user_id.value(),
+                        sortable_timestamp(&alert.raised_at),
+                        to_json(&alert)?
+                    ],
+                )?;
+                Ok(inserted == 1)
+            })
+            .await
+    }
+
+    async fn find_undelivered(&self, user_id: &str) -> Result<Vec<BudgetAlertRaised>> {
+        let user_id = user_id.to_string();
+        self.db
+            .call(move |conn| {
+                query_entities(
+                    conn,
+                    "SELECT data FROM budget_alerts WHERE user_id = ?1 AND delivered_at IS NULL
+                     ORDER BY raised_at, alert_id",
+                    [user_id],
+                )
+            })
+            .await
+    }
+
+    async fn update(&self, alert: BudgetAlertRaised) -> Result<()> {
+        self.db
+            .call(move |conn| {
+                conn.execute(
+                    "UPDATE budget_alerts SET delivered_at = ?2, data = ?3 WHERE alert_id = ?1",
+                    params![
+                        alert.alert_id,
+                        alert.delivered_at.as_ref().map(sortable_timestamp),
+                        to_json(&alert)?
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_applied_once() {
+        let dir = std::env::temp_dir().join(format!("axi-budget-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test.db");
+
+        SqliteDatabase::open(&path).unwrap();
+        // 2回目の起動ではマイグレーションを再実行しない（再実行すると CREATE TABLE が失敗する）
+        SqliteDatabase::open(&path).unwrap();
+
+        let connection = Connection::open(&path).unwrap();
+        let version: usize = connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_filter_columns_are_backfilled() {
+        // 絞り込み用カラム追加前のスキーマに保存された取引
+        let mut connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch(MIGRATIONS[0]).unwrap();
+        connection.pragma_update(None, "user_version", 1).unwrap();
+        let transaction = Transaction::new(
+            UserId::new("user123".to_string()),
+            TransactionType::Flow,
+            crate::domain::value_objects::Amount::jpy(1000),
+            "立替".to_string(),
+            TransactionCategory::Food,
+        );
+        connection
+            .execute(
+                "INSERT INTO transactions (transaction_id, user_id, transaction_date, data)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    transaction.transaction_id.value(),
+                    transaction.user_id.value(),
+                    sortable_timestamp(&transaction.transaction_date.timestamp()),
+                    to_json(&transaction).unwrap()
+                ],
+            )
+            .unwrap();
+
+        migrate(&mut connection).unwrap();
+
+        let (transaction_type, category, direction): (String, String, String) = connection
+            .query_row(
+                "SELECT transaction_type, category, direction FROM transactions",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(transaction_type, "FLOW");
+        assert_eq!(category, "FOOD");
+        // 向きの導入前の取引は支出
+        assert_eq!(direction, "EXPENSE");
+    }
+}
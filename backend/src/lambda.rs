@@ -0,0 +1,336 @@
+// Lambda HTTP アダプター
+// API Gateway (REST API v1 / HTTP API v2) と Lambda Function URL のイベントを
+// axum の Router に渡し、レスポンスを各イベント形式に変換して返す
+
+use anyhow::{bail, Context, Result};
+use axum::body::{to_bytes, Body};
+use axum::http::{header, HeaderMap, Request, Response};
+use axum::Router;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use tower::ServiceExt;
+
+/// Lambda イベントを Router で処理し、イベント形式に応じたレスポンスを返す
+///
+/// Function URL は HTTP API v2 と同じペイロード形式（`version: "2.0"`）で届く
+pub async fn handle_event(app: Router, event: Value) -> Result<Value> {
+    if event.get("version").and_then(Value::as_str) == Some("2.0") {
+        let event: HttpApiEvent =
+            serde_json::from_value(event).context("invalid HTTP API v2 event")?;
+        let response = app.oneshot(event.into_request()?).await?;
+        http_api_response(response).await
+    } else if event.get("httpMethod").is_some() {
+        let event: RestApiEvent =
+            serde_json::from_value(event).context("invalid REST API v1 event")?;
+        let response = app.oneshot(event.into_request()?).await?;
+        rest_api_response(response).await
+    } else {
+        bail!("unsupported Lambda event: not an API Gateway or Function URL request")
+    }
+}
+
+/// API Gateway REST API (ペイロード v1.0) のリクエストイベント
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct RestApiEvent {
+    http_method: String,
+    /// ステージ名を含まないリソースパス
+    path: String,
+    headers: Option<HashMap<String, String>>,
+    multi_value_headers: Option<HashMap<String, Vec<String>>>,
+    query_string_parameters: Option<HashMap<String, String>>,
+    multi_value_query_string_parameters: Option<HashMap<String, Vec<String>>>,
+    body: Option<String>,
+    is_base64_encoded: bool,
+}
+
+impl RestApiEvent {
+    fn into_request(self) -> Result<Request<Body>> {
+        // 複数値の方が情報を失わないため優先する。キー順は決定的にしておく
+        let query: BTreeMap<String, Vec<String>> = match self.multi_value_query_string_parameters {
+            Some(params) => params.into_iter().collect(),
+            None => self
+                .query_string_parameters
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(key, value)| (key, vec![value]))
+                .collect(),
+        };
+        let mut serializer = form_urlencoded::Serializer::new(String::new());
+        for (key, values) in &query {
+            for value in values {
+                serializer.append_pair(key, value);
+            }
+        }
+        let uri = with_query(&self.path, &serializer.finish());
+
+        let mut builder = Request::builder()
+            .method(self.http_method.as_str())
+            .uri(uri);
+        match self.multi_value_headers {
+            Some(headers) => {
+                for (name, values) in headers {
+                    for value in values {
+                        builder = builder.header(name.as_str(), value);
+                    }
+                }
+            }
+            None => {
+                for (name, value) in self.headers.unwrap_or_default() {
+                    builder = builder.header(name.as_str(), value);
+                }
+            }
+        }
+
+        Ok(builder.body(decode_body(self.body, self.is_base64_encoded)?)?)
+    }
+}
+
+/// API Gateway HTTP API (ペイロード v2.0) / Function URL のリクエストイベント
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct HttpApiEvent {
+    /// `$default` 以外のステージではステージ名が先頭に付く
+    raw_path: String,
+    raw_query_string: String,
+    cookies: Option<Vec<String>>,
+    headers: Option<HashMap<String, String>>,
+    body: Option<String>,
+    is_base64_encoded: bool,
+    request_context: HttpApiRequestContext,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct HttpApiRequestContext {
+    stage: Option<String>,
+    http: HttpApiDescription,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct HttpApiDescription {
+    method: String,
+}
+
+impl HttpApiEvent {
+    fn into_request(self) -> Result<Request<Body>> {
+        let path = strip_stage(&self.raw_path, self.request_context.stage.as_deref());
+        let uri = with_query(path, &self.raw_query_string);
+
+        let mut builder = Request::builder()
+            .method(self.request_context.http.method.as_str())
+            .uri(uri);
+        for (name, value) in self.headers.unwrap_or_default() {
+            builder = builder.header(name.as_str(), value);
+        }
+        // v2 では Cookie ヘッダーが `cookies` 配列に分離されている
+        if let Some(cookies) = self.cookies.filter(|cookies| !cookies.is_empty()) {
+            builder = builder.header(header::COOKIE, cookies.join("; "));
+        }
+
+        Ok(builder.body(decode_body(self.body, self.is_base64_encoded)?)?)
+    }
+}
+
+/// パス先頭のステージ名（`/<stage>`）を取り除く
+fn strip_stage<'a>(path: &'a str, stage: Option<&str>) -> &'a str {
+    let Some(stage) = stage.filter(|stage| !stage.is_empty() && *stage != "$default") else {
+        return path;
+    };
+    match path
+        .strip_prefix('/')
+        .and_then(|rest| rest.strip_prefix(stage))
+    {
+        Some("") => "/",
+        Some(rest) if rest.starts_with('/') => rest,
+        _ => path,
+    }
+}
+
+fn with_query(path: &str, query: &str) -> String {
+    let path = if path.is_empty() { "/" } else { path };
+    if query.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}?{}", path, query)
+    }
+}
+
+fn decode_body(body: Option<String>, is_base64_encoded: bool) -> Result<Body> {
+    match body {
+        None => Ok(Body::empty()),
+        Some(body) if is_base64_encoded => Ok(Body::from(
+            BASE64.decode(body).context("invalid base64 request body")?,
+        )),
+        Some(body) => Ok(Body::from(body)),
+    }
+}
+
+/// レスポンスボディをテキストのまま返せるかどうか（それ以外は base64 で返す）
+fn is_text(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        // Content-Type の無い空ボディ等はテキスト扱い
+        return true;
+    };
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    mime.starts_with("text/")
+        || mime.ends_with("+json")
+        || mime.ends_with("+xml")
+        || matches!(
+            mime.as_str(),
+            "application/json"
+                | "application/xml"
+                | "application/javascript"
+                | "application/x-www-form-urlencoded"
+        )
+}
+
+/// ボディを読み出し、`body` と `isBase64Encoded` の組にする
+async fn encode_body(response: Response<Body>) -> Result<(u16, HeaderMap, String, bool)> {
+    let (parts, body) = response.into_parts();
+    let bytes = to_bytes(body, usize::MAX).await?;
+    let (body, is_base64_encoded) = if is_text(&parts.headers) {
+        match String::from_utf8(bytes.to_vec()) {
+            Ok(text) => (text, false),
+            Err(_) => (BASE64.encode(&bytes), true),
+        }
+    } else {
+        (BASE64.encode(&bytes), true)
+    };
+    Ok((
+        parts.status.as_u16(),
+        parts.headers,
+        body,
+        is_base64_encoded,
+    ))
+}
+
+/// REST API v1 形式のレスポンス（ヘッダーは `multiValueHeaders` で返す）
+async fn rest_api_response(response: Response<Body>) -> Result<Value> {
+    let (status, headers, body, is_base64_encoded) = encode_body(response).await?;
+
+    let mut multi_value_headers = Map::new();
+    for name in headers.keys() {
+        let values: Vec<Value> = headers
+            .get_all(name)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .map(|value| Value::String(value.to_string()))
+            .collect();
+        multi_value_headers.insert(name.to_string(), Value::Array(values));
+    }
+
+    Ok(json!({
+        "statusCode": status,
+        "multiValueHeaders": multi_value_headers,
+        "body": body,
+        "isBase64Encoded": is_base64_encoded,
+    }))
+}
+
+/// HTTP API v2 / Function URL 形式のレスポンス
+///
+/// 複数値ヘッダーはカンマ区切りにまとめ、`Set-Cookie` は `cookies` 配列で返す
+async fn http_api_response(response: Response<Body>) -> Result<Value> {
+    let (status, headers, body, is_base64_encoded) = encode_body(response).await?;
+
+    let mut single_value_headers = Map::new();
+    let mut cookies = Vec::new();
+    for name in headers.keys() {
+        let values: Vec<&str> = headers
+            .get_all(name)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect();
+        if name == header::SET_COOKIE {
+            cookies.extend(values.into_iter().map(str::to_string));
+        } else {
+            single_value_headers.insert(name.to_string(), Value::String(values.join(",")));
+        }
+    }
+
+    Ok(json!({
+        "statusCode": status,
+        "headers": single_value_headers,
+        "cookies": cookies,
+        "body": body,
+        "isBase64Encoded": is_base64_encoded,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_stage() {
+        assert_eq!(strip_stage("/dev/api/users", Some("dev")), "/api/users");
+        assert_eq!(strip_stage("/dev", Some("dev")), "/");
+        assert_eq!(strip_stage("/api/users", Some("$default")), "/api/users");
+        // ステージ名と前方一致するだけのパスは変更しない
+        assert_eq!(strip_stage("/devices", Some("dev")), "/devices");
+        assert_eq!(strip_stage("/api/users", None), "/api/users");
+    }
+
+    #[test]
+    fn test_rest_api_query_string_keeps_all_values() {
+        let event: RestApiEvent = serde_json::from_value(json!({
+            "httpMethod": "GET",
+            "path": "/api/users/user123/transactions",
+            "queryStringParameters": { "tag": "b" },
+            "multiValueQueryStringParameters": { "tag": ["a", "b"], "q": ["食費 2025"] }
+        }))
+        .unwrap();
+
+        let request = event.into_request().unwrap();
+        assert_eq!(
+            request.uri().to_string(),
+            "/api/users/user123/transactions?q=%E9%A3%9F%E8%B2%BB+2025&tag=a&tag=b"
+        );
+    }
+
+    #[test]
+    fn test_http_api_request_keeps_cookies_and_binary_body() {
+        let event: HttpApiEvent = serde_json::from_value(json!({
+            "version": "2.0",
+            "rawPath": "/api/users",
+            "rawQueryString": "a=1&a=2",
+            "cookies": ["session=abc", "theme=dark"],
+            "headers": { "content-type": "application/octet-stream" },
+            "body": BASE64.encode([0u8, 159, 146, 150]),
+            "isBase64Encoded": true,
+            "requestContext": { "stage": "$default", "http": { "method": "PUT" } }
+        }))
+        .unwrap();
+
+        let request = event.into_request().unwrap();
+        assert_eq!(request.method(), "PUT");
+        assert_eq!(request.uri().to_string(), "/api/users?a=1&a=2");
+        assert_eq!(request.headers()[header::COOKIE], "session=abc; theme=dark");
+    }
+
+    #[test]
+    fn test_is_text() {
+        let mut headers = HeaderMap::new();
+        assert!(is_text(&headers));
+        headers.insert(
+            header::CONTENT_TYPE,
+            "application/json; charset=utf-8".parse().unwrap(),
+        );
+        assert!(is_text(&headers));
+        headers.insert(header::CONTENT_TYPE, "image/png".parse().unwrap());
+        assert!(!is_text(&headers));
+    }
+}
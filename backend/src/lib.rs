@@ -0,0 +1,9 @@
+pub mod application;
+pub mod auth;
+pub mod commands;
+pub mod config;
+pub mod domain;
+pub mod handlers;
+pub mod infrastructure;
+pub mod lambda;
+pub mod streams;
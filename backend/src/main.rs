@@ -1,41 +1,201 @@
-use lambda_web::LambdaError;
 use lambda_runtime::{service_fn, Error, LambdaEvent};
 use serde_json::Value;
+use std::sync::Arc;
 
-mod domain;
-mod infrastructure;
-mod application;
-mod handlers;
-
-use handlers::create_router;
+use axi_budget_backend::application::cursor::CursorCodec;
+use axi_budget_backend::application::{
+    AggregateUseCase, AppState, DynBudgetAlertNotifier, DynTransactionRepository, Repositories,
+};
+use axi_budget_backend::auth::JwtVerifier;
+use axi_budget_backend::commands::Command;
+use axi_budget_backend::config::{AlertConfig, Config, StorageBackend, StorageConfig};
+use axi_budget_backend::domain::services::AggregateService;
+use axi_budget_backend::handlers::create_router;
+use axi_budget_backend::infrastructure::*;
+use axi_budget_backend::lambda;
+use axi_budget_backend::streams::{self, StdoutLog, StreamConsumer};
+use axum::Router;
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
+    // 引数があれば保守コマンドとして実行する
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        return run_command(&args).await;
+    }
+
+    let config = Config::from_env()?;
+    let repositories = repositories(&config.storage).await?;
+    let notifier = notifier(&config.alerts)?;
+    let app = create_router(app_state(&config, repositories.clone(), notifier.clone()).await?);
+
     // Lambda環境での実行かローカル実行かを判定
     if std::env::var("AWS_LAMBDA_FUNCTION_NAME").is_ok() {
-        // Lambda環境での実行
-        lambda_runtime::run(service_fn(lambda_handler)).await
+        // Lambda環境での実行（HTTP リクエストとテーブルのストリームを同じ関数で受ける）
+        let consumer = Arc::new(StreamConsumer::new(
+            repositories,
+            config.storage.aggregate_updates,
+            notifier,
+            Box::new(StdoutLog),
+        ));
+        lambda_runtime::run(service_fn(move |event| {
+            lambda_handler(app.clone(), consumer.clone(), event)
+        }))
+        .await
     } else {
         // ローカル開発環境での実行
-        local_server().await
+        local_server(app).await
     }
 }
 
-async fn lambda_handler(event: LambdaEvent<Value>) -> Result<Value, LambdaError> {
-    let app = create_router();
-    // 簡略実装：Axumアプリをlambdaイベントとして実行
-    Ok(serde_json::json!({
-        "statusCode": 200,
-        "body": "AxI Budget API is running on Lambda"
-    }))
+/// 設定されたストレージバックエンドでアプリケーション状態を構築
+async fn app_state(
+    config: &Config,
+    repositories: Repositories,
+    notifier: DynBudgetAlertNotifier,
+) -> anyhow::Result<AppState> {
+    let verifier = Arc::new(JwtVerifier::load(&config.auth).await?);
+    let cursor_codec = match &config.cursor_secret {
+        Some(secret) => CursorCodec::new(secret.as_bytes()),
+        None => CursorCodec::random()?,
+    };
+    Ok(AppState::new(
+        verifier,
+        cursor_codec,
+        repositories,
+        config.storage.aggregate_updates,
+        notifier,
+    ))
 }
 
-async fn local_server() -> Result<(), Error> {
-    let app = create_router();
+/// 予算アラートの通知先（ログには常に出力し、Webhook は設定されている場合だけ送る）
+fn notifier(alerts: &AlertConfig) -> anyhow::Result<DynBudgetAlertNotifier> {
+    let mut notifiers: Vec<DynBudgetAlertNotifier> = vec![Arc::new(LogNotifier)];
+    if let Some(url) = &alerts.webhook_url {
+        notifiers.push(Arc::new(WebhookNotifier::new(
+            url.clone(),
+            alerts.webhook_secret.as_deref().map(str::as_bytes),
+        )?));
+    }
+    Ok(Arc::new(notifiers))
+}
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000")
-        .await
-        .unwrap();
+/// 設定されたストレージバックエンドのリポジトリ一式を構築
+async fn repositories(storage: &StorageConfig) -> anyhow::Result<Repositories> {
+    Ok(match storage.backend {
+        StorageBackend::DynamoDb => {
+            let aws_config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+            let client = aws_sdk_dynamodb::Client::new(&aws_config);
+            let table_name = storage.table_name.clone();
+
+            Repositories {
+                users: Arc::new(DynamoUserRepository::new(
+                    client.clone(),
+                    table_name.clone(),
+                )),
+                transactions: Arc::new(DynamoTransactionRepository::new(
+                    client.clone(),
+                    table_name.clone(),
+                )),
+                budgets: Arc::new(DynamoBudgetRepository::new(
+                    client.clone(),
+                    table_name.clone(),
+                )),
+                groups: Arc::new(DynamoGroupRepository::new(
+                    client.clone(),
+                    table_name.clone(),
+                )),
+                group_transactions: Arc::new(DynamoGroupTransactionRepository::new(
+                    client.clone(),
+                    table_name.clone(),
+                )),
+                settlements: Arc::new(DynamoSettlementRepository::new(
+                    client.clone(),
+                    table_name.clone(),
+                )),
+                accounts: Arc::new(DynamoAccountRepository::new(
+                    client.clone(),
+                    table_name.clone(),
+                )),
+                categories: Arc::new(DynamoCategoryRepository::new(
+                    client.clone(),
+                    table_name.clone(),
+                )),
+                aggregates: Arc::new(DynamoAggregateRepository::new(
+                    client.clone(),
+                    table_name.clone(),
+                )),
+                budget_alerts: Arc::new(DynamoBudgetAlertRepository::new(client, table_name)),
+            }
+        }
+        StorageBackend::InMemory => {
+            // グループ取引の立て替え分は取引リポジトリに書き込むため共有する
+            let transactions: DynTransactionRepository =
+                Arc::new(InMemoryTransactionRepository::new());
+
+            Repositories {
+                users: Arc::new(InMemoryUserRepository::new()),
+                transactions: transactions.clone(),
+                budgets: Arc::new(InMemoryBudgetRepository::new()),
+                groups: Arc::new(InMemoryGroupRepository::new()),
+                group_transactions: Arc::new(InMemoryGroupTransactionRepository::new(transactions)),
+                settlements: Arc::new(InMemorySettlementRepository::new()),
+                accounts: Arc::new(InMemoryAccountRepository::new()),
+                categories: Arc::new(InMemoryCategoryRepository::new()),
+                aggregates: Arc::new(InMemoryAggregateRepository::new()),
+                budget_alerts: Arc::new(InMemoryBudgetAlertRepository::new()),
+            }
+        }
+        StorageBackend::Sqlite => {
+            // 起動時にスキーママイグレーションを適用する
+            let db = SqliteDatabase::open(&storage.sqlite_path)?;
+
+            Repositories {
+                users: Arc::new(SqliteUserRepository::new(db.clone())),
+                transactions: Arc::new(SqliteTransactionRepository::new(db.clone())),
+                budgets: Arc::new(SqliteBudgetRepository::new(db.clone())),
+                groups: Arc::new(SqliteGroupRepository::new(db.clone())),
+                group_transactions: Arc::new(SqliteGroupTransactionRepository::new(db.clone())),
+                settlements: Arc::new(SqliteSettlementRepository::new(db.clone())),
+                accounts: Arc::new(SqliteAccountRepository::new(db.clone())),
+                categories: Arc::new(SqliteCategoryRepository::new(db.clone())),
+                aggregates: Arc::new(SqliteAggregateRepository::new(db.clone())),
+                budget_alerts: Arc::new(SqliteBudgetAlertRepository::new(db)),
+            }
+        }
+    })
+}
+
+/// 保守コマンドを実行（突き合わせで食い違いが見つかった場合は終了コード1で終わる）
+async fn run_command(args: &[String]) -> Result<(), Error> {
+    let command = Command::parse(args)?;
+    let repositories = repositories(&StorageConfig::from_env()?).await?;
+    let use_case = AggregateUseCase::new(AggregateService::new(
+        repositories.aggregates,
+        repositories.transactions,
+    ));
+    if !command.run(&use_case, &mut std::io::stdout()).await? {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// DynamoDB Streams のイベントはコンシューマーで、
+/// API Gateway / Function URL のイベントは Router に渡して処理
+async fn lambda_handler(
+    app: Router,
+    consumer: Arc<StreamConsumer>,
+    event: LambdaEvent<Value>,
+) -> Result<Value, Error> {
+    if streams::is_stream_event(&event.payload) {
+        Ok(consumer.handle_event(event.payload).await?)
+    } else {
+        Ok(lambda::handle_event(app, event.payload).await?)
+    }
+}
+
+async fn local_server(app: Router) -> Result<(), Error> {
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
 
     println!("Server running on http://0.0.0.0:3000");
     axum::serve(listener, app).await.unwrap();
@@ -0,0 +1,388 @@
+// DynamoDB Streams コンシューマー
+// テーブルの変更レコードをアイテムの `type` 属性で振り分け、監査ログの出力・
+// 予算アラートの判定・月次集計の更新を行う。失敗したレコードは部分バッチレスポンスで返す
+// （読み取れないレコードは再試行しても結果が変わらないため、ログに残して読み飛ばす）
+
+use crate::application::*;
+use crate::config::AggregateUpdates;
+use crate::domain::entities::{Budget, Transaction};
+use crate::domain::services::{AggregateService, BudgetAlertService, BudgetService};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::dynamodb::item_types;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_dynamo::{AttributeValue, Item};
+use serde_json::{json, Value};
+
+/// DynamoDB Streams のイベントかどうか
+pub fn is_stream_event(event: &Value) -> bool {
+    event
+        .get("Records")
+        .and_then(Value::as_array)
+        .and_then(|records| records.first())
+        .and_then(|record| record.get("eventSource"))
+        .and_then(Value::as_str)
+        == Some("aws:dynamodb")
+}
+
+/// コンシューマーが出力する監査ログ（と読み飛ばしたレコードの記録）の書き込み先
+pub trait StreamLog: Send + Sync {
+    fn write(&self, entry: Value);
+}
+
+/// 1件1行の JSON として標準出力に書き込む（Lambda 上では CloudWatch Logs に送られる）
+pub struct StdoutLog;
+
+impl StreamLog for StdoutLog {
+    fn write(&self, entry: Value) {
+        println!("{}", entry);
+    }
+}
+
+/// 変更前後のイメージが揃っていないレコードのエラーに添える説明
+const VIEW_TYPE_HINT: &str = "(the stream view type must be NEW_AND_OLD_IMAGES)";
+
+#[derive(Debug, Deserialize)]
+struct StreamEvent {
+    #[serde(rename = "Records")]
+    records: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum EventName {
+    Insert,
+    Modify,
+    Remove,
+}
+
+/// ストリームの変更レコード
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StreamRecord {
+    #[serde(rename = "eventID")]
+    event_id: String,
+    event_name: EventName,
+    dynamodb: StreamRecordData,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StreamRecordData {
+    /// 変更がストリームに記録された時刻（エポック秒）
+    approximate_creation_date_time: Option<f64>,
+    keys: Item,
+    new_image: Option<Item>,
+    old_image: Option<Item>,
+    sequence_number: String,
+}
+
+/// レコードが表すエンティティの変更（作成なら変更前、削除なら変更後がない）
+///
+/// レコードごとに1つ作ってすぐ捨てるため、バリアントの大きさの差は問題にならない
+#[allow(clippy::large_enum_variant)]
+enum Change {
+    Transaction {
+        before: Option<Transaction>,
+        after: Option<Transaction>,
+    },
+    /// 削除された予算は変更後がない
+    Budget { after: Option<Budget> },
+    /// 集計やアラートに関係しないアイテム
+    Other,
+}
+
+impl StreamRecord {
+    /// アイテムの `type` 属性（削除されたアイテムは変更前のイメージから読む）
+    fn item_type(&self) -> Option<&str> {
+        let image = self
+            .dynamodb
+            .new_image
+            .as_ref()
+            .or(self.dynamodb.old_image.as_ref())?;
+        match image.inner().get("type") {
+            Some(AttributeValue::S(item_type)) => Some(item_type),
+            _ => None,
+        }
+    }
+
+    fn changed_at(&self) -> DateTime<Utc> {
+        self.dynamodb
+            .approximate_creation_date_time
+            .and_then(|seconds| DateTime::from_timestamp(seconds as i64, 0))
+            .unwrap_or_else(Utc::now)
+    }
+
+    fn change(&self) -> Result<Change> {
+        if self.dynamodb.new_image.is_none() && self.dynamodb.old_image.is_none() {
+            bail!("stream record has no image {}", VIEW_TYPE_HINT);
+        }
+        Ok(match self.item_type() {
+            Some(item_types::TRANSACTION) => {
+                let (before, after) = self.entities()?;
+                Change::Transaction { before, after }
+            }
+            Some(item_types::BUDGET) => {
+                let (_, after) = self.entities::<Budget>()?;
+                Change::Budget { after }
+            }
+            _ => Change::Other,
+        })
+    }
+
+    /// 変更前と変更後のエンティティ
+    fn entities<T: DeserializeOwned>(&self) -> Result<(Option<T>, Option<T>)> {
+        let (has_before, has_after) = match self.event_name {
+            EventName::Insert => (false, true),
+            EventName::Modify => (true, true),
+            EventName::Remove => (true, false),
+        };
+        Ok((
+            decode_image(self.dynamodb.old_image.as_ref(), has_before, "OldImage")?,
+            decode_image(self.dynamodb.new_image.as_ref(), has_after, "NewImage")?,
+        ))
+    }
+
+    fn audit_entry(&self) -> Value {
+        json!({
+            "kind": "audit",
+            "eventId": self.event_id,
+            "eventName": self.event_name_str(),
+            "itemType": self.item_type(),
+            "keys": self.dynamodb.keys,
+            "sequenceNumber": self.dynamodb.sequence_number,
+            "changedAt": self.changed_at(),
+        })
+    }
+
+    /// 読み取れずに読み飛ばしたレコードの記録
+    fn skipped_entry(record: &Value, sequence_number: &str, err: &anyhow::Error) -> Value {
+        json!({
+            "kind": "skipped",
+            "eventId": record.get("eventID"),
+            "sequenceNumber": sequence_number,
+            "error": format!("{:#}", err),
+        })
+    }
+
+    fn event_name_str(&self) -> &'static str {
+        match self.event_name {
+            EventName::Insert => "INSERT",
+            EventName::Modify => "MODIFY",
+            EventName::Remove => "REMOVE",
+        }
+    }
+}
+
+fn decode_image<T: DeserializeOwned>(
+    image: Option<&Item>,
+    expected: bool,
+    name: &str,
+) -> Result<Option<T>> {
+    if !expected {
+        return Ok(None);
+    }
+    let image =
+        image.with_context(|| format!("stream record has no {} {}", name, VIEW_TYPE_HINT))?;
+    serde_dynamo::from_item(image.clone())
+        .map(Some)
+        .with_context(|| format!("failed to deserialize {}", name))
+}
+
+/// DynamoDB Streams コンシューマー
+///
+/// レコードごとに監査ログ、予算アラートの判定、月次集計、アラートの通知の順に処理する。
+/// 処理に失敗したレコードは再試行されるため、冪等でない月次集計の更新を判定の後に行う
+/// （予算アラートは期間と閾値ごとに一度しか発生しないため、再試行しても重複しない）。
+/// 通知の失敗ではレコードを再試行しない（未送信のアラートは次の判定の後に再送される）。
+/// 読み取れないレコードは再試行せず、`skipped` のログを残して読み飛ばす。
+/// 月次集計は `AggregateUpdates::Stream` の構成でのみ更新する（API と二重に加算しないため）
+pub struct StreamConsumer {
+    aggregate_use_case: AggregateUseCase<DynAggregateRepository, DynTransactionRepository>,
+    budget_alert_use_case: BudgetAlertUseCase<
+        DynBudgetRepository,
+        DynTransactionRepository,
+        DynUserRepository,
+        DynCategoryRepository,
+        DynBudgetAlertRepository,
+        DynBudgetAlertNotifier,
+    >,
+    aggregate_updates: AggregateUpdates,
+    log: Box<dyn StreamLog>,
+}
+
+impl StreamConsumer {
+    pub fn new(
+        repositories: Repositories,
+        aggregate_updates: AggregateUpdates,
+        notifier: DynBudgetAlertNotifier,
+        log: Box<dyn StreamLog>,
+    ) -> Self {
+        Self {
+            aggregate_use_case: AggregateUseCase::new(AggregateService::new(
+                repositories.aggregates,
+                repositories.transactions.clone(),
+            )),
+            budget_alert_use_case: BudgetAlertUseCase::new(BudgetAlertService::new(
+                BudgetService::new(
+                    repositories.budgets,
+                    repositories.transactions,
+                    repositories.users,
+                    repositories.categories,
+                ),
+                repositories.budget_alerts,
+                notifier,
+            )),
+            aggregate_updates,
+            log,
+        }
+    }
+
+    /// ストリームのイベントを処理し、部分バッチレスポンスを返す
+    ///
+    /// Lambda は失敗したレコードのうち最小のシーケンス番号から再試行するため、
+    /// 最初に失敗したレコード以降は処理せず、すべて失敗として返す
+    pub async fn handle_event(&self, event: Value) -> Result<Value> {
+        let event: StreamEvent =
+            serde_json::from_value(event).context("invalid DynamoDB Streams event")?;
+        // シーケンス番号のないレコードは失敗として返せないため、バッチ全体を失敗にする
+        let sequence_numbers = event
+            .records
+            .iter()
+            .map(|record| {
+                record["dynamodb"]["SequenceNumber"]
+                    .as_str()
+                    .context("stream record has no sequence number")
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut failures = Vec::new();
+        for (record, sequence_number) in event.records.iter().zip(sequence_numbers) {
+            if failures.is_empty() {
+                match self.handle_record(record, sequence_number).await {
+                    Ok(()) => continue,
+                    Err(err) => eprintln!(
+                        "Failed to process stream record {}: {:?}",
+                        sequence_number, err
+                    ),
+                }
+            }
+            failures.push(json!({ "itemIdentifier": sequence_number }));
+        }
+        Ok(json!({ "batchItemFailures": failures }))
+    }
+
+    async fn handle_record(&self, record: &Value, sequence_number: &str) -> Result<()> {
+        // 形式の誤りやイメージの欠落は再試行しても直らず、以降のレコードを止めてしまう
+        let decoded = StreamRecord::deserialize(record)
+            .context("invalid stream record")
+            .and_then(|decoded| decoded.change().map(|change| (decoded, change)));
+        let (record, change) = match decoded {
+            Ok(decoded) => decoded,
+            Err(err) => {
+                self.log
+                    .write(StreamRecord::skipped_entry(record, sequence_number, &err));
+                return Ok(());
+            }
+        };
+
+        // 集計とアラートのアイテムは取引・予算から導かれるため監査しない
+        if !matches!(
+            record.item_type(),
+            Some(item_types::MONTHLY_AGGREGATE | item_types::BUDGET_ALERT)
+        ) {
+            self.log.write(record.audit_entry());
+        }
+        let alerted_user = self
+            .evaluate_budget_alerts(&change, record.changed_at())
+            .await?;
+        if let (Change::Transaction { before, after }, AggregateUpdates::Stream) =
+            (&change, self.aggregate_updates)
+        {
+            self.aggregate_use_case
+                .apply_change(before.as_ref(), after.as_ref())
+                .await?;
+        }
+        if let Some(user_id) = alerted_user {
+            if let Err(err) = self
+                .budget_alert_use_case
+                .deliver_pending(&user_id, Utc::now())
+                .await
+            {
+                eprintln!("Failed to deliver budget alerts: {:?}", err);
+            }
+        }
+        Ok(())
+    }
+
+    /// 支出または予算が変わったときに、変更の時点の消化状況で予算アラートを判定する
+    ///
+    /// 判定したユーザーを返す（通知はレコードの処理の最後に行う）
+    async fn evaluate_budget_alerts(
+        &self,
+        change: &Change,
+        now: DateTime<Utc>,
+    ) -> Result<Option<UserId>> {
+        let (user_id, budget) = match change {
+            Change::Transaction { before, after } => match after.as_ref().or(before.as_ref()) {
+                Some(transaction) => (transaction.user_id.clone(), None),
+                None => return Ok(None),
+            },
+            Change::Budget {
+                after: Some(budget),
+            } => (budget.user_id.clone(), Some(budget.clone())),
+            Change::Budget { after: None } | Change::Other => return Ok(None),
+        };
+        self.budget_alert_use_case
+            .evaluate(&user_id, budget, now)
+            .await?;
+        Ok(Some(user_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_stream_event() {
+        assert!(is_stream_event(&json!({
+            "Records": [{ "eventSource": "aws:dynamodb", "eventName": "INSERT" }]
+        })));
+        assert!(!is_stream_event(&json!({
+            "Records": [{ "eventSource": "aws:sqs" }]
+        })));
+        assert!(!is_stream_event(&json!({ "Records": [] })));
+        assert!(!is_stream_event(
+            &json!({ "version": "2.0", "rawPath": "/" })
+        ));
+    }
+
+    #[test]
+    fn test_entities_require_images() {
+        let record = |event_name: &str, old_image: Value| -> StreamRecord {
+            serde_json::from_value(json!({
+                "eventID": "1",
+                "eventName": event_name,
+                "dynamodb": {
+                    "Keys": { "PK": { "S": "USER#user123" }, "SK": { "S": "BUDGET#b1" } },
+                    "OldImage": old_image,
+                    "SequenceNumber": "100"
+                }
+            }))
+            .unwrap()
+        };
+        let image = json!({ "type": { "S": "Budget" }, "budget_id": { "S": "b1" } });
+
+        // 削除は変更前のイメージから種類を判定する
+        let removed = record("REMOVE", image.clone());
+        assert_eq!(removed.item_type(), Some(item_types::BUDGET));
+        // 予算として読めないイメージ
+        assert!(removed.change().is_err());
+        // KEYS_ONLY などで変更後のイメージがない
+        assert!(record("MODIFY", image).entities::<Value>().is_err());
+        assert!(record("REMOVE", Value::Null).change().is_err());
+    }
+}
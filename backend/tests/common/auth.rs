@@ -0,0 +1,41 @@
+// 認証ヘルパー
+// tests/fixtures/auth のテスト用鍵で検証器を作り、トークンに署名する
+
+use axi_budget_backend::auth::JwtVerifier;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde_json::json;
+
+pub const ISSUER: &str = "https://securetoken.google.com/axi-budget";
+pub const AUDIENCE: &str = "axi-budget";
+
+fn fixture(name: &str) -> String {
+    format!(
+        "{}/tests/fixtures/auth/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+/// テスト用 JWKS を信頼する検証器
+pub fn verifier() -> JwtVerifier {
+    JwtVerifier::from_jwks_file(&fixture("jwks.json"), ISSUER, AUDIENCE).unwrap()
+}
+
+/// 1時間有効なトークン
+pub fn token(user_id: &str) -> String {
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some("test-key-1".to_string());
+    let claims = json!({
+        "sub": user_id,
+        "iss": ISSUER,
+        "aud": AUDIENCE,
+        "exp": chrono::Utc::now().timestamp() + 3600,
+    });
+    let private_key = std::fs::read(fixture("private_key.pem")).unwrap();
+    encode(
+        &header,
+        &claims,
+        &EncodingKey::from_rsa_pem(&private_key).unwrap(),
+    )
+    .unwrap()
+}
@@ -0,0 +1,778 @@
+// リポジトリ適合性テスト
+// すべてのストレージ実装が同じ振る舞いをすることを確認する共通シナリオ
+
+use axi_budget_backend::domain::repositories::*;
+use axi_budget_backend::domain::*;
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+
+fn user(id: &str) -> UserId {
+    UserId::new(id.to_string())
+}
+
+fn transaction(user_id: &str, description: &str) -> Transaction {
+    Transaction::new(
+        user(user_id),
+        TransactionType::Real,
+        Amount::jpy(1000),
+        description.to_string(),
+        TransactionCategory::Food,
+    )
+}
+
+pub async fn user_repository(repository: impl UserRepository) {
+    let mut profile = UserProfile::new(user("user123"));
+    repository.save(profile.clone()).await.unwrap();
+
+    let found = repository.find_by_id("user123").await.unwrap().unwrap();
+    assert_eq!(found.user_id, profile.user_id);
+    assert_eq!(found.currency, "JPY");
+    assert_eq!(found.created_at, profile.created_at);
+
+    profile.update(Some("太郎".to_string()), None, Some("UTC".to_string()));
+    repository.update(profile).await.unwrap();
+    let found = repository.find_by_id("user123").await.unwrap().unwrap();
+    assert_eq!(found.display_name.as_deref(), Some("太郎"));
+    assert_eq!(found.timezone, "UTC");
+
+    repository.delete("user123").await.unwrap();
+    assert!(repository.find_by_id("user123").await.unwrap().is_none());
+    // 存在しないユーザーの削除はエラーにしない
+    repository.delete("user123").await.unwrap();
+}
+
+pub async fn transaction_repository(repository: impl TransactionRepository) {
+    let base = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    let mut saved = Vec::new();
+    for day in 0..3 {
+        let mut transaction = transaction("user123", &format!("day {}", day));
+        transaction.transaction_date = TransactionDate::new(base + Duration::days(day));
+        transaction.add_tag("tag".to_string());
+        repository.save(transaction.clone()).await.unwrap();
+        saved.push(transaction);
+    }
+    repository
+        .save(transaction("other-user", "他人の取引"))
+        .await
+        .unwrap();
+
+    let found = repository
+        .find_by_id(saved[0].transaction_id.value())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.amount, Amount::jpy(1000));
+    assert_eq!(found.tags, vec!["tag".to_string()]);
+    assert_eq!(found.transaction_date, saved[0].transaction_date);
+    assert!(repository.find_by_id("missing").await.unwrap().is_none());
+
+    // 新しい取引から順に返す
+    let descriptions: Vec<_> = repository
+        .find_by_user_id("user123")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.description)
+        .collect();
+    assert_eq!(descriptions, vec!["day 2", "day 1", "day 0"]);
+
+    // 取引日を変更しても重複しない
+    let mut moved = saved[0].clone();
+    moved.update(Some("moved".to_string()), None);
+    moved.transaction_date = TransactionDate::new(base + Duration::days(10));
+    repository.update(moved.clone()).await.unwrap();
+    let transactions = repository.find_by_user_id("user123").await.unwrap();
+    assert_eq!(transactions.len(), 3);
+    assert_eq!(transactions[0].description, "moved");
+
+    repository
+        .delete(moved.transaction_id.value())
+        .await
+        .unwrap();
+    assert!(repository
+        .find_by_id(moved.transaction_id.value())
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        repository.find_by_user_id("user123").await.unwrap().len(),
+        2
+    );
+    repository.delete("missing").await.unwrap();
+
+    // 口座間移動の両側はまとめて保存・削除する
+    let transfer = AccountTransfer::new(
+        user("user123"),
+        "bank".to_string(),
+        "wallet".to_string(),
+        Amount::jpy(20000),
+        "ATM引き出し".to_string(),
+    );
+    let sides = transfer.transactions().to_vec();
+    repository.save_all(sides.clone()).await.unwrap();
+    let found = repository
+        .find_by_id(sides[1].transaction_id.value())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.transfer, sides[1].transfer);
+    assert_eq!(found.direction, TransactionDirection::Income);
+    assert_eq!(
+        repository.find_by_user_id("user123").await.unwrap().len(),
+        4
+    );
+
+    let ids: Vec<String> = sides.iter().map(|t| t.transaction_id.0.clone()).collect();
+    repository.delete_all(&ids).await.unwrap();
+    assert_eq!(
+        repository.find_by_user_id("user123").await.unwrap().len(),
+        2
+    );
+    repository.save_all(Vec::new()).await.unwrap();
+    repository.delete_all(&ids).await.unwrap();
+}
+
+pub async fn budget_repository(repository: impl BudgetRepository) {
+    let mut food = Budget::new(
+        user("user123"),
+        BudgetTarget::category(TransactionCategory::Food),
+        Amount::jpy(30000),
+        BudgetPeriod::Monthly,
+        0.8,
+    );
+    let yearly = Budget::new(
+        user("user123"),
+        BudgetTarget::Tags {
+            tags: vec!["学費".to_string()],
+        },
+        Amount::jpy(100000),
+        BudgetPeriod::Yearly,
+        0.9,
+    );
+    repository.save(food.clone()).await.unwrap();
+    repository.save(yearly.clone()).await.unwrap();
+
+    let found = repository
+        .find_by_id(&yearly.budget_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.period, BudgetPeriod::Yearly);
+    assert_eq!(found.target, yearly.target);
+    assert_eq!(found.alert_threshold, 0.9);
+
+    // 予算IDの昇順で返す
+    let mut expected = vec![food.budget_id.clone(), yearly.budget_id.clone()];
+    expected.sort();
+    let ids: Vec<_> = repository
+        .find_by_user_id("user123")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|b| b.budget_id)
+        .collect();
+    assert_eq!(ids, expected);
+    assert!(repository
+        .find_by_user_id("other")
+        .await
+        .unwrap()
+        .is_empty());
+
+    food.update(None, Some(Amount::jpy(35000)), None, Some(25), None, None)
+        .unwrap();
+    repository.update(food.clone()).await.unwrap();
+    let found = repository
+        .find_by_id(&food.budget_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.amount, Amount::jpy(35000));
+    assert_eq!(found.month_start_day, Some(25));
+
+    repository.delete(&food.budget_id).await.unwrap();
+    assert!(repository
+        .find_by_id(&food.budget_id)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        repository.find_by_user_id("user123").await.unwrap().len(),
+        1
+    );
+    repository.delete("missing").await.unwrap();
+}
+
+pub async fn account_repository(repository: impl AccountRepository) {
+    let mut card = Account::new(
+        user("user123"),
+        "カード".to_string(),
+        AccountType::CreditCard,
+        Amount::jpy(0),
+    );
+    card.credit_limit = Some(Amount::jpy(300000));
+    card.billing_cycle = Some(BillingCycle {
+        closing_day: 15,
+        payment_day: 10,
+        payment_month_offset: 1,
+        payment_account_id: Some("bank".to_string()),
+    });
+    let wallet = Account::new(
+        user("user123"),
+        "財布".to_string(),
+        AccountType::Cash,
+        Amount::jpy(5000),
+    );
+    repository.save(card.clone()).await.unwrap();
+    repository.save(wallet.clone()).await.unwrap();
+    repository
+        .save(Account::new(
+            user("other"),
+            "他人の口座".to_string(),
+            AccountType::Bank,
+            Amount::jpy(0),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        repository.find_by_id(&card.account_id).await.unwrap(),
+        Some(card.clone())
+    );
+
+    // 口座IDの昇順で返す
+    let mut expected = vec![card.account_id.clone(), wallet.account_id.clone()];
+    expected.sort();
+    let ids: Vec<_> = repository
+        .find_by_user_id("user123")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| a.account_id)
+        .collect();
+    assert_eq!(ids, expected);
+
+    card.update(None, None, None, Some(false));
+    repository.update(card.clone()).await.unwrap();
+    let found = repository
+        .find_by_id(&card.account_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!found.is_active);
+
+    repository.delete(&card.account_id).await.unwrap();
+    assert!(repository
+        .find_by_id(&card.account_id)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        repository.find_by_user_id("user123").await.unwrap().len(),
+        1
+    );
+    repository.delete("missing").await.unwrap();
+}
+
+pub async fn category_repository(repository: impl CategoryRepository) {
+    let defaults = Category::defaults(&user("user123"));
+    repository.save_all(defaults.clone()).await.unwrap();
+    let mut dining = Category::new(
+        user("user123"),
+        "外食".to_string(),
+        TransactionCategory::Food,
+    );
+    dining.parent_id = Some(defaults[0].category_id.clone());
+    dining.color = Some("#FF8800".to_string());
+    repository.save(dining.clone()).await.unwrap();
+    repository
+        .save(Category::new(
+            user("other"),
+            "他人のカテゴリ".to_string(),
+            TransactionCategory::Other,
+        ))
+        .await
+        .unwrap();
+    repository.save_all(Vec::new()).await.unwrap();
+
+    assert_eq!(
+        repository.find_by_id(&dining.category_id).await.unwrap(),
+        Some(dining.clone())
+    );
+    assert_eq!(repository.find_by_id("missing").await.unwrap(), None);
+
+    // カテゴリIDの昇順で返す
+    let mut expected: Vec<_> = defaults.iter().map(|c| c.category_id.clone()).collect();
+    expected.push(dining.category_id.clone());
+    expected.sort();
+    let ids: Vec<_> = repository
+        .find_by_user_id("user123")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.category_id)
+        .collect();
+    assert_eq!(ids, expected);
+
+    dining.update(None, None, None, Some(None), Some(true));
+    repository.update(dining.clone()).await.unwrap();
+    let found = repository
+        .find_by_id(&dining.category_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(found.is_archived);
+    assert_eq!(found.parent_id, None);
+}
+
+pub async fn group_repository(repository: impl GroupRepository) {
+    let mut trip = Group::new("旅行".to_string(), "沖縄".to_string(), user("alice"));
+    trip.add_member(user("carol"));
+    trip.add_member(user("bob"));
+    let share_house = Group::new("シェアハウス".to_string(), String::new(), user("bob"));
+    repository.save(trip.clone()).await.unwrap();
+    repository.save(share_house.clone()).await.unwrap();
+
+    // メンバーの並び順を保持する
+    let found = repository
+        .find_by_id(&trip.group_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found, trip);
+    assert!(repository.find_by_id("missing").await.unwrap().is_none());
+
+    // グループIDの昇順で返す
+    let mut expected = vec![trip.group_id.clone(), share_house.group_id.clone()];
+    expected.sort();
+    let ids: Vec<_> = repository
+        .find_by_user_id("bob")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|g| g.group_id)
+        .collect();
+    assert_eq!(ids, expected);
+
+    trip.remove_member(&user("carol"));
+    repository.update(trip.clone()).await.unwrap();
+    let found = repository
+        .find_by_id(&trip.group_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.members, vec![user("alice"), user("bob")]);
+    assert!(repository
+        .find_by_user_id("carol")
+        .await
+        .unwrap()
+        .is_empty());
+
+    repository.delete(&trip.group_id).await.unwrap();
+    assert!(repository
+        .find_by_id(&trip.group_id)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(repository
+        .find_by_user_id("alice")
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(repository.find_by_user_id("bob").await.unwrap().len(), 1);
+}
+
+pub async fn group_transaction_repository(
+    groups: impl GroupRepository,
+    repository: impl GroupTransactionRepository,
+    transactions: impl TransactionRepository,
+) {
+    let mut trip = Group::new("旅行".to_string(), String::new(), user("alice"));
+    trip.add_member(user("bob"));
+    let share_house = Group::new("シェアハウス".to_string(), String::new(), user("bob"));
+    groups.save(trip.clone()).await.unwrap();
+    groups.save(share_house.clone()).await.unwrap();
+
+    let group_transaction = |group: &Group, description: &str, day: u32| {
+        let members = group.members.clone();
+        let mut transaction = GroupTransaction::new(
+            group,
+            user("bob"),
+            Amount::jpy(3001),
+            description.to_string(),
+            TransactionCategory::Food,
+            SplitRule::Equal(members),
+        )
+        .unwrap();
+        transaction.transaction_date =
+            TransactionDate::new(NaiveDate::from_ymd_opt(2024, 3, day).unwrap());
+        transaction
+    };
+    let mut dinner = group_transaction(&trip, "夕食", 10);
+    let lunch = group_transaction(&trip, "昼食", 5);
+    let rent = group_transaction(&share_house, "家賃", 1);
+    for transaction in [&lunch, &rent] {
+        repository.save(transaction.clone(), None).await.unwrap();
+    }
+    // 支払者の立て替え分の取引はユーザーの取引として一緒に保存する
+    let payer_transaction = dinner.payer_transaction().unwrap();
+    repository
+        .save(dinner.clone(), Some(payer_transaction.clone()))
+        .await
+        .unwrap();
+    let found = transactions
+        .find_by_id(payer_transaction.transaction_id.value())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.user_id, payer_transaction.user_id);
+    assert_eq!(found.amount, payer_transaction.amount);
+    assert_eq!(found.transaction_date, dinner.transaction_date);
+    assert_eq!(transactions.find_by_user_id("bob").await.unwrap().len(), 1);
+    assert!(transactions
+        .find_by_user_id("alice")
+        .await
+        .unwrap()
+        .is_empty());
+
+    // 取引日の古い順に返し、割り勘方法と負担額を保持する
+    let found = repository.find_by_group_id(&trip.group_id).await.unwrap();
+    assert_eq!(found, vec![lunch.clone(), dinner.clone()]);
+    assert_eq!(
+        repository
+            .find_by_group_id(&share_house.group_id)
+            .await
+            .unwrap(),
+        vec![rent]
+    );
+    assert!(repository
+        .find_by_group_id("missing")
+        .await
+        .unwrap()
+        .is_empty());
+
+    // 同じ取引IDの保存は上書きする
+    dinner.description = "夕食（居酒屋）".to_string();
+    repository.save(dinner.clone(), None).await.unwrap();
+    let found = repository.find_by_group_id(&trip.group_id).await.unwrap();
+    assert_eq!(found, vec![lunch, dinner]);
+}
+
+pub async fn transaction_pages(repository: impl TransactionRepository) {
+    let base = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    for day in 0..5 {
+        let mut transaction = transaction("user123", &format!("day {}", day));
+        transaction.transaction_date = TransactionDate::new(base + Duration::days(day));
+        if day % 2 == 1 {
+            transaction.transaction_type = TransactionType::Flow;
+            transaction.category = TransactionCategory::Transportation;
+        }
+        if day == 2 {
+            transaction.direction = TransactionDirection::Income;
+        }
+        repository.save(transaction).await.unwrap();
+    }
+    // 同時刻の取引は取引IDで順序が決まる
+    let mut same_time = transaction("user123", "day 4 (2)");
+    same_time.transaction_date = TransactionDate::new(base + Duration::days(4));
+    repository.save(same_time).await.unwrap();
+    repository
+        .save(transaction("other-user", "他人の取引"))
+        .await
+        .unwrap();
+
+    // 2件ずつ読み進めると、一括取得と同じ順序で漏れなく重複なく返る
+    let mut query = TransactionQuery::new(2);
+    let mut paged = Vec::new();
+    loop {
+        let page = repository.find_page("user123", &query).await.unwrap();
+        assert!(page.transactions.len() <= 2);
+        paged.extend(page.transactions);
+        match page.next {
+            Some(next) => query.after = Some(next),
+            None => break,
+        }
+    }
+    let all: Vec<_> = repository
+        .find_by_user_id("user123")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.transaction_id)
+        .collect();
+    assert_eq!(all.len(), 6);
+    assert_eq!(
+        paged
+            .into_iter()
+            .map(|t| t.transaction_id)
+            .collect::<Vec<_>>(),
+        all
+    );
+
+    // 最終ページがちょうど埋まる場合は続き無し
+    let page = repository
+        .find_page("user123", &TransactionQuery::new(6))
+        .await
+        .unwrap();
+    assert_eq!(page.transactions.len(), 6);
+    assert!(page.next.is_none());
+
+    // 種別・カテゴリ・期間（開始は含み、終了は含まない）で絞り込む
+    let mut query = TransactionQuery::new(10);
+    query.transaction_type = Some(TransactionType::Flow);
+    let descriptions: Vec<_> = repository
+        .find_page("user123", &query)
+        .await
+        .unwrap()
+        .transactions
+        .into_iter()
+        .map(|t| t.description)
+        .collect();
+    assert_eq!(descriptions, vec!["day 3", "day 1"]);
+
+    let mut query = TransactionQuery::new(10);
+    query.category = Some(TransactionCategory::Food);
+    query.from = Some(TransactionDate::new(base + Duration::days(2)));
+    query.until = Some(TransactionDate::new(base + Duration::days(4)));
+    let descriptions: Vec<_> = repository
+        .find_page("user123", &query)
+        .await
+        .unwrap()
+        .transactions
+        .into_iter()
+        .map(|t| t.description)
+        .collect();
+    assert_eq!(descriptions, vec!["day 2"]);
+
+    // 収入・支出で絞り込む
+    for (direction, expected) in [
+        (TransactionDirection::Income, vec!["day 2"]),
+        (
+            TransactionDirection::Expense,
+            vec!["day 4 (2)", "day 4", "day 3", "day 1", "day 0"],
+        ),
+    ] {
+        let mut query = TransactionQuery::new(10);
+        query.direction = Some(direction);
+        let mut descriptions: Vec<_> = repository
+            .find_page("user123", &query)
+            .await
+            .unwrap()
+            .transactions
+            .into_iter()
+            .map(|t| t.description)
+            .collect();
+        // 同時刻の取引の順序は取引IDで決まるため、ここでは並びを揃えて比較する
+        descriptions.sort();
+        descriptions.reverse();
+        assert_eq!(descriptions, expected);
+    }
+
+    // フィルタで読み飛ばす件数が多くても続きの有無を正しく返す
+    let mut query = TransactionQuery::new(1);
+    query.transaction_type = Some(TransactionType::Flow);
+    let page = repository.find_page("user123", &query).await.unwrap();
+    assert_eq!(page.transactions[0].description, "day 3");
+    query.after = page.next;
+    let page = repository.find_page("user123", &query).await.unwrap();
+    assert_eq!(page.transactions[0].description, "day 1");
+    assert!(page.next.is_none());
+}
+
+pub async fn settlement_repository(repository: impl SettlementRepository) {
+    let mut lunch = Settlement::new(user("alice"), user("bob"), Amount::jpy(3000), None).unwrap();
+    let taxi = Settlement::new(
+        user("carol"),
+        user("alice"),
+        Amount::jpy(1500),
+        Some("タクシー".to_string()),
+    )
+    .unwrap();
+    repository.save(lunch.clone()).await.unwrap();
+    repository.save(taxi.clone()).await.unwrap();
+
+    assert_eq!(
+        repository
+            .find_by_id(&lunch.settlement_id)
+            .await
+            .unwrap()
+            .unwrap(),
+        lunch
+    );
+    assert!(repository.find_by_id("missing").await.unwrap().is_none());
+
+    // 債権者・債務者のどちらの立場でも取得でき、精算ID順に並ぶ
+    let mut expected = vec![lunch.settlement_id.clone(), taxi.settlement_id.clone()];
+    expected.sort();
+    let ids: Vec<_> = repository
+        .find_by_user_id("alice")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.settlement_id)
+        .collect();
+    assert_eq!(ids, expected);
+    assert_eq!(repository.find_by_user_id("bob").await.unwrap().len(), 1);
+    assert!(repository.find_by_user_id("dave").await.unwrap().is_empty());
+
+    // 更新はどちらの立場から見ても反映される
+    lunch.repay(Amount::jpy(1000)).unwrap();
+    repository.update(lunch.clone()).await.unwrap();
+    let seen_by_debtor = repository.find_by_user_id("bob").await.unwrap();
+    assert_eq!(seen_by_debtor, vec![lunch.clone()]);
+    assert_eq!(
+        repository
+            .find_by_id(&lunch.settlement_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .status,
+        SettlementStatus::PartiallyRepaid
+    );
+}
+
+pub async fn aggregate_repository(repository: impl AggregateRepository + 'static) {
+    let repository = std::sync::Arc::new(repository);
+    let april = NaiveDate::from_ymd_opt(2025, 4, 1).unwrap();
+    let may = NaiveDate::from_ymd_opt(2025, 5, 1).unwrap();
+    let on = |user_id: &str, date: NaiveDate| {
+        let mut transaction = transaction(user_id, "集計");
+        transaction.transaction_date = TransactionDate::new(date);
+        transaction
+    };
+    assert_eq!(repository.find("user123", april).await.unwrap(), None);
+
+    // 同じ月への同時の加算が失われない
+    let mut tasks = Vec::new();
+    for _ in 0..5 {
+        let repository = repository.clone();
+        let delta = MonthlyAggregate::changes(&[], &[on("user123", april)]).remove(0);
+        tasks.push(tokio::spawn(async move { repository.apply(delta).await }));
+    }
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+    for delta in MonthlyAggregate::changes(&[], &[on("user123", may), on("other", april)]) {
+        repository.apply(delta).await.unwrap();
+    }
+    let found = repository.find("user123", april).await.unwrap().unwrap();
+    assert_eq!(found.entries.len(), 1);
+    assert_eq!(found.entries[0].count, 5);
+    assert_eq!(found.entries[0].amount, 5000);
+
+    // 月の順に返す
+    let months: Vec<_> = repository
+        .find_by_user_id("user123")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| a.month)
+        .collect();
+    assert_eq!(months, [april, may]);
+
+    // 差し引いて0になった行は残らない
+    for delta in MonthlyAggregate::changes(&[on("user123", may)], &[]) {
+        repository.apply(delta).await.unwrap();
+    }
+    assert!(repository
+        .find("user123", may)
+        .await
+        .unwrap()
+        .unwrap()
+        .is_empty());
+
+    let rebuilt = MonthlyAggregate::from_transactions(&[on("user123", may)]);
+    repository
+        .replace_all("user123", rebuilt.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        repository.find_by_user_id("user123").await.unwrap(),
+        rebuilt
+    );
+    assert_eq!(repository.find_by_user_id("other").await.unwrap().len(), 1);
+
+    // 置き換えた後も加算できる
+    for delta in MonthlyAggregate::changes(&[], &[on("user123", may)]) {
+        repository.apply(delta).await.unwrap();
+    }
+    let found = repository.find("user123", may).await.unwrap().unwrap();
+    assert_eq!(found.entries[0].count, 2);
+}
+
+pub async fn budget_alert_repository(repository: impl BudgetAlertRepository + 'static) {
+    let repository = std::sync::Arc::new(repository);
+    let budget = Budget::new(
+        user("user123"),
+        BudgetTarget::All,
+        Amount::jpy(10000),
+        BudgetPeriod::Monthly,
+        0.8,
+    );
+    let alert = |month: u32, threshold: f64, day: u32| {
+        let mut budget = budget.clone();
+        budget.alert_threshold = threshold;
+        let start = NaiveDate::from_ymd_opt(2025, month, 1).unwrap();
+        let window = PeriodWindow {
+            start,
+            end: start + chrono::Months::new(1),
+        };
+        let raised_at = Utc.with_ymd_and_hms(2025, month, day, 0, 0, 0).unwrap();
+        BudgetAlertRaised::new(&budget, &window, 0.9, Amount::jpy(9000), raised_at)
+    };
+    assert!(repository
+        .find_by_user_id("user123")
+        .await
+        .unwrap()
+        .is_empty());
+
+    // 同じアラートの同時の保存はどれか1つだけが成功する
+    let april = alert(4, 0.8, 20);
+    let mut tasks = Vec::new();
+    for _ in 0..5 {
+        let repository = repository.clone();
+        let april = april.clone();
+        tasks.push(tokio::spawn(async move {
+            repository.save_if_absent(april).await
+        }));
+    }
+    let mut saved = 0;
+    for task in tasks {
+        if task.await.unwrap().unwrap() {
+            saved += 1;
+        }
+    }
+    assert_eq!(saved, 1);
+
+    // 期間や閾値が違えば別のアラート
+    let may = alert(5, 0.8, 10);
+    let lowered = alert(4, 0.5, 25);
+    assert!(repository.save_if_absent(may.clone()).await.unwrap());
+    assert!(repository.save_if_absent(lowered.clone()).await.unwrap());
+    let mut other = alert(4, 0.8, 20);
+    other.user_id = user("other");
+    other.alert_id = format!("other-{}", other.alert_id);
+    assert!(repository.save_if_absent(other).await.unwrap());
+
+    // 発生の新しい順に返す
+    assert_eq!(
+        repository.find_by_user_id("user123").await.unwrap(),
+        vec![may.clone(), lowered.clone(), april.clone()]
+    );
+    assert_eq!(repository.find_by_user_id("other").await.unwrap().len(), 1);
+
+    // 未送信のアラートは古い順に返し、通知済みにしたものは含めない
+    assert_eq!(
+        repository.find_undelivered("user123").await.unwrap(),
+        vec![april.clone(), lowered.clone(), may.clone()]
+    );
+    let mut delivered = april;
+    delivered.delivered_at = Some(Utc.with_ymd_and_hms(2025, 4, 20, 0, 1, 0).unwrap());
+    repository.update(delivered.clone()).await.unwrap();
+    assert_eq!(
+        repository.find_undelivered("user123").await.unwrap(),
+        vec![lowered.clone(), may.clone()]
+    );
+    assert_eq!(
+        repository.find_by_user_id("user123").await.unwrap(),
+        vec![may, lowered, delivered]
+    );
+}
@@ -0,0 +1,84 @@
+// 統合テスト用の共通ヘルパー
+// DynamoDB Local をコンテナで起動し、本番と同じキー設計のテーブルを作成する
+
+#![allow(dead_code)]
+
+pub mod auth;
+pub mod conformance;
+
+use aws_sdk_dynamodb::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_dynamodb::types::{
+    AttributeDefinition, BillingMode, GlobalSecondaryIndex, KeySchemaElement, KeyType, Projection,
+    ProjectionType, ScalarAttributeType,
+};
+use aws_sdk_dynamodb::Client;
+use testcontainers::core::WaitFor;
+use testcontainers::GenericImage;
+
+pub const TABLE_NAME: &str = "axi-budget-test";
+pub const DYNAMODB_LOCAL_PORT: u16 = 8000;
+
+/// DynamoDB Local のイメージ
+pub fn dynamodb_local() -> GenericImage {
+    GenericImage::new("amazon/dynamodb-local", "latest")
+        .with_exposed_port(DYNAMODB_LOCAL_PORT)
+        .with_wait_for(WaitFor::message_on_stdout(
+            "Initializing DynamoDB Local with the following configuration",
+        ))
+}
+
+/// 起動済みの DynamoDB Local に接続するクライアント
+pub fn client(host_port: u16) -> Client {
+    let config = aws_sdk_dynamodb::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new("ap-northeast-1"))
+        .endpoint_url(format!("http://127.0.0.1:{}", host_port))
+        .credentials_provider(Credentials::new("local", "local", None, None, "test"))
+        .build();
+    Client::from_conf(config)
+}
+
+/// terraform/modules/common/core_system/dynamodb.tf と同じ構成のテーブルを作成
+pub async fn create_table(client: &Client) {
+    let key = |name: &str, key_type: KeyType| {
+        KeySchemaElement::builder()
+            .attribute_name(name)
+            .key_type(key_type)
+            .build()
+            .unwrap()
+    };
+    let attribute = |name: &str| {
+        AttributeDefinition::builder()
+            .attribute_name(name)
+            .attribute_type(ScalarAttributeType::S)
+            .build()
+            .unwrap()
+    };
+
+    let gsi1 = GlobalSecondaryIndex::builder()
+        .index_name("GSI1")
+        .key_schema(key("GSI1PK", KeyType::Hash))
+        .key_schema(key("GSI1SK", KeyType::Range))
+        .projection(
+            Projection::builder()
+                .projection_type(ProjectionType::All)
+                .build(),
+        )
+        .build()
+        .unwrap();
+
+    client
+        .create_table()
+        .table_name(TABLE_NAME)
+        .billing_mode(BillingMode::PayPerRequest)
+        .key_schema(key("PK", KeyType::Hash))
+        .key_schema(key("SK", KeyType::Range))
+        .attribute_definitions(attribute("PK"))
+        .attribute_definitions(attribute("SK"))
+        .attribute_definitions(attribute("GSI1PK"))
+        .attribute_definitions(attribute("GSI1SK"))
+        .global_secondary_indexes(gsi1)
+        .send()
+        .await
+        .expect("failed to create table");
+}
@@ -0,0 +1,72 @@
+// DynamoBudgetRepository の統合テスト
+// Docker が必要なため通常の `cargo test` では実行せず、`cargo test -- --ignored` で実行する
+
+mod common;
+
+use axi_budget_backend::domain::repositories::BudgetRepository;
+use axi_budget_backend::domain::*;
+use axi_budget_backend::infrastructure::DynamoBudgetRepository;
+use testcontainers::clients::Cli;
+
+fn budget(user_id: &str, category: TransactionCategory, amount: i64) -> Budget {
+    Budget::new(
+        UserId::new(user_id.to_string()),
+        BudgetTarget::category(category),
+        Amount::jpy(amount),
+        BudgetPeriod::Monthly,
+        0.8,
+    )
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (DynamoDB Local)"]
+async fn test_budget_lifecycle() {
+    let docker = Cli::default();
+    let container = docker.run(common::dynamodb_local());
+    let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+    common::create_table(&client).await;
+    let repository = DynamoBudgetRepository::new(client, common::TABLE_NAME.to_string());
+
+    let mut food = budget("user123", TransactionCategory::Food, 30000);
+    let transport = budget("user123", TransactionCategory::Transportation, 10000);
+    repository.save(food.clone()).await.unwrap();
+    repository.save(transport.clone()).await.unwrap();
+    repository
+        .save(budget("other-user", TransactionCategory::Food, 5000))
+        .await
+        .unwrap();
+
+    let found = repository
+        .find_by_id(&food.budget_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        found.target,
+        BudgetTarget::category(TransactionCategory::Food)
+    );
+    assert_eq!(found.amount, Amount::jpy(30000));
+    assert_eq!(
+        repository.find_by_user_id("user123").await.unwrap().len(),
+        2
+    );
+
+    food.amount = Amount::jpy(40000);
+    repository.update(food.clone()).await.unwrap();
+    let found = repository
+        .find_by_id(&food.budget_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.amount, Amount::jpy(40000));
+
+    repository.delete(&food.budget_id).await.unwrap();
+    assert!(repository
+        .find_by_id(&food.budget_id)
+        .await
+        .unwrap()
+        .is_none());
+    let remaining = repository.find_by_user_id("user123").await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].budget_id, transport.budget_id);
+}
@@ -0,0 +1,144 @@
+// DynamoGroupRepository の統合テスト
+// Docker が必要なため通常の `cargo test` では実行せず、`cargo test -- --ignored` で実行する
+
+mod common;
+
+use axi_budget_backend::domain::repositories::GroupRepository;
+use axi_budget_backend::domain::*;
+use axi_budget_backend::infrastructure::DynamoGroupRepository;
+use testcontainers::clients::Cli;
+
+fn user(id: &str) -> UserId {
+    UserId::new(id.to_string())
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (DynamoDB Local)"]
+async fn test_group_members_are_stored_as_items() {
+    let docker = Cli::default();
+    let container = docker.run(common::dynamodb_local());
+    let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+    common::create_table(&client).await;
+    let repository = DynamoGroupRepository::new(client.clone(), common::TABLE_NAME.to_string());
+
+    let mut group = Group::new("旅行".to_string(), "沖縄旅行".to_string(), user("alice"));
+    group.add_member(user("bob"));
+    group.add_member(user("carol"));
+    repository.save(group.clone()).await.unwrap();
+
+    let found = repository
+        .find_by_id(&group.group_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found, group);
+
+    // メンバーはリスト属性ではなく MEMBER# アイテムとして格納される
+    let members = client
+        .query()
+        .table_name(common::TABLE_NAME)
+        .key_condition_expression("PK = :pk AND begins_with(SK, :sk)")
+        .expression_attribute_values(
+            ":pk",
+            aws_sdk_dynamodb::types::AttributeValue::S(format!("GROUP#{}", group.group_id)),
+        )
+        .expression_attribute_values(
+            ":sk",
+            aws_sdk_dynamodb::types::AttributeValue::S("MEMBER#".to_string()),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(members.count, 3);
+
+    group.remove_member(&user("bob"));
+    repository.update(group.clone()).await.unwrap();
+    let found = repository
+        .find_by_id(&group.group_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.members, vec![user("alice"), user("carol")]);
+    assert!(repository.find_by_user_id("bob").await.unwrap().is_empty());
+
+    repository.delete(&group.group_id).await.unwrap();
+    assert!(repository
+        .find_by_id(&group.group_id)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(repository
+        .find_by_user_id("alice")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (DynamoDB Local)"]
+async fn test_find_by_user_id_uses_memberships() {
+    let docker = Cli::default();
+    let container = docker.run(common::dynamodb_local());
+    let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+    common::create_table(&client).await;
+    let repository = DynamoGroupRepository::new(client, common::TABLE_NAME.to_string());
+
+    let mut trip = Group::new("旅行".to_string(), String::new(), user("alice"));
+    trip.add_member(user("bob"));
+    let share_house = Group::new("シェアハウス".to_string(), String::new(), user("bob"));
+    let other = Group::new("その他".to_string(), String::new(), user("carol"));
+    for group in [&trip, &share_house, &other] {
+        repository.save(group.clone()).await.unwrap();
+    }
+
+    let mut names: Vec<_> = repository
+        .find_by_user_id("bob")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|group| group.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["シェアハウス".to_string(), "旅行".to_string()]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (DynamoDB Local)"]
+async fn test_large_group_is_written_incrementally() {
+    let docker = Cli::default();
+    let container = docker.run(common::dynamodb_local());
+    let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+    common::create_table(&client).await;
+    let repository = DynamoGroupRepository::new(client, common::TABLE_NAME.to_string());
+
+    // TransactWriteItems の上限（100件）を超えるメンバー数でも保存できる
+    let mut group = Group::new("サークル".to_string(), String::new(), user("owner"));
+    for i in 0..150 {
+        group.add_member(user(&format!("member-{:03}", i)));
+    }
+    repository.save(group.clone()).await.unwrap();
+    assert_eq!(
+        repository.find_by_id(&group.group_id).await.unwrap(),
+        Some(group.clone())
+    );
+
+    // 追加・削除・並び替えが反映される
+    group.remove_member(&user("member-010"));
+    group.add_member(user("late-joiner"));
+    group.members.swap(1, 2);
+    repository.update(group.clone()).await.unwrap();
+    assert_eq!(
+        repository.find_by_id(&group.group_id).await.unwrap(),
+        Some(group.clone())
+    );
+
+    let groups = repository.find_by_user_id("late-joiner").await.unwrap();
+    assert_eq!(groups, vec![group.clone()]);
+
+    repository.delete(&group.group_id).await.unwrap();
+    assert!(repository
+        .find_by_id(&group.group_id)
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,118 @@
+// DynamoTransactionRepository の統合テスト
+// Docker が必要なため通常の `cargo test` では実行せず、`cargo test -- --ignored` で実行する
+
+mod common;
+
+use axi_budget_backend::domain::repositories::TransactionRepository;
+use axi_budget_backend::domain::*;
+use axi_budget_backend::infrastructure::DynamoTransactionRepository;
+use chrono::{Duration, NaiveDate};
+use testcontainers::clients::Cli;
+
+fn transaction(user_id: &str, description: &str) -> Transaction {
+    Transaction::new(
+        UserId::new(user_id.to_string()),
+        TransactionType::Real,
+        Amount::jpy(1000),
+        description.to_string(),
+        TransactionCategory::Food,
+    )
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (DynamoDB Local)"]
+async fn test_transaction_round_trip() {
+    let docker = Cli::default();
+    let container = docker.run(common::dynamodb_local());
+    let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+    common::create_table(&client).await;
+    let repository = DynamoTransactionRepository::new(client, common::TABLE_NAME.to_string());
+
+    let mut transaction = transaction("user123", "ランチ");
+    transaction.add_tag("仕事".to_string());
+    repository.save(transaction.clone()).await.unwrap();
+
+    let found = repository
+        .find_by_id(transaction.transaction_id.value())
+        .await
+        .unwrap()
+        .expect("transaction should be found by id");
+    assert_eq!(found.transaction_id, transaction.transaction_id);
+    assert_eq!(found.user_id, transaction.user_id);
+    assert_eq!(found.amount, transaction.amount);
+    assert_eq!(found.description, "ランチ");
+    assert_eq!(found.tags, vec!["仕事".to_string()]);
+    assert_eq!(found.transaction_date, transaction.transaction_date);
+
+    assert!(repository.find_by_id("missing").await.unwrap().is_none());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (DynamoDB Local)"]
+async fn test_find_by_user_id_returns_newest_first() {
+    let docker = Cli::default();
+    let container = docker.run(common::dynamodb_local());
+    let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+    common::create_table(&client).await;
+    let repository = DynamoTransactionRepository::new(client, common::TABLE_NAME.to_string());
+
+    let base = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    for day in 0..3 {
+        let mut transaction = transaction("user123", &format!("day {}", day));
+        transaction.transaction_date = TransactionDate::new(base + Duration::days(day));
+        repository.save(transaction).await.unwrap();
+    }
+    repository
+        .save(transaction("other-user", "他人の取引"))
+        .await
+        .unwrap();
+
+    let transactions = repository.find_by_user_id("user123").await.unwrap();
+    let descriptions: Vec<_> = transactions
+        .iter()
+        .map(|t| t.description.as_str())
+        .collect();
+    assert_eq!(descriptions, vec!["day 2", "day 1", "day 0"]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (DynamoDB Local)"]
+async fn test_update_and_delete() {
+    let docker = Cli::default();
+    let container = docker.run(common::dynamodb_local());
+    let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+    common::create_table(&client).await;
+    let repository = DynamoTransactionRepository::new(client, common::TABLE_NAME.to_string());
+
+    let mut transaction = transaction("user123", "before");
+    repository.save(transaction.clone()).await.unwrap();
+
+    // 取引日の変更でソートキーが変わっても、アイテムが重複しないこと
+    transaction.update(Some("after".to_string()), None);
+    transaction.transaction_date =
+        TransactionDate::new(transaction.transaction_date.date() - Duration::days(10));
+    repository.update(transaction.clone()).await.unwrap();
+
+    let transactions = repository.find_by_user_id("user123").await.unwrap();
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0].description, "after");
+    assert_eq!(
+        transactions[0].transaction_date,
+        transaction.transaction_date
+    );
+
+    repository
+        .delete(transaction.transaction_id.value())
+        .await
+        .unwrap();
+    assert!(repository
+        .find_by_id(transaction.transaction_id.value())
+        .await
+        .unwrap()
+        .is_none());
+    assert!(repository
+        .find_by_user_id("user123")
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,35 @@
+// DynamoUserRepository の統合テスト
+// Docker が必要なため通常の `cargo test` では実行せず、`cargo test -- --ignored` で実行する
+
+mod common;
+
+use axi_budget_backend::domain::repositories::UserRepository;
+use axi_budget_backend::domain::*;
+use axi_budget_backend::infrastructure::DynamoUserRepository;
+use testcontainers::clients::Cli;
+
+#[tokio::test]
+#[ignore = "requires Docker (DynamoDB Local)"]
+async fn test_user_profile_lifecycle() {
+    let docker = Cli::default();
+    let container = docker.run(common::dynamodb_local());
+    let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+    common::create_table(&client).await;
+    let repository = DynamoUserRepository::new(client, common::TABLE_NAME.to_string());
+
+    let mut user = UserProfile::new(UserId::new("user123".to_string()));
+    repository.save(user.clone()).await.unwrap();
+
+    let found = repository.find_by_id("user123").await.unwrap().unwrap();
+    assert_eq!(found.user_id, user.user_id);
+    assert_eq!(found.timezone, "Asia/Tokyo");
+    assert!(found.display_name.is_none());
+
+    user.update(Some("太郎".to_string()), None, None);
+    repository.update(user).await.unwrap();
+    let found = repository.find_by_id("user123").await.unwrap().unwrap();
+    assert_eq!(found.display_name.as_deref(), Some("太郎"));
+
+    repository.delete("user123").await.unwrap();
+    assert!(repository.find_by_id("user123").await.unwrap().is_none());
+}
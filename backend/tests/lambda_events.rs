@@ -0,0 +1,135 @@
+// Lambda イベント アダプターのテスト
+// 記録済みのイベントペイロード（tests/fixtures/lambda）をインメモリ構成の Router に流す
+
+mod common;
+
+use axi_budget_backend::application::cursor::CursorCodec;
+use axi_budget_backend::application::{
+    AppState, DynBudgetAlertNotifier, DynTransactionRepository, Repositories,
+};
+use axi_budget_backend::config::AggregateUpdates;
+use axi_budget_backend::handlers::create_router;
+use axi_budget_backend::infrastructure::*;
+use axi_budget_backend::lambda::handle_event;
+use axum::Router;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+fn app() -> Router {
+    let transactions: DynTransactionRepository = Arc::new(InMemoryTransactionRepository::new());
+    create_router(AppState::new(
+        Arc::new(common::auth::verifier()),
+        CursorCodec::new(b"test-secret"),
+        Repositories {
+            users: Arc::new(InMemoryUserRepository::new()),
+            transactions: transactions.clone(),
+            budgets: Arc::new(InMemoryBudgetRepository::new()),
+            groups: Arc::new(InMemoryGroupRepository::new()),
+            group_transactions: Arc::new(InMemoryGroupTransactionRepository::new(transactions)),
+            settlements: Arc::new(InMemorySettlementRepository::new()),
+            accounts: Arc::new(InMemoryAccountRepository::new()),
+            categories: Arc::new(InMemoryCategoryRepository::new()),
+            aggregates: Arc::new(InMemoryAggregateRepository::new()),
+            budget_alerts: Arc::new(InMemoryBudgetAlertRepository::new()),
+        },
+        AggregateUpdates::Inline,
+        Arc::new(Vec::<DynBudgetAlertNotifier>::new()),
+    ))
+}
+
+fn fixture(name: &str) -> Value {
+    let path = format!(
+        "{}/tests/fixtures/lambda/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// 記録済みイベントに user123 のトークンを付与する（トークンは期限があるため実行時に署名）
+fn authorized(name: &str) -> Value {
+    let mut event = fixture(name);
+    let authorization = format!("Bearer {}", common::auth::token("user123"));
+    event["headers"]["Authorization"] = json!(authorization);
+    if event.get("multiValueHeaders").is_some() {
+        event["multiValueHeaders"]["Authorization"] = json!([authorization]);
+    }
+    event
+}
+
+fn body_json(response: &Value) -> Value {
+    serde_json::from_str(response["body"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_rest_api_v1_event() {
+    let response = handle_event(app(), authorized("apigw_rest_v1"))
+        .await
+        .unwrap();
+
+    assert_eq!(response["statusCode"], 201);
+    assert_eq!(response["isBase64Encoded"], false);
+    assert_eq!(
+        response["multiValueHeaders"]["content-type"],
+        json!(["application/json"])
+    );
+    let body = body_json(&response);
+    assert_eq!(body["userId"], "user123");
+    assert_eq!(body["displayName"], "山田太郎");
+}
+
+#[tokio::test]
+async fn test_http_api_v2_event_strips_stage_prefix() {
+    let app = app();
+    handle_event(app.clone(), authorized("apigw_rest_v1"))
+        .await
+        .unwrap();
+
+    // rawPath は `/dev/api/users/profile`
+    let response = handle_event(app, authorized("apigw_http_v2"))
+        .await
+        .unwrap();
+
+    assert_eq!(response["statusCode"], 200);
+    assert_eq!(response["headers"]["content-type"], "application/json");
+    assert_eq!(response["cookies"], json!([]));
+    assert_eq!(body_json(&response)["userId"], "user123");
+}
+
+#[tokio::test]
+async fn test_function_url_event_with_base64_body() {
+    let response = handle_event(app(), authorized("function_url"))
+        .await
+        .unwrap();
+
+    assert_eq!(response["statusCode"], 201);
+    let body = body_json(&response);
+    assert_eq!(body["category"], "FOOD");
+    assert_eq!(body["amount"]["value"], 30000);
+}
+
+#[tokio::test]
+async fn test_error_status_is_preserved() {
+    let mut event = authorized("apigw_http_v2");
+    event["rawPath"] = json!("/dev/api/transactions/unknown");
+
+    let response = handle_event(app(), event).await.unwrap();
+
+    assert_eq!(response["statusCode"], 404);
+    assert_eq!(body_json(&response)["code"], "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_missing_token_is_unauthorized() {
+    let response = handle_event(app(), fixture("apigw_http_v2")).await.unwrap();
+
+    assert_eq!(response["statusCode"], 401);
+    assert_eq!(response["headers"]["www-authenticate"], "Bearer");
+    assert_eq!(body_json(&response)["code"], "UNAUTHORIZED");
+}
+
+#[tokio::test]
+async fn test_unsupported_event_is_rejected() {
+    let event = json!({ "Records": [] });
+    assert!(handle_event(app(), event).await.is_err());
+}
@@ -0,0 +1,306 @@
+// リポジトリ適合性テスト
+// 同じシナリオ（tests/common/conformance.rs）を各ストレージ実装に対して実行する
+
+mod common;
+
+use common::conformance;
+
+mod memory {
+    use super::conformance;
+    use axi_budget_backend::infrastructure::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_user_repository() {
+        conformance::user_repository(InMemoryUserRepository::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_transaction_repository() {
+        conformance::transaction_repository(InMemoryTransactionRepository::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_transaction_pages() {
+        conformance::transaction_pages(InMemoryTransactionRepository::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_budget_repository() {
+        conformance::budget_repository(InMemoryBudgetRepository::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_account_repository() {
+        conformance::account_repository(InMemoryAccountRepository::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_category_repository() {
+        conformance::category_repository(InMemoryCategoryRepository::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_repository() {
+        conformance::aggregate_repository(InMemoryAggregateRepository::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_budget_alert_repository() {
+        conformance::budget_alert_repository(InMemoryBudgetAlertRepository::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_group_repository() {
+        conformance::group_repository(InMemoryGroupRepository::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_group_transaction_repository() {
+        let transactions = Arc::new(InMemoryTransactionRepository::new());
+        conformance::group_transaction_repository(
+            InMemoryGroupRepository::new(),
+            InMemoryGroupTransactionRepository::new(transactions.clone()),
+            transactions,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_settlement_repository() {
+        conformance::settlement_repository(InMemorySettlementRepository::new()).await;
+    }
+}
+
+mod sqlite {
+    use super::conformance;
+    use axi_budget_backend::infrastructure::*;
+
+    fn db() -> SqliteDatabase {
+        SqliteDatabase::open_in_memory().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_user_repository() {
+        conformance::user_repository(SqliteUserRepository::new(db())).await;
+    }
+
+    #[tokio::test]
+    async fn test_transaction_repository() {
+        conformance::transaction_repository(SqliteTransactionRepository::new(db())).await;
+    }
+
+    #[tokio::test]
+    async fn test_transaction_pages() {
+        conformance::transaction_pages(SqliteTransactionRepository::new(db())).await;
+    }
+
+    #[tokio::test]
+    async fn test_budget_repository() {
+        conformance::budget_repository(SqliteBudgetRepository::new(db())).await;
+    }
+
+    #[tokio::test]
+    async fn test_account_repository() {
+        conformance::account_repository(SqliteAccountRepository::new(db())).await;
+    }
+
+    #[tokio::test]
+    async fn test_category_repository() {
+        conformance::category_repository(SqliteCategoryRepository::new(db())).await;
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_repository() {
+        conformance::aggregate_repository(SqliteAggregateRepository::new(db())).await;
+    }
+
+    #[tokio::test]
+    async fn test_budget_alert_repository() {
+        conformance::budget_alert_repository(SqliteBudgetAlertRepository::new(db())).await;
+    }
+
+    #[tokio::test]
+    async fn test_group_repository() {
+        conformance::group_repository(SqliteGroupRepository::new(db())).await;
+    }
+
+    #[tokio::test]
+    async fn test_group_transaction_repository() {
+        // グループ取引はグループを参照し、立て替え分を取引に書き込むため同じデータベースを使う
+        let db = db();
+        conformance::group_transaction_repository(
+            SqliteGroupRepository::new(db.clone()),
+            SqliteGroupTransactionRepository::new(db.clone()),
+            SqliteTransactionRepository::new(db),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_settlement_repository() {
+        conformance::settlement_repository(SqliteSettlementRepository::new(db())).await;
+    }
+}
+
+// Docker が必要なため通常の `cargo test` では実行せず、`cargo test -- --ignored` で実行する
+mod dynamodb {
+    use super::{common, conformance};
+    use axi_budget_backend::infrastructure::*;
+    use testcontainers::clients::Cli;
+
+    #[tokio::test]
+    #[ignore = "requires Docker (DynamoDB Local)"]
+    async fn test_user_repository() {
+        let docker = Cli::default();
+        let container = docker.run(common::dynamodb_local());
+        let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+        common::create_table(&client).await;
+        conformance::user_repository(DynamoUserRepository::new(
+            client,
+            common::TABLE_NAME.to_string(),
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Docker (DynamoDB Local)"]
+    async fn test_transaction_repository() {
+        let docker = Cli::default();
+        let container = docker.run(common::dynamodb_local());
+        let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+        common::create_table(&client).await;
+        conformance::transaction_repository(DynamoTransactionRepository::new(
+            client,
+            common::TABLE_NAME.to_string(),
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Docker (DynamoDB Local)"]
+    async fn test_transaction_pages() {
+        let docker = Cli::default();
+        let container = docker.run(common::dynamodb_local());
+        let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+        common::create_table(&client).await;
+        conformance::transaction_pages(DynamoTransactionRepository::new(
+            client,
+            common::TABLE_NAME.to_string(),
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Docker (DynamoDB Local)"]
+    async fn test_budget_repository() {
+        let docker = Cli::default();
+        let container = docker.run(common::dynamodb_local());
+        let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+        common::create_table(&client).await;
+        conformance::budget_repository(DynamoBudgetRepository::new(
+            client,
+            common::TABLE_NAME.to_string(),
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Docker (DynamoDB Local)"]
+    async fn test_account_repository() {
+        let docker = Cli::default();
+        let container = docker.run(common::dynamodb_local());
+        let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+        common::create_table(&client).await;
+        conformance::account_repository(DynamoAccountRepository::new(
+            client,
+            common::TABLE_NAME.to_string(),
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Docker (DynamoDB Local)"]
+    async fn test_category_repository() {
+        let docker = Cli::default();
+        let container = docker.run(common::dynamodb_local());
+        let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+        common::create_table(&client).await;
+        conformance::category_repository(DynamoCategoryRepository::new(
+            client,
+            common::TABLE_NAME.to_string(),
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Docker (DynamoDB Local)"]
+    async fn test_aggregate_repository() {
+        let docker = Cli::default();
+        let container = docker.run(common::dynamodb_local());
+        let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+        common::create_table(&client).await;
+        conformance::aggregate_repository(DynamoAggregateRepository::new(
+            client,
+            common::TABLE_NAME.to_string(),
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Docker (DynamoDB Local)"]
+    async fn test_budget_alert_repository() {
+        let docker = Cli::default();
+        let container = docker.run(common::dynamodb_local());
+        let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+        common::create_table(&client).await;
+        conformance::budget_alert_repository(DynamoBudgetAlertRepository::new(
+            client,
+            common::TABLE_NAME.to_string(),
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Docker (DynamoDB Local)"]
+    async fn test_group_repository() {
+        let docker = Cli::default();
+        let container = docker.run(common::dynamodb_local());
+        let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+        common::create_table(&client).await;
+        conformance::group_repository(DynamoGroupRepository::new(
+            client,
+            common::TABLE_NAME.to_string(),
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Docker (DynamoDB Local)"]
+    async fn test_group_transaction_repository() {
+        let docker = Cli::default();
+        let container = docker.run(common::dynamodb_local());
+        let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+        common::create_table(&client).await;
+        conformance::group_transaction_repository(
+            DynamoGroupRepository::new(client.clone(), common::TABLE_NAME.to_string()),
+            DynamoGroupTransactionRepository::new(client.clone(), common::TABLE_NAME.to_string()),
+            DynamoTransactionRepository::new(client, common::TABLE_NAME.to_string()),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Docker (DynamoDB Local)"]
+    async fn test_settlement_repository() {
+        let docker = Cli::default();
+        let container = docker.run(common::dynamodb_local());
+        let client = common::client(container.get_host_port_ipv4(common::DYNAMODB_LOCAL_PORT));
+        common::create_table(&client).await;
+        conformance::settlement_repository(DynamoSettlementRepository::new(
+            client,
+            common::TABLE_NAME.to_string(),
+        ))
+        .await;
+    }
+}
@@ -0,0 +1,299 @@
+// DynamoDB Streams コンシューマーのテスト
+// 記録済みのストリームイベント（tests/fixtures/streams）をインメモリ構成のコンシューマーに流す
+
+use async_trait::async_trait;
+use axi_budget_backend::application::{DynTransactionRepository, Repositories};
+use axi_budget_backend::config::AggregateUpdates;
+use axi_budget_backend::domain::entities::{
+    Budget, BudgetAlertRaised, MonthlyAggregate, UserProfile,
+};
+use axi_budget_backend::domain::notifiers::BudgetAlertNotifier;
+use axi_budget_backend::domain::repositories::*;
+use axi_budget_backend::domain::value_objects::UserId;
+use axi_budget_backend::infrastructure::*;
+use axi_budget_backend::streams::{StreamConsumer, StreamLog};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+/// 書き込まれたログを保持する
+#[derive(Clone, Default)]
+struct RecordedLog(Arc<Mutex<Vec<Value>>>);
+
+impl RecordedLog {
+    fn entries(&self, kind: &str) -> Vec<Value> {
+        let entries = self.0.lock().unwrap();
+        entries
+            .iter()
+            .filter(|entry| entry["kind"] == kind)
+            .cloned()
+            .collect()
+    }
+}
+
+impl StreamLog for RecordedLog {
+    fn write(&self, entry: Value) {
+        self.0.lock().unwrap().push(entry);
+    }
+}
+
+/// 通知されたアラートを保持する
+#[derive(Clone, Default)]
+struct RecordedNotifier(Arc<Mutex<Vec<BudgetAlertRaised>>>);
+
+impl RecordedNotifier {
+    fn alerts(&self) -> Vec<BudgetAlertRaised> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl BudgetAlertNotifier for RecordedNotifier {
+    async fn notify(&self, alert: &BudgetAlertRaised) -> anyhow::Result<()> {
+        self.0.lock().unwrap().push(alert.clone());
+        Ok(())
+    }
+}
+
+struct Harness {
+    consumer: StreamConsumer,
+    repositories: Repositories,
+    log: RecordedLog,
+    notifier: RecordedNotifier,
+}
+
+fn consumer() -> Harness {
+    let transactions: DynTransactionRepository = Arc::new(InMemoryTransactionRepository::new());
+    let repositories = Repositories {
+        users: Arc::new(InMemoryUserRepository::new()),
+        transactions: transactions.clone(),
+        budgets: Arc::new(InMemoryBudgetRepository::new()),
+        groups: Arc::new(InMemoryGroupRepository::new()),
+        group_transactions: Arc::new(InMemoryGroupTransactionRepository::new(transactions)),
+        settlements: Arc::new(InMemorySettlementRepository::new()),
+        accounts: Arc::new(InMemoryAccountRepository::new()),
+        categories: Arc::new(InMemoryCategoryRepository::new()),
+        aggregates: Arc::new(InMemoryAggregateRepository::new()),
+        budget_alerts: Arc::new(InMemoryBudgetAlertRepository::new()),
+    };
+    let log = RecordedLog::default();
+    let notifier = RecordedNotifier::default();
+    Harness {
+        consumer: StreamConsumer::new(
+            repositories.clone(),
+            AggregateUpdates::Stream,
+            Arc::new(notifier.clone()),
+            Box::new(log.clone()),
+        ),
+        repositories,
+        log,
+        notifier,
+    }
+}
+
+fn fixture(name: &str) -> Value {
+    let path = format!(
+        "{}/tests/fixtures/streams/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// レコードの変更後のイメージをエンティティとして読む
+fn new_image<T: DeserializeOwned>(event: &Value, index: usize) -> T {
+    let image: serde_dynamo::Item =
+        serde_json::from_value(event["Records"][index]["dynamodb"]["NewImage"].clone()).unwrap();
+    serde_dynamo::from_item(image).unwrap()
+}
+
+fn sequence_number(event: &Value, index: usize) -> Value {
+    event["Records"][index]["dynamodb"]["SequenceNumber"].clone()
+}
+
+/// 取引のない月の空の集計を除いた月次集計
+async fn aggregates(harness: &Harness) -> Vec<MonthlyAggregate> {
+    let mut aggregates = harness
+        .repositories
+        .aggregates
+        .find_by_user_id("user123")
+        .await
+        .unwrap();
+    aggregates.retain(|a| !a.is_empty());
+    aggregates
+}
+
+#[tokio::test]
+async fn test_transaction_changes_update_aggregates() {
+    let harness = consumer();
+    let response = harness
+        .consumer
+        .handle_event(fixture("transactions"))
+        .await
+        .unwrap();
+
+    assert_eq!(response, json!({ "batchItemFailures": [] }));
+
+    // 作成・更新・削除を経て、1月の食費 1,500円だけが残る
+    let aggregates = aggregates(&harness).await;
+    assert_eq!(aggregates.len(), 1);
+    assert_eq!(aggregates[0].month.to_string(), "2025-01-01");
+    assert_eq!(aggregates[0].entries.len(), 1);
+    assert_eq!(aggregates[0].entries[0].amount, 1500);
+    assert_eq!(aggregates[0].entries[0].count, 1);
+
+    // 集計アイテム自身の変更は監査しない
+    let audit = harness.log.entries("audit");
+    let item_types: Vec<&str> = audit
+        .iter()
+        .map(|entry| entry["itemType"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        item_types,
+        [
+            "Transaction",
+            "Transaction",
+            "Transaction",
+            "Transaction",
+            "UserProfile"
+        ]
+    );
+    assert_eq!(audit[1]["eventName"], "MODIFY");
+    assert_eq!(audit[1]["keys"]["PK"], json!({ "S": "USER#user123" }));
+    assert_eq!(audit[1]["changedAt"], "2025-01-15T04:00:00Z");
+    assert!(harness.notifier.alerts().is_empty());
+}
+
+#[tokio::test]
+async fn test_failed_record_and_later_records_are_reported() {
+    let harness = consumer();
+    // 予算の判定に使うタイムゾーンが読めず、取引のレコードの処理に失敗する
+    let mut profile = UserProfile::new(UserId::new("user123".to_string()));
+    profile.timezone = "Mars/Olympus".to_string();
+    harness.repositories.users.save(profile).await.unwrap();
+    let budget: Budget = new_image(&fixture("budget_alert"), 2);
+    harness.repositories.budgets.save(budget).await.unwrap();
+    let event = fixture("transactions");
+
+    let response = harness.consumer.handle_event(event.clone()).await.unwrap();
+
+    // 失敗したレコード以降は処理せず、すべて再試行させる
+    let failures: Vec<Value> = (0..6)
+        .map(|index| json!({ "itemIdentifier": sequence_number(&event, index) }))
+        .collect();
+    assert_eq!(response, json!({ "batchItemFailures": failures }));
+    assert!(aggregates(&harness).await.is_empty());
+    assert_eq!(harness.log.entries("audit").len(), 1);
+}
+
+#[tokio::test]
+async fn test_undecodable_record_is_skipped() {
+    let harness = consumer();
+    let mut event = fixture("transactions");
+    event["Records"][1]["dynamodb"]["NewImage"]["amount"] = json!({ "S": "1500円" });
+
+    let response = harness.consumer.handle_event(event.clone()).await.unwrap();
+
+    // 再試行しても読めないレコードでシャードを止めず、記録して後続を処理する
+    assert_eq!(response, json!({ "batchItemFailures": [] }));
+    let skipped = harness.log.entries("skipped");
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0]["sequenceNumber"], sequence_number(&event, 1));
+    assert!(skipped[0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("failed to deserialize NewImage"));
+    // 読み飛ばした更新（1,200円 → 1,500円）は集計に反映されない
+    let aggregates = aggregates(&harness).await;
+    assert_eq!(aggregates.len(), 1);
+    assert_eq!(aggregates[0].entries[0].amount, 1200);
+    assert_eq!(harness.log.entries("audit").len(), 4);
+}
+
+#[tokio::test]
+async fn test_missing_image_is_skipped() {
+    let harness = consumer();
+    let mut event = fixture("transactions");
+    // ビュータイプが KEYS_ONLY の場合
+    for record in event["Records"].as_array_mut().unwrap() {
+        let dynamodb = record["dynamodb"].as_object_mut().unwrap();
+        dynamodb.remove("NewImage");
+        dynamodb.remove("OldImage");
+    }
+
+    let response = harness.consumer.handle_event(event).await.unwrap();
+
+    assert_eq!(response, json!({ "batchItemFailures": [] }));
+    let skipped = harness.log.entries("skipped");
+    assert_eq!(skipped.len(), 6);
+    assert!(skipped[0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("NEW_AND_OLD_IMAGES"));
+    assert!(aggregates(&harness).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_record_without_sequence_number_fails_whole_batch() {
+    let harness = consumer();
+    let mut event = fixture("transactions");
+    event["Records"][3]["dynamodb"]
+        .as_object_mut()
+        .unwrap()
+        .remove("SequenceNumber");
+
+    assert!(harness.consumer.handle_event(event).await.is_err());
+    // 途中まで処理した後に全体を再試行すると二重に加算されるため、何も処理しない
+    assert!(aggregates(&harness).await.is_empty());
+    assert!(harness.log.entries("audit").is_empty());
+}
+
+#[tokio::test]
+async fn test_budget_alerts_are_evaluated_at_change_time() {
+    let harness = consumer();
+    let event = fixture("budget_alert");
+    // ストリームはテーブルへの書き込み後に届くため、バッチ処理時点のテーブルは最終状態になっている
+    harness
+        .repositories
+        .transactions
+        .save(new_image(&event, 1))
+        .await
+        .unwrap();
+    harness
+        .repositories
+        .budgets
+        .save(new_image(&event, 2))
+        .await
+        .unwrap();
+
+    let response = harness.consumer.handle_event(event.clone()).await.unwrap();
+
+    assert_eq!(response, json!({ "batchItemFailures": [] }));
+    // 予算作成のレコードは作成時の予算額（1万円）で判定して閾値を超え、
+    // 取引と予算変更のレコードは引き上げ後の予算額（2万円）で判定して閾値を下回る
+    let alerts = harness.notifier.alerts();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].user_id.value(), "user123");
+    assert_eq!(alerts[0].budget_id, "b0c7e1f2-0000-4000-8000-0000000000b1");
+    assert_eq!(alerts[0].period_start.to_string(), "2025-01-01");
+    assert_eq!(alerts[0].usage, 0.9);
+    assert_eq!(
+        alerts[0].raised_at.to_rfc3339(),
+        "2025-01-01T00:00:00+00:00"
+    );
+    // 通知したアラートは通知済みとして記録される
+    let history = harness
+        .repositories
+        .budget_alerts
+        .find_by_user_id("user123")
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].alert_id, alerts[0].alert_id);
+    assert!(history[0].delivered_at.is_some());
+    assert_eq!(harness.log.entries("audit").len(), 3);
+
+    // 同じバッチを再試行しても、同じ期間・閾値のアラートは再び発生しない
+    harness.consumer.handle_event(event).await.unwrap();
+    assert_eq!(harness.notifier.alerts().len(), 1);
+}
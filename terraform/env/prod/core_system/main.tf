@@ -52,6 +52,10 @@ module "core_system" {
   # Lambda configuration
   lambda_timeout = 30
 
+  # Authentication (Cognito User Pool issues the JWTs)
+  auth_issuer   = "https://${module.cognito.user_pool_endpoint}"
+  auth_audience = module.cognito.user_pool_client_id
+
   # CloudFront configuration
   cloudfront_price_class = "PriceClass_All" # Use all edge locations for production
 }
@@ -31,6 +31,10 @@ resource "aws_dynamodb_table" "main" {
     projection_type = "ALL"
   }
 
+  # 変更は同じ Lambda 関数が処理する（月次集計・予算アラート・監査ログ）
+  stream_enabled   = true
+  stream_view_type = "NEW_AND_OLD_IMAGES"
+
   point_in_time_recovery {
     enabled = true
   }
@@ -33,12 +33,28 @@ resource "aws_iam_role_policy" "lambda_dynamodb_policy" {
           "dynamodb:UpdateItem",
           "dynamodb:DeleteItem",
           "dynamodb:BatchGetItem",
-          "dynamodb:BatchWriteItem"
+          "dynamodb:BatchWriteItem",
+          "dynamodb:TransactWriteItems"
         ]
         Resource = [
           aws_dynamodb_table.main.arn,
           "${aws_dynamodb_table.main.arn}/index/*"
         ]
+      },
+      {
+        Effect = "Allow"
+        Action = [
+          "dynamodb:DescribeStream",
+          "dynamodb:GetRecords",
+          "dynamodb:GetShardIterator",
+          "dynamodb:ListStreams"
+        ]
+        Resource = aws_dynamodb_table.main.stream_arn
+      },
+      {
+        Effect   = "Allow"
+        Action   = ["sqs:SendMessage"]
+        Resource = aws_sqs_queue.table_stream_failures.arn
       }
     ]
   })
@@ -49,6 +65,12 @@ resource "aws_iam_role_policy_attachment" "lambda_basic_execution" {
   role       = aws_iam_role.lambda_execution_role.name
 }
 
+# ページングカーソルの署名鍵（全インスタンスで共有する）
+resource "random_password" "cursor_secret" {
+  length  = 48
+  special = false
+}
+
 # Lambda Function
 resource "aws_lambda_function" "api" {
   function_name = "${var.project_name}-${var.environment}-api"
@@ -62,8 +84,15 @@ resource "aws_lambda_function" "api" {
 
   environment {
     variables = {
-      DYNAMODB_TABLE_NAME = aws_dynamodb_table.main.name
-      ENVIRONMENT         = var.environment
+      DYNAMODB_TABLE_NAME  = aws_dynamodb_table.main.name
+      ENVIRONMENT          = var.environment
+      AUTH_ISSUER          = var.auth_issuer
+      AUTH_AUDIENCE        = var.auth_audience
+      AUTH_JWKS_URL        = "${var.auth_issuer}/.well-known/jwks.json"
+      CURSOR_SECRET        = random_password.cursor_secret.result
+      AGGREGATE_UPDATES    = "stream"
+      ALERT_WEBHOOK_URL    = var.alert_webhook_url
+      ALERT_WEBHOOK_SECRET = var.alert_webhook_secret
     }
   }
 
@@ -75,6 +104,30 @@ resource "aws_lambda_function" "api" {
   ]
 }
 
+# DynamoDB Streams -> Lambda（失敗したレコードは部分バッチレスポンスで返す）
+# 再試行は有限回とし、それでも失敗したバッチの情報は SQS に退避してシャードを先へ進める
+resource "aws_lambda_event_source_mapping" "table_stream" {
+  event_source_arn               = aws_dynamodb_table.main.stream_arn
+  function_name                  = aws_lambda_function.api.arn
+  starting_position              = "LATEST"
+  batch_size                     = 100
+  function_response_types        = ["ReportBatchItemFailures"]
+  maximum_retry_attempts         = 5
+  bisect_batch_on_function_error = true
+
+  destination_config {
+    on_failure {
+      destination_arn = aws_sqs_queue.table_stream_failures.arn
+    }
+  }
+}
+
+# 再試行を使い切ったストリームレコードの退避先（シャード ID とシーケンス番号の範囲が届く）
+resource "aws_sqs_queue" "table_stream_failures" {
+  name                      = "${var.project_name}-${var.environment}-table-stream-failures"
+  message_retention_seconds = 1209600
+}
+
 # Placeholder for Lambda deployment package
 data "archive_file" "lambda_placeholder" {
   type        = "zip"
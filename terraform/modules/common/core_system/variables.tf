@@ -38,6 +38,31 @@ variable "log_retention_days" {
   default     = 14
 }
 
+# Authentication (Bearer JWT verification)
+variable "auth_issuer" {
+  description = "Expected JWT issuer (e.g. https://cognito-idp.<region>.amazonaws.com/<user_pool_id>)"
+  type        = string
+}
+
+variable "auth_audience" {
+  description = "Expected JWT audience (Cognito app client ID)"
+  type        = string
+}
+
+# Budget alerts
+variable "alert_webhook_url" {
+  description = "URL to POST budget alerts to (alerts are only logged when empty)"
+  type        = string
+  default     = ""
+}
+
+variable "alert_webhook_secret" {
+  description = "Key for the HMAC-SHA256 signature sent with budget alert webhooks"
+  type        = string
+  default     = ""
+  sensitive   = true
+}
+
 # Lambda function configuration
 variable "lambda_timeout" {
   description = "Lambda function timeout in seconds"